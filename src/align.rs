@@ -0,0 +1,79 @@
+use unicode_width::UnicodeWidthChar;
+
+use crate::ansi::AnsiState;
+
+/// 行在可用宽度内的对齐方式
+///
+/// ---
+///
+/// Alignment of a line within the available width
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Align {
+    /// 左对齐，不添加填充
+    ///
+    /// ---
+    ///
+    /// Left aligned, no padding is added
+    #[default]
+    Left,
+    /// 居中对齐
+    ///
+    /// ---
+    ///
+    /// Centered
+    Center,
+    /// 右对齐
+    ///
+    /// ---
+    ///
+    /// Right aligned
+    Right
+}
+
+/// 计算按指定方式对齐一行所需的前导空格数
+///
+/// 全宽字符按两列计算，ANSI 转义序列不计入显示宽度。行宽超过可用宽度时不添加
+/// 填充；居中时多出的一列留在右侧
+///
+/// # 参数
+/// - `line`: 不含换行符的一行文本
+/// - `width`: 可用的显示宽度
+/// - `align`: 对齐方式
+///
+/// # 示例
+/// ```
+/// use slow_scan_print::{Align, align_padding};
+///
+/// assert_eq!(align_padding("世界ab", 10, Align::Center), 2);
+/// assert_eq!(align_padding("\x1b[1m世界ab\x1b[0m", 10, Align::Right), 4);
+/// assert_eq!(align_padding("abc", 10, Align::Left), 0);
+/// ```
+///
+/// ---
+///
+/// Compute the number of leading spaces needed to align a line
+///
+/// Full-width characters count as two columns and ANSI escape sequences do
+/// not count toward the display width. No padding is added when the line is
+/// wider than the available width; when centering, the odd column is left on
+/// the right
+///
+/// # Arguments
+/// - `line`: A line of text without line breaks
+/// - `width`: Available display width
+/// - `align`: Alignment
+pub fn align_padding(line: &str, width: usize, align: Align) -> usize {
+    let mut ansi = AnsiState::default();
+    let line_width = line
+        .chars()
+        .filter(|it| !ansi.advance(*it))
+        .map(|it| it.width_cjk().unwrap_or(0))
+        .sum::<usize>();
+    let rest = width.saturating_sub(line_width);
+
+    match align {
+        Align::Left => 0,
+        Align::Center => rest / 2,
+        Align::Right => rest
+    }
+}
@@ -0,0 +1,62 @@
+/// 识别 ANSI 转义序列的状态机
+///
+/// 支持 CSI（`ESC [ ... 终止字节`）、OSC（`ESC ] ... BEL` 或 `ESC ] ... ESC \`）
+/// 以及其余双字符转义序列（如 `ESC c`）
+///
+/// ---
+///
+/// State machine recognizing ANSI escape sequences
+///
+/// Supports CSI (`ESC [ ... final byte`), OSC (`ESC ] ... BEL` or
+/// `ESC ] ... ESC \`) and the remaining two-character escapes (such as
+/// `ESC c`)
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub(crate) enum AnsiState {
+    #[default]
+    Ground,
+    Escape,
+    Csi,
+    Osc,
+    OscEscape
+}
+
+impl AnsiState {
+    /// 输入一个字符并推进状态，返回该字符是否属于转义序列
+    ///
+    /// ---
+    ///
+    /// Feed a character and advance the state, returning whether the
+    /// character belongs to an escape sequence
+    pub(crate) fn advance(&mut self, c: char) -> bool {
+        match self {
+            Self::Ground => {
+                if c != '\x1b' {
+                    return false;
+                }
+                *self = Self::Escape;
+            }
+            Self::Escape => {
+                *self = match c {
+                    '[' => Self::Csi,
+                    ']' => Self::Osc,
+                    _ => Self::Ground
+                }
+            }
+            Self::Csi => {
+                if ('\x40'..='\x7E').contains(&c) {
+                    *self = Self::Ground;
+                }
+            }
+            Self::Osc => match c {
+                '\x07' => *self = Self::Ground,
+                '\x1b' => *self = Self::OscEscape,
+                _ => {}
+            },
+            Self::OscEscape => {
+                *self = if c == '\\' { Self::Ground } else { Self::Osc }
+            }
+        }
+
+        true
+    }
+}
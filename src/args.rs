@@ -1,9 +1,29 @@
+use std::collections::HashMap;
+use std::fs;
 use std::process::exit;
 use std::time::Duration;
 
+use clap::builder::RangedU64ValueParser;
+use clap::parser::ValueSource;
 use clap::{Arg, ArgAction, Command};
+use console::Term;
+#[cfg(feature = "regex")]
+use regex::Regex;
 use rust_i18n::t;
-use slow_scan_print::SlowScanConfig;
+use slow_scan_print::{
+    Align,
+    ColorDepth,
+    LetterFrequency,
+    ScriptSegment,
+    SlowScanConfig,
+    detect_cjk_locale,
+    parse_schedule,
+    parse_script,
+    parse_timing
+};
+
+use crate::config::ConfigFile;
+use crate::line_log::LogTarget;
 
 /// 命令行参数解析结果
 ///
@@ -31,6 +51,13 @@ use slow_scan_print::SlowScanConfig;
 #[derive(Debug, Clone)]
 pub(crate) struct Args {
     pub slow_scan_config: SlowScanConfig,
+    /// 显示全部内容至少需要的时间，不足时提高基础延迟
+    ///
+    /// ---
+    ///
+    /// Minimum time revealing the whole content takes, the base delay being
+    /// raised when it falls short
+    pub min_total_duration: Option<Duration>,
     /// 是否启用行模式
     ///
     /// 如果为 `true`，则按行而不是按字符进行延迟输出
@@ -41,6 +68,143 @@ pub(crate) struct Args {
     ///
     /// If `true`, output will be delayed by line instead of by character
     pub line_mode: bool,
+    /// 是否启用单词模式
+    ///
+    /// 如果为 `true`，则每个单词连同其后的空白一起输出，并在之后延迟一次
+    ///
+    /// ---
+    ///
+    /// Whether word mode is enabled
+    ///
+    /// If `true`, every word is output along with the whitespace following it
+    /// and delayed once afterwards
+    pub word_mode: bool,
+    /// 是否以十六进制转储的形式逐行打印
+    ///
+    /// ---
+    ///
+    /// Whether to print a hex dump row by row
+    pub hex: bool,
+    /// 是否不经缓冲逐字节读取输入并立即显示
+    ///
+    /// ---
+    ///
+    /// Whether to read the input byte by byte without buffering and show it
+    /// immediately
+    pub unbuffered: bool,
+    /// 是否为统一差异格式的输入着色
+    ///
+    /// ---
+    ///
+    /// Whether to color input in the unified diff format
+    pub diff_color: bool,
+    /// 输出的颜色深度，更高深度的颜色会被降级
+    ///
+    /// ---
+    ///
+    /// Color depth of the output, colors of a higher depth being downgraded
+    pub color_depth: ColorDepth,
+    /// 是否逐个码点显示，但每个字素簇只延迟一次
+    ///
+    /// ---
+    ///
+    /// Whether to reveal code point by code point but delay once per grapheme
+    /// cluster
+    pub build_graphemes: bool,
+    /// 是否将每个输入文件同时显示在终端中各自的横向区域中
+    ///
+    /// ---
+    ///
+    /// Whether to reveal every input file at once in its own horizontal band
+    /// of the terminal
+    pub split: bool,
+    /// 逐渐变暗的拖尾中的字符数量
+    ///
+    /// ---
+    ///
+    /// Number of characters in the fading trail
+    pub trail: Option<usize>,
+    /// 在固定数量的帧内显示全部输入时的帧数
+    ///
+    /// ---
+    ///
+    /// Number of frames when revealing the whole input over a fixed number of
+    /// frames
+    pub frames: Option<usize>,
+    /// 每帧的时长
+    ///
+    /// ---
+    ///
+    /// Duration of each frame
+    pub frame_time: Duration,
+    /// 从时间文件读取的每段延迟和字节数
+    ///
+    /// ---
+    ///
+    /// Delay and byte count of every segment read from the timing file
+    pub timing: Option<Vec<(Duration, usize)>>,
+    /// 从节奏表读取的行号到每个字符延迟的映射
+    ///
+    /// ---
+    ///
+    /// Mapping from line index to the delay of every character read from the
+    /// schedule
+    pub schedule: Option<HashMap<usize, Duration>>,
+    /// 从脚本文件读取的各段带样式和节奏的文本，设置后不读取输入文件
+    ///
+    /// ---
+    ///
+    /// Segments of styled and paced text read from the script file, input
+    /// files are not read when set
+    pub script: Option<Vec<ScriptSegment>>,
+    /// 需要遮盖的敏感内容
+    ///
+    /// 每行完整显示并停顿 `redact_hold` 后，匹配的部分会被替换为 `*`
+    ///
+    /// ---
+    ///
+    /// Secrets to mask
+    ///
+    /// After each line is fully revealed and held for `redact_hold`, the
+    /// matched parts are replaced with `*`
+    #[cfg(feature = "regex")]
+    pub redact: Option<Regex>,
+    /// 遮盖敏感内容之前的停顿时间
+    ///
+    /// ---
+    ///
+    /// Pause before masking secrets
+    #[cfg(feature = "regex")]
+    pub redact_hold: Duration,
+    /// 是否输出到新分配的伪终端而不是标准输出
+    ///
+    /// ---
+    ///
+    /// Whether to write to a newly allocated pseudo-terminal instead of stdout
+    #[cfg(all(unix, feature = "pty"))]
+    pub pty: bool,
+    /// 代替输入文件运行并读取其标准输出的命令
+    ///
+    /// ---
+    ///
+    /// Command run and read from its stdout instead of the input files
+    #[cfg(feature = "command")]
+    pub cmd: Option<String>,
+    /// 是否在显示输入文件之后继续监视其变化，只显示新追加的内容
+    ///
+    /// ---
+    ///
+    /// Whether to keep watching the input file after revealing it, revealing
+    /// only newly appended content
+    #[cfg(feature = "watch")]
+    pub watch: bool,
+    /// 是否按 Markdown 结构调整节奏并显示
+    ///
+    /// ---
+    ///
+    /// Whether to reveal with the pacing adjusted to the Markdown structure
+    #[cfg(feature = "markdown")]
+    pub markdown: bool,
     /// 是否隐藏光标
     ///
     /// 如果为 `true`，将在输出过程中隐藏终端光标
@@ -51,6 +215,167 @@ pub(crate) struct Args {
     ///
     /// If `true`, terminal cursor will be hidden during output
     pub hide_cursor: bool,
+    /// 是否在每显示满一屏后等待按键
+    ///
+    /// ---
+    ///
+    /// Whether to wait for a key press after each full screen
+    pub page: bool,
+    /// 逐行模式下是否去除每行末尾的空格和制表符
+    ///
+    /// ---
+    ///
+    /// Whether to strip trailing spaces and tabs from each line in line mode
+    pub strip_trailing_whitespace: bool,
+    /// 逐行模式下每段的最大字节数，更长的行会被切分为多段显示
+    ///
+    /// ---
+    ///
+    /// Maximum number of bytes of each segment in line mode, longer lines are
+    /// split into several segments
+    pub max_line_bytes: Option<usize>,
+    /// 逐行模式下每一行（包括之后的停顿）占用的时间
+    ///
+    /// ---
+    ///
+    /// Time every line takes in line mode, the pause after it included
+    pub equal_line_time: Option<Duration>,
+    /// 逐行模式下记录每行及其计划偏移和延迟的目标
+    ///
+    /// ---
+    ///
+    /// Target recording every line with its scheduled offset and delay in line
+    /// mode
+    pub log_target: Option<LogTarget>,
+    /// 是否在开始显示之前等待按键
+    ///
+    /// ---
+    ///
+    /// Whether to wait for a key press before starting to reveal
+    pub wait_start: bool,
+    /// 是否在第一个字节到达之前显示旋转指示器
+    ///
+    /// ---
+    ///
+    /// Whether to show a spinner until the first byte arrives
+    pub spinner: bool,
+    /// 输出为终端时每行在终端宽度内的对齐方式
+    ///
+    /// ---
+    ///
+    /// Alignment of each line within the terminal width when the output is a
+    /// terminal
+    pub align: Align,
+    /// 输出为终端时，是否在未以换行结尾的输出末尾补上换行
+    ///
+    /// ---
+    ///
+    /// Whether to append a line break to output not ending with one when the
+    /// output is a terminal
+    pub auto_final_newline: bool,
+    /// 输出为终端时，是否在内容前后各输出一条暗色的分隔线
+    ///
+    /// ---
+    ///
+    /// Whether to write a dim rule before and after the content when the
+    /// output is a terminal
+    pub dim_prompt: bool,
+    /// 按单词自动换行的宽度
+    ///
+    /// ---
+    ///
+    /// Width for wrapping lines at word boundaries
+    pub wrap: Option<usize>,
+    /// 是否只显示以 `\r` 原地刷新的行的最后状态
+    ///
+    /// ---
+    ///
+    /// Whether to show only the final state of lines refreshed in place with
+    /// `\r`
+    pub collapse_cr: bool,
+    /// 重新排版段落的宽度
+    ///
+    /// ---
+    ///
+    /// Width to re-flow paragraphs to
+    pub reflow: Option<usize>,
+    /// 是否在显示之前删除可能改变终端状态的转义序列和控制字符
+    ///
+    /// ---
+    ///
+    /// Whether to remove escape sequences and control characters that could
+    /// change the state of the terminal before revealing
+    pub safe: bool,
+    /// 是否在备用屏幕中打印
+    ///
+    /// 如果为 `true` 且输出为终端，将在备用屏幕中打印，结束后恢复原屏幕内容
+    ///
+    /// ---
+    ///
+    /// Whether to print in the alternate screen
+    ///
+    /// If `true` and output is a terminal, printing happens in the alternate
+    /// screen and the previous screen content is restored afterwards
+    pub alt_screen: bool,
+    /// 重复打印的次数
+    ///
+    /// 大于 1 时会先读取全部输入，再按次数重复打印
+    ///
+    /// ---
+    ///
+    /// Number of times to print the content
+    ///
+    /// When greater than 1 the whole input is read first and then printed
+    /// repeatedly
+    pub repeat: u32,
+    /// 两次重复打印之间的暂停时间
+    ///
+    /// ---
+    ///
+    /// Pause between two successive repetitions
+    pub repeat_delay: Duration,
+    /// 是否以基准测试模式运行
+    ///
+    /// 如果为 `true`，输出会被丢弃，结束后打印总耗时和单元数量
+    ///
+    /// ---
+    ///
+    /// Whether to run in benchmark mode
+    ///
+    /// If `true`, output is discarded and the total duration and unit count
+    /// are printed at the end
+    pub benchmark: bool,
+    /// 是否在结束时输出按字符类型统计的数量和延迟
+    ///
+    /// ---
+    ///
+    /// Whether to print the count and delay per character class at the end
+    pub profile: bool,
+    /// 是否在结束时向标准错误输出单行 JSON 格式的运行统计
+    ///
+    /// ---
+    ///
+    /// Whether to print the run statistics as a single line of JSON to stderr
+    /// at the end
+    pub stats_json: bool,
+    /// 写入每个单元计划和实际时间的 CSV 文件的路径
+    ///
+    /// ---
+    ///
+    /// Path of the CSV file the scheduled and actual time of every unit is
+    /// written to
+    pub trace: Option<String>,
+    /// 定时刷新输出的间隔
+    ///
+    /// 设置后不再在每个单元后刷新，而是由后台线程按此间隔刷新
+    ///
+    /// ---
+    ///
+    /// Interval of the timed output flush
+    ///
+    /// When set, output is no longer flushed after every unit but by a
+    /// background thread at this interval
+    pub flush_interval: Option<Duration>,
     /// 要处理的文件列表
     ///
     /// 支持多个文件输入，特殊值 "-" 表示从标准输入读取
@@ -64,39 +389,50 @@ pub(crate) struct Args {
 }
 
 impl Args {
-    /// 从命令行参数创建新的 `Args` 实例
+    /// 构建解析命令行参数使用的 [`Command`]
     ///
-    /// 使用 `clap` 库解析命令行参数并转换为结构化数据
-    ///
-    /// # 返回值
-    /// 返回解析后的 `Args` 实例
-    ///
-    /// # Panics
-    /// - 当无法解析延迟时间字符串时会 panic
-    /// - 当遇到意外的内部错误时会 panic
+    /// 互相冲突的选项通过 `conflicts_with` 声明，由 `clap` 报告错误
     ///
     /// ---
     ///
-    /// Create a new `Args` instance from command line arguments
+    /// Build the [`Command`] parsing the command line arguments
     ///
-    /// Uses `clap` library to parse command line arguments and convert to structured data
-    ///
-    /// # Returns
-    /// Returns parsed `Args` instance
-    ///
-    /// # Panics
-    /// - Panics when delay time string cannot be parsed
-    /// - Panics when unexpected internal errors occur
-    pub fn new() -> Self {
+    /// Conflicting options are declared with `conflicts_with`, leaving `clap`
+    /// to report the error
+    pub(crate) fn command() -> Command {
         let args = [
             Arg::new("delay")
                 .short('d')
                 .long("delay")
                 .value_name("TIME")
                 .action(ArgAction::Set)
-                .default_value("20ms")
+                .conflicts_with("screen-time")
                 .help(t!("clap.delay.help").to_string())
                 .long_help(t!("clap.delay.long_help").to_string()),
+            Arg::new("rate")
+                .long("rate")
+                .value_name("FACTOR")
+                .action(ArgAction::Set)
+                .value_parser(clap::value_parser!(f64))
+                .help(t!("clap.rate").to_string()),
+            Arg::new("speed")
+                .short('s')
+                .long("speed")
+                .value_name("FACTOR")
+                .action(ArgAction::Set)
+                .value_parser(clap::value_parser!(f64))
+                .help(t!("clap.speed").to_string()),
+            Arg::new("screen-time")
+                .long("screen-time")
+                .value_name("TIME")
+                .action(ArgAction::Set)
+                .help(t!("clap.screen_time").to_string()),
+            Arg::new("min-total-duration")
+                .long("min-total-duration")
+                .value_name("TIME")
+                .action(ArgAction::Set)
+                .conflicts_with_all(["line-mode", "hex", "split"])
+                .help(t!("clap.min_total_duration").to_string()),
             Arg::new("full-width-delay")
                 .short('f')
                 .long("full-width-delay")
@@ -109,21 +445,386 @@ impl Args {
                 .value_name("TIME")
                 .action(ArgAction::Set)
                 .help(t!("clap.control_char_delay").to_string()),
+            Arg::new("uppercase-delay")
+                .long("uppercase-delay")
+                .value_name("TIME")
+                .action(ArgAction::Set)
+                .help(t!("clap.uppercase_delay").to_string()),
+            Arg::new("space-delay")
+                .long("space-delay")
+                .value_name("TIME")
+                .action(ArgAction::Set)
+                .help(t!("clap.space_delay").to_string()),
+            Arg::new("pua-delay")
+                .long("pua-delay")
+                .value_name("TIME")
+                .action(ArgAction::Set)
+                .help(t!("clap.pua_delay").to_string()),
+            Arg::new("frequency-pacing")
+                .long("frequency-pacing")
+                .action(ArgAction::SetTrue)
+                .help(t!("clap.frequency_pacing").to_string()),
             Arg::new("tail-delay")
                 .short('t')
                 .long("tail-delay")
                 .action(ArgAction::SetTrue)
                 .help(t!("clap.tail_delay").to_string()),
+            Arg::new("caret-notation")
+                .long("caret-notation")
+                .action(ArgAction::SetTrue)
+                .help(t!("clap.caret_notation").to_string()),
+            Arg::new("unicode-lines")
+                .long("unicode-lines")
+                .action(ArgAction::SetTrue)
+                .help(t!("clap.unicode_lines").to_string()),
+            Arg::new("no-full-width")
+                .long("no-full-width")
+                .action(ArgAction::SetTrue)
+                .help(t!("clap.no_full_width").to_string()),
+            Arg::new("east-asian")
+                .long("east-asian")
+                .value_name("WIDTH")
+                .action(ArgAction::Set)
+                .default_value("auto")
+                .value_parser(["auto", "wide", "narrow"])
+                .help(t!("clap.east_asian").to_string()),
+            Arg::new("emoji-width")
+                .long("emoji-width")
+                .value_name("WIDTH")
+                .action(ArgAction::Set)
+                .value_parser(["1", "2"])
+                .help(t!("clap.emoji_width").to_string()),
+            Arg::new("coalesce-runs")
+                .long("coalesce-runs")
+                .value_name("COUNT")
+                .action(ArgAction::Set)
+                .value_parser(clap::value_parser!(usize))
+                .help(t!("clap.coalesce_runs").to_string()),
+            Arg::new("burst")
+                .long("burst")
+                .value_names(["K", "PAUSE"])
+                .num_args(2)
+                .action(ArgAction::Set)
+                .help(t!("clap.burst").to_string()),
+            Arg::new("teletype")
+                .long("teletype")
+                .value_name("WIDTH")
+                .action(ArgAction::Set)
+                .value_parser(clap::value_parser!(usize))
+                .conflicts_with_all(["line-mode", "hex", "wrap", "reflow"])
+                .help(t!("clap.teletype").to_string()),
+            Arg::new("split")
+                .long("split")
+                .action(ArgAction::SetTrue)
+                .conflicts_with_all(["line-mode", "hex", "script", "repeat"])
+                .help(t!("clap.split").to_string()),
+            Arg::new("trail")
+                .long("trail")
+                .value_name("COUNT")
+                .action(ArgAction::Set)
+                .value_parser(clap::value_parser!(usize))
+                .conflicts_with_all(["line-mode", "hex"])
+                .help(t!("clap.trail").to_string()),
+            Arg::new("round-to")
+                .long("round-to")
+                .value_name("TIME")
+                .action(ArgAction::Set)
+                .help(t!("clap.round_to").to_string()),
+            Arg::new("inline-directives")
+                .long("inline-directives")
+                .action(ArgAction::SetTrue)
+                .help(t!("clap.inline_directives").to_string()),
+            Arg::new("prompt-marks")
+                .long("prompt-marks")
+                .action(ArgAction::SetTrue)
+                .help(t!("clap.prompt_marks").to_string()),
             Arg::new("line-mode")
                 .short('l')
                 .long("line-mode")
                 .action(ArgAction::SetTrue)
+                .conflicts_with_all(["hex", "timing"])
                 .help(t!("clap.line_mode").to_string()),
+            Arg::new("word-mode")
+                .short('w')
+                .long("word-mode")
+                .action(ArgAction::SetTrue)
+                .conflicts_with_all([
+                    "line-mode",
+                    "hex",
+                    "trail",
+                    "teletype",
+                    "split",
+                    "diff-color",
+                    "timing",
+                    "frames",
+                    "build-graphemes",
+                    "script",
+                    "equal-line-time",
+                    "schedule",
+                    "align",
+                    "unbuffered"
+                ])
+                .help(t!("clap.word_mode").to_string()),
+            Arg::new("strip-trailing-whitespace")
+                .long("strip-trailing-whitespace")
+                .action(ArgAction::SetTrue)
+                .help(t!("clap.strip_trailing_whitespace").to_string()),
+            Arg::new("max-line-bytes")
+                .long("max-line-bytes")
+                .value_name("BYTES")
+                .action(ArgAction::Set)
+                .value_parser(RangedU64ValueParser::<usize>::new().range(1..))
+                .help(t!("clap.max_line_bytes").to_string()),
+            Arg::new("equal-line-time")
+                .long("equal-line-time")
+                .value_name("TIME")
+                .action(ArgAction::Set)
+                .conflicts_with_all(["diff-color", "max-line-bytes"])
+                .help(t!("clap.equal_line_time").to_string()),
+            Arg::new("log-target")
+                .long("log-target")
+                .value_name("TARGET")
+                .action(ArgAction::Set)
+                .value_parser([
+                    "stderr",
+                    #[cfg(feature = "syslog")]
+                    "syslog"
+                ])
+                .help(t!("clap.log_target").to_string()),
+            Arg::new("head-units")
+                .long("head-units")
+                .value_name("COUNT")
+                .action(ArgAction::Set)
+                .value_parser(clap::value_parser!(usize))
+                .conflicts_with_all([
+                    "frames",
+                    "timing",
+                    "build-graphemes",
+                    "diff-color"
+                ])
+                .help(t!("clap.head_units").to_string()),
+            Arg::new("wrap")
+                .long("wrap")
+                .value_name("COLUMNS")
+                .action(ArgAction::Set)
+                .value_parser(clap::value_parser!(usize))
+                .help(t!("clap.wrap").to_string()),
+            Arg::new("collapse-cr")
+                .long("collapse-cr")
+                .action(ArgAction::SetTrue)
+                .help(t!("clap.collapse_cr").to_string()),
+            Arg::new("reflow")
+                .long("reflow")
+                .value_name("WIDTH")
+                .action(ArgAction::Set)
+                .value_parser(RangedU64ValueParser::<usize>::new().range(1..))
+                .conflicts_with_all(["hex", "timing", "wrap"])
+                .help(t!("clap.reflow").to_string()),
+            Arg::new("safe")
+                .long("safe")
+                .action(ArgAction::SetTrue)
+                .help(t!("clap.safe").to_string()),
+            Arg::new("align")
+                .long("align")
+                .value_name("ALIGN")
+                .action(ArgAction::Set)
+                .default_value("left")
+                .value_parser(["left", "center", "right"])
+                .help(t!("clap.align").to_string()),
+            Arg::new("alt-screen")
+                .long("alt-screen")
+                .action(ArgAction::SetTrue)
+                .help(t!("clap.alt_screen").to_string()),
+            Arg::new("hex")
+                .long("hex")
+                .action(ArgAction::SetTrue)
+                .conflicts_with_all(["timing", "wrap"])
+                .help(t!("clap.hex").to_string()),
+            Arg::new("unbuffered")
+                .long("unbuffered")
+                .action(ArgAction::SetTrue)
+                .conflicts_with_all([
+                    "line-mode",
+                    "hex",
+                    "timing",
+                    "diff-color",
+                    "frames",
+                    "build-graphemes",
+                    "wrap",
+                    "reflow",
+                    "repeat",
+                    "safe"
+                ])
+                .help(t!("clap.unbuffered").to_string()),
+            Arg::new("build-graphemes")
+                .long("build-graphemes")
+                .action(ArgAction::SetTrue)
+                .conflicts_with_all([
+                    "line-mode",
+                    "hex",
+                    "timing",
+                    "diff-color"
+                ])
+                .help(t!("clap.build_graphemes").to_string()),
+            Arg::new("frames")
+                .long("frames")
+                .value_name("COUNT")
+                .action(ArgAction::Set)
+                .value_parser(RangedU64ValueParser::<usize>::new().range(1..))
+                .conflicts_with_all([
+                    "line-mode",
+                    "hex",
+                    "timing",
+                    "diff-color",
+                    "build-graphemes"
+                ])
+                .help(t!("clap.frames").to_string()),
+            Arg::new("fps")
+                .long("fps")
+                .value_name("FPS")
+                .action(ArgAction::Set)
+                .default_value("30")
+                .value_parser(clap::value_parser!(u32).range(1..))
+                .help(t!("clap.fps").to_string()),
+            Arg::new("diff-color")
+                .long("diff-color")
+                .action(ArgAction::SetTrue)
+                .conflicts_with_all(["line-mode", "hex", "timing"])
+                .help(t!("clap.diff_color").to_string()),
+            Arg::new("color-depth")
+                .long("color-depth")
+                .value_name("DEPTH")
+                .action(ArgAction::Set)
+                .default_value("auto")
+                .value_parser(["auto", "16", "256", "truecolor"])
+                .help(t!("clap.color_depth").to_string()),
+            Arg::new("timing")
+                .long("timing")
+                .value_name("PATH")
+                .action(ArgAction::Set)
+                .conflicts_with("wrap")
+                .help(t!("clap.timing").to_string()),
+            Arg::new("schedule")
+                .long("schedule")
+                .value_name("PATH")
+                .action(ArgAction::Set)
+                .conflicts_with_all([
+                    "line-mode",
+                    "hex",
+                    "timing",
+                    "diff-color",
+                    "frames",
+                    "build-graphemes",
+                    "head-units",
+                    "unbuffered"
+                ])
+                .help(t!("clap.schedule").to_string()),
+            Arg::new("script")
+                .long("script")
+                .value_name("PATH")
+                .action(ArgAction::Set)
+                .conflicts_with_all([
+                    "line-mode",
+                    "hex",
+                    "timing",
+                    "schedule",
+                    "diff-color",
+                    "frames",
+                    "build-graphemes",
+                    "unbuffered",
+                    "reflow",
+                    "repeat"
+                ])
+                .help(t!("clap.script").to_string()),
+            Arg::new("reference-width")
+                .long("reference-width")
+                .value_name("COLUMNS")
+                .action(ArgAction::Set)
+                .value_parser(clap::value_parser!(usize))
+                .help(t!("clap.reference_width").to_string()),
             Arg::new("hide-cursor")
                 .short('i') // "ignore cursor" 可能不是很准，但大概是就行
                 .long("hide-cursor")
                 .action(ArgAction::SetTrue)
                 .help(t!("clap.hide_cursor").to_string()),
+            Arg::new("page")
+                .long("page")
+                .action(ArgAction::SetTrue)
+                .help(t!("clap.page").to_string()),
+            Arg::new("wait-start")
+                .long("wait-start")
+                .action(ArgAction::SetTrue)
+                .help(t!("clap.wait_start").to_string()),
+            Arg::new("spinner")
+                .long("spinner")
+                .action(ArgAction::SetTrue)
+                .help(t!("clap.spinner").to_string()),
+            Arg::new("auto-final-newline")
+                .long("auto-final-newline")
+                .action(ArgAction::SetTrue)
+                .help(t!("clap.auto_final_newline").to_string()),
+            Arg::new("dim-prompt")
+                .long("dim-prompt")
+                .action(ArgAction::SetTrue)
+                .help(t!("clap.dim_prompt").to_string()),
+            Arg::new("repeat")
+                .short('r')
+                .long("repeat")
+                .value_name("COUNT")
+                .action(ArgAction::Set)
+                .default_value("1")
+                .value_parser(clap::value_parser!(u32))
+                .help(t!("clap.repeat").to_string()),
+            Arg::new("repeat-delay")
+                .long("repeat-delay")
+                .value_name("TIME")
+                .action(ArgAction::Set)
+                .default_value("0s")
+                .help(t!("clap.repeat_delay").to_string()),
+            Arg::new("benchmark")
+                .long("benchmark")
+                .action(ArgAction::SetTrue)
+                .help(t!("clap.benchmark").to_string()),
+            Arg::new("profile")
+                .long("profile")
+                .action(ArgAction::SetTrue)
+                .conflicts_with_all([
+                    "word-mode",
+                    "diff-color",
+                    "timing",
+                    "frames",
+                    "build-graphemes",
+                    "script",
+                    "equal-line-time",
+                    "schedule"
+                ])
+                .help(t!("clap.profile").to_string()),
+            Arg::new("stats-json")
+                .long("stats-json")
+                .action(ArgAction::SetTrue)
+                .help(t!("clap.stats_json").to_string()),
+            Arg::new("trace")
+                .long("trace")
+                .value_name("PATH")
+                .action(ArgAction::Set)
+                .conflicts_with_all([
+                    "line-mode",
+                    "hex",
+                    "split",
+                    "unbuffered",
+                    "timing"
+                ])
+                .help(t!("clap.trace").to_string()),
+            Arg::new("flush-interval")
+                .long("flush-interval")
+                .value_name("TIME")
+                .action(ArgAction::Set)
+                .help(t!("clap.flush_interval").to_string()),
+            Arg::new("config")
+                .long("config")
+                .value_name("PATH")
+                .action(ArgAction::Set)
+                .help(t!("clap.config").to_string()),
             Arg::new("files")
                 .action(ArgAction::Append)
                 .default_value("-")
@@ -141,78 +842,745 @@ impl Args {
                 .long_help(t!("clap.long_help").to_string())
         ];
 
-        let matches = Command::new(env!("CARGO_PKG_NAME"))
+        #[cfg(feature = "regex")]
+        let args = {
+            let mut args = args.to_vec();
+            args.push(
+                Arg::new("redact")
+                    .long("redact")
+                    .value_name("REGEX")
+                    .action(ArgAction::Set)
+                    .value_parser(|it: &str| Regex::new(it))
+                    .conflicts_with_all([
+                        "hex",
+                        "timing",
+                        "head-units",
+                        "unbuffered",
+                        "schedule",
+                        "script",
+                        "equal-line-time",
+                        "word-mode",
+                        "profile"
+                    ])
+                    .help(t!("clap.redact").to_string())
+            );
+            args.push(
+                Arg::new("redact-hold")
+                    .long("redact-hold")
+                    .value_name("TIME")
+                    .action(ArgAction::Set)
+                    .default_value("500ms")
+                    .help(t!("clap.redact_hold").to_string())
+            );
+            args
+        };
+
+        #[cfg(all(unix, feature = "pty"))]
+        let args = {
+            let mut args = args.to_vec();
+            args.push(
+                Arg::new("pty")
+                    .long("pty")
+                    .action(ArgAction::SetTrue)
+                    .conflicts_with("benchmark")
+                    .help(t!("clap.pty").to_string())
+            );
+            args
+        };
+
+        #[cfg(feature = "command")]
+        let args = {
+            let mut args = args.to_vec();
+            args.push(
+                Arg::new("cmd")
+                    .long("cmd")
+                    .value_name("COMMAND")
+                    .action(ArgAction::Set)
+                    .conflicts_with_all(["split", "script"])
+                    .help(t!("clap.cmd").to_string())
+            );
+            args
+        };
+
+        #[cfg(feature = "watch")]
+        let args = {
+            let mut args = args.to_vec();
+            args.push(
+                Arg::new("watch")
+                    .long("watch")
+                    .action(ArgAction::SetTrue)
+                    .conflicts_with_all([
+                        "min-total-duration",
+                        "split",
+                        "script",
+                        #[cfg(feature = "command")]
+                        "cmd",
+                        "repeat"
+                    ])
+                    .help(t!("clap.watch").to_string())
+            );
+            args
+        };
+
+        #[cfg(feature = "markdown")]
+        let args = {
+            let mut args = args.to_vec();
+            args.push(
+                Arg::new("markdown")
+                    .long("markdown")
+                    .action(ArgAction::SetTrue)
+                    .conflicts_with_all([
+                        "line-mode",
+                        "hex",
+                        "timing",
+                        "diff-color",
+                        "frames",
+                        "build-graphemes",
+                        "unbuffered",
+                        "schedule",
+                        "script",
+                        "word-mode",
+                        "profile"
+                    ])
+                    .help(t!("clap.markdown").to_string())
+            );
+            args
+        };
+
+        #[cfg(feature = "jitter")]
+        let args = {
+            let mut args = args.to_vec();
+            args.push(
+                Arg::new("jitter")
+                    .long("jitter")
+                    .value_name("PERCENT")
+                    .action(ArgAction::Set)
+                    .value_parser(clap::value_parser!(f64))
+                    .help(t!("clap.jitter").to_string())
+            );
+            args
+        };
+
+        Command::new(env!("CARGO_PKG_NAME"))
             .disable_version_flag(true)
             .disable_help_flag(true)
             .version(env!("CARGO_PKG_VERSION"))
             .about(t!("clap.about").to_string())
             .author(env!("CARGO_PKG_AUTHORS"))
             .args(&args)
-            .get_matches();
+    }
+
+    /// 从命令行参数创建新的 `Args` 实例
+    ///
+    /// 使用 `clap` 库解析命令行参数并转换为结构化数据
+    ///
+    /// # 返回值
+    /// 返回解析后的 `Args` 实例
+    ///
+    /// # Panics
+    /// - 当无法解析延迟时间字符串时会 panic
+    /// - 当遇到意外的内部错误时会 panic
+    ///
+    /// ---
+    ///
+    /// Create a new `Args` instance from command line arguments
+    ///
+    /// Uses `clap` library to parse command line arguments and convert to structured data
+    ///
+    /// # Returns
+    /// Returns parsed `Args` instance
+    ///
+    /// # Panics
+    /// - Panics when delay time string cannot be parsed
+    /// - Panics when unexpected internal errors occur
+    pub fn new() -> Self {
+        let matches = Self::command().get_matches();
 
         let unreachable_msg = t!("error.unreachable");
 
-        let delay = matches.get_one::<String>("delay").map_or_else(
-            || unreachable!("{}", unreachable_msg),
+        let files = matches
+            .get_many::<String>("files")
+            .unwrap_or_else(|| unreachable!("{}", unreachable_msg))
+            .map(|it| it.to_owned())
+            .collect::<Vec<String>>();
+
+        let config = matches.get_one::<String>("config").map_or_else(
+            ConfigFile::default,
             |it| {
-                duration_str::parse_std(it).unwrap_or_else(|_| {
-                    eprintln!("{}", t!("error.convert_string_to_duration"));
+                if it == "-" && files.iter().any(|it| it == "-") {
+                    eprintln!("{}", t!("error.stdin_claimed_twice"));
+                    exit(2)
+                }
+
+                ConfigFile::load(it).unwrap_or_else(|it| {
+                    eprintln!("{}", it);
                     exit(1)
                 })
             }
         );
 
-        let full_width_delay =
-            matches.get_one::<String>("full-width-delay").map_or_else(
-                || delay * 2,
-                |it| {
-                    duration_str::parse_std(it).unwrap_or_else(|_| {
-                        eprintln!("{}", t!("error.convert_string_to_duration"));
-                        exit(1)
-                    })
-                }
+        let screen_time = matches
+            .get_one::<String>("screen-time")
+            .map(|it| parse_duration(it));
+
+        let min_total_duration = matches
+            .get_one::<String>("min-total-duration")
+            .map(|it| parse_duration(it));
+
+        let line_mode = *matches
+            .get_one::<bool>("line-mode")
+            .unwrap_or_else(|| unreachable!("{}", unreachable_msg))
+            || config.line_mode.unwrap_or(false);
+
+        let word_mode = *matches
+            .get_one::<bool>("word-mode")
+            .unwrap_or_else(|| unreachable!("{}", unreachable_msg));
+
+        // 没有显式指定延迟时按模式选择默认值，逐行模式下即每行之后的延迟
+        let defaults = if line_mode {
+            SlowScanConfig::default_for_line_mode()
+        } else {
+            SlowScanConfig::default()
+        };
+
+        let delay = config
+            .delay
+            .as_ref()
+            .filter(|_| {
+                matches.value_source("delay") != Some(ValueSource::CommandLine)
+            })
+            .or(matches.get_one::<String>("delay"))
+            .map_or_else(
+                || {
+                    defaults
+                        .inter_chunk_delay()
+                        .unwrap_or(*defaults.base_delay())
+                },
+                |it| parse_duration(it)
             );
 
-        let control_char_delay =
-            matches.get_one::<String>("control-char-delay").map_or_else(
-                || Duration::ZERO,
-                |it| {
-                    duration_str::parse_std(it).unwrap_or_else(|_| {
-                        eprintln!("{}", t!("error.convert_string_to_duration"));
-                        exit(1)
+        let delay = screen_time.map_or(delay, |it| {
+            let (rows, columns) = Term::stdout().size();
+            *SlowScanConfig::default()
+                .set_base_delay_from_screen_time(
+                    it,
+                    columns.into(),
+                    rows.into()
+                )
+                .base_delay()
+        });
+
+        let full_width_delay = matches
+            .get_one::<String>("full-width-delay")
+            .or(config.full_width_delay.as_ref())
+            .map_or_else(|| delay * 2, |it| parse_duration(it));
+
+        let control_char_delay = matches
+            .get_one::<String>("control-char-delay")
+            .or(config.control_char_delay.as_ref())
+            .map_or_else(|| Duration::ZERO, |it| parse_duration(it));
+
+        let rate = matches.get_one::<f64>("rate").copied().unwrap_or(1.0);
+        // 缩放后溢出，或把非零的延迟缩放为零的倍率同样无效
+        let out_of_range = [delay, full_width_delay, control_char_delay]
+            .into_iter()
+            .any(|it| {
+                Duration::try_from_secs_f64(it.as_secs_f64() * rate)
+                    .map_or(true, |scaled| {
+                        rate > 0.0 && !it.is_zero() && scaled.is_zero()
                     })
-                }
-            );
+            });
+        if !rate.is_finite() || rate < 0.0 || out_of_range {
+            eprintln!("{}", t!("error.invalid_rate"));
+            exit(1)
+        }
+
+        let speed = matches.get_one::<f64>("speed").copied().unwrap_or(1.0);
+        if !(0.01..=100.0).contains(&speed) {
+            eprintln!("{}", t!("error.invalid_speed"));
+            exit(1)
+        }
+
+        let uppercase_delay = matches
+            .get_one::<String>("uppercase-delay")
+            .map(|it| parse_duration(it));
+
+        let space_delay = matches
+            .get_one::<String>("space-delay")
+            .map(|it| parse_duration(it));
+
+        let pua_delay = matches
+            .get_one::<String>("pua-delay")
+            .map(|it| parse_duration(it));
+
+        let letter_frequency = matches
+            .get_one::<bool>("frequency-pacing")
+            .unwrap_or_else(|| unreachable!("{}", unreachable_msg))
+            .then(LetterFrequency::default);
 
         let tail_delay = *matches
             .get_one::<bool>("tail-delay")
+            .unwrap_or_else(|| unreachable!("{}", unreachable_msg))
+            || config.tail_delay.unwrap_or(false);
+
+        let inline_directives = *matches
+            .get_one::<bool>("inline-directives")
+            .unwrap_or_else(|| unreachable!("{}", unreachable_msg))
+            || config.inline_directives.unwrap_or(false);
+
+        let prompt_marks = *matches
+            .get_one::<bool>("prompt-marks")
+            .unwrap_or_else(|| unreachable!("{}", unreachable_msg));
+
+        let caret_notation = *matches
+            .get_one::<bool>("caret-notation")
+            .unwrap_or_else(|| unreachable!("{}", unreachable_msg));
+
+        let unicode_lines = *matches
+            .get_one::<bool>("unicode-lines")
+            .unwrap_or_else(|| unreachable!("{}", unreachable_msg));
+
+        let half_width_only = *matches
+            .get_one::<bool>("no-full-width")
+            .unwrap_or_else(|| unreachable!("{}", unreachable_msg));
+
+        let cjk_ambiguous_wide =
+            match matches.get_one::<String>("east-asian").map(String::as_str) {
+                Some("wide") => true,
+                Some("narrow") => false,
+                _ => detect_cjk_locale()
+            };
+
+        let emoji_width = matches
+            .get_one::<String>("emoji-width")
+            .map(|it| if it == "1" { 1 } else { 2 });
+
+        let coalesce_runs = matches.get_one::<usize>("coalesce-runs").copied();
+
+        let burst = matches.get_many::<String>("burst").map(|mut it| {
+            let size = it.next().and_then(|it| it.parse::<usize>().ok());
+            let pause = it.next().map(|it| parse_duration(it));
+
+            match (size.filter(|it| *it > 0), pause) {
+                (Some(size), Some(pause)) => (size, pause),
+                _ => {
+                    eprintln!("{}", t!("error.invalid_burst_size"));
+                    exit(1)
+                }
+            }
+        });
+
+        let teletype = matches.get_one::<usize>("teletype").copied();
+
+        let trail = matches.get_one::<usize>("trail").copied();
+
+        let split = *matches
+            .get_one::<bool>("split")
             .unwrap_or_else(|| unreachable!("{}", unreachable_msg));
 
+        let round_to = matches
+            .get_one::<String>("round-to")
+            .map(|it| parse_duration(it));
+
+        let reference_width =
+            matches.get_one::<usize>("reference-width").copied();
+
+        let head_units = matches.get_one::<usize>("head-units").copied();
+
+        let flush_interval = matches
+            .get_one::<String>("flush-interval")
+            .map(|it| parse_duration(it));
+
         let slow_scan_config = *SlowScanConfig::default()
             .set_base_delay(delay)
             .set_full_width_delay(full_width_delay)
             .set_control_char_delay(control_char_delay)
-            .set_tail_delay(tail_delay);
+            .scale_delays(rate)
+            .set_uppercase_delay(uppercase_delay)
+            .set_letter_frequency(letter_frequency)
+            .set_space_delay(space_delay)
+            .set_pua_delay(pua_delay)
+            .set_tail_delay(tail_delay)
+            .set_head_units(head_units)
+            .set_inline_directives(inline_directives)
+            .set_prompt_marks(prompt_marks)
+            .set_chunk_reference_width(reference_width)
+            .set_coalesce_runs(coalesce_runs)
+            .set_burst(burst)
+            .set_teletype_width(teletype)
+            .set_round_to(round_to)
+            .set_emoji_width(emoji_width)
+            .set_caret_notation(caret_notation)
+            .set_unicode_lines(unicode_lines)
+            .set_half_width_only(half_width_only)
+            .set_cjk_ambiguous_wide(cjk_ambiguous_wide)
+            .set_speed_factor(speed)
+            .set_flush_each_unit(flush_interval.is_none());
 
-        let line_mode = *matches
-            .get_one::<bool>("line-mode")
+        #[cfg(feature = "jitter")]
+        let slow_scan_config = {
+            let jitter =
+                matches.get_one::<f64>("jitter").copied().unwrap_or(0.0);
+            if !(0.0..=100.0).contains(&jitter) {
+                eprintln!("{}", t!("error.invalid_jitter"));
+                exit(1)
+            }
+
+            let mut config = slow_scan_config;
+            config.set_jitter(jitter / 100.0);
+            config
+        };
+
+        let hex = *matches
+            .get_one::<bool>("hex")
+            .unwrap_or_else(|| unreachable!("{}", unreachable_msg));
+
+        let unbuffered = *matches
+            .get_one::<bool>("unbuffered")
+            .unwrap_or_else(|| unreachable!("{}", unreachable_msg));
+
+        let diff_color = *matches
+            .get_one::<bool>("diff-color")
+            .unwrap_or_else(|| unreachable!("{}", unreachable_msg));
+
+        let color_depth = match matches
+            .get_one::<String>("color-depth")
+            .map(String::as_str)
+        {
+            Some("16") => ColorDepth::Ansi16,
+            Some("256") => ColorDepth::Ansi256,
+            Some("truecolor") => ColorDepth::TrueColor,
+            _ => ColorDepth::detect()
+        };
+
+        let build_graphemes = *matches
+            .get_one::<bool>("build-graphemes")
+            .unwrap_or_else(|| unreachable!("{}", unreachable_msg));
+
+        let frames = matches.get_one::<usize>("frames").copied();
+
+        let frame_time = matches.get_one::<u32>("fps").map_or_else(
+            || unreachable!("{}", unreachable_msg),
+            |it| Duration::from_secs(1) / *it
+        );
+
+        let timing = matches.get_one::<String>("timing").map(|it| {
+            let text = fs::read_to_string(it).unwrap_or_else(|src| {
+                eprintln!(
+                    "{}",
+                    t!("error.cannot_read_timing", uri = it, src = src)
+                );
+                exit(1)
+            });
+
+            parse_timing(&text).unwrap_or_else(|src| {
+                eprintln!(
+                    "{}",
+                    t!("error.invalid_timing", uri = it, src = src)
+                );
+                exit(1)
+            })
+        });
+
+        let schedule = matches.get_one::<String>("schedule").map(|it| {
+            let text = fs::read_to_string(it).unwrap_or_else(|src| {
+                eprintln!(
+                    "{}",
+                    t!("error.cannot_read_schedule", uri = it, src = src)
+                );
+                exit(1)
+            });
+
+            parse_schedule(&text).unwrap_or_else(|src| {
+                eprintln!(
+                    "{}",
+                    t!("error.invalid_schedule", uri = it, src = src)
+                );
+                exit(1)
+            })
+        });
+
+        let script = matches.get_one::<String>("script").map(|it| {
+            let text = fs::read_to_string(it).unwrap_or_else(|src| {
+                eprintln!(
+                    "{}",
+                    t!("error.cannot_read_script", uri = it, src = src)
+                );
+                exit(1)
+            });
+
+            parse_script(&text).unwrap_or_else(|src| {
+                eprintln!(
+                    "{}",
+                    t!("error.invalid_script", uri = it, src = src)
+                );
+                exit(1)
+            })
+        });
+
+        #[cfg(feature = "regex")]
+        let redact = matches.get_one::<Regex>("redact").cloned();
+
+        #[cfg(feature = "regex")]
+        let redact_hold = matches.get_one::<String>("redact-hold").map_or_else(
+            || unreachable!("{}", unreachable_msg),
+            |it| parse_duration(it)
+        );
+
+        #[cfg(all(unix, feature = "pty"))]
+        let pty = *matches
+            .get_one::<bool>("pty")
+            .unwrap_or_else(|| unreachable!("{}", unreachable_msg));
+
+        #[cfg(feature = "command")]
+        let cmd = matches.get_one::<String>("cmd").cloned();
+
+        #[cfg(feature = "watch")]
+        let watch = *matches
+            .get_one::<bool>("watch")
+            .unwrap_or_else(|| unreachable!("{}", unreachable_msg));
+
+        // 只能监视单个普通文件
+        #[cfg(feature = "watch")]
+        if watch && (files.len() != 1 || files[0] == "-") {
+            eprintln!("{}", t!("error.watch_needs_one_file"));
+            exit(2)
+        }
+
+        #[cfg(feature = "markdown")]
+        let markdown = *matches
+            .get_one::<bool>("markdown")
             .unwrap_or_else(|| unreachable!("{}", unreachable_msg));
 
         let hide_cursor = *matches
             .get_one::<bool>("hide-cursor")
+            .unwrap_or_else(|| unreachable!("{}", unreachable_msg))
+            || config.hide_cursor.unwrap_or(false);
+
+        let page = *matches
+            .get_one::<bool>("page")
             .unwrap_or_else(|| unreachable!("{}", unreachable_msg));
 
-        let files = matches
-            .get_many::<String>("files")
-            .unwrap_or_else(|| unreachable!("{}", unreachable_msg))
-            .map(|it| it.to_owned())
-            .collect::<Vec<String>>();
+        let strip_trailing_whitespace = *matches
+            .get_one::<bool>("strip-trailing-whitespace")
+            .unwrap_or_else(|| unreachable!("{}", unreachable_msg));
+
+        let max_line_bytes =
+            matches.get_one::<usize>("max-line-bytes").copied();
+
+        let equal_line_time = matches
+            .get_one::<String>("equal-line-time")
+            .map(|it| parse_duration(it));
+
+        let log_target = matches.get_one::<String>("log-target").map(|it| {
+            match it.as_str() {
+                #[cfg(feature = "syslog")]
+                "syslog" => LogTarget::Syslog,
+                _ => LogTarget::Stderr
+            }
+        });
+
+        let wait_start = *matches
+            .get_one::<bool>("wait-start")
+            .unwrap_or_else(|| unreachable!("{}", unreachable_msg));
+
+        let spinner = *matches
+            .get_one::<bool>("spinner")
+            .unwrap_or_else(|| unreachable!("{}", unreachable_msg));
+
+        let auto_final_newline = *matches
+            .get_one::<bool>("auto-final-newline")
+            .unwrap_or_else(|| unreachable!("{}", unreachable_msg));
+
+        let dim_prompt = *matches
+            .get_one::<bool>("dim-prompt")
+            .unwrap_or_else(|| unreachable!("{}", unreachable_msg));
+
+        let wrap = matches.get_one::<usize>("wrap").copied();
+
+        let collapse_cr = *matches
+            .get_one::<bool>("collapse-cr")
+            .unwrap_or_else(|| unreachable!("{}", unreachable_msg));
+
+        let reflow = matches.get_one::<usize>("reflow").copied();
+
+        let safe = *matches
+            .get_one::<bool>("safe")
+            .unwrap_or_else(|| unreachable!("{}", unreachable_msg));
+
+        let align = match matches.get_one::<String>("align").map(String::as_str)
+        {
+            Some("center") => Align::Center,
+            Some("right") => Align::Right,
+            _ => Align::Left
+        };
+
+        let alt_screen = *matches
+            .get_one::<bool>("alt-screen")
+            .unwrap_or_else(|| unreachable!("{}", unreachable_msg));
+
+        let repeat = *matches
+            .get_one::<u32>("repeat")
+            .unwrap_or_else(|| unreachable!("{}", unreachable_msg));
+
+        let repeat_delay =
+            matches.get_one::<String>("repeat-delay").map_or_else(
+                || unreachable!("{}", unreachable_msg),
+                |it| parse_duration(it)
+            );
+
+        let profile = *matches
+            .get_one::<bool>("profile")
+            .unwrap_or_else(|| unreachable!("{}", unreachable_msg));
+
+        let stats_json = *matches
+            .get_one::<bool>("stats-json")
+            .unwrap_or_else(|| unreachable!("{}", unreachable_msg));
+
+        let trace = matches.get_one::<String>("trace").cloned();
+
+        let benchmark = *matches
+            .get_one::<bool>("benchmark")
+            .unwrap_or_else(|| unreachable!("{}", unreachable_msg));
 
         Self {
             slow_scan_config,
+            min_total_duration,
             line_mode,
+            word_mode,
+            hex,
+            unbuffered,
+            diff_color,
+            color_depth,
+            build_graphemes,
+            split,
+            trail,
+            frames,
+            frame_time,
+            timing,
+            schedule,
+            script,
+            #[cfg(feature = "regex")]
+            redact,
+            #[cfg(feature = "regex")]
+            redact_hold,
+            #[cfg(all(unix, feature = "pty"))]
+            pty,
+            #[cfg(feature = "command")]
+            cmd,
+            #[cfg(feature = "watch")]
+            watch,
+            #[cfg(feature = "markdown")]
+            markdown,
             hide_cursor,
+            page,
+            strip_trailing_whitespace,
+            max_line_bytes,
+            equal_line_time,
+            log_target,
+            wait_start,
+            spinner,
+            auto_final_newline,
+            dim_prompt,
+            wrap,
+            collapse_cr,
+            reflow,
+            safe,
+            align,
+            alt_screen,
+            repeat,
+            repeat_delay,
+            benchmark,
+            profile,
+            stats_json,
+            trace,
+            flush_interval,
             files
         }
     }
 }
+
+/// 解析时间字符串，失败时打印错误并退出程序
+///
+/// ---
+///
+/// Parse a time string, printing an error and exiting on failure
+fn parse_duration(s: &str) -> Duration {
+    duration_str::parse_std(s).unwrap_or_else(|_| {
+        eprintln!("{}", t!("error.convert_string_to_duration"));
+        exit(1)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use clap::error::ErrorKind;
+
+    use super::*;
+
+    fn conflict(args: &[&str]) -> bool {
+        Args::command()
+            .try_get_matches_from(
+                std::iter::once("slow-scan-print").chain(args.iter().copied())
+            )
+            .is_err_and(|it| it.kind() == ErrorKind::ArgumentConflict)
+    }
+
+    #[test]
+    fn command_is_valid() { Args::command().debug_assert() }
+
+    #[test]
+    fn conflicting_options_are_rejected() {
+        assert!(conflict(&["--hex", "--line-mode"]));
+        assert!(conflict(&["--line-mode", "--hex"]));
+        assert!(conflict(&["-w", "--split"]));
+        assert!(conflict(&["--script", "s.txt", "--repeat", "2"]));
+        assert!(conflict(&["-d", "10ms", "--screen-time", "1s"]));
+    }
+
+    #[test]
+    fn word_mode_conflicts_with_other_writers() {
+        for it in [
+            &["--diff-color"][..],
+            &["--timing", "t.txt"],
+            &["--frames", "2"],
+            &["--build-graphemes"],
+            &["--script", "s.txt"],
+            &["--equal-line-time", "1s"],
+            &["--schedule", "s.txt"],
+            &["--align", "right"],
+            &["--unbuffered"]
+        ] {
+            assert!(conflict(&[&["-w"][..], it].concat()), "{it:?}");
+        }
+    }
+
+    #[test]
+    fn profile_conflicts_with_unmeasured_modes() {
+        assert!(conflict(&["--profile", "-w"]));
+        assert!(conflict(&["--profile", "--frames", "2"]));
+        assert!(!conflict(&["--profile", "--line-mode"]));
+        assert!(!conflict(&["--profile", "--hex"]));
+    }
+
+    #[test]
+    fn compatible_options_are_accepted() {
+        assert!(!conflict(&["--hex"]));
+        assert!(!conflict(&["--line-mode", "--wrap", "40"]));
+        assert!(!conflict(&["--screen-time", "1s"]));
+    }
+
+    #[cfg(feature = "regex")]
+    #[test]
+    fn feature_options_conflict() {
+        assert!(conflict(&["--redact", "[0-9]+", "--hex"]));
+        assert!(!conflict(&["--redact", "[0-9]+", "--line-mode"]));
+        assert!(conflict(&["-w", "--redact", "[0-9]+"]));
+    }
+
+    #[cfg(feature = "markdown")]
+    #[test]
+    fn markdown_conflicts_with_word_mode() {
+        assert!(conflict(&["-w", "--markdown"]));
+    }
+}
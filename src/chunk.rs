@@ -0,0 +1,85 @@
+use unicode_width::UnicodeWidthChar;
+
+/// 按显示宽度将字符串切分为块，供 `slow_scan_write_by_chunks` 使用
+///
+/// 每个块的显示宽度不超过 `target_cols`，只在字符边界处切分，因此宽度不足以
+/// 容纳下一个全宽字符时块会略窄于目标；单个字符宽于目标时独占一块。全宽字符
+/// 按两列计算，控制字符按 0 列计算。`target_cols` 为 0 时视为 1
+///
+/// # 参数
+/// - `s`: 要切分的字符串
+/// - `target_cols`: 每个块的目标显示宽度
+///
+/// # 示例
+/// ```
+/// use slow_scan_print::chunk_by_width;
+///
+/// let chunks = chunk_by_width("ab世界cd界", 3);
+///
+/// assert_eq!(chunks, ["ab", "世", "界c", "d界"]);
+/// assert_eq!(chunks.concat(), "ab世界cd界");
+/// ```
+///
+/// ---
+///
+/// Split a string into chunks by display width, for use with
+/// `slow_scan_write_by_chunks`
+///
+/// The display width of every chunk does not exceed `target_cols`. Strings
+/// are only split at character boundaries, so a chunk is slightly narrower
+/// than the target when the next full-width character does not fit; a single
+/// character wider than the target gets a chunk of its own. Full-width
+/// characters count as two columns and control characters as zero. A
+/// `target_cols` of 0 is treated as 1
+///
+/// # Arguments
+/// - `s`: String to split
+/// - `target_cols`: Target display width of each chunk
+pub fn chunk_by_width(s: &str, target_cols: usize) -> Vec<&str> {
+    let target_cols = target_cols.max(1);
+    let mut chunks = Vec::new();
+    let mut start = 0;
+    let mut width = 0;
+
+    for (i, it) in s.char_indices() {
+        let char_width = it.width_cjk().unwrap_or(0);
+
+        if width + char_width > target_cols && i > start {
+            chunks.push(&s[start..i]);
+            start = i;
+            width = 0;
+        }
+
+        width += char_width;
+    }
+
+    if start < s.len() {
+        chunks.push(&s[start..]);
+    }
+
+    chunks
+}
+
+/// 带有同步点的块迭代器中的一个单元，供
+/// `slow_scan_write_by_chunks_with_barriers` 使用
+///
+/// ---
+///
+/// A single unit of a chunk iterator with synchronization points, for use
+/// with `slow_scan_write_by_chunks_with_barriers`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ChunkUnit<T> {
+    /// 按正常节奏写入的数据块
+    ///
+    /// ---
+    ///
+    /// Chunk of data written at the normal pace
+    Data(T),
+    /// 同步点，刷新输出并等待回调完成后才继续
+    ///
+    /// ---
+    ///
+    /// Synchronization point, flushing the output and waiting for the
+    /// callback to complete before continuing
+    Barrier
+}
@@ -0,0 +1,110 @@
+use crate::SlowScanConfig;
+
+/// 按配置决定字符延迟的分类
+///
+/// 与 [`CharClass`](crate::CharClass) 只按显示宽度分类不同，这里还区分了使用
+/// 单独延迟设置的字符，分类的优先顺序与 `slow_scan_write_by_chars` 选择延迟的
+/// 顺序一致。[`Space`](Self::Space) 和 [`Uppercase`](Self::Uppercase) 只在设置
+/// 了对应的延迟时出现
+///
+/// ---
+///
+/// Classification of a character deciding its delay according to the
+/// configuration
+///
+/// Unlike [`CharClass`](crate::CharClass), which only classifies by display
+/// width, characters using separate delay settings are distinguished as well,
+/// in the same order of precedence in which `slow_scan_write_by_chars` chooses
+/// delays. [`Space`](Self::Space) and [`Uppercase`](Self::Uppercase) only
+/// appear when the matching delay is set
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum DelayClass {
+    /// 半角字符，使用 `base_delay`
+    ///
+    /// ---
+    ///
+    /// Half-width character, using `base_delay`
+    HalfWidth,
+    /// 全角字符，使用 `full_width_delay`
+    ///
+    /// ---
+    ///
+    /// Full-width character, using `full_width_delay`
+    FullWidth,
+    /// 换行符以外的控制字符，使用 `control_char_delay`
+    ///
+    /// ---
+    ///
+    /// Control character other than line breaks, using `control_char_delay`
+    Control,
+    /// 换行符，启用 `unicode_lines` 时包括 Unicode 行分隔符和段落分隔符，使用
+    /// `control_char_delay`
+    ///
+    /// ---
+    ///
+    /// Line break, including the Unicode line and paragraph separators when
+    /// `unicode_lines` is enabled, using `control_char_delay`
+    Newline,
+    /// 空白字符，使用 `space_delay`
+    ///
+    /// ---
+    ///
+    /// Whitespace character, using `space_delay`
+    Space,
+    /// 大写字母，使用 `uppercase_delay`
+    ///
+    /// ---
+    ///
+    /// Uppercase letter, using `uppercase_delay`
+    Uppercase,
+    /// 私用区字符，使用 `pua_delay`，未设置时使用 `base_delay`
+    ///
+    /// ---
+    ///
+    /// Private use character, using `pua_delay`, or `base_delay` when unset
+    PrivateUse
+}
+
+/// 按配置将字符流逐个分类为 `(字符, 延迟分类)`
+///
+/// # 示例
+/// ```
+/// use std::time::Duration;
+///
+/// use slow_scan_print::{DelayClass, SlowScanConfig, classify_chars};
+///
+/// let mut config = SlowScanConfig::default();
+/// config.set_uppercase_delay(Some(Duration::from_millis(30)));
+///
+/// let classes = classify_chars("Ab 世\t\n\u{e000}".chars(), &config)
+///     .map(|(_, it)| it)
+///     .collect::<Vec<_>>();
+///
+/// assert_eq!(
+///     classes,
+///     [
+///         DelayClass::Uppercase,
+///         DelayClass::HalfWidth,
+///         DelayClass::HalfWidth,
+///         DelayClass::FullWidth,
+///         DelayClass::Control,
+///         DelayClass::Newline,
+///         DelayClass::PrivateUse
+///     ]
+/// );
+/// ```
+///
+/// ---
+///
+/// Classify a stream of characters one by one into `(character, delay class)`
+/// according to the configuration
+pub fn classify_chars<I>(
+    iter: I,
+    config: &SlowScanConfig
+) -> impl Iterator<Item = (char, DelayClass)>
+where
+    I: Iterator<Item = char>
+{
+    let config = *config;
+    iter.map(move |it| (it, config.delay_class(it)))
+}
@@ -0,0 +1,474 @@
+use std::io::{self, Write};
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
+#[cfg(not(feature = "unstable"))]
+use std::thread::sleep;
+#[cfg(feature = "unstable")]
+use std::thread::sleep_until;
+use std::time::{Duration, Instant};
+
+/// 低于该时长的等待改为忙等，约等于平台默认的休眠粒度
+///
+/// ---
+///
+/// Waits shorter than this are busy-waited, roughly the default sleep
+/// granularity of the platform
+#[cfg(all(feature = "precise-short-sleep", windows))]
+const SPIN_THRESHOLD: Duration = Duration::from_millis(16);
+#[cfg(all(feature = "precise-short-sleep", not(windows)))]
+const SPIN_THRESHOLD: Duration = Duration::from_millis(2);
+
+/// 可中断的时钟每次最多连续休眠的时长
+///
+/// ---
+///
+/// Longest stretch an interruptible clock sleeps without checking its flag
+const INTERRUPT_SLICE: Duration = Duration::from_millis(50);
+
+/// 为慢速扫描输出提供时间来源的 trait
+///
+/// 默认使用 [`SystemClock`]，替换为自定义实现后可以在模拟环境中以虚拟时间驱动
+/// 输出，或在测试中精确检查每次延迟的时间点
+///
+/// # 示例
+/// ```
+/// use std::time::{Duration, Instant};
+///
+/// use slow_scan_print::{Clock, SlowScanConfig, SlowScanWrite};
+///
+/// struct VirtualClock {
+///     now: Instant,
+///     deadlines: Vec<Instant>
+/// }
+///
+/// impl Clock for VirtualClock {
+///     fn now(&mut self) -> Instant { self.now }
+///
+///     fn sleep_until(&mut self, deadline: Instant) {
+///         self.now = self.now.max(deadline);
+///         self.deadlines.push(deadline);
+///     }
+/// }
+///
+/// let start = Instant::now();
+/// let mut clock = VirtualClock {
+///     now: start,
+///     deadlines: Vec::new()
+/// };
+///
+/// let mut config = SlowScanConfig::default();
+/// config.set_base_delay(Duration::from_secs(1));
+///
+/// let mut writer = Vec::new();
+/// writer
+///     .slow_scan_write_by_chars_with_clock("abc".chars(), config, &mut clock)
+///     .unwrap();
+///
+/// assert_eq!(
+///     clock.deadlines,
+///     [
+///         start + Duration::from_secs(1),
+///         start + Duration::from_secs(2)
+///     ]
+/// );
+/// ```
+///
+/// ---
+///
+/// A trait providing the time source for slow scan output
+///
+/// [`SystemClock`] is used by default. Replacing it with a custom
+/// implementation allows driving the output with virtual time in simulations,
+/// or checking the exact instant of every delay in tests
+pub trait Clock {
+    /// 返回当前时间点
+    ///
+    /// ---
+    ///
+    /// Return the current instant
+    fn now(&mut self) -> Instant;
+
+    /// 阻塞直到指定的时间点
+    ///
+    /// ---
+    ///
+    /// Block until the given instant
+    fn sleep_until(&mut self, deadline: Instant);
+}
+
+/// 使用系统时间和线程休眠的时钟
+///
+/// - 默认情况下使用 `std::thread::sleep`，延迟精度受系统调度影响
+/// - 启用 `unstable` 特性后使用 `std::thread::sleep_until`
+/// - 启用 `precise-short-sleep` 特性后，短于平台休眠粒度（Windows 上约 16ms，
+///   其他平台约 2ms）的等待会改为配合 `std::hint::spin_loop` 和
+///   `std::thread::yield_now` 的忙等，以在 musl 等休眠粒度较粗的目标上保证亚毫秒
+///   级的节奏。忙等期间会占用一个 CPU 核心，因此只建议在确实需要很短延迟时启用
+///
+/// ---
+///
+/// Clock using the system time and thread sleeping
+///
+/// - By default uses `std::thread::sleep` with precision affected by system
+///   scheduling
+/// - When `unstable` feature is enabled, uses `std::thread::sleep_until`
+/// - When `precise-short-sleep` feature is enabled, waits shorter than the
+///   platform sleep granularity (about 16ms on Windows, 2ms elsewhere) are
+///   busy-waited with `std::hint::spin_loop` and `std::thread::yield_now`,
+///   honoring sub-millisecond pacing on targets with coarse sleep granularity
+///   such as musl. Busy waiting keeps a CPU core occupied, so only enable it
+///   when very short delays are really needed
+#[derive(Debug, Clone, Copy, Default)]
+pub struct SystemClock;
+
+impl Clock for SystemClock {
+    fn now(&mut self) -> Instant { Instant::now() }
+
+    fn sleep_until(&mut self, deadline: Instant) {
+        #[cfg(feature = "precise-short-sleep")]
+        if spins(deadline, Instant::now()) {
+            while Instant::now() < deadline {
+                std::hint::spin_loop();
+                std::thread::yield_now();
+            }
+
+            return;
+        }
+
+        #[cfg(not(feature = "unstable"))]
+        sleep(deadline.saturating_duration_since(Instant::now()));
+        #[cfg(feature = "unstable")]
+        sleep_until(deadline);
+    }
+}
+
+/// 判断从 `now` 到 `deadline` 的等待是否短到需要忙等
+///
+/// ---
+///
+/// Determine whether the wait from `now` to `deadline` is short enough to be
+/// busy-waited
+#[cfg(feature = "precise-short-sleep")]
+fn spins(deadline: Instant, now: Instant) -> bool {
+    deadline.saturating_duration_since(now) < SPIN_THRESHOLD
+}
+
+/// 测试使用的虚拟时钟
+///
+/// 等待时不实际休眠，只把当前时间推进到截止时间并记录下来，与 [`Clock`] 示例
+/// 中的实现相同
+///
+/// ---
+///
+/// Virtual clock used by tests
+///
+/// Waiting does not actually sleep but moves the current time to the deadline
+/// and records it, the same as the implementation in the [`Clock`] example
+#[cfg(test)]
+#[derive(Debug, Clone)]
+pub(crate) struct VirtualClock {
+    /// 当前的虚拟时间
+    ///
+    /// ---
+    ///
+    /// Current virtual time
+    pub now: Instant,
+    /// 按顺序记录的每次等待的截止时间
+    ///
+    /// ---
+    ///
+    /// Deadline of every wait, recorded in order
+    pub deadlines: Vec<Instant>
+}
+
+#[cfg(test)]
+impl VirtualClock {
+    /// 创建从指定时间开始的虚拟时钟
+    ///
+    /// ---
+    ///
+    /// Create a virtual clock starting at the given instant
+    pub fn new(now: Instant) -> Self {
+        Self {
+            now,
+            deadlines: Vec::new()
+        }
+    }
+}
+
+#[cfg(test)]
+impl Clock for VirtualClock {
+    fn now(&mut self) -> Instant { self.now }
+
+    fn sleep_until(&mut self, deadline: Instant) {
+        self.now = self.now.max(deadline);
+        self.deadlines.push(deadline);
+    }
+}
+
+/// 可以被标志位中断的时钟
+///
+/// 把较长的等待拆分为不超过 50ms 的多段交给内部时钟完成，每段之前检查
+/// `flag`；标志位被设置后当前等待会在一段之内返回，之后的等待也都立即返回。
+/// 适合在信号处理函数中设置标志位，使数秒的单元延迟也能及时响应 Ctrl-C
+///
+/// # 示例
+/// ```
+/// use std::sync::Arc;
+/// use std::sync::atomic::{AtomicBool, Ordering};
+/// use std::time::{Duration, Instant};
+///
+/// use slow_scan_print::{Clock, InterruptibleClock};
+///
+/// /// 在虚拟时间到达 `interrupt_at` 时设置标志位的时钟
+/// struct SignallingClock {
+///     now: Instant,
+///     interrupt_at: Instant,
+///     flag: Arc<AtomicBool>,
+///     deadlines: Vec<Instant>
+/// }
+///
+/// impl Clock for SignallingClock {
+///     fn now(&mut self) -> Instant { self.now }
+///
+///     fn sleep_until(&mut self, deadline: Instant) {
+///         self.now = self.now.max(deadline);
+///         self.deadlines.push(deadline);
+///         if self.now >= self.interrupt_at {
+///             self.flag.store(true, Ordering::Release);
+///         }
+///     }
+/// }
+///
+/// let start = Instant::now();
+/// let ms = Duration::from_millis;
+/// let flag = Arc::new(AtomicBool::new(false));
+/// let mut clock = InterruptibleClock::new(
+///     SignallingClock {
+///         now: start,
+///         interrupt_at: start + ms(120),
+///         flag: flag.clone(),
+///         deadlines: Vec::new()
+///     },
+///     flag
+/// );
+///
+/// // 10 秒的等待在中断后的下一段结束时返回
+/// // A 10 second wait returns at the end of the slice following the interrupt
+/// clock.sleep_until(start + Duration::from_secs(10));
+/// assert!(clock.is_interrupted());
+/// assert_eq!(
+///     clock.inner().deadlines,
+///     [start + ms(50), start + ms(100), start + ms(150)]
+/// );
+///
+/// clock.sleep_until(start + Duration::from_secs(20));
+/// assert_eq!(clock.inner().deadlines.len(), 3);
+/// ```
+///
+/// ---
+///
+/// Clock that can be interrupted by a flag
+///
+/// Long waits are split into slices of at most 50ms handed to the inner clock,
+/// checking `flag` before each one; once the flag is set the current wait
+/// returns within a slice and all later waits return immediately. Setting the
+/// flag from a signal handler lets even multi-second per-unit delays react to
+/// Ctrl-C promptly
+#[derive(Debug, Clone)]
+pub struct InterruptibleClock<C = SystemClock> {
+    inner: C,
+    flag: Arc<AtomicBool>
+}
+
+impl<C: Clock> InterruptibleClock<C> {
+    pub fn new(inner: C, flag: Arc<AtomicBool>) -> Self { Self { inner, flag } }
+
+    /// 返回内部时钟的引用
+    ///
+    /// ---
+    ///
+    /// Return a reference to the inner clock
+    pub fn inner(&self) -> &C { &self.inner }
+
+    /// 标志位是否已被设置
+    ///
+    /// ---
+    ///
+    /// Whether the flag has been set
+    pub fn is_interrupted(&self) -> bool { self.flag.load(Ordering::Acquire) }
+}
+
+impl<C: Clock> Clock for InterruptibleClock<C> {
+    fn now(&mut self) -> Instant { self.inner.now() }
+
+    fn sleep_until(&mut self, deadline: Instant) {
+        while !self.is_interrupted() {
+            let now = self.inner.now();
+            if now >= deadline {
+                break;
+            }
+
+            self.inner.sleep_until(deadline.min(now + INTERRUPT_SLICE));
+        }
+    }
+}
+
+/// 记录每次等待的计划时间点和实际结束时间点的时钟
+///
+/// 等待交给内部时钟完成，每次等待结束后记录一行 `(计划偏移, 实际偏移)`，偏移
+/// 都相对于时钟创建的时间点。计划偏移即写入方法请求的截止时间，实际偏移为等待
+/// 返回时内部时钟的当前时间，两者之差就是该次等待的误差。每个之后带有延迟的
+/// 单元对应一行，可以用 [`write_csv`](Self::write_csv) 导出以便绘图分析
+///
+/// # 示例
+/// ```
+/// use std::time::{Duration, Instant};
+///
+/// use slow_scan_print::{Clock, SlowScanConfig, SlowScanWrite, TraceClock};
+///
+/// /// 每次等待都多睡 1ms 的时钟
+/// struct LaggingClock {
+///     now: Instant
+/// }
+///
+/// impl Clock for LaggingClock {
+///     fn now(&mut self) -> Instant { self.now }
+///
+///     fn sleep_until(&mut self, deadline: Instant) {
+///         self.now = self.now.max(deadline) + Duration::from_millis(1);
+///     }
+/// }
+///
+/// let mut clock = TraceClock::new(LaggingClock {
+///     now: Instant::now()
+/// });
+///
+/// let mut config = SlowScanConfig::default();
+/// config
+///     .set_base_delay(Duration::from_millis(10))
+///     .set_tail_delay(true);
+///
+/// let mut writer = Vec::new();
+/// writer
+///     .slow_scan_write_by_chars_with_clock("abc".chars(), config, &mut clock)
+///     .unwrap();
+///
+/// let mut csv = Vec::new();
+/// clock.write_csv(&mut csv).unwrap();
+///
+/// // 每个单元一行，误差在稳定版的路径上逐次累积
+/// // One row per unit, the error adding up on the stable path
+/// let csv = String::from_utf8(csv).unwrap();
+/// let mut lines = csv.lines();
+/// assert_eq!(
+///     lines.next(),
+///     Some("unit_index,scheduled_offset_ms,actual_offset_ms")
+/// );
+/// assert_eq!(lines.clone().count(), 3);
+/// assert!(lines.all(|it| it.split(',').count() == 3));
+/// # #[cfg(not(feature = "unstable"))]
+/// assert_eq!(csv.lines().nth(3), Some("2,32.000,33.000"));
+/// ```
+///
+/// ---
+///
+/// Clock recording the scheduled and the actual end of every wait
+///
+/// Waits are handed to the inner clock, and a row of `(scheduled offset,
+/// actual offset)` is recorded after each one, both offsets being relative to
+/// the instant the clock was created. The scheduled offset is the deadline
+/// requested by the write method and the actual offset is the current time of
+/// the inner clock when the wait returns, their difference being the error of
+/// that wait. Every unit followed by a delay makes one row, which can be
+/// exported with [`write_csv`](Self::write_csv) for plotting
+#[derive(Debug, Clone)]
+pub struct TraceClock<C = SystemClock> {
+    inner: C,
+    start: Instant,
+    rows: Vec<(Duration, Duration)>
+}
+
+impl<C: Clock> TraceClock<C> {
+    /// 创建记录等待的时钟，以当前时间作为偏移的起点
+    ///
+    /// ---
+    ///
+    /// Create a clock recording waits, offsets starting from the current time
+    pub fn new(mut inner: C) -> Self {
+        Self {
+            start: inner.now(),
+            inner,
+            rows: Vec::new()
+        }
+    }
+
+    /// 返回记录的 `(计划偏移, 实际偏移)`
+    ///
+    /// ---
+    ///
+    /// Return the recorded `(scheduled offset, actual offset)` pairs
+    pub fn rows(&self) -> &[(Duration, Duration)] { &self.rows }
+
+    /// 以 CSV 格式写入记录，列为 `unit_index,scheduled_offset_ms,actual_offset_ms`，
+    /// 偏移以毫秒为单位并保留三位小数
+    ///
+    /// ---
+    ///
+    /// Write the records as CSV with the columns
+    /// `unit_index,scheduled_offset_ms,actual_offset_ms`, the offsets being in
+    /// milliseconds with three decimals
+    pub fn write_csv<W: Write>(&self, mut writer: W) -> io::Result<()> {
+        writeln!(writer, "unit_index,scheduled_offset_ms,actual_offset_ms")?;
+        for (i, (scheduled, actual)) in self.rows.iter().enumerate() {
+            writeln!(
+                writer,
+                "{},{:.3},{:.3}",
+                i,
+                scheduled.as_secs_f64() * 1000.0,
+                actual.as_secs_f64() * 1000.0
+            )?;
+        }
+
+        writer.flush()
+    }
+}
+
+impl<C: Clock> Clock for TraceClock<C> {
+    fn now(&mut self) -> Instant { self.inner.now() }
+
+    fn sleep_until(&mut self, deadline: Instant) {
+        self.inner.sleep_until(deadline);
+
+        let actual = self.inner.now();
+        self.rows.push((
+            deadline.saturating_duration_since(self.start),
+            actual.saturating_duration_since(self.start)
+        ));
+    }
+}
+
+#[cfg(all(test, feature = "precise-short-sleep"))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn spins_only_below_threshold() {
+        let now = Instant::now();
+
+        assert!(spins(now, now));
+        assert!(spins(now + SPIN_THRESHOLD / 2, now));
+        assert!(!spins(now + SPIN_THRESHOLD, now));
+        assert!(!spins(now + SPIN_THRESHOLD * 2, now));
+    }
+
+    #[test]
+    fn waits_until_deadline_on_both_sides_of_threshold() {
+        for delay in [SPIN_THRESHOLD / 4, SPIN_THRESHOLD * 2] {
+            let deadline = Instant::now() + delay;
+            SystemClock.sleep_until(deadline);
+
+            assert!(Instant::now() >= deadline, "{:?}", delay);
+        }
+    }
+}
@@ -0,0 +1,101 @@
+use std::collections::VecDeque;
+use std::iter::Peekable;
+
+/// 只保留每行最后一次回车之后内容的字符迭代器适配器
+///
+/// 以 `\r` 原地刷新的进度输出（如 `10%\r50%\r100%`）只会留下最终状态，中间的
+/// 更新和 `\r` 本身都被丢弃；`\r\n` 仍视为换行。每行需要读完后才能确定最终
+/// 状态，因此按行缓冲。由 [`collapse_cr`] 创建
+///
+/// ---
+///
+/// Character iterator adapter keeping only what follows the last carriage
+/// return of every line
+///
+/// Progress output refreshed in place with `\r` (such as `10%\r50%\r100%`)
+/// is reduced to its final state, discarding the intermediate updates and the
+/// `\r` themselves; `\r\n` is still treated as a line break. The final state
+/// is only known once a line has been read, so lines are buffered. Created by
+/// [`collapse_cr`]
+#[derive(Debug, Clone)]
+pub struct CollapseCr<I: Iterator<Item = char>> {
+    iter: Peekable<I>,
+    out: VecDeque<char>
+}
+
+/// 将以 `\r` 原地刷新的行折叠为最后的状态
+///
+/// # 参数
+/// - `iter`: 字符迭代器
+///
+/// # 示例
+/// ```
+/// use std::time::Duration;
+///
+/// use slow_scan_print::{SlowScanConfig, SlowScanWrite, collapse_cr};
+///
+/// assert_eq!(
+///     collapse_cr("a\r\nx\ry\n".chars()).collect::<String>(),
+///     "a\r\ny\n"
+/// );
+///
+/// let mut config = SlowScanConfig::default();
+/// config.set_base_delay(Duration::ZERO);
+///
+/// let mut revealed = String::new();
+/// let mut writer = Vec::new();
+/// writer
+///     .slow_scan_write_by_chars_with_hook(
+///         collapse_cr("10%\r50%\r100%".chars()),
+///         config,
+///         |_, it| {
+///             revealed.push(it);
+///             Ok(())
+///         }
+///     )
+///     .unwrap();
+///
+/// assert_eq!(revealed, "100%");
+/// assert_eq!(writer, b"100%");
+/// ```
+///
+/// ---
+///
+/// Collapse lines refreshed in place with `\r` to their final state
+///
+/// # Arguments
+/// - `iter`: Iterator of characters
+pub fn collapse_cr<I>(iter: I) -> CollapseCr<I::IntoIter>
+where
+    I: IntoIterator<Item = char>
+{
+    CollapseCr {
+        iter: iter.into_iter().peekable(),
+        out: VecDeque::new()
+    }
+}
+
+impl<I: Iterator<Item = char>> Iterator for CollapseCr<I> {
+    type Item = char;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.out.is_empty() {
+            // 读完一整行，每遇到单独的 `\r` 就丢弃之前的内容
+            loop {
+                match self.iter.next() {
+                    Some('\r') if self.iter.peek() != Some(&'\n') => {
+                        self.out.clear();
+                    }
+                    Some('\n') => {
+                        self.out.push_back('\n');
+                        break;
+                    }
+                    Some(it) => self.out.push_back(it),
+                    None => break
+                }
+            }
+        }
+
+        self.out.pop_front()
+    }
+}
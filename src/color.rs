@@ -0,0 +1,411 @@
+use std::env;
+use std::io::{self, Write};
+
+/// 缓冲的控制序列的最大长度，超过时原样输出
+///
+/// ---
+///
+/// Maximum length of a buffered control sequence, beyond which it is written
+/// unchanged
+const MAX_SEQUENCE_LEN: usize = 64;
+
+/// xterm 默认调色板中 16 种基本颜色的 RGB 值
+///
+/// ---
+///
+/// RGB values of the 16 basic colors in the default xterm palette
+const ANSI16: [(u8, u8, u8); 16] = [
+    (0, 0, 0),
+    (205, 0, 0),
+    (0, 205, 0),
+    (205, 205, 0),
+    (0, 0, 238),
+    (205, 0, 205),
+    (0, 205, 205),
+    (229, 229, 229),
+    (127, 127, 127),
+    (255, 0, 0),
+    (0, 255, 0),
+    (255, 255, 0),
+    (92, 92, 255),
+    (255, 0, 255),
+    (0, 255, 255),
+    (255, 255, 255)
+];
+
+/// 256 色调色板中 6×6×6 颜色立方体每个分量的取值
+///
+/// ---
+///
+/// Values of every component of the 6×6×6 color cube in the 256-color palette
+const CUBE: [u8; 6] = [0, 95, 135, 175, 215, 255];
+
+/// 终端支持的颜色深度
+///
+/// ---
+///
+/// Color depth supported by a terminal
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum ColorDepth {
+    /// 16 种基本颜色
+    ///
+    /// ---
+    ///
+    /// 16 basic colors
+    Ansi16,
+    /// 256 色调色板
+    ///
+    /// ---
+    ///
+    /// 256-color palette
+    Ansi256,
+    /// 24 位真彩色
+    ///
+    /// ---
+    ///
+    /// 24-bit truecolor
+    TrueColor
+}
+
+impl ColorDepth {
+    /// 根据 `COLORTERM` 和 `TERM` 环境变量检测终端的颜色深度
+    ///
+    /// `COLORTERM` 为 `truecolor` 或 `24bit` 时为真彩色，`TERM` 包含
+    /// `256color` 时为 256 色，否则为 16 色
+    ///
+    /// ---
+    ///
+    /// Detect the color depth of the terminal from the `COLORTERM` and `TERM`
+    /// environment variables
+    ///
+    /// It is truecolor when `COLORTERM` is `truecolor` or `24bit`, 256 colors
+    /// when `TERM` contains `256color`, and 16 colors otherwise
+    pub fn detect() -> Self {
+        let colorterm = env::var("COLORTERM").unwrap_or_default();
+        let term = env::var("TERM").unwrap_or_default();
+
+        if matches!(colorterm.as_str(), "truecolor" | "24bit") {
+            Self::TrueColor
+        } else if term.contains("256color") {
+            Self::Ansi256
+        } else {
+            Self::Ansi16
+        }
+    }
+}
+
+/// 将 SGR 序列的参数降级到指定的颜色深度
+///
+/// 真彩色（`38;2;R;G;B`、`48;2;R;G;B`）和 256 色（`38;5;N`、`48;5;N`）的前景
+/// 和背景颜色会被替换为目标颜色深度中最接近的颜色，其余参数保持不变
+///
+/// # 示例
+/// ```
+/// use slow_scan_print::{ColorDepth, downgrade_sgr};
+///
+/// assert_eq!(downgrade_sgr("1;38;2;255;0;0", ColorDepth::Ansi16), "1;91");
+/// assert_eq!(downgrade_sgr("48;2;0;0;0", ColorDepth::Ansi16), "40");
+/// assert_eq!(
+///     downgrade_sgr("38;2;255;0;0", ColorDepth::Ansi256),
+///     "38;5;196"
+/// );
+/// assert_eq!(downgrade_sgr("38;5;196", ColorDepth::Ansi16), "91");
+/// assert_eq!(
+///     downgrade_sgr("38;2;255;0;0", ColorDepth::TrueColor),
+///     "38;2;255;0;0"
+/// );
+/// ```
+///
+/// ---
+///
+/// Downgrade the parameters of an SGR sequence to the given color depth
+///
+/// Truecolor (`38;2;R;G;B`, `48;2;R;G;B`) and 256-color (`38;5;N`, `48;5;N`)
+/// foreground and background colors are replaced with the nearest color of
+/// the target depth, and other parameters are kept unchanged
+pub fn downgrade_sgr(params: &str, depth: ColorDepth) -> String {
+    let params = params.split(';').collect::<Vec<_>>();
+    let mut result = Vec::with_capacity(params.len());
+    let mut i = 0;
+
+    while i < params.len() {
+        let extended = match params[i] {
+            "38" => Some(30),
+            "48" => Some(40),
+            _ => None
+        };
+        let parse = |offset: usize| {
+            params.get(i + offset).and_then(|it| it.parse::<u8>().ok())
+        };
+
+        let color = match (extended, params.get(i + 1).copied()) {
+            (Some(base), Some("2")) => parse(2)
+                .zip(parse(3))
+                .zip(parse(4))
+                .map(|((r, g), b)| (base, 5, Color::Rgb(r, g, b))),
+            (Some(base), Some("5")) => {
+                parse(2).map(|it| (base, 3, Color::Indexed(it)))
+            }
+            _ => None
+        };
+
+        match color {
+            Some((base, len, color)) => {
+                result.push(color.sgr(base, depth));
+                i += len;
+            }
+            None => {
+                result.push(params[i].to_string());
+                i += 1;
+            }
+        }
+    }
+
+    result.join(";")
+}
+
+/// SGR 序列中的扩展颜色
+///
+/// ---
+///
+/// Extended color in an SGR sequence
+#[derive(Debug, Clone, Copy)]
+enum Color {
+    Rgb(u8, u8, u8),
+    Indexed(u8)
+}
+
+impl Color {
+    /// 返回以 `base`（前景为 30，背景为 40）表示该颜色在指定颜色深度下的 SGR
+    /// 参数
+    ///
+    /// ---
+    ///
+    /// Return the SGR parameters of this color at the given depth, with
+    /// `base` being 30 for the foreground and 40 for the background
+    fn sgr(self, base: u8, depth: ColorDepth) -> String {
+        let extended = base + 8;
+
+        match (self, depth) {
+            (Self::Rgb(r, g, b), ColorDepth::TrueColor) => {
+                format!("{};2;{};{};{}", extended, r, g, b)
+            }
+            (
+                Self::Indexed(it),
+                ColorDepth::TrueColor | ColorDepth::Ansi256
+            ) => {
+                format!("{};5;{}", extended, it)
+            }
+            (Self::Rgb(r, g, b), ColorDepth::Ansi256) => {
+                format!("{};5;{}", extended, nearest_256((r, g, b)))
+            }
+            (Self::Indexed(it), ColorDepth::Ansi16) if it < 16 => {
+                ansi16(base, it)
+            }
+            (Self::Indexed(it), ColorDepth::Ansi16) => {
+                ansi16(base, nearest_16(indexed_rgb(it)))
+            }
+            (Self::Rgb(r, g, b), ColorDepth::Ansi16) => {
+                ansi16(base, nearest_16((r, g, b)))
+            }
+        }
+    }
+}
+
+/// 返回 16 种基本颜色之一的 SGR 参数，亮色使用 90 或 100 起始的参数
+///
+/// ---
+///
+/// Return the SGR parameter of one of the 16 basic colors, bright colors
+/// using the parameters starting at 90 or 100
+fn ansi16(base: u8, index: u8) -> String {
+    if index < 8 {
+        (base + index).to_string()
+    } else {
+        (base + 60 + index - 8).to_string()
+    }
+}
+
+/// 返回 256 色调色板中某个颜色的 RGB 值
+///
+/// ---
+///
+/// Return the RGB value of a color in the 256-color palette
+fn indexed_rgb(index: u8) -> (u8, u8, u8) {
+    match index {
+        0..=15 => ANSI16[index as usize],
+        16..=231 => {
+            let it = index - 16;
+            (
+                CUBE[(it / 36) as usize],
+                CUBE[(it / 6 % 6) as usize],
+                CUBE[(it % 6) as usize]
+            )
+        }
+        _ => {
+            let level = 8 + (index - 232) * 10;
+            (level, level, level)
+        }
+    }
+}
+
+/// 返回 16 种基本颜色中与给定颜色最接近的颜色的序号
+///
+/// ---
+///
+/// Return the index of the basic color nearest to the given color
+fn nearest_16(rgb: (u8, u8, u8)) -> u8 {
+    (0..16)
+        .min_by_key(|it| distance(rgb, ANSI16[*it as usize]))
+        .unwrap_or(0)
+}
+
+/// 返回 256 色调色板中除 16 种基本颜色之外与给定颜色最接近的颜色的序号
+///
+/// ---
+///
+/// Return the index of the color nearest to the given one in the 256-color
+/// palette, apart from the 16 basic colors
+fn nearest_256(rgb: (u8, u8, u8)) -> u8 {
+    (16..=255)
+        .min_by_key(|it| distance(rgb, indexed_rgb(*it)))
+        .unwrap_or(16)
+}
+
+/// 返回两个颜色的欧氏距离的平方
+///
+/// ---
+///
+/// Return the squared Euclidean distance between two colors
+fn distance(a: (u8, u8, u8), b: (u8, u8, u8)) -> u32 {
+    let component = |a: u8, b: u8| (a as i32 - b as i32).pow(2) as u32;
+
+    component(a.0, b.0) + component(a.1, b.1) + component(a.2, b.2)
+}
+
+/// 将写入的 SGR 颜色序列降级到指定颜色深度的写入器包装
+///
+/// 控制序列即使被拆分到多次写入中也能被识别；颜色深度为真彩色时数据原样写入
+///
+/// # 示例
+/// ```
+/// use std::io::Write;
+///
+/// use slow_scan_print::{ColorDepth, ColorDowngrade};
+///
+/// let mut writer = ColorDowngrade::new(Vec::new(), ColorDepth::Ansi16);
+/// for it in "\x1b[38;2;255;0;0mred\x1b[0m".bytes() {
+///     writer.write_all(&[it]).unwrap();
+/// }
+///
+/// assert_eq!(writer.into_inner(), b"\x1b[91mred\x1b[0m");
+/// ```
+///
+/// ---
+///
+/// Writer wrapper downgrading the SGR color sequences written to the given
+/// color depth
+///
+/// Control sequences are recognized even when split across several writes;
+/// data is written unchanged when the color depth is truecolor
+#[derive(Debug)]
+pub struct ColorDowngrade<W: Write> {
+    inner: W,
+    depth: ColorDepth,
+    /// 尚未结束的控制序列，以 `ESC` 开头
+    ///
+    /// ---
+    ///
+    /// Control sequence not terminated yet, starting with `ESC`
+    pending: Vec<u8>
+}
+
+impl<W: Write> ColorDowngrade<W> {
+    pub fn new(inner: W, depth: ColorDepth) -> Self {
+        Self {
+            inner,
+            depth,
+            pending: Vec::new()
+        }
+    }
+
+    /// 取回内部的写入器，尚未结束的控制序列会被丢弃
+    ///
+    /// ---
+    ///
+    /// Take back the inner writer, discarding any unterminated control
+    /// sequence
+    pub fn into_inner(self) -> W { self.inner }
+
+    /// 处理控制序列中的一个字节，序列结束或无法识别时将其写入
+    ///
+    /// ---
+    ///
+    /// Process a byte of a control sequence, writing the sequence out once it
+    /// is terminated or not recognized
+    fn push(&mut self, byte: u8) -> io::Result<()> {
+        self.pending.push(byte);
+
+        match (self.pending.len(), byte) {
+            // 新的序列打断了尚未结束的序列
+            (_, 0x1B) => {
+                self.pending.pop();
+                self.write_pending()?;
+                self.pending.push(byte);
+                Ok(())
+            }
+            (2, b'[') => Ok(()),
+            (2, _) => self.write_pending(),
+            (_, 0x20..=0x3F) if self.pending.len() < MAX_SEQUENCE_LEN => Ok(()),
+            (_, b'm') => {
+                let params = &self.pending[2..self.pending.len() - 1];
+                // 私有参数和中间字节的序列不属于 SGR 颜色
+                if params.iter().all(|it| it.is_ascii_digit() || *it == b';') {
+                    let params = String::from_utf8_lossy(params);
+                    let params = downgrade_sgr(&params, self.depth);
+                    self.pending = format!("\x1b[{}m", params).into_bytes();
+                }
+                self.write_pending()
+            }
+            _ => self.write_pending()
+        }
+    }
+
+    fn write_pending(&mut self) -> io::Result<()> {
+        let pending = std::mem::take(&mut self.pending);
+        self.inner.write_all(&pending)
+    }
+}
+
+impl<W: Write> Write for ColorDowngrade<W> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        if self.depth == ColorDepth::TrueColor {
+            return self.inner.write(buf);
+        }
+
+        let mut rest = buf;
+        while !rest.is_empty() {
+            if !self.pending.is_empty() {
+                self.push(rest[0])?;
+                rest = &rest[1..];
+                continue;
+            }
+
+            match rest.iter().position(|it| *it == 0x1B) {
+                Some(i) => {
+                    self.inner.write_all(&rest[..i])?;
+                    self.pending.push(0x1B);
+                    rest = &rest[i + 1..];
+                }
+                None => {
+                    self.inner.write_all(rest)?;
+                    rest = &[];
+                }
+            }
+        }
+
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> { self.inner.flush() }
+}
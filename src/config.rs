@@ -0,0 +1,173 @@
+use std::borrow::Cow;
+use std::io::Read;
+use std::{fmt, fs, io};
+
+use rust_i18n::t;
+use serde::Deserialize;
+
+/// 从 TOML 配置文件读取的选项
+///
+/// 字段与同名命令行参数对应，命令行中显式给出的参数优先于配置文件
+///
+/// ---
+///
+/// Options read from a TOML configuration file
+///
+/// Fields correspond to the command line arguments of the same name, arguments
+/// given explicitly on the command line take precedence over the file
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(rename_all = "kebab-case", deny_unknown_fields)]
+pub(crate) struct ConfigFile {
+    pub delay: Option<String>,
+    pub full_width_delay: Option<String>,
+    pub control_char_delay: Option<String>,
+    pub tail_delay: Option<bool>,
+    pub inline_directives: Option<bool>,
+    pub line_mode: Option<bool>,
+    pub hide_cursor: Option<bool>
+}
+
+impl ConfigFile {
+    /// 通过 URI 字符串读取并解析配置文件
+    ///
+    /// # 参数
+    /// - `uri`: 配置文件路径（"-" 表示从标准输入读取）
+    ///
+    /// # 错误
+    /// 返回 [`Error`] 类型错误，包含无法读取或解析的原因
+    ///
+    /// ---
+    ///
+    /// Read and parse a configuration file by URI string
+    ///
+    /// # Arguments
+    /// - `uri`: Configuration file path ("-" for stdin)
+    ///
+    /// # Errors
+    /// Returns [`Error`] containing reasons when failing to read or parse
+    pub(crate) fn load(uri: &str) -> Result<Self, Error<'_>> {
+        if uri == "-" {
+            Self::from_reader(io::stdin().lock(), uri)
+        } else {
+            fs::File::open(uri)
+                .map_err(|it| Error {
+                    kind: ErrorKind::CannotRead,
+                    uri: Cow::Borrowed(uri),
+                    source: it.to_string()
+                })
+                .and_then(|it| Self::from_reader(it, uri))
+        }
+    }
+
+    /// 从读取器读取并解析配置，`uri` 仅用于错误信息
+    ///
+    /// ---
+    ///
+    /// Read and parse the configuration from a reader, `uri` is only used in
+    /// error messages
+    pub(crate) fn from_reader<R: Read>(
+        mut reader: R,
+        uri: &str
+    ) -> Result<Self, Error<'_>> {
+        let mut content = String::new();
+        reader.read_to_string(&mut content).map_err(|it| Error {
+            kind: ErrorKind::CannotRead,
+            uri: Cow::Borrowed(uri),
+            source: it.to_string()
+        })?;
+
+        toml::from_str(&content).map_err(|it| Error {
+            kind: ErrorKind::Invalid,
+            uri: Cow::Borrowed(uri),
+            source: it.to_string()
+        })
+    }
+}
+
+/// 配置文件错误类型
+///
+/// ---
+///
+/// Configuration file error types
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum ErrorKind {
+    /// 无法读取配置文件
+    ///
+    /// ---
+    ///
+    /// Failed to read the configuration file
+    CannotRead,
+    /// 配置文件内容无效
+    ///
+    /// ---
+    ///
+    /// The configuration file content is invalid
+    Invalid
+}
+
+/// 配置文件错误
+///
+/// 包含错误类型、相关 URI 和底层错误原因
+///
+/// ---
+///
+/// Configuration file error
+///
+/// Contains error type, related URI and underlying error cause
+#[derive(Debug)]
+pub(crate) struct Error<'a> {
+    kind: ErrorKind,
+    uri: Cow<'a, str>,
+    source: String
+}
+
+impl fmt::Display for Error<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let msg = match self.kind {
+            ErrorKind::CannotRead => t!(
+                "error.cannot_read_config",
+                uri = self.uri,
+                src = self.source
+            ),
+            ErrorKind::Invalid => {
+                t!("error.invalid_config", uri = self.uri, src = self.source)
+            }
+        };
+
+        f.write_str(msg.as_ref())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reads_fields_from_reader() {
+        let toml = b"delay = \"200ms\"\ntail-delay = true\nline-mode = true\n";
+        let config = ConfigFile::from_reader(&toml[..], "-").unwrap();
+
+        assert_eq!(config.delay.as_deref(), Some("200ms"));
+        assert_eq!(config.tail_delay, Some(true));
+        assert_eq!(config.line_mode, Some(true));
+        assert_eq!(config.full_width_delay, None);
+        assert_eq!(config.hide_cursor, None);
+    }
+
+    #[test]
+    fn rejects_unknown_fields() {
+        let error =
+            ConfigFile::from_reader(&b"speed = 2\n"[..], "-").unwrap_err();
+
+        assert_eq!(error.kind, ErrorKind::Invalid);
+        assert_eq!(error.uri, "-");
+    }
+
+    #[test]
+    fn reports_unreadable_input() {
+        let error =
+            ConfigFile::from_reader(&[0xFF, 0xFE][..], "-").unwrap_err();
+
+        assert_eq!(error.kind, ErrorKind::CannotRead);
+    }
+}
@@ -0,0 +1,104 @@
+use std::io;
+
+/// 可以隐藏和显示光标的终端
+///
+/// ---
+///
+/// Terminal able to hide and show the cursor
+pub trait CursorControl {
+    /// 隐藏光标
+    ///
+    /// ---
+    ///
+    /// Hide the cursor
+    fn hide_cursor(&self) -> io::Result<()>;
+
+    /// 显示光标
+    ///
+    /// ---
+    ///
+    /// Show the cursor
+    fn show_cursor(&self) -> io::Result<()>;
+}
+
+impl CursorControl for console::Term {
+    fn hide_cursor(&self) -> io::Result<()> { console::Term::hide_cursor(self) }
+
+    fn show_cursor(&self) -> io::Result<()> { console::Term::show_cursor(self) }
+}
+
+/// 在存在期间隐藏光标的守卫
+///
+/// 创建时隐藏光标，被丢弃时（包括因 panic 展开而被丢弃时）重新显示光标，
+/// 避免隐藏的光标在出错后遗留在终端中
+///
+/// # 示例
+/// ```
+/// use std::cell::Cell;
+/// use std::io;
+/// use std::panic::{self, AssertUnwindSafe};
+///
+/// use slow_scan_print::{CursorControl, CursorGuard};
+///
+/// #[derive(Default)]
+/// struct MockTerminal {
+///     hidden: Cell<bool>
+/// }
+///
+/// impl CursorControl for &MockTerminal {
+///     fn hide_cursor(&self) -> io::Result<()> {
+///         self.hidden.set(true);
+///         Ok(())
+///     }
+///
+///     fn show_cursor(&self) -> io::Result<()> {
+///         self.hidden.set(false);
+///         Ok(())
+///     }
+/// }
+///
+/// let term = MockTerminal::default();
+///
+/// let guard = CursorGuard::new(&term).unwrap();
+/// assert!(term.hidden.get());
+/// drop(guard);
+/// assert!(!term.hidden.get());
+///
+/// let result = panic::catch_unwind(AssertUnwindSafe(|| {
+///     let _guard = CursorGuard::new(&term).unwrap();
+///     assert!(term.hidden.get());
+///     panic!("interrupted");
+/// }));
+///
+/// assert!(result.is_err());
+/// assert!(!term.hidden.get());
+/// ```
+///
+/// ---
+///
+/// Guard hiding the cursor while it is alive
+///
+/// The cursor is hidden on construction and shown again on drop (including
+/// drops caused by panic unwinding), so that a hidden cursor is not left
+/// behind in the terminal after an error
+#[derive(Debug)]
+pub struct CursorGuard<T: CursorControl> {
+    term: T
+}
+
+impl<T: CursorControl> CursorGuard<T> {
+    /// 隐藏光标并创建守卫
+    ///
+    /// ---
+    ///
+    /// Hide the cursor and create the guard
+    pub fn new(term: T) -> io::Result<Self> {
+        term.hide_cursor()?;
+
+        Ok(Self { term })
+    }
+}
+
+impl<T: CursorControl> Drop for CursorGuard<T> {
+    fn drop(&mut self) { let _ = self.term.show_cursor(); }
+}
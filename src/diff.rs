@@ -0,0 +1,140 @@
+/// 统一差异格式中一行的类型
+///
+/// ---
+///
+/// Kind of a line in the unified diff format
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DiffLineKind {
+    /// 文件头，如 `diff --git`、`--- a/file`、`+++ b/file`
+    ///
+    /// ---
+    ///
+    /// File header such as `diff --git`, `--- a/file` and `+++ b/file`
+    Header,
+    /// 块头，如 `@@ -1,2 +1,2 @@`
+    ///
+    /// ---
+    ///
+    /// Hunk header such as `@@ -1,2 +1,2 @@`
+    Hunk,
+    /// 新增的行
+    ///
+    /// ---
+    ///
+    /// Added line
+    Added,
+    /// 删除的行
+    ///
+    /// ---
+    ///
+    /// Removed line
+    Removed,
+    /// 上下文行或不属于差异的普通文本
+    ///
+    /// ---
+    ///
+    /// Context line or plain text that is not part of a diff
+    Context
+}
+
+impl DiffLineKind {
+    /// 返回该类型行使用的 SGR 颜色序列，不着色时返回 `None`
+    ///
+    /// ---
+    ///
+    /// Return the SGR color sequence used for this kind of line, or `None` if
+    /// it is not colored
+    pub fn color(self) -> Option<&'static str> {
+        match self {
+            Self::Header => Some("\x1b[1m"),
+            Self::Hunk => Some("\x1b[36m"),
+            Self::Added => Some("\x1b[32m"),
+            Self::Removed => Some("\x1b[31m"),
+            Self::Context => None
+        }
+    }
+}
+
+/// 逐行识别统一差异格式的分类器
+///
+/// 只有在块头之后以 `+`、`-` 开头的行才会被视为新增或删除的行，因此不像差异
+/// 的普通文本会全部被归为 [`DiffLineKind::Context`]
+///
+/// # 示例
+/// ```
+/// use slow_scan_print::{DiffClassifier, DiffLineKind};
+///
+/// let diff = "\
+/// - not a diff yet
+/// --- a/greeting.txt
+/// +++ b/greeting.txt
+/// @@ -1,2 +1,2 @@
+///  hello
+/// -world
+/// +世界";
+///
+/// let mut classifier = DiffClassifier::new();
+/// let colors = diff
+///     .lines()
+///     .map(|it| classifier.classify(it).color())
+///     .collect::<Vec<_>>();
+///
+/// assert_eq!(
+///     colors,
+///     [
+///         None,
+///         Some("\x1b[1m"),
+///         Some("\x1b[1m"),
+///         Some("\x1b[36m"),
+///         None,
+///         Some("\x1b[31m"),
+///         Some("\x1b[32m")
+///     ]
+/// );
+/// ```
+///
+/// ---
+///
+/// Classifier recognizing the unified diff format line by line
+///
+/// Only lines starting with `+` or `-` after a hunk header are treated as
+/// added or removed, so plain text that does not look like a diff is
+/// classified as [`DiffLineKind::Context`] throughout
+#[derive(Debug, Clone, Default)]
+pub struct DiffClassifier {
+    in_hunk: bool
+}
+
+impl DiffClassifier {
+    pub fn new() -> Self { Self::default() }
+
+    /// 识别下一行（不含换行符）的类型
+    ///
+    /// ---
+    ///
+    /// Classify the next line, without its line break
+    pub fn classify(&mut self, line: &str) -> DiffLineKind {
+        if line.starts_with("@@") {
+            self.in_hunk = true;
+            return DiffLineKind::Hunk;
+        }
+
+        if self.in_hunk {
+            match line.chars().next() {
+                Some('+') => return DiffLineKind::Added,
+                Some('-') => return DiffLineKind::Removed,
+                Some(' ' | '\\') | None => return DiffLineKind::Context,
+                _ => self.in_hunk = false
+            }
+        }
+
+        if ["diff ", "--- ", "+++ "]
+            .iter()
+            .any(|it| line.starts_with(it))
+        {
+            DiffLineKind::Header
+        } else {
+            DiffLineKind::Context
+        }
+    }
+}
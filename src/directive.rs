@@ -0,0 +1,307 @@
+use std::collections::VecDeque;
+use std::ops::RangeInclusive;
+use std::time::Duration;
+
+/// 单条指令可容纳的最大字符数，超出后按普通字符处理
+///
+/// ---
+///
+/// Maximum number of characters a single directive may contain, longer
+/// sequences are treated as ordinary characters
+const MAX_DIRECTIVE_LEN: usize = 64;
+
+/// 单个提示符标记可容纳的最大字符数，超出后按普通字符处理
+///
+/// ---
+///
+/// Maximum number of characters a single prompt mark may contain, longer
+/// sequences are treated as ordinary characters
+const MAX_PROMPT_MARK_LEN: usize = 256;
+
+/// `speed` 指令允许的倍率范围，超出范围的指令按无法识别处理
+///
+/// 指令来自输入内容，可能不受信任；限制范围可以避免极端倍率使延迟溢出
+///
+/// ---
+///
+/// Range of factors the `speed` directive accepts, directives out of range are
+/// treated as unrecognized
+///
+/// Directives come from the input, which may be untrusted; limiting the range
+/// keeps extreme factors from overflowing the delays
+const SPEED_RANGE: RangeInclusive<f64> = 0.01..=100.0;
+
+/// 嵌入在输入中的节奏控制指令
+///
+/// ---
+///
+/// Pacing directive embedded in the input
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) enum Directive {
+    /// `speed=<倍率>`：之后的所有延迟除以该倍率，倍率须在 0.01 到 100 之间
+    ///
+    /// ---
+    ///
+    /// `speed=<factor>`: all following delays are divided by the factor, which
+    /// must be between 0.01 and 100
+    Speed(f64),
+    /// `pause=<时间>`：立即暂停指定时间
+    ///
+    /// ---
+    ///
+    /// `pause=<time>`: pause for the given time immediately
+    Pause(Duration),
+    /// `delay=<时间>`：替换之后使用的 `base_delay`
+    ///
+    /// ---
+    ///
+    /// `delay=<time>`: replace the `base_delay` used afterwards
+    Delay(Duration)
+}
+
+impl Directive {
+    /// 解析 `name=value` 形式的指令主体
+    ///
+    /// ---
+    ///
+    /// Parse a directive body in `name=value` form
+    fn parse(body: &str) -> Option<Self> {
+        let (name, value) = body.split_once('=')?;
+
+        match name.trim() {
+            "speed" => value
+                .trim()
+                .parse::<f64>()
+                .ok()
+                .filter(|it| SPEED_RANGE.contains(it))
+                .map(Self::Speed),
+            "pause" => parse_duration(value).map(Self::Pause),
+            "delay" => parse_duration(value).map(Self::Delay),
+            _ => None
+        }
+    }
+}
+
+/// 解析形如 `500ms`、`1.5s`、`200us` 的时间，不带单位时按秒计算
+///
+/// ---
+///
+/// Parse times like `500ms`, `1.5s` or `200us`, a bare number is read as
+/// seconds
+pub(crate) fn parse_duration(s: &str) -> Option<Duration> {
+    let s = s.trim();
+    let split = s
+        .find(|it: char| !(it.is_ascii_digit() || it == '.'))
+        .unwrap_or(s.len());
+    let (number, unit) = s.split_at(split);
+    let number = number.parse::<f64>().ok()?;
+
+    let secs = match unit.trim() {
+        "" | "s" => number,
+        "ms" => number / 1E3,
+        "us" | "µs" => number / 1E6,
+        "ns" => number / 1E9,
+        _ => return None
+    };
+
+    Duration::try_from_secs_f64(secs).ok()
+}
+
+/// 输入流中的一个单元
+///
+/// ---
+///
+/// A single unit of the input stream
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) enum Unit {
+    Char(char),
+    Directive(Directive),
+    /// 完整的 OSC 133 提示符标记，包括起始的 `ESC` 和终止符
+    ///
+    /// ---
+    ///
+    /// Complete OSC 133 prompt mark, including the leading `ESC` and the
+    /// terminator
+    PromptMark(String)
+}
+
+/// 从字符流中识别 `ESC [ > name=value BEL` 指令和 OSC 133 提示符标记的迭代器
+/// 适配器
+///
+/// 未启用或无法识别的序列会原样作为字符产出
+///
+/// ---
+///
+/// Iterator adapter recognizing `ESC [ > name=value BEL` directives and OSC
+/// 133 prompt marks in a character stream
+///
+/// Sequences are yielded unchanged as characters when disabled or not
+/// recognized
+pub(crate) struct Directives<I> {
+    iter: I,
+    enabled: bool,
+    prompt_marks: bool,
+    pending: VecDeque<char>
+}
+
+impl<I: Iterator<Item = char>> Directives<I> {
+    pub(crate) fn new(iter: I, enabled: bool, prompt_marks: bool) -> Self {
+        Self {
+            iter,
+            enabled,
+            prompt_marks,
+            pending: VecDeque::new()
+        }
+    }
+
+    /// 读取与 `expected` 逐字符相同的输入，返回是否全部匹配
+    ///
+    /// ---
+    ///
+    /// Read input matching `expected` character by character, returning
+    /// whether all of it matched
+    fn read_expected(&mut self, raw: &mut String, expected: &str) -> bool {
+        for expected in expected.chars() {
+            match self.iter.next() {
+                Some(it) => {
+                    raw.push(it);
+                    if it != expected {
+                        return false;
+                    }
+                }
+                None => return false
+            }
+        }
+
+        true
+    }
+
+    /// 在读取到 `ESC [` 之后尝试读取指令
+    ///
+    /// ---
+    ///
+    /// Try to read a directive after an `ESC [`
+    fn read_directive(&mut self, raw: &mut String) -> Option<Directive> {
+        if !self.read_expected(raw, ">") {
+            return None;
+        }
+
+        while raw.len() < MAX_DIRECTIVE_LEN {
+            match self.iter.next() {
+                Some('\x07') => {
+                    if let Some(it) = Directive::parse(&raw[2..]) {
+                        return Some(it);
+                    }
+                    raw.push('\x07');
+                    break;
+                }
+                Some(it) => raw.push(it),
+                None => break
+            }
+        }
+
+        None
+    }
+
+    /// 在读取到 `ESC ]` 之后尝试读取以 `BEL` 或 `ESC \` 结尾的 OSC 133 提示符
+    /// 标记
+    ///
+    /// ---
+    ///
+    /// Try to read an OSC 133 prompt mark terminated by `BEL` or `ESC \`
+    /// after an `ESC ]`
+    fn read_prompt_mark(&mut self, raw: &mut String) -> Option<String> {
+        if !self.read_expected(raw, "133;") {
+            return None;
+        }
+
+        while raw.len() < MAX_PROMPT_MARK_LEN {
+            match self.iter.next() {
+                Some('\x07') => {
+                    raw.push('\x07');
+                    return Some(format!("\x1b{}", raw));
+                }
+                Some('\x1b') => {
+                    raw.push('\x1b');
+                    if !self.read_expected(raw, "\\") {
+                        return None;
+                    }
+                    return Some(format!("\x1b{}", raw));
+                }
+                Some(it) => raw.push(it),
+                None => break
+            }
+        }
+
+        None
+    }
+}
+
+impl<I: Iterator<Item = char>> Iterator for Directives<I> {
+    type Item = Unit;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if let Some(it) = self.pending.pop_front() {
+            return Some(Unit::Char(it));
+        }
+
+        let it = self.iter.next()?;
+
+        if !(self.enabled || self.prompt_marks) || it != '\x1b' {
+            return Some(Unit::Char(it));
+        }
+
+        // 识别失败时把已读取的字符放回缓冲
+        let mut raw = String::new();
+        let unit = match self.iter.next() {
+            Some('[') if self.enabled => {
+                raw.push('[');
+                self.read_directive(&mut raw).map(Unit::Directive)
+            }
+            Some(']') if self.prompt_marks => {
+                raw.push(']');
+                self.read_prompt_mark(&mut raw).map(Unit::PromptMark)
+            }
+            Some(it) => {
+                raw.push(it);
+                None
+            }
+            None => None
+        };
+
+        unit.or_else(|| {
+            self.pending.extend(raw.chars());
+            Some(Unit::Char(it))
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn speed_within_range_is_accepted() {
+        assert_eq!(Directive::parse("speed=2"), Some(Directive::Speed(2.0)));
+        assert_eq!(
+            Directive::parse("speed=0.01"),
+            Some(Directive::Speed(0.01))
+        );
+        assert_eq!(
+            Directive::parse("speed=100"),
+            Some(Directive::Speed(100.0))
+        );
+    }
+
+    #[test]
+    fn extreme_speed_is_ignored() {
+        for it in ["1e-300", "1e300", "0", "-1", "inf", "NaN", "0.001", "101"] {
+            assert_eq!(
+                Directive::parse(&format!("speed={}", it)),
+                None,
+                "{}",
+                it
+            );
+        }
+    }
+}
@@ -0,0 +1,62 @@
+use std::io::{self, Write};
+
+/// 分隔线使用的字符
+///
+/// ---
+///
+/// Character the rule is drawn with
+const RULE_CHAR: char = '─';
+
+/// 写入一条暗色的水平分隔线并换行，用于将显示的内容与 shell 提示符分隔开
+///
+/// 分隔线使用 SGR 2（暗色）绘制，之后用 SGR 22 恢复正常亮度，不影响其余样式
+///
+/// # 参数
+/// - `writer`: 写入目标
+/// - `width`: 分隔线的列数
+/// - `line_ending`: 分隔线之后的换行符
+///
+/// # 示例
+/// ```
+/// use std::time::Duration;
+///
+/// use slow_scan_print::{SlowScanConfig, SlowScanWrite, write_dim_rule};
+///
+/// let mut config = SlowScanConfig::default();
+/// config.set_base_delay(Duration::ZERO);
+///
+/// let mut writer = Vec::new();
+/// write_dim_rule(&mut writer, 3, "\n").unwrap();
+/// writer
+///     .slow_scan_write_by_chars("hi\n".chars(), config)
+///     .unwrap();
+/// write_dim_rule(&mut writer, 3, "\n").unwrap();
+///
+/// assert_eq!(
+///     String::from_utf8(writer).unwrap(),
+///     "\x1b[2m───\x1b[22m\nhi\n\x1b[2m───\x1b[22m\n"
+/// );
+/// ```
+///
+/// ---
+///
+/// Write a dim horizontal rule followed by a line break, used to separate the
+/// revealed content from the shell prompt
+///
+/// The rule is drawn with SGR 2 (dim) and normal intensity is restored with
+/// SGR 22 afterwards, leaving other styles untouched
+///
+/// # Arguments
+/// - `writer`: Destination to write to
+/// - `width`: Number of columns of the rule
+/// - `line_ending`: Line break after the rule
+pub fn write_dim_rule<W: Write + ?Sized>(
+    writer: &mut W,
+    width: usize,
+    line_ending: &str
+) -> io::Result<()> {
+    let rule = RULE_CHAR.to_string().repeat(width);
+
+    write!(writer, "\x1b[2m{}\x1b[22m{}", rule, line_ending)?;
+    writer.flush()
+}
@@ -0,0 +1,86 @@
+/// 英文字母的出现频率（百分比），依次对应 `a` 到 `z`
+///
+/// ---
+///
+/// Occurrence frequencies of English letters in percent, for `a` to `z` in
+/// order
+const ENGLISH: [f64; 26] = [
+    8.17, 1.49, 2.78, 4.25, 12.70, 2.23, 2.02, 6.09, 6.97, 0.15, 0.77, 4.03,
+    2.41, 6.75, 7.51, 1.93, 0.10, 5.99, 6.33, 9.06, 2.76, 0.98, 2.36, 0.15,
+    1.97, 0.07
+];
+
+/// 按字母出现频率缩放延迟的频率表
+///
+/// 最常见字母的延迟缩放为 0.5 倍，最罕见字母为 1.5 倍，其余字母按频率线性插值；
+/// 字母不区分大小写，非 ASCII 字母不缩放。默认值为内置的英文字母频率表
+///
+/// # 示例
+/// ```
+/// use slow_scan_print::LetterFrequency;
+///
+/// let table = LetterFrequency::default();
+///
+/// assert_eq!(table.factor('e'), 0.5);
+/// assert_eq!(table.factor('Z'), 1.5);
+/// assert_eq!(table.factor('!'), 1.0);
+///
+/// let mut frequencies = [1.0; 26];
+/// frequencies[0] = 3.0;
+/// let table = LetterFrequency::new(frequencies);
+///
+/// assert_eq!(table.factor('a'), 0.5);
+/// assert_eq!(table.factor('b'), 1.5);
+/// ```
+///
+/// ---
+///
+/// Letter frequency table scaling delays by how often letters occur
+///
+/// The delay of the most common letter is scaled by 0.5 and that of the
+/// rarest by 1.5, the other letters being interpolated linearly by frequency;
+/// letters are case-insensitive and non-ASCII letters are not scaled. The
+/// default is the built-in English letter frequency table
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct LetterFrequency {
+    frequencies: [f64; 26]
+}
+
+impl LetterFrequency {
+    /// 通过依次对应 `a` 到 `z` 的频率创建频率表，频率的单位不限
+    ///
+    /// ---
+    ///
+    /// Create a table from frequencies for `a` to `z` in order, in any unit
+    pub const fn new(frequencies: [f64; 26]) -> Self { Self { frequencies } }
+
+    /// 返回某个字符的延迟缩放倍率
+    ///
+    /// ---
+    ///
+    /// Return the delay scaling factor of a character
+    pub fn factor(&self, c: char) -> f64 {
+        if !c.is_ascii_alphabetic() {
+            return 1.0;
+        }
+
+        let frequency =
+            self.frequencies[(c.to_ascii_lowercase() as u8 - b'a') as usize];
+        let (min, max) = self
+            .frequencies
+            .iter()
+            .fold((f64::INFINITY, f64::NEG_INFINITY), |(min, max), it| {
+                (min.min(*it), max.max(*it))
+            });
+
+        if max > min {
+            1.5 - (frequency - min) / (max - min)
+        } else {
+            1.0
+        }
+    }
+}
+
+impl Default for LetterFrequency {
+    fn default() -> Self { Self::new(ENGLISH) }
+}
@@ -0,0 +1,146 @@
+use std::fmt::Write as _;
+use std::io::{self, Read};
+
+/// 每行显示的字节数
+///
+/// ---
+///
+/// Number of bytes shown on each row
+pub const HEX_DUMP_ROW_LEN: usize = 16;
+
+/// 按 `hexdump -C` 的格式化一行十六进制转储
+///
+/// 每行包含偏移量、16 个字节的十六进制表示（每 8 个字节之间多一个空格）以及
+/// 可打印 ASCII 字符栏，不足 16 个字节时以空格补齐
+///
+/// # 参数
+/// - `offset`: 该行第一个字节的偏移量
+/// - `bytes`: 该行的字节，最多 16 个
+///
+/// # 示例
+/// ```
+/// use slow_scan_print::hex_dump_row;
+///
+/// assert_eq!(
+///     hex_dump_row(0, b"Hello, World!\n"),
+///     "00000000  48 65 6c 6c 6f 2c 20 57  6f 72 6c 64 21 0a        |Hello, World!.|\n"
+/// );
+/// ```
+///
+/// ---
+///
+/// Format a single row of a hex dump in the `hexdump -C` layout
+///
+/// Each row contains the offset, the hexadecimal form of 16 bytes (with an
+/// extra space after the first 8) and a printable ASCII gutter, padded with
+/// spaces when shorter than 16 bytes
+///
+/// # Arguments
+/// - `offset`: Offset of the first byte on the row
+/// - `bytes`: Bytes of the row, at most 16
+pub fn hex_dump_row(offset: u64, bytes: &[u8]) -> String {
+    let mut row = format!("{:08x}  ", offset);
+
+    for i in 0..HEX_DUMP_ROW_LEN {
+        if i == HEX_DUMP_ROW_LEN / 2 {
+            row.push(' ');
+        }
+
+        match bytes.get(i) {
+            Some(it) => {
+                let _ = write!(row, "{:02x} ", it);
+            }
+            None => row.push_str("   ")
+        }
+    }
+
+    row.push_str(" |");
+    row.extend(bytes.iter().map(|it| {
+        if it.is_ascii_graphic() || *it == b' ' {
+            *it as char
+        } else {
+            '.'
+        }
+    }));
+    row.push_str("|\n");
+
+    row
+}
+
+/// 从读取器逐行产出十六进制转储的迭代器
+///
+/// 每个元素是一行由 [`hex_dump_row`] 格式化的文本，适合直接交给
+/// `slow_scan_write_by_chunks` 逐行显示
+///
+/// # 示例
+/// ```
+/// use slow_scan_print::HexDump;
+///
+/// let rows = HexDump::new(&[0u8; 20][..])
+///     .collect::<Result<Vec<_>, _>>()
+///     .unwrap();
+///
+/// assert_eq!(rows.len(), 2);
+/// assert!(rows[1].starts_with("00000010  00 00 00 00  "));
+/// ```
+///
+/// ---
+///
+/// Iterator yielding a hex dump of a reader row by row
+///
+/// Each item is a row formatted by [`hex_dump_row`], suitable for passing
+/// directly to `slow_scan_write_by_chunks` to reveal it row by row
+#[derive(Debug)]
+pub struct HexDump<R> {
+    reader: R,
+    offset: u64,
+    done: bool
+}
+
+impl<R: Read> HexDump<R> {
+    pub fn new(reader: R) -> Self {
+        Self {
+            reader,
+            offset: 0,
+            done: false
+        }
+    }
+}
+
+impl<R: Read> Iterator for HexDump<R> {
+    type Item = io::Result<String>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.done {
+            return None;
+        }
+
+        let mut buf = [0; HEX_DUMP_ROW_LEN];
+        let mut len = 0;
+
+        while len < buf.len() {
+            match self.reader.read(&mut buf[len..]) {
+                Ok(0) => break,
+                Ok(it) => len += it,
+                Err(it) if it.kind() == io::ErrorKind::Interrupted => {}
+                Err(it) => {
+                    self.done = true;
+                    return Some(Err(it));
+                }
+            }
+        }
+
+        if len < buf.len() {
+            self.done = true;
+        }
+
+        if len == 0 {
+            return None;
+        }
+
+        let row = hex_dump_row(self.offset, &buf[..len]);
+        self.offset += len as u64;
+
+        Some(Ok(row))
+    }
+}
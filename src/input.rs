@@ -1,8 +1,30 @@
 use std::borrow::Cow;
-use std::fs::File;
+use std::fs::{self, File};
+use std::io::{Read, Seek};
+#[cfg(feature = "command")]
+use std::process::{Child, Command, Stdio};
 use std::{fmt, io};
 
 use rust_i18n::t;
+use slow_scan_print::{Utf16Endian, Utf16Reader};
+#[cfg(feature = "xz")]
+use xz2::read::XzDecoder;
+
+/// zstd 帧开头的魔数
+///
+/// ---
+///
+/// Magic number at the start of a zstd frame
+#[cfg(feature = "zstd")]
+const ZSTD_MAGIC: [u8; 4] = [0x28, 0xB5, 0x2F, 0xFD];
+
+/// xz 流开头的魔数
+///
+/// ---
+///
+/// Magic number at the start of an xz stream
+#[cfg(feature = "xz")]
+const XZ_MAGIC: [u8; 6] = [0xFD, 0x37, 0x7A, 0x58, 0x5A, 0x00];
 
 /// 表示输入源的类型，支持标准输入、文件和空输入
 ///
@@ -13,7 +35,6 @@ use rust_i18n::t;
 /// Represents input sources including standard input, files and empty input
 ///
 /// Provides capabilities to create from URI strings and concatenate multiple sources
-#[derive(Debug)]
 pub(crate) enum InputSource {
     /// 标准输入源
     ///
@@ -26,7 +47,42 @@ pub(crate) enum InputSource {
     /// ---
     ///
     /// File input source
-    File(File)
+    File(File),
+    /// zstd 压缩的文件输入源
+    ///
+    /// ---
+    ///
+    /// zstd compressed file input source
+    #[cfg(feature = "zstd")]
+    Zstd(zstd::Decoder<'static, io::BufReader<File>>),
+    /// xz 压缩的文件输入源
+    ///
+    /// ---
+    ///
+    /// xz compressed file input source
+    #[cfg(feature = "xz")]
+    Xz(XzDecoder<File>),
+    /// 以 BOM 开头的 UTF-16 文件输入源，解码为 UTF-8 读取
+    ///
+    /// ---
+    ///
+    /// UTF-16 file input source starting with a BOM, read decoded into UTF-8
+    Utf16(Utf16Reader<File>),
+    /// 子进程标准输出的输入源
+    ///
+    /// 读完输出后等待子进程退出，退出状态不为成功时返回错误。子进程与本进程
+    /// 位于同一个进程组（控制台）中，因此在终端中按下 Ctrl-C 时会一同终止
+    ///
+    /// ---
+    ///
+    /// Input source reading the stdout of a child process
+    ///
+    /// Once the output is exhausted the child is waited for, and an error is
+    /// returned when it did not exit successfully. The child shares the process
+    /// group (console) of this process, so pressing Ctrl-C in the terminal
+    /// terminates it as well
+    #[cfg(feature = "command")]
+    Command(Child)
 }
 
 impl InputSource {
@@ -47,7 +103,7 @@ impl InputSource {
     ///
     /// # Errors
     /// Returns [`Error`] containing reasons when failing to open resource
-    pub(crate) fn open(uri: &str) -> Result<Self, Error> {
+    pub(crate) fn open(uri: &str) -> Result<Self, Error<'_>> {
         if uri.is_empty() {
             return Err(Error {
                 kind: ErrorKind::UriIsEmpty,
@@ -60,11 +116,128 @@ impl InputSource {
             return Ok(Self::Stdin(io::stdin()));
         }
 
-        File::open(uri).map(Self::File).map_err(|it| Error {
-            kind: ErrorKind::CannotOpenUri,
-            uri: Cow::Borrowed(uri),
-            source: Some(it)
-        })
+        if fs::metadata(uri).is_ok_and(|it| it.is_dir()) {
+            return Err(Error {
+                kind: ErrorKind::IsDirectory,
+                uri: Cow::Borrowed(uri),
+                source: None
+            });
+        }
+
+        File::open(uri)
+            .and_then(Self::from_file)
+            .map_err(|it| Error {
+                kind: ErrorKind::CannotOpenUri,
+                uri: Cow::Borrowed(uri),
+                source: Some(it)
+            })
+    }
+
+    /// 通过系统的 shell 运行命令，以其标准输出作为输入源
+    ///
+    /// # 参数
+    /// - `command`: 要运行的命令行，Unix 上交给 `sh -c`，Windows 上交给
+    ///   `cmd /C`
+    ///
+    /// # 错误
+    /// 无法启动命令时返回 [`Error`] 类型错误
+    ///
+    /// ---
+    ///
+    /// Run a command through the system shell, using its stdout as the input
+    /// source
+    ///
+    /// # Arguments
+    /// - `command`: Command line to run, handed to `sh -c` on Unix and to
+    ///   `cmd /C` on Windows
+    ///
+    /// # Errors
+    /// Returns [`Error`] when the command cannot be started
+    #[cfg(feature = "command")]
+    pub(crate) fn command(command: &str) -> Result<Self, Error<'_>> {
+        #[cfg(windows)]
+        let mut shell = Command::new("cmd");
+        #[cfg(windows)]
+        shell.arg("/C");
+        #[cfg(not(windows))]
+        let mut shell = Command::new("sh");
+        #[cfg(not(windows))]
+        shell.arg("-c");
+
+        shell
+            .arg(command)
+            .stdout(Stdio::piped())
+            .spawn()
+            .map(Self::Command)
+            .map_err(|it| Error {
+                kind: ErrorKind::CannotRunCommand,
+                uri: Cow::Borrowed(command),
+                source: Some(it)
+            })
+    }
+
+    /// 从文件创建输入源，按内容开头的魔数识别压缩格式并解压，按 BOM 识别
+    /// UTF-16 编码并解码
+    ///
+    /// 仅检查普通文件，无法回退读取位置的管道等按原样读取；未识别的内容同样
+    /// 按原样读取
+    ///
+    /// ---
+    ///
+    /// Create an input source from a file, recognizing the compression format
+    /// by the magic number at the start of the content and decompressing it,
+    /// and recognizing UTF-16 by its BOM and decoding it
+    ///
+    /// Only regular files are inspected, pipes and the like that cannot seek
+    /// back are read as is; unrecognized content is read as is as well
+    fn from_file(mut file: File) -> io::Result<Self> {
+        if !file.metadata()?.is_file() {
+            return Ok(Self::File(file));
+        }
+
+        let mut magic = Vec::with_capacity(6);
+        file.by_ref().take(6).read_to_end(&mut magic)?;
+        file.rewind()?;
+
+        #[cfg(feature = "zstd")]
+        if magic.starts_with(&ZSTD_MAGIC) {
+            return zstd::Decoder::new(file).map(Self::Zstd);
+        }
+
+        #[cfg(feature = "xz")]
+        if magic.starts_with(&XZ_MAGIC) {
+            return Ok(Self::Xz(XzDecoder::new_multi_decoder(file)));
+        }
+
+        if let Some(it) = Utf16Endian::from_bom(&magic) {
+            return Ok(Self::Utf16(Utf16Reader::new(file, it)));
+        }
+
+        Ok(Self::File(file))
+    }
+}
+
+impl fmt::Debug for InputSource {
+    /// 只输出变体的名称，解压器等内部读取器没有实现 [`fmt::Debug`]
+    ///
+    /// ---
+    ///
+    /// Print only the name of the variant, as inner readers such as the
+    /// decompressors do not implement [`fmt::Debug`]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            InputSource::Stdin(_) => "Stdin",
+            InputSource::File(_) => "File",
+            #[cfg(feature = "zstd")]
+            InputSource::Zstd(_) => "Zstd",
+            #[cfg(feature = "xz")]
+            InputSource::Xz(_) => "Xz",
+            InputSource::Utf16(_) => "Utf16",
+            #[cfg(feature = "command")]
+            InputSource::Command(_) => "Command"
+        };
+
+        f.write_str(name)
     }
 }
 
@@ -72,11 +245,47 @@ impl io::Read for InputSource {
     fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
         match self {
             InputSource::Stdin(it) => it.read(buf),
-            InputSource::File(it) => it.read(buf)
+            InputSource::File(it) => it.read(buf),
+            #[cfg(feature = "zstd")]
+            InputSource::Zstd(it) => it.read(buf),
+            #[cfg(feature = "xz")]
+            InputSource::Xz(it) => it.read(buf),
+            InputSource::Utf16(it) => it.read(buf),
+            #[cfg(feature = "command")]
+            InputSource::Command(it) => read_child(it, buf)
         }
     }
 }
 
+/// 读取子进程的标准输出，读完后等待子进程退出并检查退出状态
+///
+/// ---
+///
+/// Read the stdout of a child process, waiting for it to exit and checking its
+/// exit status once the output is exhausted
+#[cfg(feature = "command")]
+fn read_child(child: &mut Child, buf: &mut [u8]) -> io::Result<usize> {
+    let len = match child.stdout.as_mut() {
+        Some(it) => it.read(buf)?,
+        None => 0
+    };
+    if len > 0 || buf.is_empty() {
+        return Ok(len);
+    }
+
+    // 先关闭管道，避免子进程因写入阻塞而无法退出
+    drop(child.stdout.take());
+    let status = child.wait()?;
+
+    if status.success() {
+        Ok(0)
+    } else {
+        Err(io::Error::other(
+            t!("error.command_failed", status = status).to_string()
+        ))
+    }
+}
+
 /// 输入源错误类型
 ///
 /// ---
@@ -95,7 +304,20 @@ pub(crate) enum ErrorKind {
     /// ---
     ///
     /// Failed to open specified URI
-    CannotOpenUri
+    CannotOpenUri,
+    /// URI 指向一个目录
+    ///
+    /// ---
+    ///
+    /// URI points to a directory
+    IsDirectory,
+    /// 无法启动命令
+    ///
+    /// ---
+    ///
+    /// Failed to start the command
+    #[cfg(feature = "command")]
+    CannotRunCommand
 }
 
 /// 输入源错误
@@ -133,6 +355,138 @@ impl fmt::Display for Error<'_> {
             ErrorKind::UriIsEmpty => {
                 f.write_str(t!("error.uri_is_empty").as_ref())
             }
+            ErrorKind::IsDirectory => f.write_str(
+                t!("error.uri_is_directory", uri = self.uri).as_ref()
+            ),
+            #[cfg(feature = "command")]
+            ErrorKind::CannotRunCommand => {
+                let src = self
+                    .source
+                    .as_ref()
+                    .map_or_else(String::new, |it| it.to_string());
+
+                f.write_str(
+                    t!(
+                        "error.cannot_run_command",
+                        command = self.uri,
+                        src = src
+                    )
+                    .as_ref()
+                )
+            }
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+    use std::io::Read;
+    use std::path::{Path, PathBuf};
+
+    use super::*;
+
+    const TEXT: &str = "慢速扫描 slow scan\n";
+
+    /// 在临时目录中写入测试文件并返回其路径
+    ///
+    /// ---
+    ///
+    /// Write a fixture into the temporary directory and return its path
+    fn fixture(name: &str, content: &[u8]) -> PathBuf {
+        let path = std::env::temp_dir().join(format!(
+            "slow-scan-print-{}-{}",
+            std::process::id(),
+            name
+        ));
+        fs::write(&path, content).unwrap();
+        path
+    }
+
+    /// 打开文件并读取全部内容
+    ///
+    /// ---
+    ///
+    /// Open a file and read all of its content
+    fn read_all(path: &Path) -> String {
+        let mut source = InputSource::open(path.to_str().unwrap()).unwrap();
+        let mut text = String::new();
+        source.read_to_string(&mut text).unwrap();
+        text
+    }
+
+    #[cfg(feature = "zstd")]
+    #[test]
+    fn zstd_round_trip() {
+        let compressed = zstd::encode_all(TEXT.as_bytes(), 0).unwrap();
+        let path = fixture("round-trip.zst", &compressed);
+
+        let source = InputSource::open(path.to_str().unwrap()).unwrap();
+        assert_eq!(format!("{:?}", source), "Zstd");
+        drop(source);
+        assert_eq!(read_all(&path), TEXT);
+
+        fs::remove_file(path).unwrap();
+    }
+
+    #[cfg(feature = "xz")]
+    #[test]
+    fn xz_round_trip() {
+        let mut compressed = Vec::new();
+        xz2::read::XzEncoder::new(TEXT.as_bytes(), 6)
+            .read_to_end(&mut compressed)
+            .unwrap();
+        let path = fixture("round-trip.xz", &compressed);
+
+        let source = InputSource::open(path.to_str().unwrap()).unwrap();
+        assert_eq!(format!("{:?}", source), "Xz");
+        drop(source);
+        assert_eq!(read_all(&path), TEXT);
+
+        fs::remove_file(path).unwrap();
+    }
+
+    #[test]
+    fn plain_file_is_read_as_is() {
+        let path = fixture("plain.txt", TEXT.as_bytes());
+        assert_eq!(read_all(&path), TEXT);
+
+        fs::remove_file(path).unwrap();
+    }
+
+    #[test]
+    fn directory_is_rejected() {
+        let path = std::env::temp_dir()
+            .join(format!("slow-scan-print-{}-dir", std::process::id()));
+        fs::create_dir_all(&path).unwrap();
+
+        let error = InputSource::open(path.to_str().unwrap()).unwrap_err();
+        assert_eq!(error.kind, ErrorKind::IsDirectory);
+        assert!(error.source.is_none());
+
+        fs::remove_dir(path).unwrap();
+    }
+
+    #[cfg(feature = "command")]
+    #[test]
+    fn command_output_is_read() {
+        let mut source = InputSource::command("echo hello").unwrap();
+        assert_eq!(format!("{:?}", source), "Command");
+
+        let mut text = String::new();
+        source.read_to_string(&mut text).unwrap();
+        assert_eq!(text.trim_end(), "hello");
+    }
+
+    #[cfg(feature = "command")]
+    #[test]
+    fn command_failure_is_reported_after_output() {
+        let mut source =
+            InputSource::command("echo partial && exit 3").unwrap();
+
+        let mut text = String::new();
+        let error = source.read_to_string(&mut text).unwrap_err();
+        assert_eq!(text.trim_end(), "partial");
+        assert_eq!(error.kind(), io::ErrorKind::Other);
+    }
+}
@@ -0,0 +1,31 @@
+/// 写入被取消时的进度
+///
+/// 由可取消的写入方法在取消时返回，调用方可以据此在之后从中断的位置继续
+///
+/// ---
+///
+/// Progress of a write when it is cancelled
+///
+/// Returned by the cancellable write methods on cancellation, so that the
+/// caller can later resume from where it stopped
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Interrupted {
+    /// 取消之前已经写入的字符数量，内联指令和提示符标记不计入
+    ///
+    /// ---
+    ///
+    /// Number of characters written before the cancellation, inline
+    /// directives and prompt marks not counted
+    pub units_written: usize,
+    /// 尚未写入的字符数量，只有输入的迭代器能给出准确的剩余长度时才为
+    /// `Some`，例如由 `Vec` 或切片创建的迭代器；无法得知剩余长度的输入（如
+    /// 不可定位的流）为 `None`
+    ///
+    /// ---
+    ///
+    /// Number of characters not written yet, `Some` only when the input
+    /// iterator reports an exact remaining length, such as iterators created
+    /// from a `Vec` or a slice; `None` for inputs whose remaining length is
+    /// unknown (such as non-seekable streams)
+    pub units_remaining_estimate: Option<usize>
+}
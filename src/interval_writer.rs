@@ -0,0 +1,147 @@
+use std::io::{self, BufWriter, Write};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex, MutexGuard};
+use std::thread::{self, JoinHandle};
+use std::time::Duration;
+
+/// 由后台线程按固定间隔刷新的缓冲写入器
+///
+/// 写入的数据先进入共享缓冲区，后台线程每隔 `interval` 刷新一次，使显示节奏与
+/// 刷新节奏解耦。被丢弃时会停止后台线程并刷新剩余数据
+///
+/// ---
+///
+/// Buffered writer flushed by a background thread at a fixed interval
+///
+/// Written data goes into a shared buffer which the background thread flushes
+/// every `interval`, decoupling the reveal cadence from the flush cadence. On
+/// drop the background thread is stopped and the remaining data is flushed
+pub(crate) struct IntervalWriter<W: Write + Send + 'static> {
+    inner: Arc<Mutex<BufWriter<W>>>,
+    stop: Arc<AtomicBool>,
+    handle: Option<JoinHandle<()>>
+}
+
+impl<W: Write + Send + 'static> IntervalWriter<W> {
+    pub(crate) fn new(inner: W, interval: Duration) -> Self {
+        let inner = Arc::new(Mutex::new(BufWriter::new(inner)));
+        let stop = Arc::new(AtomicBool::new(false));
+
+        let handle = {
+            let inner = Arc::clone(&inner);
+            let stop = Arc::clone(&stop);
+
+            thread::spawn(move || {
+                while !stop.load(Ordering::Acquire) {
+                    thread::park_timeout(interval);
+                    let _ = lock(&inner).flush();
+                }
+            })
+        };
+
+        Self {
+            inner,
+            stop,
+            handle: Some(handle)
+        }
+    }
+}
+
+impl<W: Write + Send + 'static> Write for IntervalWriter<W> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        lock(&self.inner).write(buf)
+    }
+
+    fn flush(&mut self) -> io::Result<()> { lock(&self.inner).flush() }
+}
+
+impl<W: Write + Send + 'static> Drop for IntervalWriter<W> {
+    fn drop(&mut self) {
+        self.stop.store(true, Ordering::Release);
+
+        if let Some(it) = self.handle.take() {
+            it.thread().unpark();
+            let _ = it.join();
+        }
+
+        let _ = lock(&self.inner).flush();
+    }
+}
+
+/// 获取锁，即使锁已中毒也继续使用其中的数据
+///
+/// ---
+///
+/// Acquire the lock, keeping on using the data even if it is poisoned
+fn lock<T>(mutex: &Mutex<T>) -> MutexGuard<'_, T> {
+    mutex.lock().unwrap_or_else(|it| it.into_inner())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::AtomicUsize;
+    use std::time::Instant;
+
+    use super::*;
+
+    /// 记录写入的数据和刷新次数的共享写入器
+    ///
+    /// ---
+    ///
+    /// Shared writer recording the written data and the number of flushes
+    #[derive(Clone, Default)]
+    struct Shared {
+        data: Arc<Mutex<Vec<u8>>>,
+        flushes: Arc<AtomicUsize>
+    }
+
+    impl Write for Shared {
+        fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+            lock(&self.data).extend_from_slice(buf);
+            Ok(buf.len())
+        }
+
+        fn flush(&mut self) -> io::Result<()> {
+            self.flushes.fetch_add(1, Ordering::AcqRel);
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn flushes_within_interval() {
+        let interval = Duration::from_millis(50);
+        let shared = Shared::default();
+        let mut writer = IntervalWriter::new(shared.clone(), interval);
+
+        let start = Instant::now();
+        writer.write_all(b"abc").unwrap();
+        // 写入本身只进入缓冲区
+        assert!(lock(&shared.data).is_empty());
+
+        while lock(&shared.data).is_empty() {
+            assert!(start.elapsed() < interval * 10, "never flushed");
+            thread::sleep(Duration::from_millis(1));
+        }
+
+        assert_eq!(*lock(&shared.data), b"abc");
+    }
+
+    #[test]
+    fn drop_stops_thread_and_flushes() {
+        let shared = Shared::default();
+        let mut writer =
+            IntervalWriter::new(shared.clone(), Duration::from_secs(60));
+        writer.write_all(b"abc").unwrap();
+
+        // 丢弃时唤醒并等待后台线程结束，而不是等到下一个间隔
+        let start = Instant::now();
+        drop(writer);
+        assert!(start.elapsed() < Duration::from_secs(10));
+        assert_eq!(*lock(&shared.data), b"abc");
+
+        // 线程结束后不再刷新
+        let flushes = shared.flushes.load(Ordering::Acquire);
+        thread::sleep(Duration::from_millis(50));
+        assert_eq!(shared.flushes.load(Ordering::Acquire), flushes);
+    }
+}
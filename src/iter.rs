@@ -0,0 +1,167 @@
+use std::time::Duration;
+
+use crate::{Clock, SlowScanConfig, SystemClock};
+
+/// 为字符迭代器提供慢速扫描节奏的扩展 trait
+///
+/// 与 [`SlowScanWrite`](crate::SlowScanWrite) 相反，写入由调用者自行完成，
+/// 迭代器只负责在产出每个字符之前完成相应的延迟
+///
+/// # 示例
+/// ```
+/// use std::time::Duration;
+///
+/// use slow_scan_print::{SlowScanConfig, SlowScanIterExt};
+///
+/// let mut config = SlowScanConfig::default();
+/// config.set_base_delay(Duration::ZERO);
+///
+/// let mut out = String::new();
+/// for it in "abc".chars().slow_scan(config) {
+///     out.push(it);
+/// }
+///
+/// assert_eq!(out, "abc");
+/// ```
+///
+/// ---
+///
+/// Extension trait pacing character iterators like a slow scan
+///
+/// Unlike [`SlowScanWrite`](crate::SlowScanWrite), the caller does the writing
+/// and the iterator only performs the matching delay before yielding each
+/// character
+pub trait SlowScanIterExt: Iterator<Item = char> + Sized {
+    /// 返回按配置延迟产出每个字符的迭代器
+    ///
+    /// ---
+    ///
+    /// Return an iterator yielding each character after the configured delay
+    fn slow_scan(self, config: SlowScanConfig) -> SlowScanIter<Self> {
+        self.slow_scan_with_clock(config, SystemClock)
+    }
+
+    /// 返回使用指定时钟按配置延迟产出每个字符的迭代器
+    ///
+    /// ---
+    ///
+    /// Return an iterator yielding each character after the configured delay,
+    /// using the given clock
+    fn slow_scan_with_clock<C: Clock>(
+        self,
+        config: SlowScanConfig,
+        clock: C
+    ) -> SlowScanIter<Self, C> {
+        SlowScanIter {
+            iter: self,
+            config,
+            clock,
+            pending: None
+        }
+    }
+}
+
+impl<I: Iterator<Item = char>> SlowScanIterExt for I {}
+
+/// 按配置延迟产出每个字符的迭代器
+///
+/// 每个字符对应的延迟在产出下一个字符之前完成，因此第一个字符会立即产出；
+/// 启用 `tail_delay` 时，最后一个字符的延迟在迭代结束前完成。由
+/// [`SlowScanIterExt::slow_scan`] 创建
+///
+/// # 示例
+/// ```
+/// use std::time::{Duration, Instant};
+///
+/// use slow_scan_print::{SlowScanConfig, SlowScanIterExt};
+/// # struct VirtualClock {
+/// #     now: std::time::Instant,
+/// #     deadlines: Vec<std::time::Instant>
+/// # }
+/// #
+/// # impl VirtualClock {
+/// #     fn new(now: std::time::Instant) -> Self {
+/// #         Self {
+/// #             now,
+/// #             deadlines: Vec::new()
+/// #         }
+/// #     }
+/// # }
+/// #
+/// # impl slow_scan_print::Clock for VirtualClock {
+/// #     fn now(&mut self) -> std::time::Instant { self.now }
+/// #
+/// #     fn sleep_until(&mut self, deadline: std::time::Instant) {
+/// #         self.now = self.now.max(deadline);
+/// #         self.deadlines.push(deadline);
+/// #     }
+/// # }
+///
+/// let start = Instant::now();
+///
+/// let mut config = SlowScanConfig::default();
+/// config
+///     .set_base_delay(Duration::from_secs(1))
+///     .set_full_width_delay(Duration::from_secs(2));
+///
+/// let mut iter = "世ab"
+///     .chars()
+///     .slow_scan_with_clock(config, VirtualClock::new(start));
+///
+/// assert_eq!(iter.next(), Some('世'));
+/// assert_eq!(iter.clock().now, start);
+/// assert_eq!(iter.next(), Some('a'));
+/// assert_eq!(iter.clock().now, start + Duration::from_secs(2));
+/// assert_eq!(iter.next(), Some('b'));
+/// assert_eq!(iter.clock().now, start + Duration::from_secs(3));
+/// assert_eq!(iter.next(), None);
+/// assert_eq!(iter.clock().now, start + Duration::from_secs(3));
+/// ```
+///
+/// ---
+///
+/// Iterator yielding each character after the configured delay
+///
+/// The delay of each character is performed before yielding the next one, so
+/// the first character is yielded immediately; with `tail_delay` enabled the
+/// delay of the last character is performed before the iteration ends.
+/// Created by [`SlowScanIterExt::slow_scan`]
+#[derive(Debug, Clone)]
+pub struct SlowScanIter<I, C = SystemClock> {
+    iter: I,
+    config: SlowScanConfig,
+    clock: C,
+    pending: Option<Duration>
+}
+
+impl<I, C> SlowScanIter<I, C> {
+    /// 返回迭代器使用的时钟
+    ///
+    /// ---
+    ///
+    /// Return the clock used by the iterator
+    pub fn clock(&self) -> &C { &self.clock }
+}
+
+impl<I: Iterator<Item = char>, C: Clock> Iterator for SlowScanIter<I, C> {
+    type Item = char;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let it = self.iter.next();
+
+        let delay = self
+            .pending
+            .take()
+            .and_then(|delay| self.config.pause_after(delay, it.is_some()));
+
+        if let Some(delay) = delay {
+            let deadline = self.clock.now() + delay;
+            self.clock.sleep_until(deadline);
+        }
+
+        let it = it?;
+        self.pending = Some(self.config.char_delay(it));
+
+        Some(it)
+    }
+}
@@ -0,0 +1,62 @@
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+/// 为延迟抖动提供随机数的 xorshift64 伪随机数生成器
+///
+/// 质量只需满足让节奏看起来自然，不适用于任何与安全相关的用途
+///
+/// ---
+///
+/// xorshift64 pseudo-random number generator providing randomness for delay
+/// jitter
+///
+/// The quality only needs to make the pace look natural, it is not suitable
+/// for anything security related
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct XorShift(u64);
+
+impl XorShift {
+    /// 以当前系统时间为种子创建生成器
+    ///
+    /// ---
+    ///
+    /// Create a generator seeded from the current system time
+    pub(crate) fn from_time() -> Self {
+        let nanos = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map_or(0, |it| it.as_nanos() as u64);
+
+        // xorshift 的状态不能为零
+        Self(nanos | 1)
+    }
+
+    /// 返回下一个 `[0, 1)` 范围内均匀分布的数
+    ///
+    /// ---
+    ///
+    /// Return the next number uniformly distributed in `[0, 1)`
+    pub(crate) fn next_f64(&mut self) -> f64 {
+        self.0 ^= self.0 << 13;
+        self.0 ^= self.0 >> 7;
+        self.0 ^= self.0 << 17;
+
+        // 取高 53 位作为尾数
+        (self.0 >> 11) as f64 / (1u64 << 53) as f64
+    }
+
+    /// 将延迟随机缩放到 `delay * (1 ± jitter)` 范围内
+    ///
+    /// `jitter` 被限制在 `[0, 1]` 内，因此结果不会为负
+    ///
+    /// ---
+    ///
+    /// Randomly scale a delay within `delay * (1 ± jitter)`
+    ///
+    /// `jitter` is clamped to `[0, 1]`, so the result is never negative
+    pub(crate) fn jitter(&mut self, delay: Duration, jitter: f64) -> Duration {
+        let jitter = jitter.clamp(0.0, 1.0);
+        let factor = 1.0 + jitter * (self.next_f64() * 2.0 - 1.0);
+
+        Duration::try_from_secs_f64(delay.as_secs_f64() * factor)
+            .unwrap_or(Duration::MAX)
+    }
+}
@@ -1,13 +1,120 @@
 #![cfg_attr(feature = "unstable", feature(thread_sleep_until))]
 
+use std::cmp::Ordering;
+use std::collections::HashMap;
+use std::fmt;
 use std::io::{self, Write};
-#[cfg(not(feature = "unstable"))]
-use std::thread::sleep;
-use std::time::Duration;
-#[cfg(feature = "unstable")]
-use std::{thread::sleep_until, time::Instant};
+use std::ops::Range;
+use std::sync::Arc;
+use std::sync::atomic::{self, AtomicBool};
+use std::time::{Duration, Instant};
 
 use getset::{Getters, Setters};
+#[cfg(feature = "graphemes")]
+use unicode_segmentation::UnicodeSegmentation;
+
+pub use crate::align::{Align, align_padding};
+pub use crate::chunk::{ChunkUnit, chunk_by_width};
+pub use crate::classify::{DelayClass, classify_chars};
+pub use crate::clock::{Clock, InterruptibleClock, SystemClock, TraceClock};
+pub use crate::collapse::{CollapseCr, collapse_cr};
+pub use crate::color::{ColorDepth, ColorDowngrade, downgrade_sgr};
+#[cfg(feature = "terminal")]
+pub use crate::cursor::{CursorControl, CursorGuard};
+pub use crate::diff::{DiffClassifier, DiffLineKind};
+use crate::directive::{Directive, Directives, Unit};
+pub use crate::frame::write_dim_rule;
+pub use crate::frequency::LetterFrequency;
+pub use crate::hex::{HEX_DUMP_ROW_LEN, HexDump, hex_dump_row};
+pub use crate::interrupt::Interrupted;
+pub use crate::iter::{SlowScanIter, SlowScanIterExt};
+#[cfg(feature = "jitter")]
+use crate::jitter::XorShift;
+pub use crate::line_writer::SlowScanLineWriter;
+pub use crate::lines::BoundedLines;
+pub use crate::locale::{detect_cjk_locale, is_cjk_locale};
+#[cfg(feature = "markdown")]
+use crate::markdown::write_markdown;
+pub use crate::page::Pager;
+pub use crate::profile::{CharClass, Profile, RunStats};
+#[cfg(all(unix, feature = "pty"))]
+pub use crate::pty::Pty;
+pub use crate::region::Region;
+use crate::region::write_interleaved;
+use crate::retry::Retry;
+#[cfg(feature = "terminal")]
+pub use crate::rich::RichUnit;
+#[cfg(feature = "terminal")]
+use crate::rich::write_rich;
+pub use crate::sanitize::sanitize_for_terminal;
+pub use crate::schedule::parse_schedule;
+pub use crate::script::{ScriptSegment, parse_script};
+pub use crate::session::SlowScanSession;
+pub use crate::spinner::SpinnerReader;
+pub use crate::tail::TailBehavior;
+#[cfg(feature = "timer-resolution")]
+pub use crate::timer::TimerResolution;
+pub use crate::timing::parse_timing;
+use crate::trail::write_trail;
+#[cfg(feature = "ratatui")]
+pub use crate::tui::BufferReveal;
+pub use crate::unbuffered::Unbuffered;
+pub use crate::utf16::{Utf16Endian, Utf16Reader};
+#[cfg(feature = "watch")]
+pub use crate::watch::FileWatch;
+use crate::words::write_by_words;
+pub use crate::wrap::{WordWrap, reflow, word_wrap, word_wrap_with_config};
+
+mod align;
+mod ansi;
+mod chunk;
+mod classify;
+mod clock;
+mod collapse;
+mod color;
+#[cfg(feature = "terminal")]
+mod cursor;
+mod diff;
+mod directive;
+mod frame;
+mod frequency;
+mod hex;
+mod interrupt;
+mod iter;
+#[cfg(feature = "jitter")]
+mod jitter;
+mod line_writer;
+mod lines;
+mod locale;
+mod macros;
+#[cfg(feature = "markdown")]
+mod markdown;
+mod page;
+mod profile;
+#[cfg(all(unix, feature = "pty"))]
+mod pty;
+mod region;
+mod retry;
+#[cfg(feature = "terminal")]
+mod rich;
+mod sanitize;
+mod schedule;
+mod script;
+mod session;
+mod spinner;
+mod tail;
+#[cfg(feature = "timer-resolution")]
+mod timer;
+mod timing;
+mod trail;
+#[cfg(feature = "ratatui")]
+mod tui;
+mod unbuffered;
+mod utf16;
+#[cfg(feature = "watch")]
+mod watch;
+mod words;
+mod wrap;
 
 /// 配置慢速扫描输出的参数
 ///
@@ -68,388 +175,7577 @@ pub struct SlowScanConfig {
     #[getset(get = "pub", set = "pub")]
     control_char_delay: Duration,
 
-    /// 是否在输出最后一个字符后也添加延迟
+    /// 输出最后一个单元之后的行为
     ///
-    /// 如果设置为 `true`，即使在输出最后一个字符后也会应用延迟
-    /// 如果设置为 `false`，最后一个字符后不添加延迟
+    /// 为 [`TailBehavior::None`] 时最后一个单元后不添加延迟，为
+    /// [`TailBehavior::LastUnit`] 时按该单元自身的延迟等待，为
+    /// [`TailBehavior::Custom`] 时等待指定的时间。设置时也可以直接传入
+    /// `bool`
     ///
     /// ---
     ///
-    /// Whether to add delay after the last character output
+    /// Behavior after the last unit is output
     ///
-    /// If set to `true`, delay will be applied even after the last character
-    /// If set to `false`, no delay is added after the last character
-    #[getset(get = "pub", set = "pub")]
-    tail_delay: bool
-}
+    /// With [`TailBehavior::None`] no delay is added after the last unit, with
+    /// [`TailBehavior::LastUnit`] the own delay of that unit is waited for,
+    /// and with [`TailBehavior::Custom`] the given time is waited for. A
+    /// `bool` can be passed directly when setting it as well
+    tail_delay: TailBehavior,
 
-impl SlowScanConfig {
-    /// 根据预期的总持续时间和块数量自动计算并设置基础延迟
-    ///
-    /// 这个方法适用于需要精确控制整体输出时长的场景，通过预期的总时长和
-    /// 块数量来自动计算每个块之间的基础延迟时间。
-    ///
-    /// # 参数
-    /// - `expectation`: 期望的总输出持续时间
-    /// - `chunk_count`: 输出的块数量（字符块或字节块）
+    /// 最多输出的单元数
     ///
-    /// # 计算规则
-    /// - 如果 `tail_delay` 为 `true`，延迟次数等于块数量
-    /// - 如果 `tail_delay` 为 `false`，延迟次数等于 `块数量 - 1`
-    /// - 基础延迟 = 期望总时长 / 延迟次数
-    /// - 如果块数量为 0，基础延迟设置为 `Duration::ZERO`
+    /// 设置后 `slow_scan_write_by_chars` 输出指定数量的字符、
+    /// `slow_scan_write_by_chunks` 输出指定数量的块、`slow_scan_write_by_words`
+    /// 输出指定数量的单词之后即停止，不再读取剩余的输入；最后输出的单元之后按
+    /// `tail_delay` 处理。为 `None` 时不限制
     ///
     /// # 示例
     /// ```
     /// use std::time::Duration;
     ///
-    /// use slow_scan_print::SlowScanConfig;
+    /// use slow_scan_print::{SlowScanConfig, SlowScanWrite};
     ///
-    /// // 希望在 1 秒内输出 10 个块
     /// let mut config = SlowScanConfig::default();
     /// config
-    ///     .set_tail_delay(true)
-    ///     .set_base_delay_from_expected_total_duration(
-    ///         Duration::from_secs(1),
-    ///         10
-    ///     );
+    ///     .set_base_delay(Duration::ZERO)
+    ///     .set_head_units(Some(5));
     ///
-    /// assert_eq!(*config.base_delay(), Duration::from_millis(100));
+    /// let mut writer = Vec::new();
+    /// writer
+    ///     .slow_scan_write_by_chars("hello, world".chars(), config)
+    ///     .unwrap();
     ///
-    /// config
-    ///     .set_tail_delay(false)
-    ///     .set_base_delay_from_expected_total_duration(
-    ///         Duration::from_secs(1),
-    ///         11
-    ///     );
-    /// assert_eq!(*config.base_delay(), Duration::from_millis(100));
-    /// ```
+    /// assert_eq!(writer, b"hello");
     ///
-    /// # 注意
-    /// - 这个方法不会修改 `full_width_delay` 和 `control_char_delay` 的设置
-    /// - 实际总时长可能因系统调度和性能而有微小偏差
-    /// - 如果 `chunk_count` 为 0 或 1（且 `tail_delay` 为 `false`），基础延迟会被设置为 0
+    /// config.set_head_units(Some(2));
+    ///
+    /// let mut writer = Vec::new();
+    /// writer
+    ///     .slow_scan_write_by_chunks(["a\n", "b\n", "c\n"].iter(), config)
+    ///     .unwrap();
+    ///
+    /// assert_eq!(writer, b"a\nb\n");
+    ///
+    /// let mut writer = Vec::new();
+    /// writer
+    ///     .slow_scan_write_by_words("one two three".chars(), config)
+    ///     .unwrap();
+    ///
+    /// assert_eq!(writer, b"one two ");
+    /// ```
     ///
     /// ---
     ///
-    /// Automatically calculates and sets base delay based on expected total duration and chunk count
+    /// Maximum number of units to output
     ///
-    /// This method is useful for scenarios requiring precise control over total output duration,
-    /// automatically calculating the base delay between chunks based on expected total time
-    /// and number of chunks.
+    /// When set, `slow_scan_write_by_chars` stops after outputting the given
+    /// number of characters, `slow_scan_write_by_chunks` after the given number
+    /// of chunks and `slow_scan_write_by_words` after the given number of
+    /// words, without reading the rest of the input; the last unit output is
+    /// followed by `tail_delay` as usual. No limit when `None`
+    #[getset(get = "pub", set = "pub")]
+    head_units: Option<usize>,
+
+    /// 是否解析输入中嵌入的节奏控制指令
     ///
-    /// # Arguments
-    /// - `expectation`: Expected total output duration
-    /// - `chunk_count`: Number of output chunks (character chunks or byte chunks)
+    /// 启用后 `slow_scan_write_by_chars` 会识别形如 `ESC [ > name=value BEL`
+    /// （即 `\x1b[>name=value\x07`）的私有转义序列，指令本身不会被输出，
+    /// 而是在读取到的位置立即调整之后的节奏：
     ///
-    /// # Calculation Rules
-    /// - If `tail_delay` is `true`, number of delays equals chunk count
-    /// - If `tail_delay` is `false`, number of delays equals `chunk_count - 1`
-    /// - Base delay = Expected total duration / Number of delays
-    /// - If chunk count is 0, base delay is set to `Duration::ZERO`
+    /// - `speed=<倍率>`：之后的所有延迟除以该倍率，例如 `speed=2` 表示两倍速
+    /// - `pause=<时间>`：立即暂停指定时间
+    /// - `delay=<时间>`：将之后使用的 `base_delay` 替换为指定时间
     ///
-    /// # Example
+    /// 时间支持 `ns`、`us`（`µs`）、`ms`、`s` 单位，不带单位时按秒计算。
+    /// 无法识别的序列会原样输出
+    ///
+    /// # 示例
     /// ```
-    /// use std::time::Duration;
+    /// use std::time::{Duration, Instant};
     ///
-    /// use slow_scan_print::SlowScanConfig;
+    /// use slow_scan_print::{SlowScanConfig, SlowScanWrite};
+    /// # struct VirtualClock {
+    /// #     now: std::time::Instant,
+    /// #     deadlines: Vec<std::time::Instant>
+    /// # }
+    /// #
+    /// # impl VirtualClock {
+    /// #     fn new(now: std::time::Instant) -> Self {
+    /// #         Self {
+    /// #             now,
+    /// #             deadlines: Vec::new()
+    /// #         }
+    /// #     }
+    /// # }
+    /// #
+    /// # impl slow_scan_print::Clock for VirtualClock {
+    /// #     fn now(&mut self) -> std::time::Instant { self.now }
+    /// #
+    /// #     fn sleep_until(&mut self, deadline: std::time::Instant) {
+    /// #         self.now = self.now.max(deadline);
+    /// #         self.deadlines.push(deadline);
+    /// #     }
+    /// # }
     ///
-    /// // Want to output 10 chunks within 1 second
+    /// let ms = Duration::from_millis;
     /// let mut config = SlowScanConfig::default();
     /// config
+    ///     .set_base_delay(ms(20))
     ///     .set_tail_delay(true)
-    ///     .set_base_delay_from_expected_total_duration(
-    ///         Duration::from_secs(1),
-    ///         10
-    ///     );
+    ///     .set_inline_directives(true);
     ///
-    /// assert_eq!(*config.base_delay(), Duration::from_millis(100));
+    /// let start = Instant::now();
+    /// let mut clock = VirtualClock::new(start);
+    /// let mut writer = Vec::new();
+    /// writer
+    ///     .slow_scan_write_by_chars_with_clock(
+    ///         "ab\x1b[>speed=2\x07cd\x1b[>delay=100ms\x07e\x1b[>pause=1s\x07f"
+    ///             .chars(),
+    ///         config,
+    ///         &mut clock
+    ///     )
+    ///     .unwrap();
     ///
-    /// config
-    ///     .set_tail_delay(false)
-    ///     .set_base_delay_from_expected_total_duration(
-    ///         Duration::from_secs(1),
-    ///         11
-    ///     );
-    /// assert_eq!(*config.base_delay(), Duration::from_millis(100));
+    /// assert_eq!(writer, b"abcdef");
+    ///
+    /// // 指令改变了之后的节奏：两倍速使延迟减半，`delay` 替换基础延迟，
+    /// // `pause` 插入一次额外的暂停，它们同样受倍率影响
+    /// // The directives change the following pace: double speed halves the
+    /// // delays, `delay` replaces the base delay and `pause` inserts an extra
+    /// // pause, all of them affected by the speed as well
+    /// let delays = clock
+    ///     .deadlines
+    ///     .iter()
+    ///     .scan(start, |last, it| Some(*it - std::mem::replace(last, *it)))
+    ///     .collect::<Vec<_>>();
+    /// assert_eq!(
+    ///     delays,
+    ///     [ms(20), ms(20), ms(10), ms(10), ms(50), ms(500), ms(50)]
+    /// );
     /// ```
     ///
-    /// # Notes
-    /// - This method does not modify `full_width_delay` and `control_char_delay` settings
-    /// - Actual total duration may have slight deviations due to system scheduling and performance
-    /// - If `chunk_count` is 0 or 1 (and `tail_delay` is `false`), base delay will be set to 0
-    pub fn set_base_delay_from_expected_total_duration(
-        &mut self,
-        expectation: Duration,
-        chunk_count: u32
-    ) -> &mut Self {
-        let delay_count = if self.tail_delay {
-            chunk_count
-        } else {
-            chunk_count.saturating_sub(1)
-        };
-
-        if delay_count > 0 {
-            self.set_base_delay(expectation / delay_count)
-        } else {
-            self.set_base_delay(Duration::ZERO)
-        }
-    }
-}
-
-impl Default for SlowScanConfig {
-    fn default() -> Self {
-        Self {
-            base_delay: Duration::from_millis(20),
-            full_width_delay: Duration::from_millis(40),
-            control_char_delay: Duration::ZERO,
-            tail_delay: false
-        }
-    }
-}
-
-/// 提供缓慢扫描式写入功能的 trait，模拟逐字符输出效果
-///
-/// 适用于需要模拟打字机效果或逐字符显示的场景
-///
-/// # 延迟精度说明
-/// - 默认情况下使用 `std::thread::sleep`，延迟精度受系统调度影响
-/// - 启用 `unstable` 特性后使用 `std::thread::sleep_until`，提供更精准的延迟控制
-///   避免因执行时间累积导致的延迟误差，同时不会带来明显的性能损失
-///
-/// ---
-///
-/// A trait for slow scanning write operations to simulate character-by-character output.
-///
-/// Useful for creating typewriter effects or progressive character display.
-///
-/// # Delay Precision Notes
-/// - By default uses `std::thread::sleep` with precision affected by system scheduling
-/// - When `unstable` feature is enabled, uses `std::thread::sleep_until` for more precise
-///   delay control, avoiding cumulative timing errors from execution time, without
-///   significant performance impact
-pub trait SlowScanWrite {
-    /// 以指定配置逐块写入数据
+    /// ---
     ///
-    /// # 参数
-    /// - `iter`: 字节块迭代器，每个元素需实现 `AsRef<[u8]>`
-    /// - `config`: 慢速扫描配置参数
+    /// Whether to parse pacing directives embedded in the input
     ///
-    /// # 返回值
-    /// - `Ok(())`: 所有数据成功写入
-    /// - `Err(io::Error)`: 写入过程中发生 I/O 错误
+    /// When enabled, `slow_scan_write_by_chars` recognizes private escape
+    /// sequences of the form `ESC [ > name=value BEL` (i.e.
+    /// `\x1b[>name=value\x07`). The directive itself is not written, instead
+    /// it adjusts the following pacing at the position it is read:
     ///
-    /// # 延迟精度
-    /// - 默认实现使用 `std::thread::sleep`，延迟精度受系统调度影响
-    /// - 启用 `unstable` 特性后使用 `std::thread::sleep_until`，提供更精准的延迟控制
-    ///   避免因执行时间累积导致的延迟误差，同时不会带来明显的性能损失
+    /// - `speed=<factor>`: all following delays are divided by the factor, e.g.
+    ///   `speed=2` means twice as fast
+    /// - `pause=<time>`: pause for the given time immediately
+    /// - `delay=<time>`: replace the `base_delay` used afterwards
     ///
-    /// # 性能说明
-    /// 基准测试表明，使用 `unstable` 特性不会带来明显的性能损失，同时提供更精确的定时控制
+    /// Times accept the `ns`, `us` (`µs`), `ms` and `s` units, a bare number is
+    /// read as seconds. Unrecognized sequences are written unchanged
+    #[getset(get = "pub", set = "pub")]
+    inline_directives: bool,
+
+    /// 是否识别并原样保留 OSC 133 提示符标记
+    ///
+    /// 启用后 `slow_scan_write_by_chars` 会把形如 `ESC ] 133 ; ... BEL` 或
+    /// `ESC ] 133 ; ... ESC \` 的 shell 提示符标记整体立即写入，不产生延迟，
+    /// 也不计为可见内容，使回放录制的 shell 会话时终端的 shell 集成不受影响
     ///
     /// # 示例
     /// ```
-    /// use std::time::Duration;
+    /// use std::time::{Duration, Instant};
     ///
     /// use slow_scan_print::{SlowScanConfig, SlowScanWrite};
+    /// # struct VirtualClock {
+    /// #     now: std::time::Instant,
+    /// #     deadlines: Vec<std::time::Instant>
+    /// # }
+    /// #
+    /// # impl VirtualClock {
+    /// #     fn new(now: std::time::Instant) -> Self {
+    /// #         Self {
+    /// #             now,
+    /// #             deadlines: Vec::new()
+    /// #         }
+    /// #     }
+    /// # }
+    /// #
+    /// # impl slow_scan_print::Clock for VirtualClock {
+    /// #     fn now(&mut self) -> std::time::Instant { self.now }
+    /// #
+    /// #     fn sleep_until(&mut self, deadline: std::time::Instant) {
+    /// #         self.now = self.now.max(deadline);
+    /// #         self.deadlines.push(deadline);
+    /// #     }
+    /// # }
+    ///
+    /// let start = Instant::now();
+    /// let mut clock = VirtualClock::new(start);
     ///
     /// let mut config = SlowScanConfig::default();
-    /// config.set_base_delay(Duration::from_millis(10));
+    /// config
+    ///     .set_base_delay(Duration::from_secs(1))
+    ///     .set_prompt_marks(true);
     ///
+    /// let input = "\x1b]133;A\x07$ \x1b]133;B\x1b\\";
     /// let mut writer = Vec::new();
     /// writer
-    ///     .slow_scan_write_by_chunks(["Hello", " World!"].iter(), config)
+    ///     .slow_scan_write_by_chars_with_clock(input.chars(), config, &mut clock)
     ///     .unwrap();
+    ///
+    /// assert_eq!(writer, input.as_bytes());
+    /// // 只有 `$` 和空格之间有延迟
+    /// // Only the gap between `$` and the space is delayed
+    /// assert_eq!(
+    ///     clock
+    ///         .deadlines
+    ///         .iter()
+    ///         .map(|it| *it - start)
+    ///         .collect::<Vec<_>>(),
+    ///     [Duration::from_secs(1)]
+    /// );
     /// ```
     ///
     /// ---
     ///
-    /// Write data chunk-by-chunk with specified configuration.
-    ///
-    /// # Arguments
-    /// - `iter`: Iterator of byte chunks where each item implements `AsRef<[u8]>`
-    /// - `config`: Slow scan configuration parameters
-    ///
-    /// # Returns
-    /// - `Ok(())`: All data written successfully
-    /// - `Err(io::Error)`: I/O error occurred during writing
+    /// Whether to recognize and preserve OSC 133 prompt marks
     ///
-    /// # Delay Precision
-    /// - Default implementation uses `std::thread::sleep` with precision affected by system scheduling
-    /// - When `unstable` feature is enabled, uses `std::thread::sleep_until` for more precise
-    ///   delay control, avoiding cumulative timing errors from execution time, without
-    ///   significant performance impact
+    /// When enabled, `slow_scan_write_by_chars` writes shell prompt marks of
+    /// the form `ESC ] 133 ; ... BEL` or `ESC ] 133 ; ... ESC \` whole and
+    /// immediately, without any delay and without counting them as visible
+    /// content, so that the shell integration of the terminal keeps working
+    /// when replaying recorded shell sessions
+    #[getset(get = "pub", set = "pub")]
+    prompt_marks: bool,
+
+    /// 自定义的字符宽度函数
     ///
-    /// # Performance Note
-    /// Benchmarking shows that using the `unstable` feature does not incur significant
-    /// performance penalty while providing more precise timing control
+    /// 设置后 `slow_scan_write_by_chars` 会用它代替
+    /// `UnicodeWidthChar::width_cjk` 判断字符类型：返回 `Some(2)` 视为全宽字符，
+    /// 返回 `None` 视为控制字符，其余视为半宽字符。适用于使用特殊字体或对 CJK
+    /// 字符有特殊处理的终端
     ///
-    /// # Example
+    /// # 示例
     /// ```
-    /// use std::time::Duration;
+    /// use std::time::{Duration, Instant};
     ///
     /// use slow_scan_print::{SlowScanConfig, SlowScanWrite};
     ///
     /// let mut config = SlowScanConfig::default();
-    /// config.set_base_delay(Duration::from_millis(10));
+    /// config
+    ///     .set_base_delay(Duration::from_secs(1))
+    ///     .set_full_width_delay(Duration::ZERO)
+    ///     .set_width_fn(Some(|_| Some(2)));
     ///
+    /// let start = Instant::now();
     /// let mut writer = Vec::new();
     /// writer
-    ///     .slow_scan_write_by_chunks(["Hello", " World!"].iter(), config)
+    ///     .slow_scan_write_by_chars("abc".chars(), config)
     ///     .unwrap();
+    ///
+    /// assert!(start.elapsed() < Duration::from_secs(1));
     /// ```
-    fn slow_scan_write_by_chunks<I>(
-        &mut self,
-        iter: I,
-        config: SlowScanConfig
-    ) -> Result<(), io::Error>
-    where
-        I: Iterator,
-        I::Item: AsRef<[u8]>;
+    ///
+    /// ---
+    ///
+    /// Custom character width function
+    ///
+    /// When set, `slow_scan_write_by_chars` uses it instead of
+    /// `UnicodeWidthChar::width_cjk` to classify characters: `Some(2)` is
+    /// treated as full-width, `None` as a control character and anything else
+    /// as half-width. Useful for terminals with special fonts or unusual CJK
+    /// handling
+    #[getset(get = "pub", set = "pub")]
+    width_fn: Option<fn(char) -> Option<usize>>,
 
-    /// 根据 Unicode 字符宽度和配置进行延迟写入
+    /// 按块的显示宽度缩放块之间延迟时使用的参考宽度
     ///
-    /// # 参数
-    /// - `iter`: 字符迭代器
-    /// - `config`: 慢速扫描配置参数
+    /// 设置后 `slow_scan_write_by_chunks` 在每个块之后的延迟为
+    /// `base_delay * max(1, 块宽度 / 参考宽度)`，较长的行会停顿更久；
+    /// 为 `None` 时所有块使用相同的 `base_delay`
     ///
-    /// # 返回值
-    /// - `Ok(())`: 所有数据成功写入
-    /// - `Err(io::Error)`: 写入过程中发生 I/O 错误
+    /// # 示例
+    /// ```
+    /// use std::time::{Duration, Instant};
     ///
-    /// # 注意
-    /// - CJK 全角字符（如中文）会使用 `full_width_delay` 配置的延迟时间
-    /// - 控制字符会使用 `control_char_delay` 配置的延迟时间
-    /// - 半角字符会使用 `base_delay` 配置的延迟时间
+    /// use slow_scan_print::{SlowScanConfig, SlowScanWrite};
+    /// # struct VirtualClock {
+    /// #     now: std::time::Instant,
+    /// #     deadlines: Vec<std::time::Instant>
+    /// # }
+    /// #
+    /// # impl VirtualClock {
+    /// #     fn new(now: std::time::Instant) -> Self {
+    /// #         Self {
+    /// #             now,
+    /// #             deadlines: Vec::new()
+    /// #         }
+    /// #     }
+    /// # }
+    /// #
+    /// # impl slow_scan_print::Clock for VirtualClock {
+    /// #     fn now(&mut self) -> std::time::Instant { self.now }
+    /// #
+    /// #     fn sleep_until(&mut self, deadline: std::time::Instant) {
+    /// #         self.now = self.now.max(deadline);
+    /// #         self.deadlines.push(deadline);
+    /// #     }
+    /// # }
     ///
-    /// # 延迟精度
-    /// - 默认实现使用 `std::thread::sleep`，延迟精度受系统调度影响
-    /// - 启用 `unstable` 特性后使用 `std::thread::sleep_until`，提供更精准的延迟控制
-    ///   避免因执行时间累积导致的延迟误差，同时不会带来明显的性能损失
+    /// let ms = Duration::from_millis;
+    /// let mut config = SlowScanConfig::default();
+    /// config
+    ///     .set_base_delay(ms(10))
+    ///     .set_tail_delay(true)
+    ///     .set_chunk_reference_width(Some(10));
     ///
-    /// # 性能说明
-    /// 基准测试表明，使用 `unstable` 特性不会带来明显的性能损失，同时提供更精确的定时控制
+    /// let start = Instant::now();
+    /// let mut clock = VirtualClock::new(start);
+    /// let lines = [
+    ///     "short\n".to_owned(),
+    ///     "a".repeat(40) + "\n",
+    ///     "ab\n".to_owned()
+    /// ];
+    /// let mut writer = Vec::new();
+    /// writer
+    ///     .slow_scan_write_by_chunks_with_clock(lines.iter(), config, &mut clock)
+    ///     .unwrap();
+    ///
+    /// // 短于参考宽度的行停顿 `base_delay`，40 列的行停顿四倍
+    /// // Lines shorter than the reference width pause for `base_delay`, the
+    /// // 40-column line pauses four times as long
+    /// let gaps = clock
+    ///     .deadlines
+    ///     .iter()
+    ///     .scan(start, |last, it| {
+    ///         let gap = *it - *last;
+    ///         *last = *it;
+    ///         Some(gap)
+    ///     })
+    ///     .collect::<Vec<_>>();
+    /// assert_eq!(gaps, [ms(10), ms(40), ms(10)]);
+    /// ```
     ///
     /// ---
     ///
-    /// Write with width-based delays using Unicode character widths and configuration.
+    /// Reference width used to scale the delay between chunks by their display
+    /// width
     ///
-    /// # Arguments
-    /// - `iter`: Iterator of characters
-    /// - `config`: Slow scan configuration parameters
+    /// When set, the delay after each chunk in `slow_scan_write_by_chunks` is
+    /// `base_delay * max(1, chunk width / reference width)`, so longer lines
+    /// pause longer; when `None` every chunk uses the same `base_delay`
+    #[getset(get = "pub", set = "pub")]
+    chunk_reference_width: Option<usize>,
+
+    /// 块之间的延迟时间
     ///
-    /// # Returns
-    /// - `Ok(())`: All data written successfully
-    /// - `Err(io::Error)`: I/O error occurred during writing
+    /// 设置后 `slow_scan_write_by_chunks` 使用该延迟代替 `base_delay`（同样
+    /// 会按 `chunk_reference_width` 放大），使逐块输出与逐字符输出的节奏可以
+    /// 分别调整；为 `None` 时使用 `base_delay`
     ///
-    /// # Notes
-    /// - CJK full-width characters (e.g., Chinese) will use the `full_width_delay` configuration
-    /// - Control characters will use the `control_char_delay` configuration
-    /// - Half-width characters will use the `base_delay` configuration
+    /// # 示例
+    /// ```
+    /// use std::time::{Duration, Instant};
     ///
-    /// # Delay Precision
-    /// - Default implementation uses `std::thread::sleep` with precision affected by system scheduling
-    /// - When `unstable` feature is enabled, uses `std::thread::sleep_until` for more precise
-    ///   delay control, avoiding cumulative timing errors from execution time, without
-    ///   significant performance impact
+    /// use slow_scan_print::{SlowScanConfig, SlowScanWrite};
+    /// # struct VirtualClock {
+    /// #     now: std::time::Instant,
+    /// #     deadlines: Vec<std::time::Instant>
+    /// # }
+    /// #
+    /// # impl VirtualClock {
+    /// #     fn new(now: std::time::Instant) -> Self {
+    /// #         Self {
+    /// #             now,
+    /// #             deadlines: Vec::new()
+    /// #         }
+    /// #     }
+    /// # }
+    /// #
+    /// # impl slow_scan_print::Clock for VirtualClock {
+    /// #     fn now(&mut self) -> std::time::Instant { self.now }
+    /// #
+    /// #     fn sleep_until(&mut self, deadline: std::time::Instant) {
+    /// #         self.now = self.now.max(deadline);
+    /// #         self.deadlines.push(deadline);
+    /// #     }
+    /// # }
     ///
-    /// # Performance Note
-    /// Benchmarking shows that using the `unstable` feature does not incur significant
-    /// performance penalty while providing more precise timing control
-    fn slow_scan_write_by_chars<I>(
-        &mut self,
-        iter: I,
-        config: SlowScanConfig
-    ) -> Result<(), io::Error>
-    where
-        I: Iterator<Item = char>;
+    /// let start = Instant::now();
+    /// let mut clock = VirtualClock::new(start);
+    ///
+    /// let mut config = SlowScanConfig::default();
+    /// config
+    ///     .set_base_delay(Duration::from_secs(1))
+    ///     .set_inter_chunk_delay(Some(Duration::from_secs(5)));
+    ///
+    /// let mut writer = Vec::new();
+    /// writer
+    ///     .slow_scan_write_by_chunks_with_clock(
+    ///         ["a\n", "b\n"].iter(),
+    ///         config,
+    ///         &mut clock
+    ///     )
+    ///     .unwrap();
+    /// writer
+    ///     .slow_scan_write_by_chars_with_clock("ab".chars(), config, &mut clock)
+    ///     .unwrap();
+    ///
+    /// assert_eq!(
+    ///     clock.deadlines,
+    ///     [
+    ///         start + Duration::from_secs(5),
+    ///         start + Duration::from_secs(6)
+    ///     ]
+    /// );
+    /// ```
+    ///
+    /// ---
+    ///
+    /// Delay between chunks
+    ///
+    /// When set, `slow_scan_write_by_chunks` uses this delay instead of
+    /// `base_delay` (scaled by `chunk_reference_width` likewise), so that the
+    /// pacing of chunk output and character output can be tuned separately;
+    /// when `None` `base_delay` is used
+    #[getset(get = "pub", set = "pub")]
+    inter_chunk_delay: Option<Duration>,
+
+    /// 是否在写入每个单元后立即刷新
+    ///
+    /// 如果设置为 `false`，仅在全部写入完成后刷新一次，适合由写入器自行决定
+    /// 刷新时机（例如定时刷新的缓冲写入器）的场景
+    ///
+    /// ---
+    ///
+    /// Whether to flush right after writing each unit
+    ///
+    /// If set to `false`, the writer is flushed only once after everything is
+    /// written, useful when the writer decides when to flush by itself (e.g. a
+    /// buffered writer flushed on a timer)
+    #[getset(get = "pub", set = "pub")]
+    flush_each_unit: bool,
+
+    /// 逐字符写入时每次刷新之间显示的字符数
+    ///
+    /// 仅在 `flush_each_unit` 为 `true` 时生效。`slow_scan_write_by_chars`
+    /// 仍然在每个字符之后延迟，但每写入这么多个字符才刷新一次，可减少刷新
+    /// 次数，缓解部分终端因更新过于频繁而产生的画面撕裂，总时长不变。为 `0`
+    /// 时视为 `1`
+    ///
+    /// # 示例
+    /// ```
+    /// use std::io::{self, Write};
+    /// use std::time::Duration;
+    ///
+    /// use slow_scan_print::{SlowScanConfig, SlowScanWrite};
+    ///
+    /// #[derive(Default)]
+    /// struct CountingWriter {
+    ///     buf: Vec<u8>,
+    ///     flushes: usize
+    /// }
+    ///
+    /// impl Write for CountingWriter {
+    ///     fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+    ///         self.buf.write(buf)
+    ///     }
+    ///
+    ///     fn flush(&mut self) -> io::Result<()> {
+    ///         self.flushes += 1;
+    ///         Ok(())
+    ///     }
+    /// }
+    ///
+    /// let mut config = SlowScanConfig::default();
+    /// config.set_base_delay(Duration::ZERO);
+    ///
+    /// let mut each = CountingWriter::default();
+    /// each.slow_scan_write_by_chars("abcdefgh".chars(), config)
+    ///     .unwrap();
+    ///
+    /// config.set_chars_per_flush(4);
+    ///
+    /// let mut grouped = CountingWriter::default();
+    /// grouped
+    ///     .slow_scan_write_by_chars("abcdefgh".chars(), config)
+    ///     .unwrap();
+    ///
+    /// assert_eq!(grouped.buf, each.buf);
+    /// assert_eq!(each.flushes, 9);
+    /// assert_eq!(grouped.flushes, 3);
+    /// ```
+    ///
+    /// ---
+    ///
+    /// Number of characters revealed between flushes when writing by chars
+    ///
+    /// Only takes effect when `flush_each_unit` is `true`.
+    /// `slow_scan_write_by_chars` still delays after every character but
+    /// flushes only once every this many characters, reducing the number of
+    /// flushes to ease tearing on terminals that dislike very granular
+    /// updates, without changing the total duration. `0` is treated as `1`
+    #[getset(get = "pub", set = "pub")]
+    chars_per_flush: usize,
+
+    /// 遇到 `WouldBlock` 时的最大重试次数
+    ///
+    /// `slow_scan_write_by_chars` 和 `slow_scan_write_by_chunks` 写入时遇到
+    /// `Interrupted` 会立即重试，遇到 `WouldBlock` 会在短暂退避（从 1 毫秒开始
+    /// 每次翻倍）后重试，用尽重试次数后才返回错误，适用于非阻塞或不稳定的输出
+    /// 目标。默认为 5
+    ///
+    /// # 示例
+    /// ```
+    /// use std::io::{self, ErrorKind, Write};
+    /// use std::time::Duration;
+    ///
+    /// use slow_scan_print::{SlowScanConfig, SlowScanWrite};
+    ///
+    /// /// 第一次写入和第一次刷新分别失败一次的写入器
+    /// struct Flaky {
+    ///     written: Vec<u8>,
+    ///     write_failed: bool,
+    ///     flush_failed: bool
+    /// }
+    ///
+    /// impl Write for Flaky {
+    ///     fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+    ///         if !self.write_failed {
+    ///             self.write_failed = true;
+    ///             return Err(ErrorKind::WouldBlock.into());
+    ///         }
+    ///         self.written.write(buf)
+    ///     }
+    ///
+    ///     fn flush(&mut self) -> io::Result<()> {
+    ///         if !self.flush_failed {
+    ///             self.flush_failed = true;
+    ///             return Err(ErrorKind::Interrupted.into());
+    ///         }
+    ///         Ok(())
+    ///     }
+    /// }
+    ///
+    /// let mut config = SlowScanConfig::default();
+    /// config.set_base_delay(Duration::ZERO);
+    ///
+    /// let mut writer = Flaky {
+    ///     written: Vec::new(),
+    ///     write_failed: false,
+    ///     flush_failed: false
+    /// };
+    /// writer
+    ///     .slow_scan_write_by_chars("ab".chars(), config)
+    ///     .unwrap();
+    ///
+    /// assert_eq!(writer.written, b"ab");
+    ///
+    /// // 不重试时 `WouldBlock` 会立即返回
+    /// // `WouldBlock` is returned immediately without retries
+    /// config.set_io_retries(0);
+    /// writer.write_failed = false;
+    ///
+    /// let error = writer
+    ///     .slow_scan_write_by_chars("ab".chars(), config)
+    ///     .unwrap_err();
+    ///
+    /// assert_eq!(error.kind(), ErrorKind::WouldBlock);
+    /// ```
+    ///
+    /// ---
+    ///
+    /// Maximum number of retries on `WouldBlock`
+    ///
+    /// When writing, `slow_scan_write_by_chars` and `slow_scan_write_by_chunks`
+    /// retry `Interrupted` immediately and `WouldBlock` after a brief backoff
+    /// (starting at 1 ms and doubled every time), only returning the error
+    /// once the retries are exhausted, which suits nonblocking or flaky sinks.
+    /// Defaults to 5
+    #[getset(get = "pub", set = "pub")]
+    io_retries: usize,
+
+    /// 合并显示连续相同字符的最小长度
+    ///
+    /// 设置为 `Some(n)` 后，`slow_scan_write_by_chars` 会把 `n` 个及以上连续
+    /// 相同的字符按每 `n` 个一组整组写入，每组只延迟一次（剩余不足 `n` 个的
+    /// 字符也合为一组），使分隔线、进度条等字符画显示得更快；输出内容不变。
+    /// 每组的延迟与组内单个字符的延迟相同，不随组长放大，否则整行花费的时间
+    /// 与逐字符显示相同，合并就失去了意义。为 `None` 时逐字符显示
+    ///
+    /// # 示例
+    /// ```
+    /// use std::time::{Duration, Instant};
+    ///
+    /// use slow_scan_print::{SlowScanConfig, SlowScanWrite};
+    /// # struct VirtualClock {
+    /// #     now: std::time::Instant,
+    /// #     deadlines: Vec<std::time::Instant>
+    /// # }
+    /// #
+    /// # impl VirtualClock {
+    /// #     fn new(now: std::time::Instant) -> Self {
+    /// #         Self {
+    /// #             now,
+    /// #             deadlines: Vec::new()
+    /// #         }
+    /// #     }
+    /// # }
+    /// #
+    /// # impl slow_scan_print::Clock for VirtualClock {
+    /// #     fn now(&mut self) -> std::time::Instant { self.now }
+    /// #
+    /// #     fn sleep_until(&mut self, deadline: std::time::Instant) {
+    /// #         self.now = self.now.max(deadline);
+    /// #         self.deadlines.push(deadline);
+    /// #     }
+    /// # }
+    ///
+    /// let ms = Duration::from_millis;
+    /// let mut config = SlowScanConfig::default();
+    /// config
+    ///     .set_base_delay(ms(10))
+    ///     .set_tail_delay(true)
+    ///     .set_coalesce_runs(Some(3));
+    ///
+    /// let start = Instant::now();
+    /// let mut clock = VirtualClock::new(start);
+    /// let mut writer = Vec::new();
+    /// writer
+    ///     .slow_scan_write_by_chars_with_clock(
+    ///         "aaaaaab".chars(),
+    ///         config,
+    ///         &mut clock
+    ///     )
+    ///     .unwrap();
+    ///
+    /// assert_eq!(writer, b"aaaaaab");
+    ///
+    /// // 两组 `aaa` 和单独的 `b` 各延迟一次 `base_delay`
+    /// // The two `aaa` groups and the lone `b` delay `base_delay` once each
+    /// let deadlines = clock
+    ///     .deadlines
+    ///     .iter()
+    ///     .map(|it| *it - start)
+    ///     .collect::<Vec<_>>();
+    /// assert_eq!(deadlines, [ms(10), ms(20), ms(30)]);
+    /// ```
+    ///
+    /// ---
+    ///
+    /// Minimum length of a run of identical characters revealed in groups
+    ///
+    /// When set to `Some(n)`, `slow_scan_write_by_chars` writes runs of `n` or
+    /// more identical characters in groups of `n`, delaying only once per group
+    /// (the remaining characters fewer than `n` form one last group), so that
+    /// ASCII art rules and progress bars are revealed faster; the output is
+    /// unchanged. Every group delays as long as a single character of it, not
+    /// scaled by the group length, as otherwise the whole line would take as
+    /// long as revealing it character by character and grouping would be
+    /// pointless. When `None`, characters are revealed one by one
+    #[getset(get = "pub", set = "pub")]
+    coalesce_runs: Option<usize>,
+
+    /// 带有 VS16（U+FE0F）的表情符号序列的显示宽度
+    ///
+    /// 不同终端对表情符号呈现序列的宽度处理不一致。设置后
+    /// `slow_scan_write_by_chars` 会将紧跟 VS16 的字符与 VS16 一并写入，并以该
+    /// 宽度代替 `unicode-width` 的结果判断延迟：`Some(2)` 使用
+    /// `full_width_delay`，`Some(1)` 使用 `base_delay`。为 `None` 时不做特殊处理
+    ///
+    /// # 示例
+    /// ```
+    /// use std::time::{Duration, Instant};
+    ///
+    /// use slow_scan_print::{SlowScanConfig, SlowScanWrite};
+    ///
+    /// let mut config = SlowScanConfig::default();
+    /// config
+    ///     .set_base_delay(Duration::ZERO)
+    ///     .set_full_width_delay(Duration::from_millis(50))
+    ///     .set_tail_delay(true)
+    ///     .set_emoji_width(Some(2));
+    ///
+    /// let start = Instant::now();
+    /// let mut writer = Vec::new();
+    /// writer
+    ///     .slow_scan_write_by_chars("\u{2764}\u{FE0F}".chars(), config)
+    ///     .unwrap();
+    ///
+    /// assert_eq!(writer, "\u{2764}\u{FE0F}".as_bytes());
+    /// assert!(start.elapsed() >= Duration::from_millis(50));
+    /// ```
+    ///
+    /// ---
+    ///
+    /// Display width of emoji sequences carrying VS16 (U+FE0F)
+    ///
+    /// Terminals disagree on the width of emoji presentation sequences. When
+    /// set, `slow_scan_write_by_chars` writes a character followed by VS16
+    /// together with the VS16 and classifies its delay by this width instead
+    /// of the guess of `unicode-width`: `Some(2)` uses `full_width_delay` and
+    /// `Some(1)` uses `base_delay`. When `None` nothing special is done
+    #[getset(get = "pub", set = "pub")]
+    emoji_width: Option<usize>,
+
+    /// 大写字母的延迟时间
+    ///
+    /// 设置后 `slow_scan_write_by_chars` 会对 `char::is_uppercase` 为 `true`
+    /// 的字符使用该延迟代替按宽度判断的延迟，可用于强调大写字母；为 `None` 时
+    /// 不做特殊处理
+    ///
+    /// # 示例
+    /// ```
+    /// use std::time::{Duration, Instant};
+    ///
+    /// use slow_scan_print::{SlowScanConfig, SlowScanWrite};
+    /// # struct VirtualClock {
+    /// #     now: std::time::Instant,
+    /// #     deadlines: Vec<std::time::Instant>
+    /// # }
+    /// #
+    /// # impl VirtualClock {
+    /// #     fn new(now: std::time::Instant) -> Self {
+    /// #         Self {
+    /// #             now,
+    /// #             deadlines: Vec::new()
+    /// #         }
+    /// #     }
+    /// # }
+    /// #
+    /// # impl slow_scan_print::Clock for VirtualClock {
+    /// #     fn now(&mut self) -> std::time::Instant { self.now }
+    /// #
+    /// #     fn sleep_until(&mut self, deadline: std::time::Instant) {
+    /// #         self.now = self.now.max(deadline);
+    /// #         self.deadlines.push(deadline);
+    /// #     }
+    /// # }
+    ///
+    /// let start = Instant::now();
+    /// let mut clock = VirtualClock::new(start);
+    ///
+    /// let mut config = SlowScanConfig::default();
+    /// config
+    ///     .set_base_delay(Duration::from_secs(1))
+    ///     .set_uppercase_delay(Some(Duration::from_secs(5)));
+    ///
+    /// let mut writer = Vec::new();
+    /// writer
+    ///     .slow_scan_write_by_chars_with_clock("aBc".chars(), config, &mut clock)
+    ///     .unwrap();
+    ///
+    /// assert_eq!(
+    ///     clock.deadlines,
+    ///     [
+    ///         start + Duration::from_secs(1),
+    ///         start + Duration::from_secs(6)
+    ///     ]
+    /// );
+    /// ```
+    ///
+    /// ---
+    ///
+    /// Delay for uppercase letters
+    ///
+    /// When set, `slow_scan_write_by_chars` uses this delay instead of the one
+    /// classified by width for characters where `char::is_uppercase` is
+    /// `true`, useful to put emphasis on capitals; when `None` nothing special
+    /// is done
+    #[getset(get = "pub", set = "pub")]
+    uppercase_delay: Option<Duration>,
+
+    /// 空格的延迟
+    ///
+    /// 设置后 `slow_scan_write_by_chars` 会对空格（包括 `U+00A0`、`U+3000`
+    /// 等非控制字符的 Unicode 空白）使用此延迟，代替按宽度判断的延迟，让单词
+    /// 之间的停顿更明显；为 `None` 时不做任何处理
+    ///
+    /// # 示例
+    /// ```
+    /// use std::time::{Duration, Instant};
+    ///
+    /// use slow_scan_print::{SlowScanConfig, SlowScanWrite};
+    /// # struct VirtualClock {
+    /// #     now: std::time::Instant,
+    /// #     deadlines: Vec<std::time::Instant>
+    /// # }
+    /// #
+    /// # impl VirtualClock {
+    /// #     fn new(now: std::time::Instant) -> Self {
+    /// #         Self {
+    /// #             now,
+    /// #             deadlines: Vec::new()
+    /// #         }
+    /// #     }
+    /// # }
+    /// #
+    /// # impl slow_scan_print::Clock for VirtualClock {
+    /// #     fn now(&mut self) -> std::time::Instant { self.now }
+    /// #
+    /// #     fn sleep_until(&mut self, deadline: std::time::Instant) {
+    /// #         self.now = self.now.max(deadline);
+    /// #         self.deadlines.push(deadline);
+    /// #     }
+    /// # }
+    ///
+    /// let start = Instant::now();
+    /// let mut clock = VirtualClock::new(start);
+    ///
+    /// let mut config = SlowScanConfig::default();
+    /// config
+    ///     .set_base_delay(Duration::from_secs(1))
+    ///     .set_space_delay(Some(Duration::from_secs(3)));
+    ///
+    /// let mut writer = Vec::new();
+    /// writer
+    ///     .slow_scan_write_by_chars_with_clock("a b".chars(), config, &mut clock)
+    ///     .unwrap();
+    ///
+    /// assert_eq!(
+    ///     clock.deadlines,
+    ///     [
+    ///         start + Duration::from_secs(1),
+    ///         start + Duration::from_secs(4)
+    ///     ]
+    /// );
+    /// ```
+    ///
+    /// ---
+    ///
+    /// Delay for spaces
+    ///
+    /// When set, `slow_scan_write_by_chars` uses this delay instead of the one
+    /// classified by width for spaces (including non-control Unicode
+    /// whitespace such as `U+00A0` and `U+3000`), making the pauses between
+    /// words more distinct; when `None` nothing special is done
+    #[getset(get = "pub", set = "pub")]
+    space_delay: Option<Duration>,
+
+    /// 按字母出现频率缩放延迟的频率表
+    ///
+    /// 设置后 `slow_scan_write_by_chars` 会将每个字母的延迟乘以
+    /// [`LetterFrequency::factor`]，模拟常见字母打得快、罕见字母打得慢的打字员；
+    /// 为 `None` 时不做任何处理
+    ///
+    /// # 示例
+    /// ```
+    /// use std::time::{Duration, Instant};
+    ///
+    /// use slow_scan_print::{LetterFrequency, SlowScanConfig, SlowScanWrite};
+    /// # struct VirtualClock {
+    /// #     now: std::time::Instant,
+    /// #     deadlines: Vec<std::time::Instant>
+    /// # }
+    /// #
+    /// # impl VirtualClock {
+    /// #     fn new(now: std::time::Instant) -> Self {
+    /// #         Self {
+    /// #             now,
+    /// #             deadlines: Vec::new()
+    /// #         }
+    /// #     }
+    /// # }
+    /// #
+    /// # impl slow_scan_print::Clock for VirtualClock {
+    /// #     fn now(&mut self) -> std::time::Instant { self.now }
+    /// #
+    /// #     fn sleep_until(&mut self, deadline: std::time::Instant) {
+    /// #         self.now = self.now.max(deadline);
+    /// #         self.deadlines.push(deadline);
+    /// #     }
+    /// # }
+    ///
+    /// let start = Instant::now();
+    /// let mut clock = VirtualClock::new(start);
+    ///
+    /// let mut config = SlowScanConfig::default();
+    /// config
+    ///     .set_base_delay(Duration::from_secs(1))
+    ///     .set_letter_frequency(Some(LetterFrequency::default()))
+    ///     .set_tail_delay(true);
+    ///
+    /// let mut writer = Vec::new();
+    /// writer
+    ///     .slow_scan_write_by_chars_with_clock("ez".chars(), config, &mut clock)
+    ///     .unwrap();
+    ///
+    /// let e = clock.deadlines[0] - start;
+    /// let z = clock.deadlines[1] - clock.deadlines[0];
+    ///
+    /// assert!(e < z);
+    /// assert_eq!(e, Duration::from_millis(500));
+    /// assert_eq!(z, Duration::from_millis(1500));
+    /// ```
+    ///
+    /// ---
+    ///
+    /// Letter frequency table scaling delays by how often letters occur
+    ///
+    /// When set, `slow_scan_write_by_chars` multiplies the delay of every
+    /// letter by [`LetterFrequency::factor`], mimicking a typist who hits
+    /// common letters fast and rare ones slow; when `None` nothing special is
+    /// done
+    #[getset(get = "pub", set = "pub")]
+    letter_frequency: Option<LetterFrequency>,
+
+    /// 私用区字符的延迟
+    ///
+    /// 私用区（如 Nerd Font 图标所在的 `U+E000..=U+F8FF`）字符的宽度没有定义，
+    /// 按宽度分类时会被误判为全宽字符或控制字符。`slow_scan_write_by_chars`
+    /// 会单独识别这些字符，设置时使用此延迟，为 `None` 时使用 `base_delay`
+    ///
+    /// # 示例
+    /// ```
+    /// use std::time::{Duration, Instant};
+    ///
+    /// use slow_scan_print::{SlowScanConfig, SlowScanWrite};
+    /// # struct VirtualClock {
+    /// #     now: std::time::Instant,
+    /// #     deadlines: Vec<std::time::Instant>
+    /// # }
+    /// #
+    /// # impl VirtualClock {
+    /// #     fn new(now: std::time::Instant) -> Self {
+    /// #         Self {
+    /// #             now,
+    /// #             deadlines: Vec::new()
+    /// #         }
+    /// #     }
+    /// # }
+    /// #
+    /// # impl slow_scan_print::Clock for VirtualClock {
+    /// #     fn now(&mut self) -> std::time::Instant { self.now }
+    /// #
+    /// #     fn sleep_until(&mut self, deadline: std::time::Instant) {
+    /// #         self.now = self.now.max(deadline);
+    /// #         self.deadlines.push(deadline);
+    /// #     }
+    /// # }
+    ///
+    /// let deadlines = |config: SlowScanConfig| {
+    ///     let start = Instant::now();
+    ///     let mut clock = VirtualClock::new(start);
+    ///
+    ///     let mut writer = Vec::new();
+    ///     writer
+    ///         .slow_scan_write_by_chars_with_clock(
+    ///             "\u{e0b0}a".chars(),
+    ///             config,
+    ///             &mut clock
+    ///         )
+    ///         .unwrap();
+    ///
+    ///     clock
+    ///         .deadlines
+    ///         .iter()
+    ///         .map(|it| *it - start)
+    ///         .collect::<Vec<_>>()
+    /// };
+    ///
+    /// let mut config = SlowScanConfig::default();
+    /// config
+    ///     .set_base_delay(Duration::from_secs(1))
+    ///     .set_full_width_delay(Duration::from_secs(2))
+    ///     .set_control_char_delay(Duration::from_secs(4));
+    ///
+    /// assert_eq!(deadlines(config), [Duration::from_secs(1)]);
+    ///
+    /// config.set_pua_delay(Some(Duration::from_secs(3)));
+    /// assert_eq!(deadlines(config), [Duration::from_secs(3)]);
+    /// ```
+    ///
+    /// ---
+    ///
+    /// Delay for private-use characters
+    ///
+    /// Private-use characters (such as Nerd Font glyphs in
+    /// `U+E000..=U+F8FF`) have an undefined width and get misclassified as
+    /// full-width or control characters when classified by width.
+    /// `slow_scan_write_by_chars` detects these characters separately and uses
+    /// this delay when set, or `base_delay` when `None`
+    #[getset(get = "pub", set = "pub")]
+    pua_delay: Option<Duration>,
+
+    /// 是否以脱字符记法显示不可打印的控制字符
+    ///
+    /// 启用后 `slow_scan_write_by_chars` 会像 `cat -v` 一样将 `\n` 和 `\t`
+    /// 以外的 C0 控制字符写为 `^X` 形式（如 NUL 写为 `^@`），`\x7f` 写为 `^?`，
+    /// 而不是原样写入，避免其干扰终端。这些字符仍使用 `control_char_delay`
+    ///
+    /// # 示例
+    /// ```
+    /// use std::time::Duration;
+    ///
+    /// use slow_scan_print::{SlowScanConfig, SlowScanWrite};
+    ///
+    /// let mut config = SlowScanConfig::default();
+    /// config
+    ///     .set_base_delay(Duration::ZERO)
+    ///     .set_caret_notation(true);
+    ///
+    /// let mut writer = Vec::new();
+    /// writer
+    ///     .slow_scan_write_by_chars("a\0b\x7f\n".chars(), config)
+    ///     .unwrap();
+    ///
+    /// assert_eq!(writer, b"a^@b^?\n");
+    /// ```
+    ///
+    /// ---
+    ///
+    /// Whether to render non-printable control characters in caret notation
+    ///
+    /// When enabled, `slow_scan_write_by_chars` writes C0 control characters
+    /// other than `\n` and `\t` as `^X` (e.g. NUL as `^@`) and `\x7f` as `^?`
+    /// like `cat -v` does, instead of emitting them raw where they could
+    /// confuse the terminal. These characters still use `control_char_delay`
+    #[getset(get = "pub", set = "pub")]
+    caret_notation: bool,
+    /// 是否把 Unicode 行分隔符 `U+2028` 和段落分隔符 `U+2029` 视为换行类
+    /// 控制字符
+    ///
+    /// 启用后这两个字符按 [`CharClass::Control`] 分类并使用
+    /// `control_char_delay`，而不是按空白字符处理
+    ///
+    /// # 示例
+    /// ```
+    /// use slow_scan_print::{CharClass, SlowScanConfig};
+    ///
+    /// let mut config = SlowScanConfig::default();
+    /// config.set_unicode_lines(true);
+    ///
+    /// assert_eq!(config.char_class('\u{2028}'), CharClass::Control);
+    /// assert_eq!(config.char_class('\u{2029}'), CharClass::Control);
+    /// assert_eq!(config.char_class('a'), CharClass::HalfWidth);
+    /// ```
+    ///
+    /// ---
+    ///
+    /// Whether the Unicode line separator `U+2028` and paragraph separator
+    /// `U+2029` are treated as newline-class control characters
+    ///
+    /// When enabled, both characters are classified as [`CharClass::Control`]
+    /// and use `control_char_delay` instead of being handled as whitespace
+    #[getset(get = "pub", set = "pub")]
+    unicode_lines: bool,
+    /// 是否把所有可打印字符都视为半角字符
+    ///
+    /// 启用后不再查询字符宽度，除控制字符外的所有字符都按宽度 1 处理并使用
+    /// `base_delay`，适合所有字符都以单倍宽度显示的字体，也省去了宽度查询的
+    /// 开销。优先于 `width_fn`
+    ///
+    /// # 示例
+    /// ```
+    /// use std::time::{Duration, Instant};
+    ///
+    /// use slow_scan_print::{CharClass, SlowScanConfig, SlowScanWrite};
+    /// # struct VirtualClock {
+    /// #     now: std::time::Instant,
+    /// #     deadlines: Vec<std::time::Instant>
+    /// # }
+    /// #
+    /// # impl VirtualClock {
+    /// #     fn new(now: std::time::Instant) -> Self {
+    /// #         Self {
+    /// #             now,
+    /// #             deadlines: Vec::new()
+    /// #         }
+    /// #     }
+    /// # }
+    /// #
+    /// # impl slow_scan_print::Clock for VirtualClock {
+    /// #     fn now(&mut self) -> std::time::Instant { self.now }
+    /// #
+    /// #     fn sleep_until(&mut self, deadline: std::time::Instant) {
+    /// #         self.now = self.now.max(deadline);
+    /// #         self.deadlines.push(deadline);
+    /// #     }
+    /// # }
+    ///
+    /// let start = Instant::now();
+    /// let mut clock = VirtualClock::new(start);
+    ///
+    /// let mut config = SlowScanConfig::default();
+    /// config
+    ///     .set_base_delay(Duration::from_secs(1))
+    ///     .set_full_width_delay(Duration::from_secs(5))
+    ///     .set_half_width_only(true);
+    ///
+    /// assert_eq!(config.char_class('世'), CharClass::HalfWidth);
+    /// assert_eq!(config.char_class('\n'), CharClass::Control);
+    ///
+    /// let mut writer = Vec::new();
+    /// writer
+    ///     .slow_scan_write_by_chars_with_clock(
+    ///         "世界你好".chars(),
+    ///         config,
+    ///         &mut clock
+    ///     )
+    ///     .unwrap();
+    ///
+    /// assert_eq!(
+    ///     clock.deadlines,
+    ///     [
+    ///         start + Duration::from_secs(1),
+    ///         start + Duration::from_secs(2),
+    ///         start + Duration::from_secs(3)
+    ///     ]
+    /// );
+    /// ```
+    ///
+    /// ---
+    ///
+    /// Whether every printable character is treated as half-width
+    ///
+    /// When enabled, character widths are no longer looked up: every character
+    /// other than control characters counts as width 1 and uses `base_delay`,
+    /// which suits fonts rendering every character at single width and also
+    /// saves the cost of the width lookup. Takes precedence over `width_fn`
+    #[getset(get = "pub", set = "pub")]
+    half_width_only: bool,
+    /// 成组显示的节奏，每 `K` 个字符之后停顿指定的时间
+    ///
+    /// 设置为 `Some((K, pause))` 后，逐字符写入时每第 `K` 个字符之后的延迟
+    /// 替换为 `pause`，其余字符照常使用按类型区分的延迟；配合较短的
+    /// `base_delay` 可以得到一阵一阵的打字节奏。`K` 为 0 时不生效
+    ///
+    /// # 示例
+    /// ```
+    /// use std::time::{Duration, Instant};
+    ///
+    /// use slow_scan_print::{SlowScanConfig, SlowScanWrite};
+    /// # struct VirtualClock {
+    /// #     now: std::time::Instant,
+    /// #     deadlines: Vec<std::time::Instant>
+    /// # }
+    /// #
+    /// # impl VirtualClock {
+    /// #     fn new(now: std::time::Instant) -> Self {
+    /// #         Self {
+    /// #             now,
+    /// #             deadlines: Vec::new()
+    /// #         }
+    /// #     }
+    /// # }
+    /// #
+    /// # impl slow_scan_print::Clock for VirtualClock {
+    /// #     fn now(&mut self) -> std::time::Instant { self.now }
+    /// #
+    /// #     fn sleep_until(&mut self, deadline: std::time::Instant) {
+    /// #         self.now = self.now.max(deadline);
+    /// #         self.deadlines.push(deadline);
+    /// #     }
+    /// # }
+    ///
+    /// let start = Instant::now();
+    /// let mut clock = VirtualClock::new(start);
+    ///
+    /// let ms = Duration::from_millis;
+    /// let mut config = SlowScanConfig::default();
+    /// config.set_base_delay(ms(10)).set_burst(Some((3, ms(500))));
+    ///
+    /// let mut writer = Vec::new();
+    /// writer
+    ///     .slow_scan_write_by_chars_with_clock(
+    ///         "abcdefg".chars(),
+    ///         config,
+    ///         &mut clock
+    ///     )
+    ///     .unwrap();
+    ///
+    /// let delays = clock
+    ///     .deadlines
+    ///     .windows(2)
+    ///     .map(|it| it[1] - it[0])
+    ///     .collect::<Vec<_>>();
+    ///
+    /// assert_eq!(clock.deadlines[0] - start, ms(10));
+    /// // 每第 3 个字符之后停顿 500ms
+    /// // Every third character is followed by a pause of 500ms
+    /// assert_eq!(delays, [ms(10), ms(500), ms(10), ms(10), ms(500)]);
+    /// ```
+    ///
+    /// ---
+    ///
+    /// Rhythm revealing characters in bursts, pausing after every `K`
+    /// characters
+    ///
+    /// When set to `Some((K, pause))`, writing character by character replaces
+    /// the delay after every `K`-th character with `pause`, while the other
+    /// characters keep their per-class delays; together with a short
+    /// `base_delay` this gives a burst-by-burst typing rhythm. Has no effect
+    /// when `K` is 0
+    #[getset(get = "pub", set = "pub")]
+    burst: Option<(usize, Duration)>,
+    /// 模拟电传打字机的页宽，以列为单位
+    ///
+    /// 设置后逐字符写入时记录当前所在的列，字符超出页宽时先插入 `\r\n`
+    /// 自动换行，并等待 `carriage_return_delay` 模拟字车返回的时间；全角字符
+    /// 计为两列，输入中的 `\n` 和 `\r` 回到第一列。为 `None` 或 0 时不换行
+    ///
+    /// # 示例
+    /// ```
+    /// use std::time::{Duration, Instant};
+    ///
+    /// use slow_scan_print::{SlowScanConfig, SlowScanWrite};
+    /// # struct VirtualClock {
+    /// #     now: std::time::Instant,
+    /// #     deadlines: Vec<std::time::Instant>
+    /// # }
+    /// #
+    /// # impl VirtualClock {
+    /// #     fn new(now: std::time::Instant) -> Self {
+    /// #         Self {
+    /// #             now,
+    /// #             deadlines: Vec::new()
+    /// #         }
+    /// #     }
+    /// # }
+    /// #
+    /// # impl slow_scan_print::Clock for VirtualClock {
+    /// #     fn now(&mut self) -> std::time::Instant { self.now }
+    /// #
+    /// #     fn sleep_until(&mut self, deadline: std::time::Instant) {
+    /// #         self.now = self.now.max(deadline);
+    /// #         self.deadlines.push(deadline);
+    /// #     }
+    /// # }
+    ///
+    /// let start = Instant::now();
+    /// let mut clock = VirtualClock::new(start);
+    ///
+    /// let ms = Duration::from_millis;
+    /// let mut config = SlowScanConfig::default();
+    /// config
+    ///     .set_base_delay(ms(10))
+    ///     .set_full_width_delay(ms(20))
+    ///     .set_teletype_width(Some(4))
+    ///     .set_carriage_return_delay(ms(100));
+    ///
+    /// let mut writer = Vec::new();
+    /// writer
+    ///     .slow_scan_write_by_chars_with_clock(
+    ///         "abc中de".chars(),
+    ///         config,
+    ///         &mut clock
+    ///     )
+    ///     .unwrap();
+    ///
+    /// // 全角字符放不下第 4 列，先换行
+    /// // The full-width character does not fit in column 4, so the line wraps
+    /// // first
+    /// assert_eq!(String::from_utf8(writer).unwrap(), "abc\r\n中de");
+    ///
+    /// let delays = clock
+    ///     .deadlines
+    ///     .windows(2)
+    ///     .map(|it| it[1] - it[0])
+    ///     .collect::<Vec<_>>();
+    ///
+    /// assert_eq!(clock.deadlines[0] - start, ms(10));
+    /// // 换行处等待字车返回
+    /// // The carriage return delay fires at the wrap point
+    /// assert_eq!(delays, [ms(10), ms(10), ms(100), ms(20), ms(10)]);
+    /// ```
+    ///
+    /// ---
+    ///
+    /// Page width of a simulated teletype, in columns
+    ///
+    /// When set, writing character by character keeps track of the current
+    /// column and, when a character would run past the page width, first
+    /// wraps by inserting `\r\n` and waits `carriage_return_delay` to mimic
+    /// the carriage travelling back; full-width characters count as two
+    /// columns, and a `\n` or `\r` in the input returns to the first column.
+    /// No wrapping happens when `None` or 0
+    #[getset(get = "pub", set = "pub")]
+    teletype_width: Option<usize>,
+    /// 电传打字机模式下自动换行之后的延迟，模拟字车返回的时间
+    ///
+    /// 只在设置了 `teletype_width` 时生效，默认为 300ms
+    ///
+    /// ---
+    ///
+    /// Delay after an automatic line wrap in teletype mode, mimicking the
+    /// travel time of the carriage
+    ///
+    /// Only takes effect when `teletype_width` is set, 300ms by default
+    #[getset(get = "pub", set = "pub")]
+    carriage_return_delay: Duration,
+    /// 将每个实际执行的延迟四舍五入到该粒度的整数倍
+    ///
+    /// 在睡眠精度较粗的平台上（如 Windows 默认的 15.6ms 计时器），`20ms / 3`
+    /// 这样的延迟会被不可预测地取整；设置为计时器的精度后，每个延迟在按
+    /// `speed` 指令缩放之后对齐到该粒度，节奏变得可以预测。短于半个粒度的延迟
+    /// 会被取整为零。为 `None` 或零时不取整
+    ///
+    /// # 示例
+    /// ```
+    /// use std::time::{Duration, Instant};
+    ///
+    /// use slow_scan_print::{SlowScanConfig, SlowScanWrite};
+    /// # struct VirtualClock {
+    /// #     now: std::time::Instant,
+    /// #     deadlines: Vec<std::time::Instant>
+    /// # }
+    /// #
+    /// # impl VirtualClock {
+    /// #     fn new(now: std::time::Instant) -> Self {
+    /// #         Self {
+    /// #             now,
+    /// #             deadlines: Vec::new()
+    /// #         }
+    /// #     }
+    /// # }
+    /// #
+    /// # impl slow_scan_print::Clock for VirtualClock {
+    /// #     fn now(&mut self) -> std::time::Instant { self.now }
+    /// #
+    /// #     fn sleep_until(&mut self, deadline: std::time::Instant) {
+    /// #         self.now = self.now.max(deadline);
+    /// #         self.deadlines.push(deadline);
+    /// #     }
+    /// # }
+    ///
+    /// let start = Instant::now();
+    /// let mut clock = VirtualClock::new(start);
+    ///
+    /// let ms = Duration::from_millis;
+    /// let mut config = SlowScanConfig::default();
+    /// config
+    ///     .set_base_delay(ms(7))
+    ///     .set_full_width_delay(ms(14))
+    ///     .set_tail_delay(true)
+    ///     .set_round_to(Some(ms(5)));
+    ///
+    /// let mut writer = Vec::new();
+    /// writer
+    ///     .slow_scan_write_by_chars_with_clock(
+    ///         "ab中".chars(),
+    ///         config,
+    ///         &mut clock
+    ///     )
+    ///     .unwrap();
+    ///
+    /// let delays = clock
+    ///     .deadlines
+    ///     .iter()
+    ///     .scan(start, |last, it| Some(*it - std::mem::replace(last, *it)))
+    ///     .collect::<Vec<_>>();
+    ///
+    /// // 7ms 对齐为 5ms，14ms 对齐为 15ms
+    /// // 7ms snaps to 5ms and 14ms snaps to 15ms
+    /// assert_eq!(delays, [ms(5), ms(5), ms(15)]);
+    /// ```
+    ///
+    /// ---
+    ///
+    /// Round every delay actually performed to a multiple of this granularity
+    ///
+    /// On platforms with a coarse sleep granularity (such as the default
+    /// 15.6ms timer of Windows), delays like `20ms / 3` get rounded
+    /// unpredictably; set to the precision of the timer, every delay is snapped
+    /// to the granularity after being scaled by `speed` directives, making the
+    /// pacing predictable. Delays shorter than half the granularity are
+    /// rounded to zero. No rounding happens when `None` or zero
+    #[getset(get = "pub", set = "pub")]
+    round_to: Option<Duration>,
+
+    /// 是否将东亚宽度不明确的字符视为全宽字符
+    ///
+    /// `±`、`·` 等字符在 CJK 区域的终端中通常占两列，在其他终端中
+    /// 占一列。为 `true` 时按 `UnicodeWidthChar::width_cjk` 计算宽度，否则按
+    /// `UnicodeWidthChar::width` 计算，可以配合 [`detect_cjk_locale`] 按终端的
+    /// 区域设置选择。设置了 `width_fn` 时不起作用
+    ///
+    /// # 示例
+    /// ```
+    /// use std::time::{Duration, Instant};
+    ///
+    /// use slow_scan_print::{SlowScanConfig, SlowScanWrite, is_cjk_locale};
+    /// # struct VirtualClock {
+    /// #     now: std::time::Instant,
+    /// #     deadlines: Vec<std::time::Instant>
+    /// # }
+    /// #
+    /// # impl VirtualClock {
+    /// #     fn new(now: std::time::Instant) -> Self {
+    /// #         Self {
+    /// #             now,
+    /// #             deadlines: Vec::new()
+    /// #         }
+    /// #     }
+    /// # }
+    /// #
+    /// # impl slow_scan_print::Clock for VirtualClock {
+    /// #     fn now(&mut self) -> std::time::Instant { self.now }
+    /// #
+    /// #     fn sleep_until(&mut self, deadline: std::time::Instant) {
+    /// #         self.now = self.now.max(deadline);
+    /// #         self.deadlines.push(deadline);
+    /// #     }
+    /// # }
+    ///
+    /// let ms = Duration::from_millis;
+    /// let delay_under = |locale: &str| {
+    ///     let mut config = SlowScanConfig::default();
+    ///     config
+    ///         .set_base_delay(ms(10))
+    ///         .set_full_width_delay(ms(40))
+    ///         .set_tail_delay(true)
+    ///         .set_cjk_ambiguous_wide(is_cjk_locale(locale));
+    ///
+    ///     let start = Instant::now();
+    ///     let mut clock = VirtualClock::new(start);
+    ///     Vec::new()
+    ///         .slow_scan_write_by_chars_with_clock("±".chars(), config, &mut clock)
+    ///         .unwrap();
+    ///     clock.deadlines[0] - start
+    /// };
+    ///
+    /// // `±` 在中文区域下按全宽处理，在英文区域下按半宽处理
+    /// // `±` is full-width under a Chinese locale and half-width under an
+    /// // English one
+    /// assert_eq!(delay_under("zh_CN.UTF-8"), ms(40));
+    /// assert_eq!(delay_under("en_US.UTF-8"), ms(10));
+    /// ```
+    ///
+    /// ---
+    ///
+    /// Whether characters of ambiguous East Asian width are full-width
+    ///
+    /// Characters such as `±` and `·` usually take two columns
+    /// in terminals of a CJK locale and one column elsewhere. When `true`
+    /// widths are computed with `UnicodeWidthChar::width_cjk`, otherwise with
+    /// `UnicodeWidthChar::width`; pair with [`detect_cjk_locale`] to choose
+    /// according to the locale of the terminal. Has no effect when `width_fn`
+    /// is set
+    #[getset(get = "pub", set = "pub")]
+    cjk_ambiguous_wide: bool,
+    /// 所有延迟的速度倍率，实际执行的延迟为有效延迟除以该倍率
+    ///
+    /// `2.0` 表示两倍速，`0.5` 表示半速；小于等于 0 时所有延迟都变为零。与按
+    /// 字符类型设置的各项延迟相互独立，缩放作用在最终选出的延迟上，与内嵌的
+    /// `speed` 指令效果相乘，并在 `round_to` 取整之前进行。
+    /// [`estimate_duration`](Self::estimate_duration) 不考虑该倍率。极小的
+    /// 倍率使延迟超出可表示的范围时，单次延迟会被限制在约 136 年以内而不是
+    /// 溢出
+    ///
+    /// # 示例
+    /// ```
+    /// use std::time::{Duration, Instant};
+    ///
+    /// use slow_scan_print::{SlowScanConfig, SlowScanWrite};
+    /// # struct VirtualClock {
+    /// #     now: std::time::Instant,
+    /// #     deadlines: Vec<std::time::Instant>
+    /// # }
+    /// #
+    /// # impl VirtualClock {
+    /// #     fn new(now: std::time::Instant) -> Self {
+    /// #         Self {
+    /// #             now,
+    /// #             deadlines: Vec::new()
+    /// #         }
+    /// #     }
+    /// # }
+    /// #
+    /// # impl slow_scan_print::Clock for VirtualClock {
+    /// #     fn now(&mut self) -> std::time::Instant { self.now }
+    /// #
+    /// #     fn sleep_until(&mut self, deadline: std::time::Instant) {
+    /// #         self.now = self.now.max(deadline);
+    /// #         self.deadlines.push(deadline);
+    /// #     }
+    /// # }
+    ///
+    /// let ms = Duration::from_millis;
+    /// let delays_at = |speed: f64| {
+    ///     let mut config = SlowScanConfig::default();
+    ///     config
+    ///         .set_base_delay(ms(20))
+    ///         .set_full_width_delay(ms(40))
+    ///         .set_tail_delay(true)
+    ///         .set_speed_factor(speed);
+    ///
+    ///     let start = Instant::now();
+    ///     let mut clock = VirtualClock::new(start);
+    ///     let mut writer = Vec::new();
+    ///     writer
+    ///         .slow_scan_write_by_chars_with_clock(
+    ///             "a世".chars(),
+    ///             config,
+    ///             &mut clock
+    ///         )
+    ///         .unwrap();
+    ///     writer
+    ///         .slow_scan_write_by_chunks_with_clock(
+    ///             ["ab"].iter(),
+    ///             config,
+    ///             &mut clock
+    ///         )
+    ///         .unwrap();
+    ///
+    ///     clock
+    ///         .deadlines
+    ///         .iter()
+    ///         .scan(start, |last, it| Some(*it - std::mem::replace(last, *it)))
+    ///         .collect::<Vec<_>>()
+    /// };
+    ///
+    /// assert_eq!(delays_at(1.0), [ms(20), ms(40), ms(20)]);
+    /// assert_eq!(delays_at(2.0), [ms(10), ms(20), ms(10)]);
+    /// assert_eq!(delays_at(0.0), [ms(0), ms(0), ms(0)]);
+    /// ```
+    ///
+    /// ---
+    ///
+    /// Speed multiplier of all delays, the delay actually performed being the
+    /// effective delay divided by this factor
+    ///
+    /// `2.0` means twice as fast and `0.5` half as fast; all delays become zero
+    /// when it is less than or equal to 0. It is independent of the delays set
+    /// per character class, scaling the delay finally chosen, multiplies with
+    /// the inline `speed` directive and is applied before the `round_to`
+    /// rounding. [`estimate_duration`](Self::estimate_duration) does not take
+    /// it into account. When a tiny factor takes a delay beyond what can be
+    /// represented, a single delay is capped at about 136 years instead of
+    /// overflowing
+    #[getset(get = "pub", set = "pub")]
+    speed_factor: f64,
+    /// 延迟随机抖动的比例，取值为 `0.0` 到 `1.0`
+    ///
+    /// 大于 0 时每次实际执行的延迟在 `delay * (1 ± jitter)` 范围内均匀随机
+    /// 取值，使打字机效果显得更自然。随机数来自以系统时间为种子的 xorshift
+    /// 生成器；超出范围的值按边界处理，因此延迟不会为负。为 0 时不生成随机数，
+    /// 输出与节奏和不启用时完全相同
+    ///
+    /// # 示例
+    /// ```
+    /// use std::time::{Duration, Instant};
+    ///
+    /// use slow_scan_print::{SlowScanConfig, SlowScanWrite};
+    /// # struct VirtualClock {
+    /// #     now: std::time::Instant,
+    /// #     deadlines: Vec<std::time::Instant>
+    /// # }
+    /// #
+    /// # impl VirtualClock {
+    /// #     fn new(now: std::time::Instant) -> Self {
+    /// #         Self {
+    /// #             now,
+    /// #             deadlines: Vec::new()
+    /// #         }
+    /// #     }
+    /// # }
+    /// #
+    /// # impl slow_scan_print::Clock for VirtualClock {
+    /// #     fn now(&mut self) -> std::time::Instant { self.now }
+    /// #
+    /// #     fn sleep_until(&mut self, deadline: std::time::Instant) {
+    /// #         self.now = self.now.max(deadline);
+    /// #         self.deadlines.push(deadline);
+    /// #     }
+    /// # }
+    ///
+    /// let ms = Duration::from_millis;
+    /// let delays_with = |jitter: f64| {
+    ///     let mut config = SlowScanConfig::default();
+    ///     config
+    ///         .set_base_delay(ms(100))
+    ///         .set_tail_delay(true)
+    ///         .set_jitter(jitter);
+    ///
+    ///     let start = Instant::now();
+    ///     let mut clock = VirtualClock::new(start);
+    ///     Vec::new()
+    ///         .slow_scan_write_by_chars_with_clock(
+    ///             "a".repeat(64).chars(),
+    ///             config,
+    ///             &mut clock
+    ///         )
+    ///         .unwrap();
+    ///
+    ///     clock
+    ///         .deadlines
+    ///         .iter()
+    ///         .scan(start, |last, it| Some(*it - std::mem::replace(last, *it)))
+    ///         .collect::<Vec<_>>()
+    /// };
+    ///
+    /// assert!(delays_with(0.0).iter().all(|it| *it == ms(100)));
+    /// assert!(
+    ///     delays_with(0.5)
+    ///         .iter()
+    ///         .all(|it| (ms(50)..=ms(150)).contains(it))
+    /// );
+    /// // 超出范围的比例按 1.0 处理
+    /// // Out-of-range fractions are treated as 1.0
+    /// assert!(delays_with(3.0).iter().all(|it| *it <= ms(200)));
+    /// ```
+    ///
+    /// ---
+    ///
+    /// Fraction of the random jitter of delays, from `0.0` to `1.0`
+    ///
+    /// When greater than 0 every delay actually performed is picked uniformly
+    /// at random within `delay * (1 ± jitter)`, giving the typewriter effect a
+    /// more organic feel. The randomness comes from a xorshift generator
+    /// seeded from the system time; values out of range are clamped, so delays
+    /// are never negative. When 0 no random numbers are generated and both the
+    /// output and the pace are exactly the same as without it
+    #[cfg(feature = "jitter")]
+    #[getset(get = "pub", set = "pub")]
+    jitter: f64
+}
+
+/// 选择表情符号呈现方式的变体选择符 VS16
+///
+/// ---
+///
+/// Variation selector VS16 choosing the emoji presentation
+const VS16: char = '\u{FE0F}';
+
+impl SlowScanConfig {
+    /// 返回适合逐行输出的默认配置
+    ///
+    /// 与 [`SlowScanConfig::default`] 不同，字符之间没有延迟，而每行之后延迟
+    /// 200ms，供 [`SlowScanWrite::slow_scan_write_by_chunks`] 按行输出时使用
+    ///
+    /// # 示例
+    /// ```
+    /// use std::time::Duration;
+    ///
+    /// use slow_scan_print::SlowScanConfig;
+    ///
+    /// let chars = SlowScanConfig::default();
+    /// let lines = SlowScanConfig::default_for_line_mode();
+    ///
+    /// assert_eq!(*lines.base_delay(), Duration::ZERO);
+    /// assert!(*chars.base_delay() > Duration::ZERO);
+    ///
+    /// assert!(lines.chunk_delay(b"line\n") > chars.chunk_delay(b"line\n"));
+    /// assert_eq!(lines.chunk_delay(b"line\n"), Duration::from_millis(200));
+    /// ```
+    ///
+    /// ---
+    ///
+    /// Return the default configuration suited to line-by-line output
+    ///
+    /// Unlike [`SlowScanConfig::default`], there is no delay between
+    /// characters but a delay of 200ms after every line, for use with
+    /// [`SlowScanWrite::slow_scan_write_by_chunks`] when writing by lines
+    pub fn default_for_line_mode() -> Self {
+        Self {
+            base_delay: Duration::ZERO,
+            full_width_delay: Duration::ZERO,
+            inter_chunk_delay: Some(Duration::from_millis(200)),
+            ..Self::default()
+        }
+    }
+
+    /// 按配置计算字符的显示宽度，控制字符返回 `None`
+    ///
+    /// ---
+    ///
+    /// Compute the display width of a character according to the
+    /// configuration, returning `None` for control characters
+    fn char_width(&self, c: char) -> Option<usize> {
+        if self.unicode_lines && is_unicode_separator(c) {
+            return None;
+        }
+        if self.half_width_only {
+            return (!c.is_control()).then_some(1);
+        }
+
+        match self.width_fn {
+            Some(width_fn) => width_fn(c),
+            None if self.cjk_ambiguous_wide => {
+                unicode_width::UnicodeWidthChar::width_cjk(c)
+            }
+            None => unicode_width::UnicodeWidthChar::width(c)
+        }
+    }
+
+    /// 按字符类型返回写入该字符后的延迟
+    ///
+    /// ---
+    ///
+    /// Return the delay after writing a character according to its type
+    fn char_delay(&self, c: char) -> Duration {
+        self.classified_delay(c, self.delay_class(c))
+    }
+
+    /// 返回已经分类的字符之后的延迟
+    ///
+    /// ---
+    ///
+    /// Return the delay after a character already classified
+    fn classified_delay(&self, c: char, class: DelayClass) -> Duration {
+        let delay = match class {
+            DelayClass::HalfWidth => self.base_delay,
+            DelayClass::FullWidth => self.full_width_delay,
+            DelayClass::Control | DelayClass::Newline => {
+                self.control_char_delay
+            }
+            DelayClass::Space => self.space_delay.unwrap_or(self.base_delay),
+            DelayClass::Uppercase => {
+                self.uppercase_delay.unwrap_or(self.base_delay)
+            }
+            DelayClass::PrivateUse => self.pua_delay.unwrap_or(self.base_delay)
+        };
+
+        match self.letter_frequency {
+            Some(it) => delay.mul_f64(it.factor(c)),
+            None => delay
+        }
+    }
+
+    /// 返回某个单元之后的延迟，后面还有单元时为 `delay`，否则由 `tail_delay`
+    /// 决定
+    ///
+    /// ---
+    ///
+    /// Return the delay after a unit, `delay` when more units follow and
+    /// decided by `tail_delay` otherwise
+    fn pause_after(&self, delay: Duration, more: bool) -> Option<Duration> {
+        if more {
+            Some(delay)
+        } else {
+            self.tail_delay.delay(delay)
+        }
+    }
+
+    /// 按 [`char_width`](Self::char_width) 得到的显示宽度返回对应的延迟
+    ///
+    /// ---
+    ///
+    /// Return the delay matching a display width obtained from
+    /// [`char_width`](Self::char_width)
+    fn width_delay(&self, width: Option<usize>) -> Duration {
+        self.class_delay(CharClass::from_width(width))
+    }
+
+    /// 返回最后一个单元之后是否有延迟
+    ///
+    /// 保留原有的 `bool` 返回类型以兼容旧代码，[`TailBehavior::None`] 以外的
+    /// 行为都返回 `true`；需要区分具体行为时使用
+    /// [`tail_behavior`](Self::tail_behavior)
+    ///
+    /// # 示例
+    /// ```
+    /// use std::time::Duration;
+    ///
+    /// use slow_scan_print::{SlowScanConfig, TailBehavior};
+    ///
+    /// let mut config = SlowScanConfig::default();
+    /// assert!(!config.tail_delay());
+    ///
+    /// config.set_tail_delay(TailBehavior::Custom(Duration::from_secs(1)));
+    /// assert!(config.tail_delay());
+    /// assert_eq!(
+    ///     config.tail_behavior(),
+    ///     &TailBehavior::Custom(Duration::from_secs(1))
+    /// );
+    /// ```
+    ///
+    /// ---
+    ///
+    /// Return whether there is a delay after the last unit
+    ///
+    /// Keeps the original `bool` return type for compatibility with existing
+    /// code, returning `true` for every behavior other than
+    /// [`TailBehavior::None`]; use [`tail_behavior`](Self::tail_behavior) to
+    /// tell the behaviors apart
+    pub fn tail_delay(&self) -> &bool {
+        match self.tail_delay {
+            TailBehavior::None => &false,
+            _ => &true
+        }
+    }
+
+    /// 返回输出最后一个单元之后的行为
+    ///
+    /// ---
+    ///
+    /// Return the behavior after the last unit is output
+    pub fn tail_behavior(&self) -> &TailBehavior { &self.tail_delay }
+
+    /// 设置输出最后一个单元之后的行为，可以直接传入 `bool`
+    ///
+    /// ---
+    ///
+    /// Set the behavior after the last unit is output, a `bool` can be passed
+    /// directly
+    pub fn set_tail_delay<T: Into<TailBehavior>>(
+        &mut self,
+        tail_delay: T
+    ) -> &mut Self {
+        self.tail_delay = tail_delay.into();
+        self
+    }
+
+    /// 按配置判断字符的类型
+    ///
+    /// ---
+    ///
+    /// Classify a character according to the configuration
+    pub fn char_class(&self, c: char) -> CharClass {
+        CharClass::from_width(self.char_width(c))
+    }
+
+    /// 按配置判断决定字符延迟的分类，见 [`DelayClass`]
+    ///
+    /// ---
+    ///
+    /// Classify a character by what decides its delay according to the
+    /// configuration, see [`DelayClass`]
+    pub fn delay_class(&self, c: char) -> DelayClass {
+        let separator = self.unicode_lines && is_unicode_separator(c);
+        let space = c.is_whitespace() && !c.is_control() && !separator;
+
+        if is_private_use(c) {
+            DelayClass::PrivateUse
+        } else if space && self.space_delay.is_some() {
+            DelayClass::Space
+        } else if c.is_uppercase() && self.uppercase_delay.is_some() {
+            DelayClass::Uppercase
+        } else {
+            match self.char_class(c) {
+                CharClass::HalfWidth => DelayClass::HalfWidth,
+                CharClass::FullWidth => DelayClass::FullWidth,
+                CharClass::Control if c == '\n' || separator => {
+                    DelayClass::Newline
+                }
+                CharClass::Control => DelayClass::Control
+            }
+        }
+    }
+
+    /// 返回某类字符使用的延迟
+    ///
+    /// ---
+    ///
+    /// Return the delay used for a character class
+    pub fn class_delay(&self, class: CharClass) -> Duration {
+        match class {
+            // 全宽字符（如中文字符）
+            CharClass::FullWidth => self.full_width_delay,
+            // 控制字符（如 \n、\t 等）延迟
+            CharClass::Control => self.control_char_delay,
+            // 半宽字符（如英文字母、数字）
+            CharClass::HalfWidth => self.base_delay
+        }
+    }
+
+    /// 按配置计算字符串的显示宽度，控制字符按 0 计算
+    ///
+    /// ---
+    ///
+    /// Compute the display width of a string according to the configuration,
+    /// counting control characters as 0
+    fn str_width(&self, s: &str) -> usize {
+        s.chars().map(|it| self.char_width(it).unwrap_or(0)).sum()
+    }
+
+    /// 将字符串中位于 `spans` 内的字符替换为按显示宽度重复的 `*`
+    ///
+    /// 控制字符保持不变，以免改变光标位置
+    ///
+    /// ---
+    ///
+    /// Replace the characters of a string inside `spans` with `*` repeated by
+    /// display width
+    ///
+    /// Control characters are kept unchanged so the cursor position is not
+    /// altered
+    fn mask_spans(&self, s: &str, spans: &[Range<usize>]) -> String {
+        let mut masked = String::with_capacity(s.len());
+
+        for (i, it) in s.char_indices() {
+            match self.char_width(it) {
+                Some(width) if spans.iter().any(|span| span.contains(&i)) => {
+                    masked.extend(std::iter::repeat_n('*', width))
+                }
+                _ => masked.push(it)
+            }
+        }
+
+        masked
+    }
+
+    /// 计算 `slow_scan_write_by_chunks` 在某个块之后的延迟
+    ///
+    /// 累加各块的延迟即可得到每个块显示时的计划偏移
+    ///
+    /// # 示例
+    /// ```
+    /// use std::time::Duration;
+    ///
+    /// use slow_scan_print::SlowScanConfig;
+    ///
+    /// let mut config = SlowScanConfig::default();
+    /// config
+    ///     .set_base_delay(Duration::from_millis(10))
+    ///     .set_chunk_reference_width(Some(2));
+    ///
+    /// let offsets = ["a\n", "abcd\n", "ab\n"]
+    ///     .iter()
+    ///     .scan(Duration::ZERO, |offset, it| {
+    ///         let current = *offset;
+    ///         *offset += config.chunk_delay(it.as_bytes());
+    ///         Some(current)
+    ///     })
+    ///     .collect::<Vec<_>>();
+    ///
+    /// assert_eq!(
+    ///     offsets,
+    ///     [
+    ///         Duration::ZERO,
+    ///         Duration::from_millis(10),
+    ///         Duration::from_millis(30)
+    ///     ]
+    /// );
+    /// ```
+    ///
+    /// ---
+    ///
+    /// Compute the delay `slow_scan_write_by_chunks` applies after a chunk
+    ///
+    /// Summing up the delays of the chunks gives the scheduled offset at which
+    /// every chunk is shown
+    pub fn chunk_delay(&self, chunk: &[u8]) -> Duration {
+        let delay = self.inter_chunk_delay.unwrap_or(self.base_delay);
+
+        match self.chunk_reference_width {
+            Some(reference) if reference > 0 => {
+                let width =
+                    self.str_width(String::from_utf8_lossy(chunk).as_ref());
+                let factor = (width as f64 / reference as f64).max(1.0);
+
+                delay.mul_f64(factor)
+            }
+            _ => delay
+        }
+    }
+
+    /// 根据预期的总持续时间和块数量自动计算并设置基础延迟
+    ///
+    /// 这个方法适用于需要精确控制整体输出时长的场景，通过预期的总时长和
+    /// 块数量来自动计算每个块之间的基础延迟时间。
+    ///
+    /// # 参数
+    /// - `expectation`: 期望的总输出持续时间
+    /// - `chunk_count`: 输出的块数量（字符块或字节块）
+    ///
+    /// # 计算规则
+    /// - 如果 `tail_delay` 为 `LastUnit`，延迟次数等于块数量
+    /// - 否则延迟次数等于 `块数量 - 1`
+    /// - 如果 `tail_delay` 为 `Custom`，期望总时长先减去该时间
+    /// - 基础延迟 = 期望总时长 / 延迟次数
+    /// - 如果块数量为 0，基础延迟设置为 `Duration::ZERO`
+    ///
+    /// # 示例
+    /// ```
+    /// use std::time::Duration;
+    ///
+    /// use slow_scan_print::SlowScanConfig;
+    ///
+    /// // 希望在 1 秒内输出 10 个块
+    /// let mut config = SlowScanConfig::default();
+    /// config
+    ///     .set_tail_delay(true)
+    ///     .set_base_delay_from_expected_total_duration(
+    ///         Duration::from_secs(1),
+    ///         10
+    ///     );
+    ///
+    /// assert_eq!(*config.base_delay(), Duration::from_millis(100));
+    ///
+    /// config
+    ///     .set_tail_delay(false)
+    ///     .set_base_delay_from_expected_total_duration(
+    ///         Duration::from_secs(1),
+    ///         11
+    ///     );
+    /// assert_eq!(*config.base_delay(), Duration::from_millis(100));
+    /// ```
+    ///
+    /// # 注意
+    /// - 这个方法不会修改 `full_width_delay` 和 `control_char_delay` 的设置
+    /// - 实际总时长可能因系统调度和性能而有微小偏差
+    /// - 如果 `chunk_count` 为 0 或 1（且 `tail_delay` 不为 `LastUnit`），基础延迟会被设置为 0
+    ///
+    /// ---
+    ///
+    /// Automatically calculates and sets base delay based on expected total duration and chunk count
+    ///
+    /// This method is useful for scenarios requiring precise control over total output duration,
+    /// automatically calculating the base delay between chunks based on expected total time
+    /// and number of chunks.
+    ///
+    /// # Arguments
+    /// - `expectation`: Expected total output duration
+    /// - `chunk_count`: Number of output chunks (character chunks or byte chunks)
+    ///
+    /// # Calculation Rules
+    /// - If `tail_delay` is `LastUnit`, number of delays equals chunk count
+    /// - Otherwise, number of delays equals `chunk_count - 1`
+    /// - If `tail_delay` is `Custom`, its time is first subtracted from the
+    ///   expected total duration
+    /// - Base delay = Expected total duration / Number of delays
+    /// - If chunk count is 0, base delay is set to `Duration::ZERO`
+    ///
+    /// # Example
+    /// ```
+    /// use std::time::Duration;
+    ///
+    /// use slow_scan_print::SlowScanConfig;
+    ///
+    /// // Want to output 10 chunks within 1 second
+    /// let mut config = SlowScanConfig::default();
+    /// config
+    ///     .set_tail_delay(true)
+    ///     .set_base_delay_from_expected_total_duration(
+    ///         Duration::from_secs(1),
+    ///         10
+    ///     );
+    ///
+    /// assert_eq!(*config.base_delay(), Duration::from_millis(100));
+    ///
+    /// config
+    ///     .set_tail_delay(false)
+    ///     .set_base_delay_from_expected_total_duration(
+    ///         Duration::from_secs(1),
+    ///         11
+    ///     );
+    /// assert_eq!(*config.base_delay(), Duration::from_millis(100));
+    /// ```
+    ///
+    /// # Notes
+    /// - This method does not modify `full_width_delay` and `control_char_delay` settings
+    /// - Actual total duration may have slight deviations due to system scheduling and performance
+    /// - If `chunk_count` is 0 or 1 (and `tail_delay` is not `LastUnit`), base delay will be set to 0
+    pub fn set_base_delay_from_expected_total_duration(
+        &mut self,
+        expectation: Duration,
+        chunk_count: u32
+    ) -> &mut Self {
+        let (expectation, delay_count) = match self.tail_delay {
+            TailBehavior::None => (expectation, chunk_count.saturating_sub(1)),
+            TailBehavior::LastUnit => (expectation, chunk_count),
+            TailBehavior::Custom(it) => (
+                expectation.saturating_sub(it),
+                chunk_count.saturating_sub(1)
+            )
+        };
+
+        if delay_count > 0 {
+            self.set_base_delay(expectation / delay_count)
+        } else {
+            self.set_base_delay(Duration::ZERO)
+        }
+    }
+
+    /// 估算按 `slow_scan_write_by_chars` 的规则显示一串字符所需的总延迟
+    ///
+    /// 按字符类型和各项延迟设置逐个累加延迟，最后一个字符之后按 `tail_delay`
+    /// 处理；不考虑内联指令和写入本身的耗时
+    ///
+    /// # 示例
+    /// ```
+    /// use std::time::Duration;
+    ///
+    /// use slow_scan_print::SlowScanConfig;
+    ///
+    /// let config = SlowScanConfig::default();
+    ///
+    /// assert_eq!(
+    ///     config.estimate_duration("ab世\n".chars()),
+    ///     Duration::from_millis(80)
+    /// );
+    /// ```
+    ///
+    /// ---
+    ///
+    /// Estimate the total delay of revealing a sequence of characters
+    /// following the rules of `slow_scan_write_by_chars`
+    ///
+    /// The delays are summed up character by character according to their
+    /// class and the delay settings, the last character being followed by
+    /// `tail_delay`; inline directives and the time spent writing are not
+    /// taken into account
+    pub fn estimate_duration<I>(&self, iter: I) -> Duration
+    where
+        I: IntoIterator<Item = char>
+    {
+        let mut iter = classify_chars(iter.into_iter(), self).peekable();
+        let mut total = Duration::ZERO;
+
+        while let Some((c, class)) = iter.next() {
+            let delay = self.classified_delay(c, class);
+            if let Some(it) = self.pause_after(delay, iter.peek().is_some()) {
+                total += it;
+            }
+        }
+
+        total
+    }
+
+    /// 按实际的字符类型组成求出使估算总时长不超过上限的最大基础延迟并设置
+    ///
+    /// `full_width_delay`、`control_char_delay` 等其余延迟保持不变，只有依赖
+    /// 基础延迟的部分（半角字符，以及未单独设置延迟的私有使用区字符）随之缩放，
+    /// 因此对于混合文本比
+    /// [`set_base_delay_from_expected_total_duration`](Self::set_base_delay_from_expected_total_duration)
+    /// 更准确。固定部分已经超过上限时基础延迟被设置为 0；输入中没有依赖基础
+    /// 延迟的字符时保持不变
+    ///
+    /// # 参数
+    /// - `max_total`: 估算总时长的上限
+    /// - `iter`: 要显示的字符
+    ///
+    /// # 示例
+    /// ```
+    /// use std::time::Duration;
+    ///
+    /// use slow_scan_print::SlowScanConfig;
+    ///
+    /// let text = "ab世界\n\ncd";
+    /// let cap = Duration::from_secs(1);
+    ///
+    /// let mut config = SlowScanConfig::default();
+    /// config
+    ///     .set_full_width_delay(Duration::from_millis(40))
+    ///     .set_control_char_delay(Duration::from_millis(10))
+    ///     .fit_base_delay_under(cap, text.chars());
+    ///
+    /// // 固定部分为 2 × 40ms + 2 × 10ms，剩余的 900ms 由 3 个半角字符分摊
+    /// // The fixed part is 2 × 40ms + 2 × 10ms, and the remaining 900ms is
+    /// // shared by 3 half-width characters
+    /// assert_eq!(*config.base_delay(), Duration::from_millis(300));
+    /// assert!(config.estimate_duration(text.chars()) <= cap);
+    /// ```
+    ///
+    /// ---
+    ///
+    /// Solve for and set the largest base delay keeping the estimated total
+    /// duration under a cap, according to the actual mix of character classes
+    ///
+    /// The other delays such as `full_width_delay` and `control_char_delay`
+    /// are kept unchanged and only the parts depending on the base delay
+    /// (half-width characters, and private-use characters without a delay of
+    /// their own) scale with it, which makes this more accurate for mixed text
+    /// than
+    /// [`set_base_delay_from_expected_total_duration`](Self::set_base_delay_from_expected_total_duration).
+    /// The base delay is set to 0 when the fixed part already exceeds the cap,
+    /// and left unchanged when no character of the input depends on it
+    ///
+    /// # Arguments
+    /// - `max_total`: Cap of the estimated total duration
+    /// - `iter`: Characters to reveal
+    pub fn fit_base_delay_under<I>(
+        &mut self,
+        max_total: Duration,
+        iter: I
+    ) -> &mut Self
+    where
+        I: IntoIterator<Item = char>
+    {
+        let chars = iter.into_iter().collect::<Vec<_>>();
+
+        // 估算总时长是基础延迟的线性函数，由两个取值求出截距和斜率
+        let mut config = *self;
+        config.base_delay = Duration::ZERO;
+        let fixed = config.estimate_duration(chars.iter().copied());
+        config.base_delay = Duration::from_secs(1);
+        let slope = config
+            .estimate_duration(chars.iter().copied())
+            .saturating_sub(fixed)
+            .as_nanos();
+
+        let nanos = (max_total.saturating_sub(fixed).as_nanos()
+            * Duration::from_secs(1).as_nanos())
+        .checked_div(slope);
+        if let Some(it) = nanos {
+            self.base_delay =
+                Duration::from_nanos(u64::try_from(it).unwrap_or(u64::MAX));
+        }
+
+        self
+    }
+
+    /// 按实际的字符类型组成求出使估算总时长不短于下限的最小基础延迟，并在
+    /// 当前的基础延迟不足时提高到该值
+    ///
+    /// 与 [`fit_base_delay_under`](Self::fit_base_delay_under) 相反，用于避免
+    /// 很短的输入在眼睛注意到之前就显示完毕。估算总时长已经达到下限，或输入中
+    /// 没有依赖基础延迟的字符时保持不变
+    ///
+    /// # 参数
+    /// - `min_total`: 估算总时长的下限
+    /// - `iter`: 要显示的字符
+    ///
+    /// # 示例
+    /// ```
+    /// use std::time::Duration;
+    ///
+    /// use slow_scan_print::SlowScanConfig;
+    ///
+    /// let mut config = SlowScanConfig::default();
+    /// config
+    ///     .set_base_delay(Duration::from_millis(20))
+    ///     .set_tail_delay(true)
+    ///     .raise_base_delay_over(Duration::from_secs(3), "abc".chars());
+    ///
+    /// assert_eq!(*config.base_delay(), Duration::from_secs(1));
+    ///
+    /// // 已经足够长时不变
+    /// // Unchanged when already long enough
+    /// config.raise_base_delay_over(Duration::from_secs(1), "abc".chars());
+    /// assert_eq!(*config.base_delay(), Duration::from_secs(1));
+    /// ```
+    ///
+    /// ---
+    ///
+    /// Solve for the smallest base delay keeping the estimated total duration
+    /// above a floor according to the actual mix of character classes, and
+    /// raise the base delay to it when the current one falls short
+    ///
+    /// The inverse of [`fit_base_delay_under`](Self::fit_base_delay_under),
+    /// used to keep very short input from being gone before the eye registers
+    /// it. Left unchanged when the estimated total duration already reaches the
+    /// floor, or when no character of the input depends on the base delay
+    ///
+    /// # Arguments
+    /// - `min_total`: Floor of the estimated total duration
+    /// - `iter`: Characters to reveal
+    pub fn raise_base_delay_over<I>(
+        &mut self,
+        min_total: Duration,
+        iter: I
+    ) -> &mut Self
+    where
+        I: IntoIterator<Item = char>
+    {
+        let chars = iter.into_iter().collect::<Vec<_>>();
+        if self.estimate_duration(chars.iter().copied()) >= min_total {
+            return self;
+        }
+
+        // 估算总时长是基础延迟的线性函数，由两个取值求出截距和斜率
+        let mut config = *self;
+        config.base_delay = Duration::ZERO;
+        let fixed = config.estimate_duration(chars.iter().copied());
+        config.base_delay = Duration::from_secs(1);
+        let slope = config
+            .estimate_duration(chars.iter().copied())
+            .saturating_sub(fixed)
+            .as_nanos();
+
+        // 向上取整，保证估算总时长不短于下限
+        let nanos = (min_total.saturating_sub(fixed).as_nanos()
+            * Duration::from_secs(1).as_nanos())
+        .checked_next_multiple_of(slope.max(1))
+        .and_then(|it| it.checked_div(slope));
+        if let Some(it) = nanos {
+            self.base_delay =
+                Duration::from_nanos(u64::try_from(it).unwrap_or(u64::MAX));
+        }
+
+        self
+    }
+
+    /// 根据显示满一屏的预期时间和屏幕尺寸计算并设置基础延迟
+    ///
+    /// 以 `列数 × 行数` 估算一屏能容纳的半角字符数量，基础延迟 = 预期时间 /
+    /// 容量。这只是近似值：实际内容很少填满每一行，全角字符占用两列并使用
+    /// `full_width_delay`，控制字符使用 `control_char_delay`，因此真实的显示
+    /// 时间通常会短于预期
+    ///
+    /// # 参数
+    /// - `screen_time`: 显示满一屏的预期时间
+    /// - `columns`: 屏幕的列数
+    /// - `rows`: 屏幕的行数
+    ///
+    /// # 示例
+    /// ```
+    /// use std::time::Duration;
+    ///
+    /// use slow_scan_print::SlowScanConfig;
+    ///
+    /// let mut config = SlowScanConfig::default();
+    /// config.set_base_delay_from_screen_time(Duration::from_secs(2), 80, 25);
+    ///
+    /// assert_eq!(*config.base_delay(), Duration::from_millis(1));
+    /// ```
+    ///
+    /// ---
+    ///
+    /// Compute and set the base delay from the expected time to fill one
+    /// screen and the screen size
+    ///
+    /// The capacity of a screen is estimated as `columns × rows` half-width
+    /// characters and the base delay is the expected time divided by the
+    /// capacity. This is only an approximation: real content rarely fills
+    /// every row, full-width characters take two columns and use
+    /// `full_width_delay`, and control characters use `control_char_delay`, so
+    /// the actual time is usually shorter than expected
+    ///
+    /// # Arguments
+    /// - `screen_time`: Expected time to fill one screen
+    /// - `columns`: Number of columns of the screen
+    /// - `rows`: Number of rows of the screen
+    pub fn set_base_delay_from_screen_time(
+        &mut self,
+        screen_time: Duration,
+        columns: u32,
+        rows: u32
+    ) -> &mut Self {
+        match columns.saturating_mul(rows) {
+            0 => self.set_base_delay(Duration::ZERO),
+            capacity => self.set_base_delay(screen_time / capacity)
+        }
+    }
+
+    /// 将基础延迟、全角字符延迟和控制字符延迟同时乘以 `factor`
+    ///
+    /// 三者之间的比例保持不变，适合一次调整整体的快慢。与内嵌的 `speed`
+    /// 指令不同，缩放在调用时直接写入配置，之后的输出不再需要换算；两者可以
+    /// 同时使用，效果相乘
+    ///
+    /// # 参数
+    /// - `factor`: 延迟的倍数，`0.5` 表示延迟减半
+    ///
+    /// 结果超出 `Duration` 的范围时饱和为 `Duration::MAX`；`factor` 为负数或
+    /// NaN 时延迟变为零
+    ///
+    /// # 示例
+    /// ```
+    /// use std::time::Duration;
+    ///
+    /// use slow_scan_print::SlowScanConfig;
+    ///
+    /// let ms = Duration::from_millis;
+    /// let mut config = SlowScanConfig::default();
+    /// config
+    ///     .set_base_delay(ms(20))
+    ///     .set_full_width_delay(ms(40))
+    ///     .set_control_char_delay(ms(10))
+    ///     .scale_delays(0.5);
+    ///
+    /// assert_eq!(*config.base_delay(), ms(10));
+    /// assert_eq!(*config.full_width_delay(), ms(20));
+    /// assert_eq!(*config.control_char_delay(), ms(5));
+    ///
+    /// config.scale_delays(1e300);
+    /// assert_eq!(*config.base_delay(), Duration::MAX);
+    /// assert_eq!(*config.control_char_delay(), Duration::MAX);
+    /// ```
+    ///
+    /// ---
+    ///
+    /// Multiply the base delay, the full-width delay and the control character
+    /// delay by `factor` at once
+    ///
+    /// The proportions between the three are kept, which suits tuning the
+    /// overall pace in one go. Unlike the inline `speed` directive, the scaling
+    /// is written into the configuration when called and the output does not
+    /// need to convert anything afterwards; both can be used together and
+    /// their effects multiply
+    ///
+    /// # Arguments
+    /// - `factor`: Multiplier of the delays, `0.5` halves them
+    ///
+    /// Results beyond the range of `Duration` saturate to `Duration::MAX`;
+    /// the delays become zero when `factor` is negative or NaN
+    pub fn scale_delays(&mut self, factor: f64) -> &mut Self {
+        self.base_delay = scale(self.base_delay, factor);
+        self.full_width_delay = scale(self.full_width_delay, factor);
+        self.control_char_delay = scale(self.control_char_delay, factor);
+        self
+    }
+}
+
+impl Default for SlowScanConfig {
+    fn default() -> Self {
+        Self {
+            base_delay: Duration::from_millis(20),
+            full_width_delay: Duration::from_millis(40),
+            control_char_delay: Duration::ZERO,
+            tail_delay: TailBehavior::None,
+            head_units: None,
+            inline_directives: false,
+            prompt_marks: false,
+            width_fn: None,
+            chunk_reference_width: None,
+            inter_chunk_delay: None,
+            flush_each_unit: true,
+            chars_per_flush: 1,
+            io_retries: 5,
+            coalesce_runs: None,
+            emoji_width: None,
+            uppercase_delay: None,
+            space_delay: None,
+            letter_frequency: None,
+            pua_delay: None,
+            caret_notation: false,
+            unicode_lines: false,
+            half_width_only: false,
+            burst: None,
+            teletype_width: None,
+            carriage_return_delay: Duration::from_millis(300),
+            round_to: None,
+            cjk_ambiguous_wide: true,
+            speed_factor: 1.0,
+            #[cfg(feature = "jitter")]
+            jitter: 0.0
+        }
+    }
+}
+
+/// 以 `base=20ms full=40ms ctrl=0ms tail=false` 的形式输出延迟配置的简要摘要，
+/// 适合用于日志
+///
+/// # 示例
+/// ```
+/// use std::time::Duration;
+///
+/// use slow_scan_print::SlowScanConfig;
+///
+/// let mut config = SlowScanConfig::default();
+/// assert_eq!(
+///     config.to_string(),
+///     "base=20ms full=40ms ctrl=0ms tail=false"
+/// );
+///
+/// config
+///     .set_base_delay(Duration::from_secs(2))
+///     .set_control_char_delay(Duration::from_micros(1500))
+///     .set_tail_delay(true);
+/// assert_eq!(config.to_string(), "base=2s full=40ms ctrl=1.5ms tail=true");
+/// ```
+///
+/// ---
+///
+/// Write a compact summary of the delay configuration in the form of
+/// `base=20ms full=40ms ctrl=0ms tail=false`, suitable for logging
+impl fmt::Display for SlowScanConfig {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "base={} full={} ctrl={} tail={}",
+            CompactDuration(self.base_delay),
+            CompactDuration(self.full_width_delay),
+            CompactDuration(self.control_char_delay),
+            match self.tail_delay {
+                TailBehavior::None => "false".to_string(),
+                TailBehavior::LastUnit => "true".to_string(),
+                TailBehavior::Custom(it) => CompactDuration(it).to_string()
+            }
+        )
+    }
+}
+
+/// 以紧凑形式显示的时间，整秒显示为 `s`，整毫秒显示为 `ms`，其余情况与
+/// `Debug` 相同
+///
+/// ---
+///
+/// Duration displayed compactly, whole seconds as `s`, whole milliseconds as
+/// `ms`, and the same as `Debug` otherwise
+struct CompactDuration(Duration);
+
+impl fmt::Display for CompactDuration {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let Self(it) = self;
+
+        if it.as_secs() > 0 && it.subsec_nanos() == 0 {
+            write!(f, "{}s", it.as_secs())
+        } else if it.subsec_nanos() % 1_000_000 == 0 {
+            write!(f, "{}ms", it.as_millis())
+        } else {
+            write!(f, "{:?}", it)
+        }
+    }
+}
+
+/// 提供缓慢扫描式写入功能的 trait，模拟逐字符输出效果
+///
+/// 适用于需要模拟打字机效果或逐字符显示的场景
+///
+/// # 延迟精度说明
+/// - 默认情况下使用 `std::thread::sleep`，延迟精度受系统调度影响
+/// - 启用 `unstable` 特性后使用 `std::thread::sleep_until`，提供更精准的延迟控制
+///   避免因执行时间累积导致的延迟误差，同时不会带来明显的性能损失
+///
+/// ---
+///
+/// A trait for slow scanning write operations to simulate character-by-character output.
+///
+/// Useful for creating typewriter effects or progressive character display.
+///
+/// # Delay Precision Notes
+/// - By default uses `std::thread::sleep` with precision affected by system scheduling
+/// - When `unstable` feature is enabled, uses `std::thread::sleep_until` for more precise
+///   delay control, avoiding cumulative timing errors from execution time, without
+///   significant performance impact
+pub trait SlowScanWrite {
+    /// 以指定配置逐块写入数据
+    ///
+    /// # 参数
+    /// - `iter`: 字节块迭代器，每个元素需实现 `AsRef<[u8]>`
+    /// - `config`: 慢速扫描配置参数
+    ///
+    /// # 返回值
+    /// - `Ok(())`: 所有数据成功写入
+    /// - `Err(io::Error)`: 写入过程中发生 I/O 错误
+    ///
+    /// # 延迟精度
+    /// - 默认实现使用 `std::thread::sleep`，延迟精度受系统调度影响
+    /// - 启用 `unstable` 特性后使用 `std::thread::sleep_until`，提供更精准的延迟控制
+    ///   避免因执行时间累积导致的延迟误差，同时不会带来明显的性能损失
+    ///
+    /// # 性能说明
+    /// 基准测试表明，使用 `unstable` 特性不会带来明显的性能损失，同时提供更精确的定时控制
+    ///
+    /// # 示例
+    /// ```
+    /// use std::time::Duration;
+    ///
+    /// use slow_scan_print::{SlowScanConfig, SlowScanWrite};
+    ///
+    /// let mut config = SlowScanConfig::default();
+    /// config.set_base_delay(Duration::from_millis(10));
+    ///
+    /// let mut writer = Vec::new();
+    /// writer
+    ///     .slow_scan_write_by_chunks(["Hello", " World!"].iter(), config)
+    ///     .unwrap();
+    /// ```
+    ///
+    /// ---
+    ///
+    /// Write data chunk-by-chunk with specified configuration.
+    ///
+    /// # Arguments
+    /// - `iter`: Iterator of byte chunks where each item implements `AsRef<[u8]>`
+    /// - `config`: Slow scan configuration parameters
+    ///
+    /// # Returns
+    /// - `Ok(())`: All data written successfully
+    /// - `Err(io::Error)`: I/O error occurred during writing
+    ///
+    /// # Delay Precision
+    /// - Default implementation uses `std::thread::sleep` with precision affected by system scheduling
+    /// - When `unstable` feature is enabled, uses `std::thread::sleep_until` for more precise
+    ///   delay control, avoiding cumulative timing errors from execution time, without
+    ///   significant performance impact
+    ///
+    /// # Performance Note
+    /// Benchmarking shows that using the `unstable` feature does not incur significant
+    /// performance penalty while providing more precise timing control
+    ///
+    /// # Example
+    /// ```
+    /// use std::time::Duration;
+    ///
+    /// use slow_scan_print::{SlowScanConfig, SlowScanWrite};
+    ///
+    /// let mut config = SlowScanConfig::default();
+    /// config.set_base_delay(Duration::from_millis(10));
+    ///
+    /// let mut writer = Vec::new();
+    /// writer
+    ///     .slow_scan_write_by_chunks(["Hello", " World!"].iter(), config)
+    ///     .unwrap();
+    /// ```
+    fn slow_scan_write_by_chunks<I>(
+        &mut self,
+        iter: I,
+        config: SlowScanConfig
+    ) -> Result<(), io::Error>
+    where
+        I: Iterator,
+        I::Item: AsRef<[u8]>;
+
+    /// 根据 Unicode 字符宽度和配置进行延迟写入
+    ///
+    /// # 参数
+    /// - `iter`: 字符迭代器
+    /// - `config`: 慢速扫描配置参数
+    ///
+    /// # 返回值
+    /// - `Ok(())`: 所有数据成功写入
+    /// - `Err(io::Error)`: 写入过程中发生 I/O 错误
+    ///
+    /// # 注意
+    /// - CJK 全角字符（如中文）会使用 `full_width_delay` 配置的延迟时间
+    /// - 控制字符会使用 `control_char_delay` 配置的延迟时间
+    /// - 半角字符会使用 `base_delay` 配置的延迟时间
+    ///
+    /// # 延迟精度
+    /// - 默认实现使用 `std::thread::sleep`，延迟精度受系统调度影响
+    /// - 启用 `unstable` 特性后使用 `std::thread::sleep_until`，提供更精准的延迟控制
+    ///   避免因执行时间累积导致的延迟误差，同时不会带来明显的性能损失
+    ///
+    /// # 性能说明
+    /// 基准测试表明，使用 `unstable` 特性不会带来明显的性能损失，同时提供更精确的定时控制
+    ///
+    /// ---
+    ///
+    /// Write with width-based delays using Unicode character widths and configuration.
+    ///
+    /// # Arguments
+    /// - `iter`: Iterator of characters
+    /// - `config`: Slow scan configuration parameters
+    ///
+    /// # Returns
+    /// - `Ok(())`: All data written successfully
+    /// - `Err(io::Error)`: I/O error occurred during writing
+    ///
+    /// # Notes
+    /// - CJK full-width characters (e.g., Chinese) will use the `full_width_delay` configuration
+    /// - Control characters will use the `control_char_delay` configuration
+    /// - Half-width characters will use the `base_delay` configuration
+    ///
+    /// # Delay Precision
+    /// - Default implementation uses `std::thread::sleep` with precision affected by system scheduling
+    /// - When `unstable` feature is enabled, uses `std::thread::sleep_until` for more precise
+    ///   delay control, avoiding cumulative timing errors from execution time, without
+    ///   significant performance impact
+    ///
+    /// # Performance Note
+    /// Benchmarking shows that using the `unstable` feature does not incur significant
+    /// performance penalty while providing more precise timing control
+    fn slow_scan_write_by_chars<I>(
+        &mut self,
+        iter: I,
+        config: SlowScanConfig
+    ) -> Result<(), io::Error>
+    where
+        I: Iterator<Item = char>;
+
+    /// 使用指定的时钟逐块写入数据
+    ///
+    /// 与 `slow_scan_write_by_chunks` 相同，但所有延迟都通过 `clock` 完成，
+    /// 可用于模拟或测试
+    ///
+    /// # 参数
+    /// - `iter`: 字节块迭代器，每个元素需实现 `AsRef<[u8]>`
+    /// - `config`: 慢速扫描配置参数
+    /// - `clock`: 提供当前时间和休眠的时钟
+    ///
+    /// # 返回值
+    /// - `Ok(())`: 所有数据成功写入
+    /// - `Err(io::Error)`: 写入过程中发生 I/O 错误
+    ///
+    /// ---
+    ///
+    /// Write data chunk-by-chunk using the given clock
+    ///
+    /// Same as `slow_scan_write_by_chunks`, but every delay goes through
+    /// `clock`, useful for simulations or tests
+    ///
+    /// # Arguments
+    /// - `iter`: Iterator of byte chunks where each item implements `AsRef<[u8]>`
+    /// - `config`: Slow scan configuration parameters
+    /// - `clock`: Clock providing the current time and sleeping
+    ///
+    /// # Returns
+    /// - `Ok(())`: All data written successfully
+    /// - `Err(io::Error)`: I/O error occurred during writing
+    fn slow_scan_write_by_chunks_with_clock<I, C>(
+        &mut self,
+        iter: I,
+        config: SlowScanConfig,
+        clock: &mut C
+    ) -> Result<(), io::Error>
+    where
+        I: Iterator,
+        I::Item: AsRef<[u8]>,
+        C: Clock + ?Sized;
+
+    /// 使用指定的时钟根据 Unicode 字符宽度和配置进行延迟写入
+    ///
+    /// 与 `slow_scan_write_by_chars` 相同，但所有延迟都通过 `clock` 完成，
+    /// 可用于模拟或测试
+    ///
+    /// # 参数
+    /// - `iter`: 字符迭代器
+    /// - `config`: 慢速扫描配置参数
+    /// - `clock`: 提供当前时间和休眠的时钟
+    ///
+    /// # 返回值
+    /// - `Ok(())`: 所有数据成功写入
+    /// - `Err(io::Error)`: 写入过程中发生 I/O 错误
+    ///
+    /// ---
+    ///
+    /// Write with width-based delays using the given clock
+    ///
+    /// Same as `slow_scan_write_by_chars`, but every delay goes through
+    /// `clock`, useful for simulations or tests
+    ///
+    /// # Arguments
+    /// - `iter`: Iterator of characters
+    /// - `config`: Slow scan configuration parameters
+    /// - `clock`: Clock providing the current time and sleeping
+    ///
+    /// # Returns
+    /// - `Ok(())`: All data written successfully
+    /// - `Err(io::Error)`: I/O error occurred during writing
+    fn slow_scan_write_by_chars_with_clock<I, C>(
+        &mut self,
+        iter: I,
+        config: SlowScanConfig,
+        clock: &mut C
+    ) -> Result<(), io::Error>
+    where
+        I: Iterator<Item = char>,
+        C: Clock + ?Sized;
+
+    /// 根据 Unicode 字符宽度和配置进行延迟写入，并在每个字符写入后调用钩子
+    ///
+    /// `on_unit` 在字符写入之后、刷新和延迟之前被调用，可向写入目标追加转义
+    /// 序列或覆盖刚显示的字符，作为各种视觉效果的扩展点。内联指令不会触发钩子
+    ///
+    /// # 参数
+    /// - `iter`: 字符迭代器
+    /// - `config`: 慢速扫描配置参数
+    /// - `on_unit`: 每个字符写入后调用的钩子，参数为写入目标和该字符
+    ///
+    /// # 返回值
+    /// - `Ok(())`: 所有数据成功写入
+    /// - `Err(io::Error)`: 写入过程中或钩子中发生 I/O 错误
+    ///
+    /// # 示例
+    /// ```
+    /// use std::time::Duration;
+    ///
+    /// use slow_scan_print::{SlowScanConfig, SlowScanWrite};
+    ///
+    /// let mut config = SlowScanConfig::default();
+    /// config
+    ///     .set_base_delay(Duration::ZERO)
+    ///     .set_full_width_delay(Duration::ZERO);
+    ///
+    /// let mut writer = Vec::new();
+    /// writer
+    ///     .slow_scan_write_by_chars_with_hook("ab世".chars(), config, |w, _| {
+    ///         w.write_all(b"|")
+    ///     })
+    ///     .unwrap();
+    ///
+    /// assert_eq!(writer, "a|b|世|".as_bytes());
+    /// ```
+    ///
+    /// ---
+    ///
+    /// Write with width-based delays, calling a hook after each character
+    ///
+    /// `on_unit` is called after the character is written and before the
+    /// flush and the delay, so it can append escape sequences or overwrite the
+    /// character just revealed, serving as an extension point for visual
+    /// effects. Inline directives do not trigger the hook
+    ///
+    /// # Arguments
+    /// - `iter`: Iterator of characters
+    /// - `config`: Slow scan configuration parameters
+    /// - `on_unit`: Hook called after each character is written, receiving the
+    ///   sink and the character
+    ///
+    /// # Returns
+    /// - `Ok(())`: All data written successfully
+    /// - `Err(io::Error)`: I/O error occurred during writing or in the hook
+    fn slow_scan_write_by_chars_with_hook<I, F>(
+        &mut self,
+        iter: I,
+        config: SlowScanConfig,
+        on_unit: F
+    ) -> Result<(), io::Error>
+    where
+        I: Iterator<Item = char>,
+        F: FnMut(&mut dyn Write, char) -> io::Result<()>;
+
+    /// 根据 Unicode 字符宽度和配置进行延迟写入，并在每次延迟开始前调用钩子
+    ///
+    /// `on_delay_start` 在某个字符写入之后、其对应的延迟开始之前被调用，
+    /// 参数为该字符，可用于播放与显示同步的按键音效。没有延迟的字符（如未启用
+    /// `tail_delay` 时的最后一个字符）不会触发钩子，钩子应尽量轻量以免拖慢节奏
+    ///
+    /// # 参数
+    /// - `iter`: 字符迭代器
+    /// - `config`: 慢速扫描配置参数
+    /// - `on_delay_start`: 每次延迟开始前调用的钩子
+    ///
+    /// # 返回值
+    /// - `Ok(())`: 所有数据成功写入
+    /// - `Err(io::Error)`: 写入过程中发生 I/O 错误
+    ///
+    /// # 示例
+    /// ```
+    /// use std::time::Duration;
+    ///
+    /// use slow_scan_print::{SlowScanConfig, SlowScanWrite};
+    ///
+    /// let mut config = SlowScanConfig::default();
+    /// config
+    ///     .set_base_delay(Duration::ZERO)
+    ///     .set_full_width_delay(Duration::ZERO);
+    ///
+    /// let mut clicks = Vec::new();
+    /// Vec::new()
+    ///     .slow_scan_write_by_chars_with_delay_hook(
+    ///         "ab世".chars(),
+    ///         config,
+    ///         |it| clicks.push(it)
+    ///     )
+    ///     .unwrap();
+    ///
+    /// assert_eq!(clicks, ['a', 'b']);
+    /// ```
+    ///
+    /// ---
+    ///
+    /// Write with width-based delays, calling a hook before each delay starts
+    ///
+    /// `on_delay_start` is called with the character after it is written and
+    /// right before its delay starts, allowing a keystroke sound to be played
+    /// in sync with the reveal. Characters without a delay (such as
+    /// the last one when `tail_delay` is disabled) do not trigger the hook. The
+    /// hook should be cheap so that it does not disturb the pacing
+    ///
+    /// # Arguments
+    /// - `iter`: Iterator of characters
+    /// - `config`: Slow scan configuration parameters
+    /// - `on_delay_start`: Hook called before each delay starts
+    ///
+    /// # Returns
+    /// - `Ok(())`: All data written successfully
+    /// - `Err(io::Error)`: I/O error occurred during writing
+    fn slow_scan_write_by_chars_with_delay_hook<I, F>(
+        &mut self,
+        iter: I,
+        config: SlowScanConfig,
+        on_delay_start: F
+    ) -> Result<(), io::Error>
+    where
+        I: Iterator<Item = char>,
+        F: FnMut(char);
+
+    /// 按时间文件记录的间隔分段写入数据
+    ///
+    /// `timing` 中的每一项依次表示写入下一段之前的延迟和该段的字节数，通常由
+    /// [`parse_timing`] 解析得到，可用于重放 `script` 录制的按键节奏。
+    /// 配置中的统一延迟不会生效
+    ///
+    /// # 参数
+    /// - `content`: 要写入的全部数据
+    /// - `timing`: 每一段的延迟和字节数
+    ///
+    /// # 返回值
+    /// - `Ok(())`: 所有数据成功写入
+    /// - `Err(io::Error)`: 写入过程中发生 I/O 错误；若各段字节数之和与
+    ///   `content` 的长度不一致，则在写入任何数据之前返回 `InvalidInput` 错误
+    ///
+    /// ---
+    ///
+    /// Write data in segments at the intervals recorded in a timing file
+    ///
+    /// Each entry of `timing` is the delay before writing the next segment and
+    /// the byte count of that segment, usually parsed by [`parse_timing`], which
+    /// allows replaying keystroke rhythms recorded by `script`. The uniform
+    /// delays of the configuration do not apply
+    ///
+    /// # Arguments
+    /// - `content`: All the data to write
+    /// - `timing`: Delay and byte count of every segment
+    ///
+    /// # Returns
+    /// - `Ok(())`: All data written successfully
+    /// - `Err(io::Error)`: I/O error occurred during writing; if the byte counts
+    ///   of the segments do not add up to the length of `content`, an
+    ///   `InvalidInput` error is returned before anything is written
+    fn slow_scan_write_timed(
+        &mut self,
+        content: &[u8],
+        timing: &[(Duration, usize)]
+    ) -> Result<(), io::Error>;
+
+    /// 使用指定的时钟按时间文件记录的间隔分段写入数据
+    ///
+    /// 与 `slow_scan_write_timed` 相同，但所有延迟都通过 `clock` 完成
+    ///
+    /// # 参数
+    /// - `content`: 要写入的全部数据
+    /// - `timing`: 每一段的延迟和字节数
+    /// - `clock`: 提供当前时间和休眠的时钟
+    ///
+    /// # 返回值
+    /// - `Ok(())`: 所有数据成功写入
+    /// - `Err(io::Error)`: 写入过程中发生 I/O 错误，或各段字节数之和与
+    ///   `content` 的长度不一致
+    ///
+    /// # 示例
+    /// ```
+    /// use std::time::{Duration, Instant};
+    ///
+    /// use slow_scan_print::{SlowScanWrite, parse_timing};
+    /// # struct VirtualClock {
+    /// #     now: std::time::Instant,
+    /// #     deadlines: Vec<std::time::Instant>
+    /// # }
+    /// #
+    /// # impl VirtualClock {
+    /// #     fn new(now: std::time::Instant) -> Self {
+    /// #         Self {
+    /// #             now,
+    /// #             deadlines: Vec::new()
+    /// #         }
+    /// #     }
+    /// # }
+    /// #
+    /// # impl slow_scan_print::Clock for VirtualClock {
+    /// #     fn now(&mut self) -> std::time::Instant { self.now }
+    /// #
+    /// #     fn sleep_until(&mut self, deadline: std::time::Instant) {
+    /// #         self.now = self.now.max(deadline);
+    /// #         self.deadlines.push(deadline);
+    /// #     }
+    /// # }
+    ///
+    /// let start = Instant::now();
+    /// let mut clock = VirtualClock::new(start);
+    ///
+    /// let timing = parse_timing("0.5 3\n0.25 1\n1 2\n").unwrap();
+    ///
+    /// let mut writer = Vec::new();
+    /// writer
+    ///     .slow_scan_write_timed_with_clock(b"ls\n\nok", &timing, &mut clock)
+    ///     .unwrap();
+    ///
+    /// assert_eq!(writer, b"ls\n\nok");
+    /// assert_eq!(
+    ///     clock.deadlines,
+    ///     [
+    ///         start + Duration::from_millis(500),
+    ///         start + Duration::from_millis(750),
+    ///         start + Duration::from_millis(1750)
+    ///     ]
+    /// );
+    ///
+    /// let mut writer = Vec::new();
+    /// assert!(
+    ///     writer
+    ///         .slow_scan_write_timed_with_clock(b"ls", &timing, &mut clock)
+    ///         .is_err()
+    /// );
+    /// assert!(writer.is_empty());
+    /// ```
+    ///
+    /// ---
+    ///
+    /// Write data in segments at the intervals recorded in a timing file,
+    /// using the given clock
+    ///
+    /// Same as `slow_scan_write_timed`, but every delay goes through `clock`
+    ///
+    /// # Arguments
+    /// - `content`: All the data to write
+    /// - `timing`: Delay and byte count of every segment
+    /// - `clock`: Clock providing the current time and sleeping
+    ///
+    /// # Returns
+    /// - `Ok(())`: All data written successfully
+    /// - `Err(io::Error)`: I/O error occurred during writing, or the byte counts
+    ///   of the segments do not add up to the length of `content`
+    fn slow_scan_write_timed_with_clock<C>(
+        &mut self,
+        content: &[u8],
+        timing: &[(Duration, usize)],
+        clock: &mut C
+    ) -> Result<(), io::Error>
+    where
+        C: Clock + ?Sized;
+
+    /// 逐块写入数据，同时返回实际写入的全部字节
+    ///
+    /// 写入的数据会同时记录到内部缓冲区中，便于测试或记录日志，而无需把真实的
+    /// 写入目标替换为 `Vec<u8>`
+    ///
+    /// # 参数
+    /// - `iter`: 字节块迭代器，每个元素需实现 `AsRef<[u8]>`
+    /// - `config`: 慢速扫描配置参数
+    ///
+    /// # 返回值
+    /// 返回写入结果以及在发生错误前已成功写入的全部字节
+    ///
+    /// ---
+    ///
+    /// Write data chunk-by-chunk, also returning every byte that was written
+    ///
+    /// Written data is recorded into an internal buffer as well, convenient for
+    /// testing or logging without replacing the real sink with a `Vec<u8>`
+    ///
+    /// # Arguments
+    /// - `iter`: Iterator of byte chunks where each item implements `AsRef<[u8]>`
+    /// - `config`: Slow scan configuration parameters
+    ///
+    /// # Returns
+    /// Returns the write result together with every byte successfully written
+    /// before any error
+    fn slow_scan_write_by_chunks_capturing<I>(
+        &mut self,
+        iter: I,
+        config: SlowScanConfig
+    ) -> (Result<(), io::Error>, Vec<u8>)
+    where
+        I: Iterator,
+        I::Item: AsRef<[u8]>;
+
+    /// 根据 Unicode 字符宽度和配置进行延迟写入，同时返回实际写入的全部字节
+    ///
+    /// 写入的数据会同时记录到内部缓冲区中，便于测试或记录日志，而无需把真实的
+    /// 写入目标替换为 `Vec<u8>`
+    ///
+    /// # 参数
+    /// - `iter`: 字符迭代器
+    /// - `config`: 慢速扫描配置参数
+    ///
+    /// # 返回值
+    /// 返回写入结果以及在发生错误前已成功写入的全部字节
+    ///
+    /// # 示例
+    /// ```
+    /// use std::io;
+    /// use std::time::Duration;
+    ///
+    /// use slow_scan_print::{SlowScanConfig, SlowScanWrite};
+    ///
+    /// let mut config = SlowScanConfig::default();
+    /// config
+    ///     .set_base_delay(Duration::ZERO)
+    ///     .set_full_width_delay(Duration::ZERO);
+    ///
+    /// let (result, captured) = io::sink()
+    ///     .slow_scan_write_by_chars_capturing("Hello, 世界".chars(), config);
+    ///
+    /// assert!(result.is_ok());
+    /// assert_eq!(captured, "Hello, 世界".as_bytes());
+    /// ```
+    ///
+    /// ---
+    ///
+    /// Write with width-based delays, also returning every byte that was
+    /// written
+    ///
+    /// Written data is recorded into an internal buffer as well, convenient for
+    /// testing or logging without replacing the real sink with a `Vec<u8>`
+    ///
+    /// # Arguments
+    /// - `iter`: Iterator of characters
+    /// - `config`: Slow scan configuration parameters
+    ///
+    /// # Returns
+    /// Returns the write result together with every byte successfully written
+    /// before any error
+    fn slow_scan_write_by_chars_capturing<I>(
+        &mut self,
+        iter: I,
+        config: SlowScanConfig
+    ) -> (Result<(), io::Error>, Vec<u8>)
+    where
+        I: Iterator<Item = char>;
+
+    /// 逐字符显示一行文本，停顿后将其中的敏感片段替换为 `*`
+    ///
+    /// 先按 `slow_scan_write_by_chars` 的规则显示整行，等待 `hold` 后使用回车
+    /// 回到行首并重新写入遮盖后的整行，光标最终停在行尾。`line` 不应包含换行符
+    ///
+    /// # 参数
+    /// - `line`: 要显示的一行文本
+    /// - `spans`: 需要遮盖的字节范围
+    /// - `hold`: 显示完整内容后、遮盖之前的停顿时间
+    /// - `config`: 慢速扫描配置参数
+    ///
+    /// # 返回值
+    /// - `Ok(())`: 所有数据成功写入
+    /// - `Err(io::Error)`: 写入过程中发生 I/O 错误
+    ///
+    /// # 示例
+    /// ```
+    /// use std::time::Duration;
+    ///
+    /// use slow_scan_print::{SlowScanConfig, SlowScanWrite};
+    ///
+    /// let mut config = SlowScanConfig::default();
+    /// config.set_base_delay(Duration::ZERO);
+    ///
+    /// let mut writer = Vec::new();
+    /// writer
+    ///     .slow_scan_write_redacted(
+    ///         "token=abc123 ok",
+    ///         &[6..12],
+    ///         Duration::ZERO,
+    ///         config
+    ///     )
+    ///     .unwrap();
+    ///
+    /// assert_eq!(writer, b"token=abc123 ok\rtoken=****** ok");
+    /// ```
+    ///
+    /// ---
+    ///
+    /// Reveal a line character by character, then replace its secrets with
+    /// `*` after a pause
+    ///
+    /// The whole line is revealed following the rules of
+    /// `slow_scan_write_by_chars`. After waiting `hold`, a carriage return moves
+    /// back to the start of the line and the masked line is written over it,
+    /// leaving the cursor at the end of the line. `line` should not contain
+    /// line breaks
+    ///
+    /// # Arguments
+    /// - `line`: Line of text to reveal
+    /// - `spans`: Byte ranges to mask
+    /// - `hold`: Pause after the full content is revealed and before masking
+    /// - `config`: Slow scan configuration parameters
+    ///
+    /// # Returns
+    /// - `Ok(())`: All data written successfully
+    /// - `Err(io::Error)`: I/O error occurred during writing
+    fn slow_scan_write_redacted(
+        &mut self,
+        line: &str,
+        spans: &[Range<usize>],
+        hold: Duration,
+        config: SlowScanConfig
+    ) -> Result<(), io::Error>;
+
+    /// 使用指定的时钟逐字符显示一行文本，停顿后遮盖其中的敏感片段
+    ///
+    /// 与 `slow_scan_write_redacted` 相同，但逐字符的延迟和遮盖前的停顿都通过
+    /// `clock` 完成，可用于模拟或测试
+    ///
+    /// # 参数
+    /// - `line`: 要显示的一行文本
+    /// - `spans`: 需要遮盖的字节范围
+    /// - `hold`: 显示完整内容后、遮盖之前的停顿时间
+    /// - `config`: 慢速扫描配置参数
+    /// - `clock`: 提供当前时间和休眠的时钟
+    ///
+    /// # 返回值
+    /// - `Ok(())`: 所有数据成功写入
+    /// - `Err(io::Error)`: 写入过程中发生 I/O 错误
+    ///
+    /// # 示例
+    /// ```
+    /// use std::time::{Duration, Instant};
+    ///
+    /// use slow_scan_print::{SlowScanConfig, SlowScanWrite};
+    /// # struct VirtualClock {
+    /// #     now: std::time::Instant,
+    /// #     deadlines: Vec<std::time::Instant>
+    /// # }
+    /// #
+    /// # impl VirtualClock {
+    /// #     fn new(now: std::time::Instant) -> Self {
+    /// #         Self {
+    /// #             now,
+    /// #             deadlines: Vec::new()
+    /// #         }
+    /// #     }
+    /// # }
+    /// #
+    /// # impl slow_scan_print::Clock for VirtualClock {
+    /// #     fn now(&mut self) -> std::time::Instant { self.now }
+    /// #
+    /// #     fn sleep_until(&mut self, deadline: std::time::Instant) {
+    /// #         self.now = self.now.max(deadline);
+    /// #         self.deadlines.push(deadline);
+    /// #     }
+    /// # }
+    ///
+    /// let ms = Duration::from_millis;
+    /// let mut config = SlowScanConfig::default();
+    /// config.set_base_delay(ms(10));
+    ///
+    /// let start = Instant::now();
+    /// let mut clock = VirtualClock::new(start);
+    /// let mut writer = Vec::new();
+    /// writer
+    ///     .slow_scan_write_redacted_with_clock(
+    ///         "pin=42",
+    ///         &[4..6],
+    ///         Duration::from_secs(1),
+    ///         config,
+    ///         &mut clock
+    ///     )
+    ///     .unwrap();
+    ///
+    /// assert_eq!(writer, b"pin=42\rpin=**");
+    ///
+    /// // 五次逐字符延迟之后是遮盖前的停顿
+    /// // Five per-character delays followed by the pause before masking
+    /// let deadlines = clock
+    ///     .deadlines
+    ///     .iter()
+    ///     .map(|it| *it - start)
+    ///     .collect::<Vec<_>>();
+    /// assert_eq!(
+    ///     deadlines,
+    ///     [ms(10), ms(20), ms(30), ms(40), ms(50), ms(1050)]
+    /// );
+    /// ```
+    ///
+    /// ---
+    ///
+    /// Reveal a line character by character using the given clock, then mask
+    /// its secrets after a pause
+    ///
+    /// Same as `slow_scan_write_redacted`, but both the per-character delays
+    /// and the pause before masking go through `clock`, useful for
+    /// simulations or tests
+    ///
+    /// # Arguments
+    /// - `line`: Line of text to reveal
+    /// - `spans`: Byte ranges to mask
+    /// - `hold`: Pause after the full content is revealed and before masking
+    /// - `config`: Slow scan configuration parameters
+    /// - `clock`: Clock providing the current time and sleeping
+    ///
+    /// # Returns
+    /// - `Ok(())`: All data written successfully
+    /// - `Err(io::Error)`: I/O error occurred during writing
+    fn slow_scan_write_redacted_with_clock<C>(
+        &mut self,
+        line: &str,
+        spans: &[Range<usize>],
+        hold: Duration,
+        config: SlowScanConfig,
+        clock: &mut C
+    ) -> Result<(), io::Error>
+    where
+        C: Clock + ?Sized;
+
+    /// 以对齐的表格形式逐单元格写入数据
+    ///
+    /// 每一列按其中最宽的单元格对齐，列之间以两个空格分隔。单元格按阅读顺序
+    /// 逐个显示，单元格内部按 `slow_scan_write_by_chars` 的规则逐字符延迟，
+    /// 单元格之间同样会在其最后一个字符后延迟；填充空格和换行会立即写入
+    ///
+    /// # 参数
+    /// - `rows`: 表格的行，每行包含若干单元格
+    /// - `config`: 慢速扫描配置参数
+    ///
+    /// # 返回值
+    /// - `Ok(())`: 所有数据成功写入
+    /// - `Err(io::Error)`: 写入过程中发生 I/O 错误
+    ///
+    /// # 示例
+    /// ```
+    /// use std::time::Duration;
+    ///
+    /// use slow_scan_print::{SlowScanConfig, SlowScanWrite};
+    ///
+    /// let mut config = SlowScanConfig::default();
+    /// config
+    ///     .set_base_delay(Duration::ZERO)
+    ///     .set_full_width_delay(Duration::ZERO);
+    ///
+    /// let rows = [vec!["a", "bb"], vec!["ccc", "d"]];
+    ///
+    /// let mut writer = Vec::new();
+    /// writer.slow_scan_table(&rows, config).unwrap();
+    ///
+    /// assert_eq!(writer, b"a    bb\nccc  d\n");
+    /// ```
+    ///
+    /// ---
+    ///
+    /// Write data as an aligned table, cell by cell
+    ///
+    /// Every column is aligned to its widest cell and columns are separated by
+    /// two spaces. Cells are revealed in reading order, the content of a cell
+    /// is delayed per character following the rules of
+    /// `slow_scan_write_by_chars`, and the delay after the last character of a
+    /// cell separates it from the next one; padding and line breaks are
+    /// written immediately
+    ///
+    /// # Arguments
+    /// - `rows`: Rows of the table, each containing several cells
+    /// - `config`: Slow scan configuration parameters
+    ///
+    /// # Returns
+    /// - `Ok(())`: All data written successfully
+    /// - `Err(io::Error)`: I/O error occurred during writing
+    fn slow_scan_table<S>(
+        &mut self,
+        rows: &[Vec<S>],
+        config: SlowScanConfig
+    ) -> Result<(), io::Error>
+    where
+        S: AsRef<str>;
+
+    /// 以里程表滚动的效果显示数字
+    ///
+    /// 每一位数字会先从 `0` 开始快速滚动到目标数字，每一帧写入后用退格符
+    /// （`\x08`）回到原位以便下一帧覆盖，最后停在目标数字上；滚动一整圈
+    /// （`0` 到 `9`）的时间约为一个 `base_delay`。非数字字符按
+    /// `slow_scan_write_by_chars` 的规则直接显示。依赖终端对退格符的处理，
+    /// 适合输出到终端时使用
+    ///
+    /// # 参数
+    /// - `value`: 要显示的数字文本，可以包含符号、小数点等非数字字符
+    /// - `config`: 慢速扫描配置参数
+    ///
+    /// # 返回值
+    /// - `Ok(())`: 所有数据成功写入
+    /// - `Err(io::Error)`: 写入过程中发生 I/O 错误
+    ///
+    /// # 示例
+    /// ```
+    /// use std::time::Duration;
+    ///
+    /// use slow_scan_print::{SlowScanConfig, SlowScanWrite};
+    ///
+    /// let mut config = SlowScanConfig::default();
+    /// config.set_base_delay(Duration::ZERO);
+    ///
+    /// let mut writer = Vec::new();
+    /// writer.slow_scan_number("-42", config).unwrap();
+    ///
+    /// assert_eq!(writer, b"-0\x081\x082\x083\x0840\x081\x082");
+    ///
+    /// // 按退格符重放输出，得到终端上最终显示的内容
+    /// let mut screen = Vec::new();
+    /// for it in writer {
+    ///     match it {
+    ///         b'\x08' => drop(screen.pop()),
+    ///         _ => screen.push(it)
+    ///     }
+    /// }
+    /// assert_eq!(screen, b"-42");
+    /// ```
+    ///
+    /// ---
+    ///
+    /// Reveal a number with an odometer roll-up effect
+    ///
+    /// Each digit first rolls quickly from `0` up to its target, every frame
+    /// being followed by a backspace (`\x08`) so that the next frame
+    /// overwrites it in place, and finally settles on the target digit; a
+    /// full roll (`0` to `9`) takes about one `base_delay`. Non-digit
+    /// characters are revealed directly following the rules of
+    /// `slow_scan_write_by_chars`. Relies on the terminal handling backspaces,
+    /// so it is meant for terminal output
+    ///
+    /// # Arguments
+    /// - `value`: Number to reveal, which may contain non-digit characters
+    ///   such as signs and decimal points
+    /// - `config`: Slow scan configuration parameters
+    ///
+    /// # Returns
+    /// - `Ok(())`: All data written successfully
+    /// - `Err(io::Error)`: I/O error occurred during writing
+    fn slow_scan_number(
+        &mut self,
+        value: &str,
+        config: SlowScanConfig
+    ) -> Result<(), io::Error>;
+
+    /// 从两端向中间汇聚地显示一行文本
+    ///
+    /// 先同时显示第一个和最后一个字符，然后是第二个和倒数第二个字符，依此
+    /// 类推，每一对字符之后按其中较长的字符延迟等待。字符通过相对光标移动
+    /// （`ESC [ n C` 和 `ESC [ n D`）写入各自的位置，列位置按显示宽度计算，
+    /// 结束后光标停在行尾。`s` 不应包含换行符，适合输出到终端时使用
+    ///
+    /// # 参数
+    /// - `s`: 要显示的一行文本
+    /// - `config`: 慢速扫描配置参数
+    ///
+    /// # 返回值
+    /// - `Ok(())`: 所有数据成功写入
+    /// - `Err(io::Error)`: 写入过程中发生 I/O 错误
+    ///
+    /// # 示例
+    /// ```
+    /// use std::time::Duration;
+    ///
+    /// use slow_scan_print::{SlowScanConfig, SlowScanWrite};
+    ///
+    /// let mut config = SlowScanConfig::default();
+    /// config.set_base_delay(Duration::ZERO);
+    ///
+    /// let mut writer = Vec::new();
+    /// writer.slow_scan_write_converge("abcd", config).unwrap();
+    ///
+    /// assert_eq!(writer, b"a\x1b[2Cd\x1b[3Dbc\x1b[1C");
+    /// ```
+    ///
+    /// ---
+    ///
+    /// Reveal a line converging from both ends toward the middle
+    ///
+    /// The first and last characters appear together, then the second and the
+    /// second-to-last, and so on, waiting after each pair for the longer delay
+    /// of the two. Characters are written at their own positions with
+    /// relative cursor movements (`ESC [ n C` and `ESC [ n D`), columns being
+    /// computed from display widths, and the cursor ends at the end of the
+    /// line. `s` should not contain line breaks; meant for terminal output
+    ///
+    /// # Arguments
+    /// - `s`: Line of text to reveal
+    /// - `config`: Slow scan configuration parameters
+    ///
+    /// # Returns
+    /// - `Ok(())`: All data written successfully
+    /// - `Err(io::Error)`: I/O error occurred during writing
+    fn slow_scan_write_converge(
+        &mut self,
+        s: &str,
+        config: SlowScanConfig
+    ) -> Result<(), io::Error>;
+
+    /// 在同一线程中交错写入多个流，每个流显示在各自的屏幕区域中
+    ///
+    /// 所有流共享同一份配置，各自按字符延迟计算下一个字符的到期时间，每次写入
+    /// 到期时间最早的流的下一个字符（相同时先写入靠前的流），使多个流同时进行
+    /// 而不需要额外的线程。每个字符之前会写入 `ESC [ row ; column H` 将光标移动
+    /// 到其所在的位置。不支持内联指令和连续字符合并
+    ///
+    /// # 参数
+    /// - `streams`: 要写入的字符迭代器及其所在的区域
+    /// - `config`: 慢速扫描配置参数
+    ///
+    /// # 返回值
+    /// - `Ok(())`: 所有数据成功写入
+    /// - `Err(io::Error)`: 写入过程中发生 I/O 错误
+    ///
+    /// # 示例
+    /// ```
+    /// use std::time::Duration;
+    ///
+    /// use slow_scan_print::{Region, SlowScanConfig, SlowScanWrite};
+    ///
+    /// let mut config = SlowScanConfig::default();
+    /// config.set_base_delay(Duration::from_millis(1));
+    ///
+    /// let mut writer = Vec::new();
+    /// writer
+    ///     .slow_scan_write_interleaved(
+    ///         [
+    ///             ("ab".chars(), Region::new(0, 0)),
+    ///             ("c".chars(), Region::new(5, 10))
+    ///         ],
+    ///         config
+    ///     )
+    ///     .unwrap();
+    ///
+    /// assert_eq!(writer, b"\x1b[1;1Ha\x1b[6;11Hc\x1b[1;2Hb");
+    /// ```
+    ///
+    /// ---
+    ///
+    /// Write several streams interleaved from one thread, each one shown in its
+    /// own region of the screen
+    ///
+    /// All streams share one configuration and compute the due time of their
+    /// next character from the character delays. Each time the next character
+    /// of the stream due earliest is written (the earlier stream first on a
+    /// tie), so that the streams animate concurrently without extra threads.
+    /// `ESC [ row ; column H` is written before every character to move the
+    /// cursor to its position. Inline directives and run coalescing are not
+    /// supported
+    ///
+    /// # Arguments
+    /// - `streams`: Character iterators to write and their regions
+    /// - `config`: Slow scan configuration parameters
+    ///
+    /// # Returns
+    /// - `Ok(())`: All data written successfully
+    /// - `Err(io::Error)`: I/O error occurred during writing
+    fn slow_scan_write_interleaved<S, I>(
+        &mut self,
+        streams: S,
+        config: SlowScanConfig
+    ) -> Result<(), io::Error>
+    where
+        S: IntoIterator<Item = (I, Region)>,
+        I: Iterator<Item = char>;
+
+    /// 使用指定的时钟交错写入多个流
+    ///
+    /// 与 [`slow_scan_write_interleaved`](Self::slow_scan_write_interleaved)
+    /// 相同，但通过 `clock` 获取当前时间和进行延迟
+    ///
+    /// # 参数
+    /// - `streams`: 要写入的字符迭代器及其所在的区域
+    /// - `config`: 慢速扫描配置参数
+    /// - `clock`: 提供当前时间和延迟的时钟
+    ///
+    /// # 返回值
+    /// - `Ok(())`: 所有数据成功写入
+    /// - `Err(io::Error)`: 写入过程中发生 I/O 错误
+    ///
+    /// # 示例
+    /// ```
+    /// use std::time::{Duration, Instant};
+    ///
+    /// use slow_scan_print::{Region, SlowScanConfig, SlowScanWrite};
+    /// # struct VirtualClock {
+    /// #     now: std::time::Instant,
+    /// #     deadlines: Vec<std::time::Instant>
+    /// # }
+    /// #
+    /// # impl VirtualClock {
+    /// #     fn new(now: std::time::Instant) -> Self {
+    /// #         Self {
+    /// #             now,
+    /// #             deadlines: Vec::new()
+    /// #         }
+    /// #     }
+    /// # }
+    /// #
+    /// # impl slow_scan_print::Clock for VirtualClock {
+    /// #     fn now(&mut self) -> std::time::Instant { self.now }
+    /// #
+    /// #     fn sleep_until(&mut self, deadline: std::time::Instant) {
+    /// #         self.now = self.now.max(deadline);
+    /// #         self.deadlines.push(deadline);
+    /// #     }
+    /// # }
+    ///
+    /// let start = Instant::now();
+    /// let mut clock = VirtualClock::new(start);
+    ///
+    /// let mut config = SlowScanConfig::default();
+    /// config
+    ///     .set_base_delay(Duration::from_secs(1))
+    ///     .set_full_width_delay(Duration::from_secs(3));
+    ///
+    /// let streams = [
+    ///     ("abc".chars(), Region::new(0, 0)),
+    ///     ("一二".chars(), Region::new(1, 0))
+    /// ];
+    ///
+    /// let mut writer = Vec::new();
+    /// writer
+    ///     .slow_scan_write_interleaved_with_clock(streams, config, &mut clock)
+    ///     .unwrap();
+    ///
+    /// assert_eq!(
+    ///     String::from_utf8(writer).unwrap(),
+    ///     "\x1b[1;1Ha\x1b[2;1H一\x1b[1;2Hb\x1b[1;3Hc\x1b[2;3H二"
+    /// );
+    /// assert_eq!(
+    ///     clock.deadlines,
+    ///     [
+    ///         start + Duration::from_secs(1),
+    ///         start + Duration::from_secs(2),
+    ///         start + Duration::from_secs(3)
+    ///     ]
+    /// );
+    /// ```
+    ///
+    /// ---
+    ///
+    /// Write several streams interleaved using the given clock
+    ///
+    /// Same as
+    /// [`slow_scan_write_interleaved`](Self::slow_scan_write_interleaved), but
+    /// the current time is obtained and delays are performed through `clock`
+    ///
+    /// # Arguments
+    /// - `streams`: Character iterators to write and their regions
+    /// - `config`: Slow scan configuration parameters
+    /// - `clock`: Clock providing the current time and sleeping
+    ///
+    /// # Returns
+    /// - `Ok(())`: All data written successfully
+    /// - `Err(io::Error)`: I/O error occurred during writing
+    fn slow_scan_write_interleaved_with_clock<S, I, C>(
+        &mut self,
+        streams: S,
+        config: SlowScanConfig,
+        clock: &mut C
+    ) -> Result<(), io::Error>
+    where
+        S: IntoIterator<Item = (I, Region)>,
+        I: Iterator<Item = char>,
+        C: Clock + ?Sized;
+
+    /// 逐个码点显示，但每个字素簇只延迟一次
+    ///
+    /// 字素簇中的每个码点都会单独写入并刷新，使组合的表情符号等逐步 “拼出”，
+    /// 但只在整个字素簇写入之后按其第一个码点的类型延迟一次，使节奏保持自然
+    ///
+    /// # 参数
+    /// - `s`: 要写入的字符串
+    /// - `config`: 慢速扫描配置参数
+    ///
+    /// # 返回值
+    /// - `Ok(())`: 所有数据成功写入
+    /// - `Err(io::Error)`: 写入过程中发生 I/O 错误
+    ///
+    /// ---
+    ///
+    /// Reveal code point by code point, but delay only once per grapheme
+    /// cluster
+    ///
+    /// Every code point of a grapheme cluster is written and flushed on its
+    /// own, so that e.g. composed emoji visibly "build up", but the delay
+    /// matching the type of its first code point is applied only once after
+    /// the whole cluster, so that the timing stays natural
+    ///
+    /// # Arguments
+    /// - `s`: String to write
+    /// - `config`: Slow scan configuration parameters
+    ///
+    /// # Returns
+    /// - `Ok(())`: All data written successfully
+    /// - `Err(io::Error)`: I/O error occurred during writing
+    #[cfg(feature = "graphemes")]
+    fn slow_scan_write_building_graphemes(
+        &mut self,
+        s: &str,
+        config: SlowScanConfig
+    ) -> Result<(), io::Error>;
+
+    /// 在固定数量的帧内显示字符串
+    ///
+    /// 将字符尽量平均地分配到 `frames` 帧中（部分帧可能显示多个字符或不显示
+    /// 字符），每帧写入后刷新并等待 `frame_time`，总时长固定为
+    /// `frames × frame_time`，适合与已知帧率的视频同步。不使用按字符类型计算
+    /// 的延迟。`frames` 为 0 时立即写入全部内容
+    ///
+    /// # 参数
+    /// - `s`: 要写入的字符串
+    /// - `frames`: 帧数
+    /// - `frame_time`: 每帧的时长，例如 30 帧每秒时为 1/30 秒
+    ///
+    /// # 返回值
+    /// - `Ok(())`: 所有数据成功写入
+    /// - `Err(io::Error)`: 写入过程中发生 I/O 错误
+    ///
+    /// ---
+    ///
+    /// Reveal a string over a fixed number of frames
+    ///
+    /// The characters are distributed across `frames` frames as evenly as
+    /// possible (some frames may reveal several characters or none), and every
+    /// frame is flushed and followed by a wait of `frame_time`, so that the
+    /// total duration is exactly `frames × frame_time`, useful to sync with a
+    /// video at a known frame rate. The per-class character delays are not
+    /// used. Everything is written at once when `frames` is 0
+    ///
+    /// # Arguments
+    /// - `s`: String to write
+    /// - `frames`: Number of frames
+    /// - `frame_time`: Duration of each frame, e.g. 1/30 s at 30 fps
+    ///
+    /// # Returns
+    /// - `Ok(())`: All data written successfully
+    /// - `Err(io::Error)`: I/O error occurred during writing
+    fn slow_scan_write_frames(
+        &mut self,
+        s: &str,
+        frames: usize,
+        frame_time: Duration
+    ) -> Result<(), io::Error>;
+
+    /// 使用指定的时钟在固定数量的帧内显示字符串
+    ///
+    /// 与 [`slow_scan_write_frames`](Self::slow_scan_write_frames) 相同，但
+    /// 通过 `clock` 获取当前时间和进行延迟
+    ///
+    /// # 参数
+    /// - `s`: 要写入的字符串
+    /// - `frames`: 帧数
+    /// - `frame_time`: 每帧的时长
+    /// - `clock`: 提供当前时间和延迟的时钟
+    ///
+    /// # 返回值
+    /// - `Ok(())`: 所有数据成功写入
+    /// - `Err(io::Error)`: 写入过程中发生 I/O 错误
+    ///
+    /// # 示例
+    /// ```
+    /// use std::io::{self, Write};
+    /// use std::time::{Duration, Instant};
+    ///
+    /// use slow_scan_print::SlowScanWrite;
+    /// # struct VirtualClock {
+    /// #     now: std::time::Instant,
+    /// #     deadlines: Vec<std::time::Instant>
+    /// # }
+    /// #
+    /// # impl VirtualClock {
+    /// #     fn new(now: std::time::Instant) -> Self {
+    /// #         Self {
+    /// #             now,
+    /// #             deadlines: Vec::new()
+    /// #         }
+    /// #     }
+    /// # }
+    /// #
+    /// # impl slow_scan_print::Clock for VirtualClock {
+    /// #     fn now(&mut self) -> std::time::Instant { self.now }
+    /// #
+    /// #     fn sleep_until(&mut self, deadline: std::time::Instant) {
+    /// #         self.now = self.now.max(deadline);
+    /// #         self.deadlines.push(deadline);
+    /// #     }
+    /// # }
+    ///
+    /// #[derive(Default)]
+    /// struct FrameLog {
+    ///     buf: Vec<u8>,
+    ///     frames: Vec<String>
+    /// }
+    ///
+    /// impl Write for FrameLog {
+    ///     fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+    ///         self.buf.write(buf)
+    ///     }
+    ///
+    ///     fn flush(&mut self) -> io::Result<()> {
+    ///         let it = String::from_utf8(std::mem::take(&mut self.buf)).unwrap();
+    ///         self.frames.push(it);
+    ///         Ok(())
+    ///     }
+    /// }
+    ///
+    /// let start = Instant::now();
+    /// let mut clock = VirtualClock::new(start);
+    ///
+    /// let mut writer = FrameLog::default();
+    /// writer
+    ///     .slow_scan_write_frames_with_clock(
+    ///         "0123456789",
+    ///         5,
+    ///         Duration::from_secs(1),
+    ///         &mut clock
+    ///     )
+    ///     .unwrap();
+    ///
+    /// assert_eq!(writer.frames[..5], ["01", "23", "45", "67", "89"]);
+    /// assert_eq!(clock.deadlines.len(), 5);
+    /// assert_eq!(clock.now, start + Duration::from_secs(5));
+    /// ```
+    ///
+    /// ---
+    ///
+    /// Reveal a string over a fixed number of frames using the given clock
+    ///
+    /// Same as [`slow_scan_write_frames`](Self::slow_scan_write_frames), but
+    /// the current time is obtained and delays are performed through `clock`
+    ///
+    /// # Arguments
+    /// - `s`: String to write
+    /// - `frames`: Number of frames
+    /// - `frame_time`: Duration of each frame
+    /// - `clock`: Clock providing the current time and sleeping
+    ///
+    /// # Returns
+    /// - `Ok(())`: All data written successfully
+    /// - `Err(io::Error)`: I/O error occurred during writing
+    fn slow_scan_write_frames_with_clock<C>(
+        &mut self,
+        s: &str,
+        frames: usize,
+        frame_time: Duration,
+        clock: &mut C
+    ) -> Result<(), io::Error>
+    where
+        C: Clock + ?Sized;
+
+    /// 使用指定的时钟逐个码点显示，但每个字素簇只延迟一次
+    ///
+    /// 与
+    /// [`slow_scan_write_building_graphemes`](Self::slow_scan_write_building_graphemes)
+    /// 相同，但通过 `clock` 获取当前时间和进行延迟
+    ///
+    /// # 参数
+    /// - `s`: 要写入的字符串
+    /// - `config`: 慢速扫描配置参数
+    /// - `clock`: 提供当前时间和延迟的时钟
+    ///
+    /// # 返回值
+    /// - `Ok(())`: 所有数据成功写入
+    /// - `Err(io::Error)`: 写入过程中发生 I/O 错误
+    ///
+    /// # 示例
+    /// ```
+    /// use std::io::{self, Write};
+    /// use std::time::{Duration, Instant};
+    ///
+    /// use slow_scan_print::{SlowScanConfig, SlowScanWrite};
+    /// # struct VirtualClock {
+    /// #     now: std::time::Instant,
+    /// #     deadlines: Vec<std::time::Instant>
+    /// # }
+    /// #
+    /// # impl VirtualClock {
+    /// #     fn new(now: std::time::Instant) -> Self {
+    /// #         Self {
+    /// #             now,
+    /// #             deadlines: Vec::new()
+    /// #         }
+    /// #     }
+    /// # }
+    /// #
+    /// # impl slow_scan_print::Clock for VirtualClock {
+    /// #     fn now(&mut self) -> std::time::Instant { self.now }
+    /// #
+    /// #     fn sleep_until(&mut self, deadline: std::time::Instant) {
+    /// #         self.now = self.now.max(deadline);
+    /// #         self.deadlines.push(deadline);
+    /// #     }
+    /// # }
+    ///
+    /// #[derive(Default)]
+    /// struct FlushLog {
+    ///     buf: Vec<u8>,
+    ///     flushed: Vec<String>
+    /// }
+    ///
+    /// impl Write for FlushLog {
+    ///     fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+    ///         self.buf.write(buf)
+    ///     }
+    ///
+    ///     fn flush(&mut self) -> io::Result<()> {
+    ///         let it = String::from_utf8(self.buf.clone()).unwrap();
+    ///         self.flushed.push(it);
+    ///         Ok(())
+    ///     }
+    /// }
+    ///
+    /// let start = Instant::now();
+    /// let mut clock = VirtualClock::new(start);
+    ///
+    /// let mut config = SlowScanConfig::default();
+    /// config
+    ///     .set_full_width_delay(Duration::from_secs(2))
+    ///     .set_tail_delay(true);
+    ///
+    /// let family = "\u{1F468}\u{200D}\u{1F469}\u{200D}\u{1F467}";
+    ///
+    /// let mut writer = FlushLog::default();
+    /// writer
+    ///     .slow_scan_write_building_graphemes_with_clock(
+    ///         family, config, &mut clock
+    ///     )
+    ///     .unwrap();
+    ///
+    /// assert_eq!(
+    ///     writer.flushed[..5],
+    ///     [
+    ///         "\u{1F468}",
+    ///         "\u{1F468}\u{200D}",
+    ///         "\u{1F468}\u{200D}\u{1F469}",
+    ///         "\u{1F468}\u{200D}\u{1F469}\u{200D}",
+    ///         family
+    ///     ]
+    /// );
+    /// assert_eq!(clock.deadlines, [start + Duration::from_secs(2)]);
+    /// ```
+    ///
+    /// ---
+    ///
+    /// Reveal code point by code point using the given clock, but delay only
+    /// once per grapheme cluster
+    ///
+    /// Same as
+    /// [`slow_scan_write_building_graphemes`](Self::slow_scan_write_building_graphemes),
+    /// but the current time is obtained and delays are performed through
+    /// `clock`
+    ///
+    /// # Arguments
+    /// - `s`: String to write
+    /// - `config`: Slow scan configuration parameters
+    /// - `clock`: Clock providing the current time and sleeping
+    ///
+    /// # Returns
+    /// - `Ok(())`: All data written successfully
+    /// - `Err(io::Error)`: I/O error occurred during writing
+    #[cfg(feature = "graphemes")]
+    fn slow_scan_write_building_graphemes_with_clock<C>(
+        &mut self,
+        s: &str,
+        config: SlowScanConfig,
+        clock: &mut C
+    ) -> Result<(), io::Error>
+    where
+        C: Clock + ?Sized;
+
+    /// 逐格填充地显示一个进度条
+    ///
+    /// 进度条形如 `[#####     ]`，共有 `width` 格。每一帧都以回车（`\r`）开头
+    /// 并重写整个进度条，从全空开始每按 `base_delay` 等待一次就多填充一格，
+    /// 直到填满为止，结束后光标停在进度条之后，不使用 `tail_delay`。进度条应
+    /// 位于行首，适合输出到终端时使用
+    ///
+    /// # 参数
+    /// - `width`: 进度条的格数
+    /// - `config`: 慢速扫描配置参数
+    ///
+    /// # 返回值
+    /// - `Ok(())`: 所有数据成功写入
+    /// - `Err(io::Error)`: 写入过程中发生 I/O 错误
+    ///
+    /// # 示例
+    /// ```
+    /// use std::time::Duration;
+    ///
+    /// use slow_scan_print::{SlowScanConfig, SlowScanWrite};
+    ///
+    /// let mut config = SlowScanConfig::default();
+    /// config.set_base_delay(Duration::ZERO);
+    ///
+    /// let mut writer = Vec::new();
+    /// writer.slow_scan_progress(3, config).unwrap();
+    ///
+    /// assert_eq!(writer, b"\r[   ]\r[#  ]\r[## ]\r[###]");
+    /// ```
+    ///
+    /// ---
+    ///
+    /// Reveal a progress bar filling cell by cell
+    ///
+    /// The bar looks like `[#####     ]` and has `width` cells. Every frame
+    /// starts with a carriage return (`\r`) and rewrites the whole bar,
+    /// starting empty and filling one more cell after every wait of
+    /// `base_delay` until it is full; the cursor ends after the bar and
+    /// `tail_delay` is not used. The bar should start at the beginning of a
+    /// line; meant for terminal output
+    ///
+    /// # Arguments
+    /// - `width`: Number of cells of the bar
+    /// - `config`: Slow scan configuration parameters
+    ///
+    /// # Returns
+    /// - `Ok(())`: All data written successfully
+    /// - `Err(io::Error)`: I/O error occurred during writing
+    fn slow_scan_progress(
+        &mut self,
+        width: usize,
+        config: SlowScanConfig
+    ) -> Result<(), io::Error>;
+
+    /// 使用指定的时钟逐格填充地显示一个进度条
+    ///
+    /// 与 [`slow_scan_progress`](Self::slow_scan_progress) 相同，但通过
+    /// `clock` 获取当前时间和进行延迟
+    ///
+    /// # 参数
+    /// - `width`: 进度条的格数
+    /// - `config`: 慢速扫描配置参数
+    /// - `clock`: 提供当前时间和延迟的时钟
+    ///
+    /// # 返回值
+    /// - `Ok(())`: 所有数据成功写入
+    /// - `Err(io::Error)`: 写入过程中发生 I/O 错误
+    ///
+    /// # 示例
+    /// ```
+    /// use std::time::{Duration, Instant};
+    ///
+    /// use slow_scan_print::{SlowScanConfig, SlowScanWrite};
+    /// # struct VirtualClock {
+    /// #     now: std::time::Instant,
+    /// #     deadlines: Vec<std::time::Instant>
+    /// # }
+    /// #
+    /// # impl VirtualClock {
+    /// #     fn new(now: std::time::Instant) -> Self {
+    /// #         Self {
+    /// #             now,
+    /// #             deadlines: Vec::new()
+    /// #         }
+    /// #     }
+    /// # }
+    /// #
+    /// # impl slow_scan_print::Clock for VirtualClock {
+    /// #     fn now(&mut self) -> std::time::Instant { self.now }
+    /// #
+    /// #     fn sleep_until(&mut self, deadline: std::time::Instant) {
+    /// #         self.now = self.now.max(deadline);
+    /// #         self.deadlines.push(deadline);
+    /// #     }
+    /// # }
+    ///
+    /// let start = Instant::now();
+    /// let mut clock = VirtualClock::new(start);
+    ///
+    /// let mut config = SlowScanConfig::default();
+    /// config.set_base_delay(Duration::from_secs(1));
+    ///
+    /// let mut writer = Vec::new();
+    /// writer
+    ///     .slow_scan_progress_with_clock(5, config, &mut clock)
+    ///     .unwrap();
+    ///
+    /// let writer = String::from_utf8(writer).unwrap();
+    /// assert_eq!(writer.matches('\r').count(), 6);
+    /// assert!(writer.ends_with("\r[#####]"));
+    /// // 每填充一格等待一次
+    /// // One wait for every filled cell
+    /// assert_eq!(
+    ///     clock.deadlines,
+    ///     (1..=5)
+    ///         .map(|it| start + Duration::from_secs(it))
+    ///         .collect::<Vec<_>>()
+    /// );
+    /// ```
+    ///
+    /// ---
+    ///
+    /// Reveal a progress bar filling cell by cell using the given clock
+    ///
+    /// Same as [`slow_scan_progress`](Self::slow_scan_progress), but the
+    /// current time is obtained and delays are performed through `clock`
+    ///
+    /// # Arguments
+    /// - `width`: Number of cells of the bar
+    /// - `config`: Slow scan configuration parameters
+    /// - `clock`: Clock providing the current time and sleeping
+    ///
+    /// # Returns
+    /// - `Ok(())`: All data written successfully
+    /// - `Err(io::Error)`: I/O error occurred during writing
+    fn slow_scan_progress_with_clock<C>(
+        &mut self,
+        width: usize,
+        config: SlowScanConfig,
+        clock: &mut C
+    ) -> Result<(), io::Error>
+    where
+        C: Clock + ?Sized;
+
+    /// 显示字符串，停留一段时间后再逐个字符擦除
+    ///
+    /// 先按 `slow_scan_write_by_chars` 的规则显示 `s`，等待 `hold` 之后从末尾
+    /// 开始逐个字符写入 `\x08 \x08`（退格、空格、退格）擦除，全宽字符写入两次，
+    /// 每擦除一个字符之后按 `control_char_delay` 等待。依赖终端处理退格，`s`
+    /// 不应包含换行符，适合输出到终端时使用
+    ///
+    /// # 参数
+    /// - `s`: 要显示并擦除的一行文本
+    /// - `hold`: 显示完成后擦除之前的停留时间
+    /// - `config`: 慢速扫描配置参数
+    ///
+    /// # 返回值
+    /// - `Ok(())`: 所有数据成功写入
+    /// - `Err(io::Error)`: 写入过程中发生 I/O 错误
+    ///
+    /// # 示例
+    /// ```
+    /// use std::time::Duration;
+    ///
+    /// use slow_scan_print::{SlowScanConfig, SlowScanWrite};
+    ///
+    /// let mut config = SlowScanConfig::default();
+    /// config
+    ///     .set_base_delay(Duration::ZERO)
+    ///     .set_control_char_delay(Duration::ZERO);
+    ///
+    /// let mut writer = Vec::new();
+    /// writer
+    ///     .slow_scan_write_then_erase("a世", Duration::ZERO, config)
+    ///     .unwrap();
+    ///
+    /// assert_eq!(writer, "a世\x08 \x08\x08 \x08\x08 \x08".as_bytes());
+    /// ```
+    ///
+    /// ---
+    ///
+    /// Reveal a string, hold it for a while, then erase it character by
+    /// character
+    ///
+    /// `s` is first revealed following the rules of
+    /// `slow_scan_write_by_chars`, and after waiting for `hold` it is erased
+    /// from the end one character at a time by writing `\x08 \x08`
+    /// (backspace, space, backspace), twice for full-width characters, waiting
+    /// for `control_char_delay` after every erased character. Relies on the
+    /// terminal handling backspaces and `s` should not contain line breaks;
+    /// meant for terminal output
+    ///
+    /// # Arguments
+    /// - `s`: Line of text to reveal and erase
+    /// - `hold`: Time to hold the text after revealing it and before erasing
+    /// - `config`: Slow scan configuration parameters
+    ///
+    /// # Returns
+    /// - `Ok(())`: All data written successfully
+    /// - `Err(io::Error)`: I/O error occurred during writing
+    fn slow_scan_write_then_erase(
+        &mut self,
+        s: &str,
+        hold: Duration,
+        config: SlowScanConfig
+    ) -> Result<(), io::Error>;
+
+    /// 使用指定的时钟显示字符串，停留一段时间后再逐个字符擦除
+    ///
+    /// 与 [`slow_scan_write_then_erase`](Self::slow_scan_write_then_erase)
+    /// 相同，但通过 `clock` 获取当前时间和进行延迟
+    ///
+    /// # 参数
+    /// - `s`: 要显示并擦除的一行文本
+    /// - `hold`: 显示完成后擦除之前的停留时间
+    /// - `config`: 慢速扫描配置参数
+    /// - `clock`: 提供当前时间和延迟的时钟
+    ///
+    /// # 返回值
+    /// - `Ok(())`: 所有数据成功写入
+    /// - `Err(io::Error)`: 写入过程中发生 I/O 错误
+    ///
+    /// # 示例
+    /// ```
+    /// use std::time::{Duration, Instant};
+    ///
+    /// use slow_scan_print::{SlowScanConfig, SlowScanWrite};
+    /// # struct VirtualClock {
+    /// #     now: std::time::Instant,
+    /// #     deadlines: Vec<std::time::Instant>
+    /// # }
+    /// #
+    /// # impl VirtualClock {
+    /// #     fn new(now: std::time::Instant) -> Self {
+    /// #         Self {
+    /// #             now,
+    /// #             deadlines: Vec::new()
+    /// #         }
+    /// #     }
+    /// # }
+    /// #
+    /// # impl slow_scan_print::Clock for VirtualClock {
+    /// #     fn now(&mut self) -> std::time::Instant { self.now }
+    /// #
+    /// #     fn sleep_until(&mut self, deadline: std::time::Instant) {
+    /// #         self.now = self.now.max(deadline);
+    /// #         self.deadlines.push(deadline);
+    /// #     }
+    /// # }
+    ///
+    /// let start = Instant::now();
+    /// let mut clock = VirtualClock::new(start);
+    ///
+    /// let mut config = SlowScanConfig::default();
+    /// config
+    ///     .set_base_delay(Duration::from_secs(1))
+    ///     .set_control_char_delay(Duration::from_secs(2));
+    ///
+    /// let mut writer = Vec::new();
+    /// writer
+    ///     .slow_scan_write_then_erase_with_clock(
+    ///         "ab",
+    ///         Duration::from_secs(5),
+    ///         config,
+    ///         &mut clock
+    ///     )
+    ///     .unwrap();
+    ///
+    /// assert_eq!(writer, b"ab\x08 \x08\x08 \x08");
+    /// // 显示 `a`、停留、擦除 `b` 之后各等待一次
+    /// // One wait each after revealing `a`, holding and erasing `b`
+    /// assert_eq!(
+    ///     clock.deadlines,
+    ///     [
+    ///         start + Duration::from_secs(1),
+    ///         start + Duration::from_secs(6),
+    ///         start + Duration::from_secs(8)
+    ///     ]
+    /// );
+    /// ```
+    ///
+    /// ---
+    ///
+    /// Reveal a string, hold it for a while, then erase it character by
+    /// character using the given clock
+    ///
+    /// Same as
+    /// [`slow_scan_write_then_erase`](Self::slow_scan_write_then_erase), but
+    /// the current time is obtained and delays are performed through `clock`
+    ///
+    /// # Arguments
+    /// - `s`: Line of text to reveal and erase
+    /// - `hold`: Time to hold the text after revealing it and before erasing
+    /// - `config`: Slow scan configuration parameters
+    /// - `clock`: Clock providing the current time and sleeping
+    ///
+    /// # Returns
+    /// - `Ok(())`: All data written successfully
+    /// - `Err(io::Error)`: I/O error occurred during writing
+    fn slow_scan_write_then_erase_with_clock<C>(
+        &mut self,
+        s: &str,
+        hold: Duration,
+        config: SlowScanConfig,
+        clock: &mut C
+    ) -> Result<(), io::Error>
+    where
+        C: Clock + ?Sized;
+
+    /// 按 Markdown 结构调整节奏并显示文本
+    ///
+    /// 标题以粗体（`ESC [ 1 m`）按正文四分之一的延迟快速显示，段落按
+    /// `slow_scan_write_by_chars` 的规则以正常节奏显示，代码块和行内代码以暗色
+    /// （`ESC [ 2 m`）立即写入，强调和加粗分别使用斜体和粗体样式，列表项以
+    /// `- ` 开头；顶层的块之间空一行并停顿 10 个 `base_delay`。不含 Markdown
+    /// 语法的文本会被当作普通段落，按正常节奏显示
+    ///
+    /// # 参数
+    /// - `s`: 要显示的 Markdown 文本
+    /// - `config`: 慢速扫描配置参数
+    ///
+    /// # 返回值
+    /// - `Ok(())`: 所有数据成功写入
+    /// - `Err(io::Error)`: 写入过程中发生 I/O 错误
+    ///
+    /// ---
+    ///
+    /// Reveal text with its pacing adjusted to the Markdown structure
+    ///
+    /// Headings are revealed quickly in bold (`ESC [ 1 m`) at a quarter of
+    /// the body delays, paragraphs at the normal pace following the rules of
+    /// `slow_scan_write_by_chars`, code blocks and inline code are written
+    /// instantly in a dim style (`ESC [ 2 m`), emphasis and strong emphasis
+    /// use italic and bold styles, and list items start with `- `; top-level
+    /// blocks are separated by a blank line and a pause of 10 `base_delay`s.
+    /// Text without Markdown syntax is treated as plain paragraphs and
+    /// revealed at the normal pace
+    ///
+    /// # Arguments
+    /// - `s`: Markdown text to reveal
+    /// - `config`: Slow scan configuration parameters
+    ///
+    /// # Returns
+    /// - `Ok(())`: All data written successfully
+    /// - `Err(io::Error)`: I/O error occurred during writing
+    #[cfg(feature = "markdown")]
+    fn slow_scan_write_markdown(
+        &mut self,
+        s: &str,
+        config: SlowScanConfig
+    ) -> Result<(), io::Error>;
+
+    /// 使用指定的时钟按 Markdown 结构调整节奏并显示文本
+    ///
+    /// 与 [`slow_scan_write_markdown`](Self::slow_scan_write_markdown) 相同，
+    /// 但通过 `clock` 获取当前时间和进行延迟
+    ///
+    /// # 参数
+    /// - `s`: 要显示的 Markdown 文本
+    /// - `config`: 慢速扫描配置参数
+    /// - `clock`: 提供当前时间和延迟的时钟
+    ///
+    /// # 返回值
+    /// - `Ok(())`: 所有数据成功写入
+    /// - `Err(io::Error)`: 写入过程中发生 I/O 错误
+    ///
+    /// # 示例
+    /// ```
+    /// use std::time::{Duration, Instant};
+    ///
+    /// use slow_scan_print::{SlowScanConfig, SlowScanWrite};
+    /// # struct VirtualClock {
+    /// #     now: std::time::Instant,
+    /// #     deadlines: Vec<std::time::Instant>
+    /// # }
+    /// #
+    /// # impl VirtualClock {
+    /// #     fn new(now: std::time::Instant) -> Self {
+    /// #         Self {
+    /// #             now,
+    /// #             deadlines: Vec::new()
+    /// #         }
+    /// #     }
+    /// # }
+    /// #
+    /// # impl slow_scan_print::Clock for VirtualClock {
+    /// #     fn now(&mut self) -> std::time::Instant { self.now }
+    /// #
+    /// #     fn sleep_until(&mut self, deadline: std::time::Instant) {
+    /// #         self.now = self.now.max(deadline);
+    /// #         self.deadlines.push(deadline);
+    /// #     }
+    /// # }
+    ///
+    /// let start = Instant::now();
+    /// let mut clock = VirtualClock::new(start);
+    ///
+    /// let mut config = SlowScanConfig::default();
+    /// config.set_base_delay(Duration::from_secs(4));
+    ///
+    /// let mut writer = Vec::new();
+    /// writer
+    ///     .slow_scan_write_markdown_with_clock(
+    ///         "# Title\n\nHello *world*.\n\n```\ncode\n```\n",
+    ///         config,
+    ///         &mut clock
+    ///     )
+    ///     .unwrap();
+    ///
+    /// assert_eq!(
+    ///     String::from_utf8(writer).unwrap(),
+    ///     "\x1b[1mTitle\x1b[22m\n\nHello \x1b[3mworld\x1b[23m.\n\n\x1b[2mcode\n\x1b[22m"
+    /// );
+    /// // 标题 5 × 1 秒，段落 12 × 4 秒，两次块间停顿各 40 秒，代码块不延迟
+    /// // Heading 5 × 1 s, paragraph 12 × 4 s, two pauses of 40 s between
+    /// // blocks, no delay for the code block
+    /// assert_eq!(clock.now - start, Duration::from_secs(133));
+    /// ```
+    ///
+    /// ---
+    ///
+    /// Reveal text with its pacing adjusted to the Markdown structure using
+    /// the given clock
+    ///
+    /// Same as [`slow_scan_write_markdown`](Self::slow_scan_write_markdown),
+    /// but the current time is obtained and delays are performed through
+    /// `clock`
+    ///
+    /// # Arguments
+    /// - `s`: Markdown text to reveal
+    /// - `config`: Slow scan configuration parameters
+    /// - `clock`: Clock providing the current time and sleeping
+    ///
+    /// # Returns
+    /// - `Ok(())`: All data written successfully
+    /// - `Err(io::Error)`: I/O error occurred during writing
+    #[cfg(feature = "markdown")]
+    fn slow_scan_write_markdown_with_clock<C>(
+        &mut self,
+        s: &str,
+        config: SlowScanConfig,
+        clock: &mut C
+    ) -> Result<(), io::Error>
+    where
+        C: Clock + ?Sized;
+
+    /// 按逐行节奏表慢速扫描输出字符串
+    ///
+    /// 出现在 `schedule` 中的行（从 0 开始计数）以其中的延迟作为每个字符的
+    /// 基础延迟，全角字符的延迟为其两倍；其余行按 `config` 正常显示。行与行
+    /// 之间照常延迟，只有最后一行之后按 `tail_delay` 处理
+    ///
+    /// # 参数
+    /// - `s`: 要显示的文本
+    /// - `schedule`: 行号到每个字符延迟的映射，可由 [`parse_schedule`] 得到
+    /// - `config`: 慢速扫描配置参数
+    ///
+    /// # 返回值
+    /// - `Ok(())`: 所有数据成功写入
+    /// - `Err(io::Error)`: 写入过程中发生 I/O 错误
+    ///
+    /// ---
+    ///
+    /// Slow scan output a string following a per-line pacing schedule
+    ///
+    /// Lines present in `schedule` (counted from 0) use the delay found there
+    /// as the base delay of every character, full-width characters taking
+    /// twice as long; the other lines are revealed following `config` as
+    /// usual. Lines are delayed from one another as usual, and only the last
+    /// line is followed by `tail_delay`
+    ///
+    /// # Arguments
+    /// - `s`: Text to reveal
+    /// - `schedule`: Mapping from line index to the delay of every character,
+    ///   as obtained from [`parse_schedule`]
+    /// - `config`: Slow scan configuration parameters
+    ///
+    /// # Returns
+    /// - `Ok(())`: All data written successfully
+    /// - `Err(io::Error)`: I/O error occurred during writing
+    fn slow_scan_write_scheduled(
+        &mut self,
+        s: &str,
+        schedule: &HashMap<usize, Duration>,
+        config: SlowScanConfig
+    ) -> Result<(), io::Error>;
+
+    /// 使用指定的时钟按逐行节奏表慢速扫描输出字符串
+    ///
+    /// 与 [`slow_scan_write_scheduled`](Self::slow_scan_write_scheduled) 相同，
+    /// 但通过 `clock` 获取当前时间和进行延迟
+    ///
+    /// # 参数
+    /// - `s`: 要显示的文本
+    /// - `schedule`: 行号到每个字符延迟的映射
+    /// - `config`: 慢速扫描配置参数
+    /// - `clock`: 提供当前时间和延迟的时钟
+    ///
+    /// # 返回值
+    /// - `Ok(())`: 所有数据成功写入
+    /// - `Err(io::Error)`: 写入过程中发生 I/O 错误
+    ///
+    /// # 示例
+    /// ```
+    /// use std::time::{Duration, Instant};
+    ///
+    /// use slow_scan_print::{SlowScanConfig, SlowScanWrite, parse_schedule};
+    /// # struct VirtualClock {
+    /// #     now: std::time::Instant,
+    /// #     deadlines: Vec<std::time::Instant>
+    /// # }
+    /// #
+    /// # impl VirtualClock {
+    /// #     fn new(now: std::time::Instant) -> Self {
+    /// #         Self {
+    /// #             now,
+    /// #             deadlines: Vec::new()
+    /// #         }
+    /// #     }
+    /// # }
+    /// #
+    /// # impl slow_scan_print::Clock for VirtualClock {
+    /// #     fn now(&mut self) -> std::time::Instant { self.now }
+    /// #
+    /// #     fn sleep_until(&mut self, deadline: std::time::Instant) {
+    /// #         self.now = self.now.max(deadline);
+    /// #         self.deadlines.push(deadline);
+    /// #     }
+    /// # }
+    ///
+    /// let start = Instant::now();
+    /// let mut clock = VirtualClock::new(start);
+    ///
+    /// let mut config = SlowScanConfig::default();
+    /// config.set_base_delay(Duration::from_millis(10));
+    ///
+    /// let schedule = parse_schedule("0,1\n2,0.5\n").unwrap();
+    ///
+    /// let mut writer = Vec::new();
+    /// writer
+    ///     .slow_scan_write_scheduled_with_clock(
+    ///         "ab\ncd\nef",
+    ///         &schedule,
+    ///         config,
+    ///         &mut clock
+    ///     )
+    ///     .unwrap();
+    ///
+    /// assert_eq!(writer, b"ab\ncd\nef");
+    /// // 第 0 行每个字符 1 秒，第 1 行 10 毫秒，第 2 行 0.5 秒；换行符和最后
+    /// // 一个字符之后不延迟
+    /// // 1 s per character on line 0, 10 ms on line 1 and 0.5 s on line 2;
+    /// // no delay after line breaks and the last character
+    /// assert_eq!(
+    ///     clock.deadlines,
+    ///     [
+    ///         start + Duration::from_millis(1000),
+    ///         start + Duration::from_millis(2000),
+    ///         start + Duration::from_millis(2000),
+    ///         start + Duration::from_millis(2010),
+    ///         start + Duration::from_millis(2020),
+    ///         start + Duration::from_millis(2020),
+    ///         start + Duration::from_millis(2520)
+    ///     ]
+    /// );
+    /// ```
+    ///
+    /// ---
+    ///
+    /// Slow scan output a string following a per-line pacing schedule using
+    /// the given clock
+    ///
+    /// Same as [`slow_scan_write_scheduled`](Self::slow_scan_write_scheduled),
+    /// but the current time is obtained and delays are performed through
+    /// `clock`
+    ///
+    /// # Arguments
+    /// - `s`: Text to reveal
+    /// - `schedule`: Mapping from line index to the delay of every character
+    /// - `config`: Slow scan configuration parameters
+    /// - `clock`: Clock providing the current time and sleeping
+    ///
+    /// # Returns
+    /// - `Ok(())`: All data written successfully
+    /// - `Err(io::Error)`: I/O error occurred during writing
+    fn slow_scan_write_scheduled_with_clock<C>(
+        &mut self,
+        s: &str,
+        schedule: &HashMap<usize, Duration>,
+        config: SlowScanConfig,
+        clock: &mut C
+    ) -> Result<(), io::Error>
+    where
+        C: Clock + ?Sized;
+
+    /// 按块写入并在同步点处调用回调
+    ///
+    /// 数据块按 `slow_scan_write_by_chunks` 的规则写入和延迟；遇到
+    /// [`ChunkUnit::Barrier`] 时先刷新输出，再调用 `on_barrier` 并等待其返回，
+    /// 可用于在动画中的特定位置播放音效或切换幻灯片等。同步点本身不延迟，也不
+    /// 计入 `head_units`
+    ///
+    /// # 参数
+    /// - `iter`: 数据块和同步点的迭代器
+    /// - `config`: 慢速扫描配置参数
+    /// - `on_barrier`: 每个同步点处调用的回调，参数为写入目标
+    ///
+    /// # 返回值
+    /// - `Ok(())`: 所有数据成功写入
+    /// - `Err(io::Error)`: 写入过程中或回调中发生 I/O 错误
+    ///
+    /// # 示例
+    /// ```
+    /// use std::time::Duration;
+    ///
+    /// use slow_scan_print::{ChunkUnit, SlowScanConfig, SlowScanWrite};
+    ///
+    /// let mut config = SlowScanConfig::default();
+    /// config.set_base_delay(Duration::ZERO);
+    ///
+    /// let units = [
+    ///     ChunkUnit::Data("ab"),
+    ///     ChunkUnit::Barrier,
+    ///     ChunkUnit::Data("cd")
+    /// ];
+    ///
+    /// let mut barriers = Vec::new();
+    /// let mut writer = Vec::new();
+    /// writer
+    ///     .slow_scan_write_by_chunks_with_barriers(
+    ///         units.into_iter(),
+    ///         config,
+    ///         |_| {
+    ///             barriers.push(());
+    ///             Ok(())
+    ///         }
+    ///     )
+    ///     .unwrap();
+    ///
+    /// assert_eq!(writer, b"abcd");
+    /// assert_eq!(barriers.len(), 1);
+    ///
+    /// // 回调在前后两个块之间执行
+    /// // The callback runs between the surrounding chunks
+    /// let mut writer = Vec::new();
+    /// writer
+    ///     .slow_scan_write_by_chunks_with_barriers(
+    ///         units.into_iter(),
+    ///         config,
+    ///         |w| w.write_all(b"|")
+    ///     )
+    ///     .unwrap();
+    ///
+    /// assert_eq!(writer, b"ab|cd");
+    /// ```
+    ///
+    /// ---
+    ///
+    /// Write by chunks, calling a callback at synchronization points
+    ///
+    /// Chunks of data are written and delayed following the rules of
+    /// `slow_scan_write_by_chunks`; at a [`ChunkUnit::Barrier`] the output is
+    /// flushed first, then `on_barrier` is called and waited for, which can be
+    /// used to play a sound or advance a slide at a specific point of the
+    /// animation. Barriers are not delayed themselves and do not count towards
+    /// `head_units`
+    ///
+    /// # Arguments
+    /// - `iter`: Iterator of chunks of data and barriers
+    /// - `config`: Slow scan configuration parameters
+    /// - `on_barrier`: Callback called at every barrier, receiving the sink
+    ///
+    /// # Returns
+    /// - `Ok(())`: All data written successfully
+    /// - `Err(io::Error)`: I/O error occurred during writing or in the
+    ///   callback
+    fn slow_scan_write_by_chunks_with_barriers<I, T, F>(
+        &mut self,
+        iter: I,
+        config: SlowScanConfig,
+        on_barrier: F
+    ) -> Result<(), io::Error>
+    where
+        I: Iterator<Item = ChunkUnit<T>>,
+        T: AsRef<[u8]>,
+        F: FnMut(&mut dyn Write) -> io::Result<()>;
+
+    /// 逐字符写入，每个字符之后的延迟由调用者指定
+    ///
+    /// 迭代器的每一项是一个字符和写入它之后的延迟，适合手工调校节奏的文本。
+    /// 配置中按字符类型区分的延迟不会生效，最后一个字符之后是否延迟仍由
+    /// `tail_delay` 决定
+    ///
+    /// # 参数
+    /// - `iter`: 字符及其之后延迟的迭代器
+    /// - `config`: 慢速扫描配置参数
+    ///
+    /// # 返回值
+    /// - `Ok(())`: 所有字符成功写入
+    /// - `Err(io::Error)`: 写入过程中发生 I/O 错误
+    ///
+    /// ---
+    ///
+    /// Write character by character, with the delay after every character
+    /// given by the caller
+    ///
+    /// Each item of the iterator is a character and the delay after writing
+    /// it, which suits hand-tuned text. The per-class delays of the
+    /// configuration do not apply, while whether to delay after the last
+    /// character is still decided by `tail_delay`
+    ///
+    /// # Arguments
+    /// - `iter`: Iterator of characters and the delays following them
+    /// - `config`: Slow scan configuration parameters
+    ///
+    /// # Returns
+    /// - `Ok(())`: All characters written successfully
+    /// - `Err(io::Error)`: I/O error occurred during writing
+    fn slow_scan_write_chars_timed<I>(
+        &mut self,
+        iter: I,
+        config: SlowScanConfig
+    ) -> Result<(), io::Error>
+    where
+        I: Iterator<Item = (char, Duration)>;
+
+    /// 使用指定的时钟逐字符写入，每个字符之后的延迟由调用者指定
+    ///
+    /// 与 `slow_scan_write_chars_timed` 相同，但所有延迟都通过 `clock` 完成
+    ///
+    /// # 参数
+    /// - `iter`: 字符及其之后延迟的迭代器
+    /// - `config`: 慢速扫描配置参数
+    /// - `clock`: 提供当前时间和休眠的时钟
+    ///
+    /// # 返回值
+    /// - `Ok(())`: 所有字符成功写入
+    /// - `Err(io::Error)`: 写入过程中发生 I/O 错误
+    ///
+    /// # 示例
+    /// ```
+    /// use std::time::{Duration, Instant};
+    ///
+    /// use slow_scan_print::{SlowScanConfig, SlowScanWrite};
+    /// # struct VirtualClock {
+    /// #     now: std::time::Instant,
+    /// #     deadlines: Vec<std::time::Instant>
+    /// # }
+    /// #
+    /// # impl VirtualClock {
+    /// #     fn new(now: std::time::Instant) -> Self {
+    /// #         Self {
+    /// #             now,
+    /// #             deadlines: Vec::new()
+    /// #         }
+    /// #     }
+    /// # }
+    /// #
+    /// # impl slow_scan_print::Clock for VirtualClock {
+    /// #     fn now(&mut self) -> std::time::Instant { self.now }
+    /// #
+    /// #     fn sleep_until(&mut self, deadline: std::time::Instant) {
+    /// #         self.now = self.now.max(deadline);
+    /// #         self.deadlines.push(deadline);
+    /// #     }
+    /// # }
+    ///
+    /// let start = Instant::now();
+    /// let mut clock = VirtualClock::new(start);
+    ///
+    /// let ms = Duration::from_millis;
+    /// let text = [
+    ///     ('H', ms(100)),
+    ///     ('i', ms(700)),
+    ///     ('世', ms(50)),
+    ///     ('!', ms(300))
+    /// ];
+    ///
+    /// // 按字符类型区分的延迟不会生效
+    /// // The per-class delays do not apply
+    /// let mut config = SlowScanConfig::default();
+    /// config
+    ///     .set_base_delay(Duration::from_secs(9))
+    ///     .set_full_width_delay(Duration::from_secs(9))
+    ///     .set_tail_delay(false);
+    ///
+    /// let mut writer = Vec::new();
+    /// writer
+    ///     .slow_scan_write_chars_timed_with_clock(
+    ///         text.into_iter(),
+    ///         config,
+    ///         &mut clock
+    ///     )
+    ///     .unwrap();
+    ///
+    /// assert_eq!(writer, "Hi世!".as_bytes());
+    /// assert_eq!(
+    ///     clock.deadlines,
+    ///     [start + ms(100), start + ms(800), start + ms(850)]
+    /// );
+    ///
+    /// // 最后一个字符之后的延迟仍由 tail_delay 决定
+    /// // The delay after the last character is still decided by tail_delay
+    /// config.set_tail_delay(true);
+    /// clock.deadlines.clear();
+    ///
+    /// let start = clock.now;
+    /// writer
+    ///     .slow_scan_write_chars_timed_with_clock(
+    ///         text.into_iter(),
+    ///         config,
+    ///         &mut clock
+    ///     )
+    ///     .unwrap();
+    ///
+    /// assert_eq!(
+    ///     clock.deadlines,
+    ///     [
+    ///         start + ms(100),
+    ///         start + ms(800),
+    ///         start + ms(850),
+    ///         start + ms(1150)
+    ///     ]
+    /// );
+    /// ```
+    ///
+    /// ---
+    ///
+    /// Write character by character with the delay after every character
+    /// given by the caller, using the given clock
+    ///
+    /// Same as `slow_scan_write_chars_timed`, but every delay goes through
+    /// `clock`
+    ///
+    /// # Arguments
+    /// - `iter`: Iterator of characters and the delays following them
+    /// - `config`: Slow scan configuration parameters
+    /// - `clock`: Clock providing the current time and sleeping
+    ///
+    /// # Returns
+    /// - `Ok(())`: All characters written successfully
+    /// - `Err(io::Error)`: I/O error occurred during writing
+    fn slow_scan_write_chars_timed_with_clock<I, C>(
+        &mut self,
+        iter: I,
+        config: SlowScanConfig,
+        clock: &mut C
+    ) -> Result<(), io::Error>
+    where
+        I: Iterator<Item = (char, Duration)>,
+        C: Clock + ?Sized;
+
+    /// 按脚本逐段显示带样式的文本
+    ///
+    /// 每段文本按其 `delay` 设置半角字符的延迟，全角字符加倍，未设置时使用
+    /// 配置中的延迟；有样式的段落之前写入对应的 SGR 序列，之后用 `ESC [ 0 m`
+    /// 重置。最后一段之后按 `tail_delay` 处理
+    ///
+    /// # 参数
+    /// - `segments`: 由 [`parse_script`] 解析得到的各段文本
+    /// - `config`: 慢速扫描配置参数
+    ///
+    /// # 返回值
+    /// - `Ok(())`: 所有文本成功写入
+    /// - `Err(io::Error)`: 写入过程中发生 I/O 错误
+    ///
+    /// ---
+    ///
+    /// Reveal styled text segment by segment following a script
+    ///
+    /// The `delay` of every segment sets the delay of its half-width
+    /// characters, doubled for full-width ones, falling back to the delays of
+    /// the configuration when unset; styled segments are preceded by their SGR
+    /// sequence and followed by an `ESC [ 0 m` reset. The last segment is
+    /// followed by `tail_delay` as usual
+    ///
+    /// # Arguments
+    /// - `segments`: Segments of text parsed by [`parse_script`]
+    /// - `config`: Slow scan configuration parameters
+    ///
+    /// # Returns
+    /// - `Ok(())`: All text written successfully
+    /// - `Err(io::Error)`: I/O error occurred during writing
+    fn slow_scan_write_script(
+        &mut self,
+        segments: &[ScriptSegment],
+        config: SlowScanConfig
+    ) -> Result<(), io::Error>;
+
+    /// 使用指定的时钟按脚本逐段显示带样式的文本
+    ///
+    /// 与 `slow_scan_write_script` 相同，但所有延迟都通过 `clock` 完成
+    ///
+    /// # 参数
+    /// - `segments`: 由 [`parse_script`] 解析得到的各段文本
+    /// - `config`: 慢速扫描配置参数
+    /// - `clock`: 提供当前时间和休眠的时钟
+    ///
+    /// # 返回值
+    /// - `Ok(())`: 所有文本成功写入
+    /// - `Err(io::Error)`: 写入过程中发生 I/O 错误
+    ///
+    /// # 示例
+    /// ```
+    /// use std::time::{Duration, Instant};
+    ///
+    /// use slow_scan_print::{SlowScanConfig, SlowScanWrite, parse_script};
+    /// # struct VirtualClock {
+    /// #     now: std::time::Instant,
+    /// #     deadlines: Vec<std::time::Instant>
+    /// # }
+    /// #
+    /// # impl VirtualClock {
+    /// #     fn new(now: std::time::Instant) -> Self {
+    /// #         Self {
+    /// #             now,
+    /// #             deadlines: Vec::new()
+    /// #         }
+    /// #     }
+    /// # }
+    /// #
+    /// # impl slow_scan_print::Clock for VirtualClock {
+    /// #     fn now(&mut self) -> std::time::Instant { self.now }
+    /// #
+    /// #     fn sleep_until(&mut self, deadline: std::time::Instant) {
+    /// #         self.now = self.now.max(deadline);
+    /// #         self.deadlines.push(deadline);
+    /// #     }
+    /// # }
+    ///
+    /// let start = Instant::now();
+    /// let mut clock = VirtualClock::new(start);
+    ///
+    /// let mut config = SlowScanConfig::default();
+    /// config.set_base_delay(Duration::from_secs(1));
+    ///
+    /// let segments = parse_script("ab\n@style bold\n@delay 100ms\nc世").unwrap();
+    ///
+    /// let mut writer = Vec::new();
+    /// writer
+    ///     .slow_scan_write_script_with_clock(&segments, config, &mut clock)
+    ///     .unwrap();
+    ///
+    /// assert_eq!(writer, "ab\n\x1b[1mc世\x1b[0m".as_bytes());
+    /// // a 和 b 使用配置中的延迟，c 使用脚本设置的延迟
+    /// // a and b use the delay of the configuration, c the one of the script
+    /// let ms = Duration::from_millis;
+    /// assert_eq!(
+    ///     clock.deadlines,
+    ///     [
+    ///         start + ms(1000),
+    ///         start + ms(2000),
+    ///         start + ms(2000),
+    ///         start + ms(2100)
+    ///     ]
+    /// );
+    /// ```
+    ///
+    /// ---
+    ///
+    /// Reveal styled text segment by segment following a script, using the
+    /// given clock
+    ///
+    /// Same as `slow_scan_write_script`, but every delay goes through `clock`
+    ///
+    /// # Arguments
+    /// - `segments`: Segments of text parsed by [`parse_script`]
+    /// - `config`: Slow scan configuration parameters
+    /// - `clock`: Clock providing the current time and sleeping
+    ///
+    /// # Returns
+    /// - `Ok(())`: All text written successfully
+    /// - `Err(io::Error)`: I/O error occurred during writing
+    fn slow_scan_write_script_with_clock<C>(
+        &mut self,
+        segments: &[ScriptSegment],
+        config: SlowScanConfig,
+        clock: &mut C
+    ) -> Result<(), io::Error>
+    where
+        C: Clock + ?Sized;
+
+    /// 逐行显示文本，使每一行占用相同的时间
+    ///
+    /// 每行的换行符之后停顿 `gap`，其余字符的延迟按配置中各类字符延迟的比例
+    /// 整体缩放，使这些延迟之和恰好为 `line_time - gap`，因此短行显示得慢，
+    /// 长行显示得快，每一行（包括之后的停顿）都占用 `line_time`。没有其他字符
+    /// 或其他字符的延迟均为 0 时，整行时间都放在换行符之后。最后一个字符之后
+    /// 按 `tail_delay` 处理
+    ///
+    /// # 参数
+    /// - `s`: 要写入的文本
+    /// - `line_time`: 每一行占用的时间
+    /// - `gap`: 每行换行符之后的固定停顿，超过 `line_time` 时字符不再延迟
+    /// - `config`: 慢速扫描配置参数
+    ///
+    /// # 返回值
+    /// - `Ok(())`: 所有文本成功写入
+    /// - `Err(io::Error)`: 写入过程中发生 I/O 错误
+    ///
+    /// ---
+    ///
+    /// Reveal text line by line, every line taking the same time
+    ///
+    /// Every line break is followed by a pause of `gap`, and the delays of the
+    /// other characters are scaled as a whole, keeping the proportions of the
+    /// per-class delays of the configuration, so that they add up to exactly
+    /// `line_time - gap`. Short lines are therefore revealed slowly and long
+    /// ones quickly, and every line, pause included, takes `line_time`. When a
+    /// line has no other characters or all of their delays are 0, the whole
+    /// line time goes after the line break. The last character is followed by
+    /// `tail_delay` as usual
+    ///
+    /// # Arguments
+    /// - `s`: Text to write
+    /// - `line_time`: Time every line takes
+    /// - `gap`: Fixed pause after every line break, characters are no longer
+    ///   delayed when it exceeds `line_time`
+    /// - `config`: Slow scan configuration parameters
+    ///
+    /// # Returns
+    /// - `Ok(())`: All text written successfully
+    /// - `Err(io::Error)`: I/O error occurred during writing
+    fn slow_scan_write_equal_lines(
+        &mut self,
+        s: &str,
+        line_time: Duration,
+        gap: Duration,
+        config: SlowScanConfig
+    ) -> Result<(), io::Error>;
+
+    /// 使用指定的时钟逐行显示文本，使每一行占用相同的时间
+    ///
+    /// 与 `slow_scan_write_equal_lines` 相同，但所有延迟都通过 `clock` 完成
+    ///
+    /// # 参数
+    /// - `s`: 要写入的文本
+    /// - `line_time`: 每一行占用的时间
+    /// - `gap`: 每行换行符之后的固定停顿，超过 `line_time` 时字符不再延迟
+    /// - `config`: 慢速扫描配置参数
+    /// - `clock`: 提供当前时间和休眠的时钟
+    ///
+    /// # 返回值
+    /// - `Ok(())`: 所有文本成功写入
+    /// - `Err(io::Error)`: 写入过程中发生 I/O 错误
+    ///
+    /// # 示例
+    /// ```
+    /// use std::time::{Duration, Instant};
+    ///
+    /// use slow_scan_print::{SlowScanConfig, SlowScanWrite};
+    /// # struct VirtualClock {
+    /// #     now: std::time::Instant,
+    /// #     deadlines: Vec<std::time::Instant>
+    /// # }
+    /// #
+    /// # impl VirtualClock {
+    /// #     fn new(now: std::time::Instant) -> Self {
+    /// #         Self {
+    /// #             now,
+    /// #             deadlines: Vec::new()
+    /// #         }
+    /// #     }
+    /// # }
+    /// #
+    /// # impl slow_scan_print::Clock for VirtualClock {
+    /// #     fn now(&mut self) -> std::time::Instant { self.now }
+    /// #
+    /// #     fn sleep_until(&mut self, deadline: std::time::Instant) {
+    /// #         self.now = self.now.max(deadline);
+    /// #         self.deadlines.push(deadline);
+    /// #     }
+    /// # }
+    ///
+    /// let start = Instant::now();
+    /// let mut clock = VirtualClock::new(start);
+    ///
+    /// let mut config = SlowScanConfig::default();
+    /// config.set_tail_delay(true);
+    ///
+    /// let line_time = Duration::from_secs(2);
+    /// let gap = Duration::from_millis(500);
+    ///
+    /// let text = "hi\na much longer line 世界\n\nend\n";
+    ///
+    /// let mut writer = Vec::new();
+    /// writer
+    ///     .slow_scan_write_equal_lines_with_clock(
+    ///         text, line_time, gap, config, &mut clock
+    ///     )
+    ///     .unwrap();
+    ///
+    /// // 每个换行符之后的时间点都相隔 line_time
+    /// // The instants after every line break are line_time apart
+    /// let ends = clock
+    ///     .deadlines
+    ///     .iter()
+    ///     .zip(text.chars())
+    ///     .filter(|(_, it)| *it == '\n')
+    ///     .map(|(it, _)| it.duration_since(start))
+    ///     .collect::<Vec<_>>();
+    ///
+    /// assert_eq!(ends.len(), 4);
+    /// for (i, it) in ends.into_iter().enumerate() {
+    ///     let expected = line_time * (i as u32 + 1);
+    ///     assert!(it.abs_diff(expected) < Duration::from_micros(1));
+    /// }
+    ///
+    /// // 短行中每个字符的延迟长于长行
+    /// // Every character of a short line is delayed longer than in a long one
+    /// assert_eq!(clock.deadlines[0] - start, Duration::from_millis(750));
+    /// ```
+    ///
+    /// ---
+    ///
+    /// Reveal text line by line, every line taking the same time, using the
+    /// given clock
+    ///
+    /// Same as `slow_scan_write_equal_lines`, but every delay goes through
+    /// `clock`
+    ///
+    /// # Arguments
+    /// - `s`: Text to write
+    /// - `line_time`: Time every line takes
+    /// - `gap`: Fixed pause after every line break, characters are no longer
+    ///   delayed when it exceeds `line_time`
+    /// - `config`: Slow scan configuration parameters
+    /// - `clock`: Clock providing the current time and sleeping
+    ///
+    /// # Returns
+    /// - `Ok(())`: All text written successfully
+    /// - `Err(io::Error)`: I/O error occurred during writing
+    fn slow_scan_write_equal_lines_with_clock<C>(
+        &mut self,
+        s: &str,
+        line_time: Duration,
+        gap: Duration,
+        config: SlowScanConfig,
+        clock: &mut C
+    ) -> Result<(), io::Error>
+    where
+        C: Clock + ?Sized;
+
+    /// 在外部节拍给出的时间点逐个写入字符
+    ///
+    /// 每个字符都等到 `ticks` 产生的下一个时间点才写入，可以由节拍检测等外部
+    /// 来源驱动显示，配置中的延迟不会生效；已经过去的时间点会立即写入。节拍
+    /// 用完之后，其余字符在上一个字符之后相隔 `base_delay` 依次写入，没有任何
+    /// 节拍时第一个字符立即写入
+    ///
+    /// # 参数
+    /// - `iter`: 要写入的字符
+    /// - `ticks`: 依次写入每个字符的时间点
+    /// - `config`: 慢速扫描配置参数
+    ///
+    /// # 返回值
+    /// - `Ok(())`: 所有字符成功写入
+    /// - `Err(io::Error)`: 写入过程中发生 I/O 错误
+    ///
+    /// ---
+    ///
+    /// Write characters one by one at the instants given by an external beat
+    ///
+    /// Every character waits for the next instant produced by `ticks` before
+    /// being written, which lets external sources such as a beat detector
+    /// drive the reveal, and the delays of the configuration do not apply;
+    /// instants already past are written at once. Once the ticks run out, the
+    /// remaining characters follow each other `base_delay` apart, and the first
+    /// character is written at once when there are no ticks at all
+    ///
+    /// # Arguments
+    /// - `iter`: Characters to write
+    /// - `ticks`: Instants at which the characters are written in turn
+    /// - `config`: Slow scan configuration parameters
+    ///
+    /// # Returns
+    /// - `Ok(())`: All characters written successfully
+    /// - `Err(io::Error)`: I/O error occurred during writing
+    fn slow_scan_write_on_ticks<I, T>(
+        &mut self,
+        iter: I,
+        ticks: T,
+        config: SlowScanConfig
+    ) -> Result<(), io::Error>
+    where
+        I: Iterator<Item = char>,
+        T: Iterator<Item = Instant>;
+
+    /// 使用指定的时钟在外部节拍给出的时间点逐个写入字符
+    ///
+    /// 与 `slow_scan_write_on_ticks` 相同，但所有等待都通过 `clock` 完成
+    ///
+    /// # 参数
+    /// - `iter`: 要写入的字符
+    /// - `ticks`: 依次写入每个字符的时间点
+    /// - `config`: 慢速扫描配置参数
+    /// - `clock`: 提供当前时间和休眠的时钟
+    ///
+    /// # 返回值
+    /// - `Ok(())`: 所有字符成功写入
+    /// - `Err(io::Error)`: 写入过程中发生 I/O 错误
+    ///
+    /// # 示例
+    /// ```
+    /// use std::cell::Cell;
+    /// use std::io::{self, Write};
+    /// use std::rc::Rc;
+    /// use std::time::{Duration, Instant};
+    ///
+    /// use slow_scan_print::{Clock, SlowScanConfig, SlowScanWrite};
+    ///
+    /// struct SharedClock(Rc<Cell<Instant>>);
+    ///
+    /// impl Clock for SharedClock {
+    ///     fn now(&mut self) -> Instant { self.0.get() }
+    ///
+    ///     fn sleep_until(&mut self, deadline: Instant) {
+    ///         self.0.set(self.0.get().max(deadline));
+    ///     }
+    /// }
+    ///
+    /// /// 记录每个字节写入时的时间点
+    /// struct Timeline {
+    ///     now: Rc<Cell<Instant>>,
+    ///     written: Vec<(u8, Instant)>
+    /// }
+    ///
+    /// impl Write for Timeline {
+    ///     fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+    ///         let now = self.now.get();
+    ///         self.written.extend(buf.iter().map(|it| (*it, now)));
+    ///         Ok(buf.len())
+    ///     }
+    ///
+    ///     fn flush(&mut self) -> io::Result<()> { Ok(()) }
+    /// }
+    ///
+    /// let start = Instant::now();
+    /// let now = Rc::new(Cell::new(start));
+    /// let ms = Duration::from_millis;
+    ///
+    /// let mut config = SlowScanConfig::default();
+    /// config.set_base_delay(ms(100));
+    ///
+    /// let ticks = [start + ms(1000), start + ms(1500), start + ms(4000)];
+    ///
+    /// let mut writer = Timeline {
+    ///     now: now.clone(),
+    ///     written: Vec::new()
+    /// };
+    /// writer
+    ///     .slow_scan_write_on_ticks_with_clock(
+    ///         "abcde".chars(),
+    ///         ticks.into_iter(),
+    ///         config,
+    ///         &mut SharedClock(now)
+    ///     )
+    ///     .unwrap();
+    ///
+    /// // 前三个字符在节拍处写入，之后按 base_delay 继续
+    /// // The first three characters land on the ticks, then base_delay takes
+    /// // over
+    /// assert_eq!(
+    ///     writer.written,
+    ///     [
+    ///         (b'a', start + ms(1000)),
+    ///         (b'b', start + ms(1500)),
+    ///         (b'c', start + ms(4000)),
+    ///         (b'd', start + ms(4100)),
+    ///         (b'e', start + ms(4200))
+    ///     ]
+    /// );
+    /// ```
+    ///
+    /// ---
+    ///
+    /// Write characters one by one at the instants given by an external beat,
+    /// using the given clock
+    ///
+    /// Same as `slow_scan_write_on_ticks`, but every wait goes through `clock`
+    ///
+    /// # Arguments
+    /// - `iter`: Characters to write
+    /// - `ticks`: Instants at which the characters are written in turn
+    /// - `config`: Slow scan configuration parameters
+    /// - `clock`: Clock providing the current time and sleeping
+    ///
+    /// # Returns
+    /// - `Ok(())`: All characters written successfully
+    /// - `Err(io::Error)`: I/O error occurred during writing
+    fn slow_scan_write_on_ticks_with_clock<I, T, C>(
+        &mut self,
+        iter: I,
+        ticks: T,
+        config: SlowScanConfig,
+        clock: &mut C
+    ) -> Result<(), io::Error>
+    where
+        I: Iterator<Item = char>,
+        T: Iterator<Item = Instant>,
+        C: Clock + ?Sized;
+
+    /// 逐个擦除光标之前已经显示的字符
+    ///
+    /// 写入 `char_count` 次 `\x08 \x08`（退格、空格、退格），每擦除一个字符
+    /// 之后按 `control_char_delay` 等待，最后一次之后的等待由 `tail_delay`
+    /// 决定。每次擦除一列，全宽字符需要计为两个；依赖终端处理退格，不能跨越
+    /// 换行。可以与其他方法组合出先显示再删除等效果
+    ///
+    /// # 参数
+    /// - `char_count`: 要擦除的字符数量
+    /// - `config`: 慢速扫描配置参数
+    ///
+    /// # 返回值
+    /// - `Ok(())`: 所有数据成功写入
+    /// - `Err(io::Error)`: 写入过程中发生 I/O 错误
+    ///
+    /// # 示例
+    /// ```
+    /// use std::time::Duration;
+    ///
+    /// use slow_scan_print::{SlowScanConfig, SlowScanWrite};
+    ///
+    /// let mut config = SlowScanConfig::default();
+    /// config.set_control_char_delay(Duration::ZERO);
+    ///
+    /// let mut writer = b"hello".to_vec();
+    /// writer.slow_scan_erase(2, config).unwrap();
+    ///
+    /// assert_eq!(writer, b"hello\x08 \x08\x08 \x08");
+    /// ```
+    ///
+    /// ---
+    ///
+    /// Erase characters already shown before the cursor one by one
+    ///
+    /// `\x08 \x08` (backspace, space, backspace) is written `char_count`
+    /// times, waiting for `control_char_delay` after every erased character,
+    /// the wait after the last one being decided by `tail_delay`. Every erase
+    /// removes one column, so full-width characters count as two; relies on
+    /// the terminal handling backspaces and cannot cross line breaks. Can be
+    /// combined with other methods into effects such as revealing then
+    /// deleting
+    ///
+    /// # Arguments
+    /// - `char_count`: Number of characters to erase
+    /// - `config`: Slow scan configuration parameters
+    ///
+    /// # Returns
+    /// - `Ok(())`: All data written successfully
+    /// - `Err(io::Error)`: I/O error occurred during writing
+    fn slow_scan_erase(
+        &mut self,
+        char_count: usize,
+        config: SlowScanConfig
+    ) -> Result<(), io::Error>;
+
+    /// 使用指定的时钟逐个擦除光标之前已经显示的字符
+    ///
+    /// 与 [`slow_scan_erase`](Self::slow_scan_erase) 相同，但当前时间的获取
+    /// 和延迟都通过 `clock` 完成
+    ///
+    /// # 参数
+    /// - `char_count`: 要擦除的字符数量
+    /// - `config`: 慢速扫描配置参数
+    /// - `clock`: 提供当前时间和休眠的时钟
+    ///
+    /// # 返回值
+    /// - `Ok(())`: 所有数据成功写入
+    /// - `Err(io::Error)`: 写入过程中发生 I/O 错误
+    ///
+    /// # 示例
+    /// ```
+    /// use std::time::{Duration, Instant};
+    ///
+    /// use slow_scan_print::{SlowScanConfig, SlowScanWrite};
+    /// # struct VirtualClock {
+    /// #     now: std::time::Instant,
+    /// #     deadlines: Vec<std::time::Instant>
+    /// # }
+    /// #
+    /// # impl VirtualClock {
+    /// #     fn new(now: std::time::Instant) -> Self {
+    /// #         Self {
+    /// #             now,
+    /// #             deadlines: Vec::new()
+    /// #         }
+    /// #     }
+    /// # }
+    /// #
+    /// # impl slow_scan_print::Clock for VirtualClock {
+    /// #     fn now(&mut self) -> std::time::Instant { self.now }
+    /// #
+    /// #     fn sleep_until(&mut self, deadline: std::time::Instant) {
+    /// #         self.now = self.now.max(deadline);
+    /// #         self.deadlines.push(deadline);
+    /// #     }
+    /// # }
+    ///
+    /// let start = Instant::now();
+    /// let mut clock = VirtualClock::new(start);
+    ///
+    /// let mut config = SlowScanConfig::default();
+    /// config.set_control_char_delay(Duration::from_millis(50));
+    ///
+    /// let mut writer = Vec::new();
+    /// writer
+    ///     .slow_scan_erase_with_clock(3, config, &mut clock)
+    ///     .unwrap();
+    ///
+    /// // 写入三次擦除序列，最后一次之后不等待
+    /// // Three erase sequences are written, without waiting after the last
+    /// assert_eq!(writer, b"\x08 \x08".repeat(3));
+    /// assert_eq!(
+    ///     clock.deadlines,
+    ///     [
+    ///         start + Duration::from_millis(50),
+    ///         start + Duration::from_millis(100)
+    ///     ]
+    /// );
+    /// ```
+    ///
+    /// ---
+    ///
+    /// Erase characters already shown before the cursor one by one using the
+    /// given clock
+    ///
+    /// Same as [`slow_scan_erase`](Self::slow_scan_erase), but the current
+    /// time is obtained and delays are performed through `clock`
+    ///
+    /// # Arguments
+    /// - `char_count`: Number of characters to erase
+    /// - `config`: Slow scan configuration parameters
+    /// - `clock`: Clock providing the current time and sleeping
+    ///
+    /// # Returns
+    /// - `Ok(())`: All data written successfully
+    /// - `Err(io::Error)`: I/O error occurred during writing
+    fn slow_scan_erase_with_clock<C>(
+        &mut self,
+        char_count: usize,
+        config: SlowScanConfig,
+        clock: &mut C
+    ) -> Result<(), io::Error>
+    where
+        C: Clock + ?Sized;
+
+    /// 逐个写入字符，最近显示的字符带有逐渐变暗的拖尾
+    ///
+    /// 最近显示的 `trail` 个字符以从亮到暗的灰色（24 位真彩色 SGR）显示，
+    /// 每写入一个字符后通过 `ESC [ n D` 退回拖尾的起点重新写入这些字符，
+    /// 更早的字符恢复正常样式。控制字符和零宽字符原样写入，写入之前拖尾先
+    /// 恢复正常；全部写完后拖尾同样恢复正常。延迟与
+    /// `slow_scan_write_by_chars` 相同。依赖终端处理光标移动，行不应超过
+    /// 终端宽度，输入不应包含自己的样式，适合输出到终端时使用。`trail` 为 0
+    /// 时与 `slow_scan_write_by_chars` 相同
+    ///
+    /// # 参数
+    /// - `iter`: 要写入的字符
+    /// - `trail`: 拖尾的字符数量
+    /// - `config`: 慢速扫描配置参数
+    ///
+    /// # 返回值
+    /// - `Ok(())`: 所有数据成功写入
+    /// - `Err(io::Error)`: 写入过程中发生 I/O 错误
+    ///
+    /// # 示例
+    /// ```
+    /// use std::time::Duration;
+    ///
+    /// use slow_scan_print::{SlowScanConfig, SlowScanWrite};
+    ///
+    /// let mut config = SlowScanConfig::default();
+    /// config.set_base_delay(Duration::ZERO);
+    ///
+    /// let mut writer = Vec::new();
+    /// writer.slow_scan_write_trail("abc".chars(), 2, config).unwrap();
+    ///
+    /// let bright = "\x1b[38;2;255;255;255m";
+    /// let dim = "\x1b[38;2;208;208;208m";
+    /// let reset = "\x1b[0m";
+    /// // 每写入一个字符都退回并以新的亮度重写拖尾，`a` 离开拖尾后恢复正常
+    /// // Every character moves back and rewrites the trail with new
+    /// // brightness, and `a` turns normal once it leaves the trail
+    /// let expected = [
+    ///     format!("{bright}a{reset}"),
+    ///     format!("\x1b[1D{dim}a{bright}b{reset}"),
+    ///     format!("\x1b[2D{reset}a{dim}b{bright}c{reset}"),
+    ///     format!("\x1b[2D{reset}bc")
+    /// ]
+    /// .concat();
+    ///
+    /// assert_eq!(String::from_utf8(writer).unwrap(), expected);
+    /// ```
+    ///
+    /// ---
+    ///
+    /// Write characters one by one, the latest ones leaving a fading trail
+    ///
+    /// The latest `trail` characters are shown in grays going from bright to
+    /// dim (24-bit truecolor SGR): after every character the cursor moves back
+    /// to the start of the trail with `ESC [ n D` and rewrites them, older
+    /// characters returning to the normal style. Control and zero-width
+    /// characters are written unchanged after the trail turns normal, and the
+    /// trail turns normal as well once everything is written. The delays are
+    /// the same as with `slow_scan_write_by_chars`. Relies on the terminal
+    /// handling cursor movements, lines should not exceed the terminal width
+    /// and the input should not carry styles of its own; meant for terminal
+    /// output. Same as `slow_scan_write_by_chars` when `trail` is 0
+    ///
+    /// # Arguments
+    /// - `iter`: Characters to write
+    /// - `trail`: Number of characters in the trail
+    /// - `config`: Slow scan configuration parameters
+    ///
+    /// # Returns
+    /// - `Ok(())`: All data written successfully
+    /// - `Err(io::Error)`: I/O error occurred during writing
+    fn slow_scan_write_trail<I>(
+        &mut self,
+        iter: I,
+        trail: usize,
+        config: SlowScanConfig
+    ) -> Result<(), io::Error>
+    where
+        I: Iterator<Item = char>;
+
+    /// 使用指定的时钟逐个写入字符，最近显示的字符带有逐渐变暗的拖尾
+    ///
+    /// 与 [`slow_scan_write_trail`](Self::slow_scan_write_trail) 相同，但
+    /// 当前时间的获取和延迟都通过 `clock` 完成
+    ///
+    /// # 参数
+    /// - `iter`: 要写入的字符
+    /// - `trail`: 拖尾的字符数量
+    /// - `config`: 慢速扫描配置参数
+    /// - `clock`: 提供当前时间和休眠的时钟
+    ///
+    /// # 返回值
+    /// - `Ok(())`: 所有数据成功写入
+    /// - `Err(io::Error)`: 写入过程中发生 I/O 错误
+    ///
+    /// ---
+    ///
+    /// Write characters one by one, the latest ones leaving a fading trail,
+    /// using the given clock
+    ///
+    /// Same as [`slow_scan_write_trail`](Self::slow_scan_write_trail), but
+    /// the current time is obtained and delays are performed through `clock`
+    ///
+    /// # Arguments
+    /// - `iter`: Characters to write
+    /// - `trail`: Number of characters in the trail
+    /// - `config`: Slow scan configuration parameters
+    /// - `clock`: Clock providing the current time and sleeping
+    ///
+    /// # Returns
+    /// - `Ok(())`: All data written successfully
+    /// - `Err(io::Error)`: I/O error occurred during writing
+    fn slow_scan_write_trail_with_clock<I, C>(
+        &mut self,
+        iter: I,
+        trail: usize,
+        config: SlowScanConfig,
+        clock: &mut C
+    ) -> Result<(), io::Error>
+    where
+        I: Iterator<Item = char>,
+        C: Clock + ?Sized;
+
+    /// 逐个字符写入，可以通过标志位中途取消
+    ///
+    /// 与 `slow_scan_write_by_chars` 相同，但每读取一个字符之前检查 `cancel`，
+    /// 标志位被设置后不再读取输入并返回 [`Interrupted`]，其中包含已经写入的
+    /// 字符数量和估计的剩余数量，便于之后从中断处继续；正常写完时返回
+    /// `None`。等待通过 [`InterruptibleClock`] 完成，因此较长的延迟也会及时
+    /// 结束。取消之前已经读取的字符（如用于判断是否为最后一个单元而预读的
+    /// 字符）仍会写入并计入 `units_written`
+    ///
+    /// # 参数
+    /// - `iter`: 要写入的字符，需要剩余数量时应使用能给出准确长度的迭代器
+    /// - `config`: 慢速扫描配置参数
+    /// - `cancel`: 取消写入的标志位
+    ///
+    /// # 返回值
+    /// - `Ok(None)`: 所有字符成功写入
+    /// - `Ok(Some(Interrupted))`: 写入被取消
+    /// - `Err(io::Error)`: 写入过程中发生 I/O 错误
+    ///
+    /// ---
+    ///
+    /// Write character by character, cancellable midway through a flag
+    ///
+    /// Same as `slow_scan_write_by_chars`, but `cancel` is checked before
+    /// reading every character; once the flag is set no more input is read and
+    /// [`Interrupted`] is returned with the number of characters written and
+    /// the estimated number remaining, so that the write can be resumed later
+    /// from where it stopped; `None` is returned when everything is written.
+    /// Waits go through an [`InterruptibleClock`], so long delays end promptly
+    /// as well. Characters read before the cancellation (such as the one read
+    /// ahead to tell whether a unit is the last) are still written and counted
+    /// in `units_written`
+    ///
+    /// # Arguments
+    /// - `iter`: Characters to write, an iterator reporting its exact length
+    ///   being needed for the remaining count
+    /// - `config`: Slow scan configuration parameters
+    /// - `cancel`: Flag cancelling the write
+    ///
+    /// # Returns
+    /// - `Ok(None)`: All characters written successfully
+    /// - `Ok(Some(Interrupted))`: The write was cancelled
+    /// - `Err(io::Error)`: I/O error occurred during writing
+    fn slow_scan_write_by_chars_cancellable<I>(
+        &mut self,
+        iter: I,
+        config: SlowScanConfig,
+        cancel: &Arc<AtomicBool>
+    ) -> Result<Option<Interrupted>, io::Error>
+    where
+        I: Iterator<Item = char>;
+
+    /// 使用指定的时钟逐个字符写入，可以通过标志位中途取消
+    ///
+    /// 与
+    /// [`slow_scan_write_by_chars_cancellable`](Self::slow_scan_write_by_chars_cancellable)
+    /// 相同，但当前时间的获取和延迟都通过 `clock` 完成；需要及时结束较长的
+    /// 延迟时，传入与 `cancel` 共享标志位的 [`InterruptibleClock`]
+    ///
+    /// # 参数
+    /// - `iter`: 要写入的字符，需要剩余数量时应使用能给出准确长度的迭代器
+    /// - `config`: 慢速扫描配置参数
+    /// - `cancel`: 取消写入的标志位
+    /// - `clock`: 提供当前时间和休眠的时钟
+    ///
+    /// # 返回值
+    /// - `Ok(None)`: 所有字符成功写入
+    /// - `Ok(Some(Interrupted))`: 写入被取消
+    /// - `Err(io::Error)`: 写入过程中发生 I/O 错误
+    ///
+    /// # 示例
+    /// ```
+    /// use std::sync::Arc;
+    /// use std::sync::atomic::{AtomicBool, Ordering};
+    /// use std::time::{Duration, Instant};
+    ///
+    /// use slow_scan_print::{
+    ///     Clock,
+    ///     InterruptibleClock,
+    ///     Interrupted,
+    ///     SlowScanConfig,
+    ///     SlowScanWrite
+    /// };
+    ///
+    /// /// 在虚拟时间到达 `cancel_at` 时取消写入的时钟
+    /// struct CancellingClock {
+    ///     now: Instant,
+    ///     cancel_at: Instant,
+    ///     cancel: Arc<AtomicBool>
+    /// }
+    ///
+    /// impl Clock for CancellingClock {
+    ///     fn now(&mut self) -> Instant { self.now }
+    ///
+    ///     fn sleep_until(&mut self, deadline: Instant) {
+    ///         self.now = self.now.max(deadline);
+    ///         if self.now >= self.cancel_at {
+    ///             self.cancel.store(true, Ordering::Release);
+    ///         }
+    ///     }
+    /// }
+    ///
+    /// let start = Instant::now();
+    /// let ms = Duration::from_millis;
+    /// let cancel = Arc::new(AtomicBool::new(false));
+    /// let mut clock = InterruptibleClock::new(
+    ///     CancellingClock {
+    ///         now: start,
+    ///         cancel_at: start + ms(25),
+    ///         cancel: cancel.clone()
+    ///     },
+    ///     cancel.clone()
+    /// );
+    ///
+    /// let mut config = SlowScanConfig::default();
+    /// config.set_base_delay(ms(10));
+    ///
+    /// let chars = "abcdef".chars().collect::<Vec<_>>();
+    /// let mut writer = Vec::new();
+    /// let interrupted = writer
+    ///     .slow_scan_write_by_chars_cancellable_with_clock(
+    ///         chars.into_iter(),
+    ///         config,
+    ///         &cancel,
+    ///         &mut clock
+    ///     )
+    ///     .unwrap();
+    ///
+    /// // 第三个字符之后的延迟中被取消，已经预读的 `d` 仍会写入
+    /// // Cancelled during the delay after the third character, and the `d`
+    /// // already read ahead is still written
+    /// assert_eq!(writer, b"abcd");
+    /// assert_eq!(
+    ///     interrupted,
+    ///     Some(Interrupted {
+    ///         units_written: 4,
+    ///         units_remaining_estimate: Some(2)
+    ///     })
+    /// );
+    /// ```
+    ///
+    /// ---
+    ///
+    /// Write character by character, cancellable midway through a flag, using
+    /// the given clock
+    ///
+    /// Same as
+    /// [`slow_scan_write_by_chars_cancellable`](Self::slow_scan_write_by_chars_cancellable),
+    /// but the current time is obtained and delays are performed through
+    /// `clock`; pass an [`InterruptibleClock`] sharing its flag with `cancel`
+    /// to end long delays promptly
+    ///
+    /// # Arguments
+    /// - `iter`: Characters to write, an iterator reporting its exact length
+    ///   being needed for the remaining count
+    /// - `config`: Slow scan configuration parameters
+    /// - `cancel`: Flag cancelling the write
+    /// - `clock`: Clock providing the current time and sleeping
+    ///
+    /// # Returns
+    /// - `Ok(None)`: All characters written successfully
+    /// - `Ok(Some(Interrupted))`: The write was cancelled
+    /// - `Err(io::Error)`: I/O error occurred during writing
+    fn slow_scan_write_by_chars_cancellable_with_clock<I, C>(
+        &mut self,
+        iter: I,
+        config: SlowScanConfig,
+        cancel: &Arc<AtomicBool>,
+        clock: &mut C
+    ) -> Result<Option<Interrupted>, io::Error>
+    where
+        I: Iterator<Item = char>,
+        C: Clock + ?Sized;
+
+    /// 逐个字符显示带有独立样式和延迟的文本段
+    ///
+    /// 每段文本以其样式显示，样式的前缀只在与上一段不同时写入，结束时恢复
+    /// 默认样式；设置了 `delay` 的文本段中每个字符之后使用该延迟，否则与
+    /// `slow_scan_write_by_chars` 一样按字符类型决定延迟。最后一个字符之后按
+    /// `tail_delay` 处理。不处理内联指令等逐字符写入的扩展功能
+    ///
+    /// # 参数
+    /// - `iter`: 要显示的文本段
+    /// - `config`: 慢速扫描配置参数
+    ///
+    /// # 返回值
+    /// - `Ok(())`: 所有数据成功写入
+    /// - `Err(io::Error)`: 写入过程中发生 I/O 错误
+    ///
+    /// ---
+    ///
+    /// Reveal pieces of text with their own styles and delays character by
+    /// character
+    ///
+    /// Every piece is shown in its style, the prefix of which is only written
+    /// when it differs from the previous piece, and the default style is
+    /// restored at the end; every character of a piece with a `delay` is
+    /// followed by that delay, otherwise the delay is decided by the character
+    /// class as in `slow_scan_write_by_chars`. The last character is followed
+    /// by `tail_delay`. Extensions of the character-by-character writes such as
+    /// inline directives are not handled
+    ///
+    /// # Arguments
+    /// - `iter`: Pieces of text to reveal
+    /// - `config`: Slow scan configuration parameters
+    ///
+    /// # Returns
+    /// - `Ok(())`: All data written successfully
+    /// - `Err(io::Error)`: I/O error occurred during writing
+    #[cfg(feature = "terminal")]
+    fn slow_scan_rich<I>(
+        &mut self,
+        iter: I,
+        config: SlowScanConfig
+    ) -> Result<(), io::Error>
+    where
+        I: Iterator<Item = RichUnit>;
+
+    /// 使用指定的时钟逐个字符显示带有独立样式和延迟的文本段
+    ///
+    /// 与 [`slow_scan_rich`](Self::slow_scan_rich) 相同，但通过 `clock` 获取
+    /// 当前时间和进行延迟
+    ///
+    /// # 参数
+    /// - `iter`: 要显示的文本段
+    /// - `config`: 慢速扫描配置参数
+    /// - `clock`: 提供当前时间和延迟的时钟
+    ///
+    /// # 返回值
+    /// - `Ok(())`: 所有数据成功写入
+    /// - `Err(io::Error)`: 写入过程中发生 I/O 错误
+    ///
+    /// # 示例
+    /// ```
+    /// use std::time::{Duration, Instant};
+    ///
+    /// use console::Style;
+    /// use slow_scan_print::{RichUnit, SlowScanConfig, SlowScanWrite};
+    /// # struct VirtualClock {
+    /// #     now: std::time::Instant,
+    /// #     deadlines: Vec<std::time::Instant>
+    /// # }
+    /// #
+    /// # impl VirtualClock {
+    /// #     fn new(now: std::time::Instant) -> Self {
+    /// #         Self {
+    /// #             now,
+    /// #             deadlines: Vec::new()
+    /// #         }
+    /// #     }
+    /// # }
+    /// #
+    /// # impl slow_scan_print::Clock for VirtualClock {
+    /// #     fn now(&mut self) -> std::time::Instant { self.now }
+    /// #
+    /// #     fn sleep_until(&mut self, deadline: std::time::Instant) {
+    /// #         self.now = self.now.max(deadline);
+    /// #         self.deadlines.push(deadline);
+    /// #     }
+    /// # }
+    ///
+    /// let start = Instant::now();
+    /// let mut clock = VirtualClock::new(start);
+    ///
+    /// let ms = Duration::from_millis;
+    /// let mut config = SlowScanConfig::default();
+    /// config.set_base_delay(ms(10));
+    ///
+    /// let red = Style::new().red().force_styling(true);
+    /// let bold = Style::new().bold().force_styling(true);
+    /// let units = [
+    ///     RichUnit::new("ab"),
+    ///     RichUnit::new("cd")
+    ///         .with_style(red.clone())
+    ///         .with_delay(ms(50)),
+    ///     RichUnit::new("e").with_style(red),
+    ///     RichUnit::new("f").with_style(bold),
+    ///     RichUnit::new("g")
+    /// ];
+    ///
+    /// let mut writer = Vec::new();
+    /// writer
+    ///     .slow_scan_rich_with_clock(units.into_iter(), config, &mut clock)
+    ///     .unwrap();
+    ///
+    /// // 相同样式的相邻文本段之间不重置样式
+    /// // No reset between adjacent pieces of the same style
+    /// assert_eq!(
+    ///     String::from_utf8(writer).unwrap(),
+    ///     "ab\x1b[31mcde\x1b[0m\x1b[1mf\x1b[0mg"
+    /// );
+    ///
+    /// let delays = clock
+    ///     .deadlines
+    ///     .iter()
+    ///     .scan(start, |last, it| Some(*it - std::mem::replace(last, *it)))
+    ///     .collect::<Vec<_>>();
+    /// assert_eq!(delays, [ms(10), ms(10), ms(50), ms(50), ms(10), ms(10)]);
+    /// ```
+    ///
+    /// ---
+    ///
+    /// Reveal pieces of text with their own styles and delays character by
+    /// character using the given clock
+    ///
+    /// Same as [`slow_scan_rich`](Self::slow_scan_rich), but the current time
+    /// is obtained and delays are performed through `clock`
+    ///
+    /// # Arguments
+    /// - `iter`: Pieces of text to reveal
+    /// - `config`: Slow scan configuration parameters
+    /// - `clock`: Clock providing the current time and sleeping
+    ///
+    /// # Returns
+    /// - `Ok(())`: All data written successfully
+    /// - `Err(io::Error)`: I/O error occurred during writing
+    #[cfg(feature = "terminal")]
+    fn slow_scan_rich_with_clock<I, C>(
+        &mut self,
+        iter: I,
+        config: SlowScanConfig,
+        clock: &mut C
+    ) -> Result<(), io::Error>
+    where
+        I: Iterator<Item = RichUnit>,
+        C: Clock + ?Sized;
+
+    /// 逐个单词写入，每个单词之后延迟一次 `base_delay`
+    ///
+    /// 按 Unicode 空白分隔单词，单词之后的空白（包括连续的多个空格和换行）与
+    /// 单词一起立即写入，开头的空白与第一个单词一起写入。每个单词之后刷新，
+    /// 最后一个单词之后按 `tail_delay` 处理。`head_units` 按单词计数
+    ///
+    /// # 参数
+    /// - `iter`: 要写入的字符
+    /// - `config`: 慢速扫描配置参数
+    ///
+    /// # 返回值
+    /// - `Ok(())`: 所有数据成功写入
+    /// - `Err(io::Error)`: 写入过程中发生 I/O 错误
+    ///
+    /// ---
+    ///
+    /// Write word by word, every word being followed by one `base_delay`
+    ///
+    /// Words are split on Unicode whitespace, the whitespace following a word
+    /// (including runs of spaces and line breaks) is written immediately along
+    /// with it, and leading whitespace is written with the first word. Output
+    /// is flushed after every word, and the last word is followed by
+    /// `tail_delay`. `head_units` counts words
+    ///
+    /// # Arguments
+    /// - `iter`: Characters to write
+    /// - `config`: Slow scan configuration parameters
+    ///
+    /// # Returns
+    /// - `Ok(())`: All data written successfully
+    /// - `Err(io::Error)`: I/O error occurred during writing
+    fn slow_scan_write_by_words<I>(
+        &mut self,
+        iter: I,
+        config: SlowScanConfig
+    ) -> Result<(), io::Error>
+    where
+        I: Iterator<Item = char>;
+
+    /// 使用指定的时钟逐个单词写入
+    ///
+    /// 与 [`slow_scan_write_by_words`](Self::slow_scan_write_by_words) 相同，
+    /// 但通过 `clock` 获取当前时间和进行延迟
+    ///
+    /// # 参数
+    /// - `iter`: 要写入的字符
+    /// - `config`: 慢速扫描配置参数
+    /// - `clock`: 提供当前时间和延迟的时钟
+    ///
+    /// # 返回值
+    /// - `Ok(())`: 所有数据成功写入
+    /// - `Err(io::Error)`: 写入过程中发生 I/O 错误
+    ///
+    /// # 示例
+    /// ```
+    /// use std::io::{self, Write};
+    /// use std::time::{Duration, Instant};
+    ///
+    /// use slow_scan_print::{SlowScanConfig, SlowScanWrite};
+    /// # struct VirtualClock {
+    /// #     now: std::time::Instant,
+    /// #     deadlines: Vec<std::time::Instant>
+    /// # }
+    /// #
+    /// # impl VirtualClock {
+    /// #     fn new(now: std::time::Instant) -> Self {
+    /// #         Self {
+    /// #             now,
+    /// #             deadlines: Vec::new()
+    /// #         }
+    /// #     }
+    /// # }
+    /// #
+    /// # impl slow_scan_print::Clock for VirtualClock {
+    /// #     fn now(&mut self) -> std::time::Instant { self.now }
+    /// #
+    /// #     fn sleep_until(&mut self, deadline: std::time::Instant) {
+    /// #         self.now = self.now.max(deadline);
+    /// #         self.deadlines.push(deadline);
+    /// #     }
+    /// # }
+    ///
+    /// /// 记录每次刷新之前写入的内容
+    /// #[derive(Default)]
+    /// struct Flushes {
+    ///     pending: Vec<u8>,
+    ///     units: Vec<String>
+    /// }
+    ///
+    /// impl Write for Flushes {
+    ///     fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+    ///         self.pending.extend_from_slice(buf);
+    ///         Ok(buf.len())
+    ///     }
+    ///
+    ///     fn flush(&mut self) -> io::Result<()> {
+    ///         if !self.pending.is_empty() {
+    ///             let unit = String::from_utf8(std::mem::take(&mut self.pending));
+    ///             self.units.push(unit.unwrap());
+    ///         }
+    ///         Ok(())
+    ///     }
+    /// }
+    ///
+    /// let start = Instant::now();
+    /// let mut clock = VirtualClock::new(start);
+    ///
+    /// let mut config = SlowScanConfig::default();
+    /// config.set_base_delay(Duration::from_secs(1));
+    ///
+    /// let mut writer = Flushes::default();
+    /// writer
+    ///     .slow_scan_write_by_words_with_clock(
+    ///         "  hello   world\nend".chars(),
+    ///         config,
+    ///         &mut clock
+    ///     )
+    ///     .unwrap();
+    ///
+    /// // 开头的空白随第一个单词写入，单词之后的空白随单词写入
+    /// // Leading whitespace goes with the first word, and whitespace after a
+    /// // word goes with that word
+    /// assert_eq!(writer.units, ["  hello   ", "world\n", "end"]);
+    /// // 没有结尾延迟时最后一个单词之后不等待
+    /// // No wait after the last word without a tail delay
+    /// assert_eq!(clock.now - start, Duration::from_secs(2));
+    /// ```
+    ///
+    /// ---
+    ///
+    /// Write word by word using the given clock
+    ///
+    /// Same as [`slow_scan_write_by_words`](Self::slow_scan_write_by_words),
+    /// but the current time is obtained and delays are performed through
+    /// `clock`
+    ///
+    /// # Arguments
+    /// - `iter`: Characters to write
+    /// - `config`: Slow scan configuration parameters
+    /// - `clock`: Clock providing the current time and sleeping
+    ///
+    /// # Returns
+    /// - `Ok(())`: All data written successfully
+    /// - `Err(io::Error)`: I/O error occurred during writing
+    fn slow_scan_write_by_words_with_clock<I, C>(
+        &mut self,
+        iter: I,
+        config: SlowScanConfig,
+        clock: &mut C
+    ) -> Result<(), io::Error>
+    where
+        I: Iterator<Item = char>,
+        C: Clock + ?Sized;
+
+    /// 使用指定的时钟根据 Unicode 字符宽度和配置进行延迟写入，并在每个字符写入
+    /// 后调用钩子
+    ///
+    /// 与 [`slow_scan_write_by_chars_with_hook`](Self::slow_scan_write_by_chars_with_hook)
+    /// 相同，但通过 `clock` 获取当前时间并执行延迟，可与
+    /// [`TraceClock`](crate::TraceClock) 一起记录每个字符的实际时间
+    ///
+    /// # 参数
+    /// - `iter`: 字符迭代器
+    /// - `config`: 慢速扫描配置参数
+    /// - `on_unit`: 每个字符写入后调用的钩子，参数为写入目标和该字符
+    /// - `clock`: 提供当前时间并执行等待的时钟
+    ///
+    /// # 返回值
+    /// - `Ok(())`: 所有数据成功写入
+    /// - `Err(io::Error)`: 写入过程中或钩子中发生 I/O 错误
+    ///
+    /// # 示例
+    /// ```
+    /// use std::time::Duration;
+    ///
+    /// use slow_scan_print::{
+    ///     SlowScanConfig,
+    ///     SlowScanWrite,
+    ///     SystemClock,
+    ///     TraceClock
+    /// };
+    ///
+    /// let mut config = SlowScanConfig::default();
+    /// config.set_base_delay(Duration::ZERO).set_tail_delay(true);
+    ///
+    /// let mut clock = TraceClock::new(SystemClock);
+    /// let mut writer = Vec::new();
+    /// writer
+    ///     .slow_scan_write_by_chars_with_hook_and_clock(
+    ///         "ab".chars(),
+    ///         config,
+    ///         |w, _| w.write_all(b"|"),
+    ///         &mut clock
+    ///     )
+    ///     .unwrap();
+    ///
+    /// assert_eq!(writer, b"a|b|");
+    /// assert_eq!(clock.rows().len(), 2);
+    /// ```
+    ///
+    /// ---
+    ///
+    /// Write with width-based delays using the given clock, calling a hook
+    /// after each character
+    ///
+    /// Same as
+    /// [`slow_scan_write_by_chars_with_hook`](Self::slow_scan_write_by_chars_with_hook),
+    /// but the current time is obtained and delays are performed through
+    /// `clock`, which can be combined with [`TraceClock`](crate::TraceClock)
+    /// to record the actual timing of every character
+    ///
+    /// # Arguments
+    /// - `iter`: Iterator of characters
+    /// - `config`: Slow scan configuration parameters
+    /// - `on_unit`: Hook called after each character is written, receiving the
+    ///   sink and the character
+    /// - `clock`: Clock providing the current time and sleeping
+    ///
+    /// # Returns
+    /// - `Ok(())`: All data written successfully
+    /// - `Err(io::Error)`: I/O error occurred during writing or in the hook
+    fn slow_scan_write_by_chars_with_hook_and_clock<I, F, C>(
+        &mut self,
+        iter: I,
+        config: SlowScanConfig,
+        on_unit: F,
+        clock: &mut C
+    ) -> Result<(), io::Error>
+    where
+        I: Iterator<Item = char>,
+        F: FnMut(&mut dyn Write, char) -> io::Result<()>,
+        C: Clock + ?Sized;
+}
+
+/// 单次延迟的上限，约 136 年
+///
+/// 极端的倍率会使延迟饱和为 `Duration::MAX`，与当前时间相加时会溢出；限制在
+/// 该上限以内后时间点总能表示
+///
+/// ---
+///
+/// Upper bound of a single delay, about 136 years
+///
+/// Extreme factors saturate delays to `Duration::MAX`, which overflows when
+/// added to the current time; capped at this bound the instant is always
+/// representable
+const MAX_DELAY: Duration = Duration::from_secs(u32::MAX as u64);
+
+/// 按顺序执行延迟的计时器
+///
+/// 启用 `unstable` 特性时会累积目标时间点并使用 `sleep_until`，以避免执行时间
+/// 带来的误差累积
+///
+/// ---
+///
+/// Timer performing delays in sequence
+///
+/// With the `unstable` feature enabled it accumulates the target instant and
+/// uses `sleep_until`, avoiding cumulative errors from execution time
+struct Pacer<'a, C: Clock + ?Sized> {
+    clock: &'a mut C,
+    #[cfg(feature = "unstable")]
+    now: Instant,
+    speed: f64,
+    factor: f64,
+    #[cfg(feature = "jitter")]
+    jitter: f64,
+    #[cfg(feature = "jitter")]
+    rng: Option<XorShift>,
+    round_to: Option<Duration>
+}
+
+impl<'a, C: Clock + ?Sized> Pacer<'a, C> {
+    fn new(clock: &'a mut C) -> Self {
+        Self {
+            #[cfg(feature = "unstable")]
+            now: clock.now(),
+            clock,
+            speed: 1.0,
+            factor: 1.0,
+            #[cfg(feature = "jitter")]
+            jitter: 0.0,
+            #[cfg(feature = "jitter")]
+            rng: None,
+            round_to: None
+        }
+    }
+
+    /// 按配置设置延迟对齐的粒度、速度倍率和抖动比例
+    ///
+    /// ---
+    ///
+    /// Set the granularity delays are snapped to, the speed factor and the
+    /// jitter fraction from the configuration
+    fn with_config(clock: &'a mut C, config: &SlowScanConfig) -> Self {
+        let mut pacer = Self::new(clock).round_to(config.round_to);
+        pacer.factor = config.speed_factor;
+        #[cfg(feature = "jitter")]
+        {
+            pacer.jitter = config.jitter;
+        }
+        pacer
+    }
+
+    /// 设置延迟对齐的粒度，见 [`SlowScanConfig::round_to`]
+    ///
+    /// ---
+    ///
+    /// Set the granularity delays are snapped to, see
+    /// [`SlowScanConfig::round_to`]
+    fn round_to(mut self, round_to: Option<Duration>) -> Self {
+        self.round_to = round_to;
+        self
+    }
+
+    /// 延迟指定时间，时间会先按当前倍率和配置的速度倍率缩放并随机抖动，再
+    /// 对齐到设置的粒度
+    ///
+    /// ---
+    ///
+    /// Delay for the given time, scaled by the current speed factor and the
+    /// configured speed factor and jittered first, then snapped to the
+    /// configured granularity
+    fn sleep(&mut self, delay: Duration) {
+        let delay = if self.speed == 1.0 {
+            delay
+        } else {
+            Duration::try_from_secs_f64(delay.as_secs_f64() / self.speed)
+                .unwrap_or(Duration::MAX)
+        };
+        let delay = match self.factor {
+            1.0 => delay,
+            it if it > 0.0 => {
+                Duration::try_from_secs_f64(delay.as_secs_f64() / it)
+                    .unwrap_or(Duration::MAX)
+            }
+            _ => Duration::ZERO
+        };
+        // 不抖动时不生成随机数，保证节奏与不启用时完全相同
+        #[cfg(feature = "jitter")]
+        let delay = if self.jitter > 0.0 {
+            self.rng
+                .get_or_insert_with(XorShift::from_time)
+                .jitter(delay, self.jitter)
+        } else {
+            delay
+        };
+        let delay = match self.round_to.filter(|it| !it.is_zero()) {
+            Some(step) => round_to_multiple(delay, step),
+            None => delay
+        }
+        .min(MAX_DELAY);
+
+        #[cfg(not(feature = "unstable"))]
+        let deadline = self.clock.now() + delay;
+        #[cfg(feature = "unstable")]
+        let deadline = {
+            self.now += delay;
+            self.now
+        };
+
+        self.clock.sleep_until(deadline);
+    }
+}
+
+/// 将时间乘以 `factor`，超出范围时饱和为 `Duration::MAX`，结果为负数或 NaN
+/// 时返回零
+///
+/// ---
+///
+/// Multiply a time by `factor`, saturating to `Duration::MAX` when out of
+/// range and returning zero for a negative or NaN result
+fn scale(delay: Duration, factor: f64) -> Duration {
+    let secs = delay.as_secs_f64() * factor;
+
+    match Duration::try_from_secs_f64(secs) {
+        Ok(it) => it,
+        Err(_) if secs > 0.0 => Duration::MAX,
+        Err(_) => Duration::ZERO
+    }
+}
+
+/// 将时间四舍五入到 `step` 的整数倍
+///
+/// ---
+///
+/// Round a time to the nearest multiple of `step`
+fn round_to_multiple(delay: Duration, step: Duration) -> Duration {
+    let step = step.as_nanos();
+    let steps = (delay.as_nanos() + step / 2) / step;
+
+    Duration::from_nanos(u64::try_from(steps * step).unwrap_or(u64::MAX))
+}
+
+/// 在写入目标的同时记录已写入字节的写入器
+///
+/// ---
+///
+/// Writer recording the written bytes alongside the sink
+struct Tee<'a, W: Write + ?Sized> {
+    inner: &'a mut W,
+    captured: Vec<u8>
+}
+
+impl<W: Write + ?Sized> Write for Tee<'_, W> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        let len = self.inner.write(buf)?;
+        self.captured.extend_from_slice(&buf[..len]);
+        Ok(len)
+    }
+
+    fn flush(&mut self) -> io::Result<()> { self.inner.flush() }
 }
 
-impl<W: Write> SlowScanWrite for W {
-    fn slow_scan_write_by_chunks<I>(
+impl<W: Write> SlowScanWrite for W {
+    fn slow_scan_write_by_chunks<I>(
+        &mut self,
+        iter: I,
+        config: SlowScanConfig
+    ) -> Result<(), io::Error>
+    where
+        I: Iterator,
+        I::Item: AsRef<[u8]>
+    {
+        self.slow_scan_write_by_chunks_with_clock(
+            iter,
+            config,
+            &mut SystemClock
+        )
+    }
+
+    fn slow_scan_write_by_chars<I>(
+        &mut self,
+        iter: I,
+        config: SlowScanConfig
+    ) -> Result<(), io::Error>
+    where
+        I: Iterator<Item = char>
+    {
+        self.slow_scan_write_by_chars_with_clock(iter, config, &mut SystemClock)
+    }
+
+    fn slow_scan_write_by_chunks_with_clock<I, C>(
+        &mut self,
+        iter: I,
+        config: SlowScanConfig,
+        clock: &mut C
+    ) -> Result<(), io::Error>
+    where
+        I: Iterator,
+        I::Item: AsRef<[u8]>,
+        C: Clock + ?Sized
+    {
+        let writer = &mut Retry::new(self, config.io_retries);
+        let mut iter = iter
+            .take(config.head_units.unwrap_or(usize::MAX))
+            .peekable();
+        let mut pacer = Pacer::with_config(clock, &config);
+
+        while let Some(it) = iter.next() {
+            writer.write_all(it.as_ref())?;
+            if config.flush_each_unit {
+                writer.flush()?;
+            }
+
+            let delay = config.chunk_delay(it.as_ref());
+            if let Some(it) = config.pause_after(delay, iter.peek().is_some()) {
+                pacer.sleep(it);
+            }
+        }
+
+        writer.flush()
+    }
+
+    fn slow_scan_write_by_chars_with_clock<I, C>(
+        &mut self,
+        iter: I,
+        config: SlowScanConfig,
+        clock: &mut C
+    ) -> Result<(), io::Error>
+    where
+        I: Iterator<Item = char>,
+        C: Clock + ?Sized
+    {
+        write_by_chars(self, iter, config, clock, |_, _| Ok(()), |_| {})
+    }
+
+    fn slow_scan_write_by_chars_with_hook<I, F>(
+        &mut self,
+        iter: I,
+        config: SlowScanConfig,
+        on_unit: F
+    ) -> Result<(), io::Error>
+    where
+        I: Iterator<Item = char>,
+        F: FnMut(&mut dyn Write, char) -> io::Result<()>
+    {
+        write_by_chars(self, iter, config, &mut SystemClock, on_unit, |_| {})
+    }
+
+    fn slow_scan_write_by_chars_with_delay_hook<I, F>(
+        &mut self,
+        iter: I,
+        config: SlowScanConfig,
+        on_delay_start: F
+    ) -> Result<(), io::Error>
+    where
+        I: Iterator<Item = char>,
+        F: FnMut(char)
+    {
+        write_by_chars(
+            self,
+            iter,
+            config,
+            &mut SystemClock,
+            |_, _| Ok(()),
+            on_delay_start
+        )
+    }
+
+    fn slow_scan_write_timed(
+        &mut self,
+        content: &[u8],
+        timing: &[(Duration, usize)]
+    ) -> Result<(), io::Error> {
+        self.slow_scan_write_timed_with_clock(content, timing, &mut SystemClock)
+    }
+
+    fn slow_scan_write_timed_with_clock<C>(
+        &mut self,
+        content: &[u8],
+        timing: &[(Duration, usize)],
+        clock: &mut C
+    ) -> Result<(), io::Error>
+    where
+        C: Clock + ?Sized
+    {
+        let total = timing.iter().map(|(_, it)| it).sum::<usize>();
+        if total != content.len() {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                format!(
+                    "timing entries cover {} bytes but the content has {} bytes",
+                    total,
+                    content.len()
+                )
+            ));
+        }
+
+        let mut rest = content;
+        let mut pacer = Pacer::new(clock);
+
+        for (delay, len) in timing {
+            let (it, tail) = rest.split_at(*len);
+            rest = tail;
+
+            pacer.sleep(*delay);
+            self.write_all(it)?;
+            self.flush()?;
+        }
+
+        self.flush()
+    }
+
+    fn slow_scan_write_by_chunks_capturing<I>(
+        &mut self,
+        iter: I,
+        config: SlowScanConfig
+    ) -> (Result<(), io::Error>, Vec<u8>)
+    where
+        I: Iterator,
+        I::Item: AsRef<[u8]>
+    {
+        let mut tee = Tee {
+            inner: self,
+            captured: Vec::new()
+        };
+        let result = tee.slow_scan_write_by_chunks(iter, config);
+
+        (result, tee.captured)
+    }
+
+    fn slow_scan_write_by_chars_capturing<I>(
+        &mut self,
+        iter: I,
+        config: SlowScanConfig
+    ) -> (Result<(), io::Error>, Vec<u8>)
+    where
+        I: Iterator<Item = char>
+    {
+        let mut tee = Tee {
+            inner: self,
+            captured: Vec::new()
+        };
+        let result = tee.slow_scan_write_by_chars(iter, config);
+
+        (result, tee.captured)
+    }
+
+    fn slow_scan_write_redacted(
+        &mut self,
+        line: &str,
+        spans: &[Range<usize>],
+        hold: Duration,
+        config: SlowScanConfig
+    ) -> Result<(), io::Error> {
+        self.slow_scan_write_redacted_with_clock(
+            line,
+            spans,
+            hold,
+            config,
+            &mut SystemClock
+        )
+    }
+
+    fn slow_scan_write_redacted_with_clock<C>(
+        &mut self,
+        line: &str,
+        spans: &[Range<usize>],
+        hold: Duration,
+        config: SlowScanConfig,
+        clock: &mut C
+    ) -> Result<(), io::Error>
+    where
+        C: Clock + ?Sized
+    {
+        let mut line_config = config;
+        line_config.tail_delay = TailBehavior::None;
+        self.slow_scan_write_by_chars_with_clock(
+            line.chars(),
+            line_config,
+            clock
+        )?;
+
+        if spans.is_empty() {
+            return Ok(());
+        }
+
+        Pacer::with_config(clock, &config).sleep(hold);
+
+        self.write_all(b"\r")?;
+        self.write_all(config.mask_spans(line, spans).as_bytes())?;
+        self.flush()
+    }
+
+    fn slow_scan_table<S>(
+        &mut self,
+        rows: &[Vec<S>],
+        config: SlowScanConfig
+    ) -> Result<(), io::Error>
+    where
+        S: AsRef<str>
+    {
+        let mut widths = Vec::<usize>::new();
+
+        for row in rows {
+            for (i, cell) in row.iter().enumerate() {
+                let width = config.str_width(cell.as_ref());
+
+                match widths.get_mut(i) {
+                    Some(it) => *it = (*it).max(width),
+                    None => widths.push(width)
+                }
+            }
+        }
+
+        let cell_count = rows.iter().map(Vec::len).sum::<usize>();
+        let mut revealed = 0;
+
+        for row in rows {
+            for (i, cell) in row.iter().enumerate() {
+                let cell = cell.as_ref();
+
+                if i > 0 {
+                    self.write_all(b"  ")?;
+                }
+
+                revealed += 1;
+
+                let mut cell_config = config;
+                if revealed < cell_count {
+                    cell_config.tail_delay = TailBehavior::LastUnit;
+                }
+                self.slow_scan_write_by_chars(cell.chars(), cell_config)?;
+
+                if i + 1 < row.len() {
+                    let padding = widths[i] - config.str_width(cell);
+                    self.write_all(" ".repeat(padding).as_bytes())?;
+                }
+            }
+
+            self.write_all(b"\n")?;
+            self.flush()?;
+        }
+
+        Ok(())
+    }
+
+    fn slow_scan_number(
+        &mut self,
+        value: &str,
+        config: SlowScanConfig
+    ) -> Result<(), io::Error> {
+        let frame_delay = config.base_delay / 10;
+        let mut chars = value.chars().peekable();
+        let mut buf = [0; 4];
+        let mut clock = SystemClock;
+        let mut pacer = Pacer::with_config(&mut clock, &config);
+
+        while let Some(it) = chars.next() {
+            for frame in 0..it.to_digit(10).unwrap_or(0) {
+                write!(self, "{}\x08", frame)?;
+                self.flush()?;
+                pacer.sleep(frame_delay);
+            }
+
+            self.write_all(it.encode_utf8(&mut buf).as_ref())?;
+            self.flush()?;
+
+            let delay = config.char_delay(it);
+            if let Some(it) = config.pause_after(delay, chars.peek().is_some())
+            {
+                pacer.sleep(it);
+            }
+        }
+
+        Ok(())
+    }
+
+    fn slow_scan_write_converge(
+        &mut self,
+        s: &str,
+        config: SlowScanConfig
+    ) -> Result<(), io::Error> {
+        // 每个字符及其起始列
+        let mut column = 0;
+        let chars = s
+            .chars()
+            .map(|it| {
+                let start = column;
+                column += config.char_width(it).unwrap_or(0);
+                (it, start)
+            })
+            .collect::<Vec<_>>();
+        let end = column;
+
+        let mut cursor = 0;
+        let mut buf = [0; 4];
+        let mut clock = SystemClock;
+        let mut pacer = Pacer::with_config(&mut clock, &config);
+        let (mut head, mut tail) = (0, chars.len());
+
+        while head < tail {
+            tail -= 1;
+            let pair = if head == tail {
+                vec![chars[head]]
+            } else {
+                vec![chars[head], chars[tail]]
+            };
+            head += 1;
+
+            for (it, start) in &pair {
+                move_cursor(self, cursor, *start)?;
+                self.write_all(it.encode_utf8(&mut buf).as_ref())?;
+                cursor = start + config.char_width(*it).unwrap_or(0);
+            }
+            self.flush()?;
+
+            let delay = pair.iter().map(|(it, _)| config.char_delay(*it));
+            let delay = delay.max().unwrap_or_default();
+            if let Some(it) = config.pause_after(delay, head < tail) {
+                pacer.sleep(it);
+            }
+        }
+
+        move_cursor(self, cursor, end)?;
+        self.flush()
+    }
+
+    fn slow_scan_write_interleaved<S, I>(
+        &mut self,
+        streams: S,
+        config: SlowScanConfig
+    ) -> Result<(), io::Error>
+    where
+        S: IntoIterator<Item = (I, Region)>,
+        I: Iterator<Item = char>
+    {
+        write_interleaved(self, streams, config, &mut SystemClock)
+    }
+
+    fn slow_scan_write_interleaved_with_clock<S, I, C>(
+        &mut self,
+        streams: S,
+        config: SlowScanConfig,
+        clock: &mut C
+    ) -> Result<(), io::Error>
+    where
+        S: IntoIterator<Item = (I, Region)>,
+        I: Iterator<Item = char>,
+        C: Clock + ?Sized
+    {
+        write_interleaved(self, streams, config, clock)
+    }
+
+    fn slow_scan_write_frames(
+        &mut self,
+        s: &str,
+        frames: usize,
+        frame_time: Duration
+    ) -> Result<(), io::Error> {
+        self.slow_scan_write_frames_with_clock(
+            s,
+            frames,
+            frame_time,
+            &mut SystemClock
+        )
+    }
+
+    fn slow_scan_write_frames_with_clock<C>(
+        &mut self,
+        s: &str,
+        frames: usize,
+        frame_time: Duration,
+        clock: &mut C
+    ) -> Result<(), io::Error>
+    where
+        C: Clock + ?Sized
+    {
+        if frames == 0 {
+            self.write_all(s.as_bytes())?;
+            return self.flush();
+        }
+
+        // 每个字符的起始字节位置，末尾追加字符串长度作为结束位置
+        let bounds = s
+            .char_indices()
+            .map(|(i, _)| i)
+            .chain([s.len()])
+            .collect::<Vec<_>>();
+        let units = bounds.len() - 1;
+        let mut pacer = Pacer::new(clock);
+
+        for i in 0..frames {
+            let start = bounds[i * units / frames];
+            let end = bounds[(i + 1) * units / frames];
+
+            self.write_all(&s.as_bytes()[start..end])?;
+            self.flush()?;
+            pacer.sleep(frame_time);
+        }
+
+        Ok(())
+    }
+
+    #[cfg(feature = "graphemes")]
+    fn slow_scan_write_building_graphemes(
+        &mut self,
+        s: &str,
+        config: SlowScanConfig
+    ) -> Result<(), io::Error> {
+        self.slow_scan_write_building_graphemes_with_clock(
+            s,
+            config,
+            &mut SystemClock
+        )
+    }
+
+    #[cfg(feature = "graphemes")]
+    fn slow_scan_write_building_graphemes_with_clock<C>(
+        &mut self,
+        s: &str,
+        config: SlowScanConfig,
+        clock: &mut C
+    ) -> Result<(), io::Error>
+    where
+        C: Clock + ?Sized
+    {
+        let mut graphemes = s.graphemes(true).peekable();
+        let mut pacer = Pacer::with_config(clock, &config);
+
+        while let Some(it) = graphemes.next() {
+            for c in it.chars() {
+                self.write_all(c.encode_utf8(&mut [0; 4]).as_ref())?;
+                if config.flush_each_unit {
+                    self.flush()?;
+                }
+            }
+
+            let first = it.chars().next().unwrap_or_default();
+            let delay = config.char_delay(first);
+            let more = graphemes.peek().is_some();
+            if let Some(it) = config.pause_after(delay, more) {
+                pacer.sleep(it);
+            }
+        }
+
+        self.flush()
+    }
+
+    fn slow_scan_progress(
+        &mut self,
+        width: usize,
+        config: SlowScanConfig
+    ) -> Result<(), io::Error> {
+        self.slow_scan_progress_with_clock(width, config, &mut SystemClock)
+    }
+
+    fn slow_scan_progress_with_clock<C>(
+        &mut self,
+        width: usize,
+        config: SlowScanConfig,
+        clock: &mut C
+    ) -> Result<(), io::Error>
+    where
+        C: Clock + ?Sized
+    {
+        let mut pacer = Pacer::with_config(clock, &config);
+
+        for filled in 0..=width {
+            // 第一帧是空进度条，之后的每一格都在等待之后填充
+            if filled > 0 {
+                pacer.sleep(config.base_delay);
+            }
+
+            write!(
+                self,
+                "\r[{}{}]",
+                "#".repeat(filled),
+                " ".repeat(width - filled)
+            )?;
+            self.flush()?;
+        }
+
+        Ok(())
+    }
+
+    fn slow_scan_write_then_erase(
+        &mut self,
+        s: &str,
+        hold: Duration,
+        config: SlowScanConfig
+    ) -> Result<(), io::Error> {
+        self.slow_scan_write_then_erase_with_clock(
+            s,
+            hold,
+            config,
+            &mut SystemClock
+        )
+    }
+
+    fn slow_scan_write_then_erase_with_clock<C>(
+        &mut self,
+        s: &str,
+        hold: Duration,
+        config: SlowScanConfig,
+        clock: &mut C
+    ) -> Result<(), io::Error>
+    where
+        C: Clock + ?Sized
+    {
+        let mut reveal = config;
+        reveal.tail_delay = TailBehavior::None;
+        self.slow_scan_write_by_chars_with_clock(s.chars(), reveal, clock)?;
+
+        let mut pacer = Pacer::with_config(clock, &config);
+        pacer.sleep(hold);
+
+        // 控制字符不占列，无需擦除
+        let mut chars = s
+            .chars()
+            .rev()
+            .map(|it| config.char_width(it).unwrap_or(0))
+            .filter(|it| *it > 0)
+            .peekable();
+
+        while let Some(width) = chars.next() {
+            self.write_all("\x08 \x08".repeat(width).as_bytes())?;
+            self.flush()?;
+
+            let more = chars.peek().is_some();
+            if let Some(it) =
+                config.pause_after(config.control_char_delay, more)
+            {
+                pacer.sleep(it);
+            }
+        }
+
+        Ok(())
+    }
+
+    #[cfg(feature = "markdown")]
+    fn slow_scan_write_markdown(
+        &mut self,
+        s: &str,
+        config: SlowScanConfig
+    ) -> Result<(), io::Error> {
+        self.slow_scan_write_markdown_with_clock(s, config, &mut SystemClock)
+    }
+
+    #[cfg(feature = "markdown")]
+    fn slow_scan_write_markdown_with_clock<C>(
+        &mut self,
+        s: &str,
+        config: SlowScanConfig,
+        clock: &mut C
+    ) -> Result<(), io::Error>
+    where
+        C: Clock + ?Sized
+    {
+        write_markdown(self, s, config, clock)
+    }
+
+    fn slow_scan_write_scheduled(
+        &mut self,
+        s: &str,
+        schedule: &HashMap<usize, Duration>,
+        config: SlowScanConfig
+    ) -> Result<(), io::Error> {
+        self.slow_scan_write_scheduled_with_clock(
+            s,
+            schedule,
+            config,
+            &mut SystemClock
+        )
+    }
+
+    fn slow_scan_write_scheduled_with_clock<C>(
+        &mut self,
+        s: &str,
+        schedule: &HashMap<usize, Duration>,
+        config: SlowScanConfig,
+        clock: &mut C
+    ) -> Result<(), io::Error>
+    where
+        C: Clock + ?Sized
+    {
+        let mut lines = s.split_inclusive('\n').enumerate().peekable();
+
+        while let Some((i, line)) = lines.next() {
+            let mut pacing = config;
+            if let Some(it) = schedule.get(&i) {
+                pacing.base_delay = *it;
+                pacing.full_width_delay = *it * 2;
+            }
+            // 后面还有行时最后一个字符之后照常延迟
+            if lines.peek().is_some() {
+                pacing.tail_delay = TailBehavior::LastUnit;
+            }
+
+            write_by_chars(
+                self,
+                line.chars(),
+                pacing,
+                clock,
+                |_, _| Ok(()),
+                |_| {}
+            )?;
+        }
+
+        Ok(())
+    }
+
+    fn slow_scan_write_by_chunks_with_barriers<I, T, F>(
         &mut self,
         iter: I,
-        config: SlowScanConfig
+        config: SlowScanConfig,
+        mut on_barrier: F
     ) -> Result<(), io::Error>
     where
-        I: Iterator,
-        I::Item: AsRef<[u8]>
+        I: Iterator<Item = ChunkUnit<T>>,
+        T: AsRef<[u8]>,
+        F: FnMut(&mut dyn Write) -> io::Result<()>
     {
+        let writer = &mut Retry::new(self, config.io_retries);
+        let mut remaining = config.head_units.unwrap_or(usize::MAX);
         let mut iter = iter.peekable();
-        #[cfg(feature = "unstable")]
-        let mut now = Instant::now();
+        let mut clock = SystemClock;
+        let mut pacer = Pacer::with_config(&mut clock, &config);
 
         while let Some(it) = iter.next() {
-            self.write_all(it.as_ref())?;
-            self.flush()?;
+            match it {
+                ChunkUnit::Data(_) if remaining == 0 => break,
+                ChunkUnit::Data(it) => {
+                    remaining -= 1;
+                    writer.write_all(it.as_ref())?;
+                    if config.flush_each_unit {
+                        writer.flush()?;
+                    }
 
-            if iter.peek().is_some() || config.tail_delay {
-                #[cfg(not(feature = "unstable"))]
-                sleep(config.base_delay);
-                #[cfg(feature = "unstable")]
-                {
-                    now += config.base_delay;
-                    sleep_until(now);
+                    let more = remaining > 0 && iter.peek().is_some();
+                    let delay = config.chunk_delay(it.as_ref());
+                    if let Some(it) = config.pause_after(delay, more) {
+                        pacer.sleep(it);
+                    }
+                }
+                ChunkUnit::Barrier => {
+                    writer.flush()?;
+                    on_barrier(writer)?;
                 }
             }
         }
 
-        Ok(())
+        writer.flush()
     }
 
-    fn slow_scan_write_by_chars<I>(
+    fn slow_scan_write_chars_timed<I>(
         &mut self,
         iter: I,
         config: SlowScanConfig
     ) -> Result<(), io::Error>
     where
-        I: Iterator<Item = char>
+        I: Iterator<Item = (char, Duration)>
+    {
+        self.slow_scan_write_chars_timed_with_clock(
+            iter,
+            config,
+            &mut SystemClock
+        )
+    }
+
+    fn slow_scan_write_chars_timed_with_clock<I, C>(
+        &mut self,
+        iter: I,
+        config: SlowScanConfig,
+        clock: &mut C
+    ) -> Result<(), io::Error>
+    where
+        I: Iterator<Item = (char, Duration)>,
+        C: Clock + ?Sized
     {
+        let writer = &mut Retry::new(self, config.io_retries);
         let mut iter = iter.peekable();
+        let mut pacer = Pacer::with_config(clock, &config);
         let mut buf = [0; 4];
-        #[cfg(feature = "unstable")]
-        let mut now = Instant::now();
 
-        while let Some(it) = iter.next() {
-            self.write_all(it.encode_utf8(&mut buf).as_ref())?;
-            self.flush()?;
+        while let Some((it, delay)) = iter.next() {
+            writer.write_all(it.encode_utf8(&mut buf).as_bytes())?;
+            if config.flush_each_unit {
+                writer.flush()?;
+            }
+
+            if let Some(it) = config.pause_after(delay, iter.peek().is_some()) {
+                pacer.sleep(it);
+            }
+        }
+
+        writer.flush()
+    }
+
+    fn slow_scan_write_script(
+        &mut self,
+        segments: &[ScriptSegment],
+        config: SlowScanConfig
+    ) -> Result<(), io::Error> {
+        self.slow_scan_write_script_with_clock(
+            segments,
+            config,
+            &mut SystemClock
+        )
+    }
+
+    fn slow_scan_write_script_with_clock<C>(
+        &mut self,
+        segments: &[ScriptSegment],
+        config: SlowScanConfig,
+        clock: &mut C
+    ) -> Result<(), io::Error>
+    where
+        C: Clock + ?Sized
+    {
+        let mut segments = segments.iter().peekable();
+
+        while let Some(it) = segments.next() {
+            let mut pacing = config;
+            if let Some(delay) = it.delay {
+                pacing.base_delay = delay;
+                pacing.full_width_delay = delay * 2;
+            }
+            // 后面还有段落时最后一个字符之后照常延迟
+            if segments.peek().is_some() {
+                pacing.tail_delay = TailBehavior::LastUnit;
+            }
+
+            if !it.sgr.is_empty() {
+                write!(self, "\x1b[{}m", it.sgr)?;
+            }
+            write_by_chars(
+                self,
+                it.text.chars(),
+                pacing,
+                clock,
+                |_, _| Ok(()),
+                |_| {}
+            )?;
+            if !it.sgr.is_empty() {
+                self.write_all(b"\x1b[0m")?;
+                self.flush()?;
+            }
+        }
+
+        Ok(())
+    }
+
+    fn slow_scan_write_equal_lines(
+        &mut self,
+        s: &str,
+        line_time: Duration,
+        gap: Duration,
+        config: SlowScanConfig
+    ) -> Result<(), io::Error> {
+        self.slow_scan_write_equal_lines_with_clock(
+            s,
+            line_time,
+            gap,
+            config,
+            &mut SystemClock
+        )
+    }
+
+    fn slow_scan_write_equal_lines_with_clock<C>(
+        &mut self,
+        s: &str,
+        line_time: Duration,
+        gap: Duration,
+        config: SlowScanConfig,
+        clock: &mut C
+    ) -> Result<(), io::Error>
+    where
+        C: Clock + ?Sized
+    {
+        let reveal = line_time.saturating_sub(gap);
+
+        let timed = s.split_inclusive('\n').flat_map(|line| {
+            let (body, newline) = match line.strip_suffix('\n') {
+                Some(it) => (it, Some('\n')),
+                None => (line, None)
+            };
+
+            let total = body.chars().map(|it| config.char_delay(it)).sum();
+            let factor = match total {
+                Duration::ZERO => 0.0,
+                it => reveal.as_secs_f64() / it.as_secs_f64()
+            };
+            let gap = if factor == 0.0 { line_time } else { gap };
+
+            body.chars()
+                .map(move |it| (it, config.char_delay(it).mul_f64(factor)))
+                .chain(newline.map(|it| (it, gap)))
+        });
+
+        self.slow_scan_write_chars_timed_with_clock(timed, config, clock)
+    }
+
+    fn slow_scan_write_on_ticks<I, T>(
+        &mut self,
+        iter: I,
+        ticks: T,
+        config: SlowScanConfig
+    ) -> Result<(), io::Error>
+    where
+        I: Iterator<Item = char>,
+        T: Iterator<Item = Instant>
+    {
+        self.slow_scan_write_on_ticks_with_clock(
+            iter,
+            ticks,
+            config,
+            &mut SystemClock
+        )
+    }
+
+    fn slow_scan_write_on_ticks_with_clock<I, T, C>(
+        &mut self,
+        iter: I,
+        mut ticks: T,
+        config: SlowScanConfig,
+        clock: &mut C
+    ) -> Result<(), io::Error>
+    where
+        I: Iterator<Item = char>,
+        T: Iterator<Item = Instant>,
+        C: Clock + ?Sized
+    {
+        let writer = &mut Retry::new(self, config.io_retries);
+        let mut last = None;
+        let mut buf = [0; 4];
+
+        for it in iter {
+            // 节拍用完后从上一个字符的时间点起按 `base_delay` 继续
+            let due = ticks
+                .next()
+                .or_else(|| last.map(|it| it + config.base_delay));
+            if let Some(due) = due {
+                clock.sleep_until(due);
+            }
+            last = Some(due.unwrap_or_else(|| clock.now()));
+
+            writer.write_all(it.encode_utf8(&mut buf).as_bytes())?;
+            if config.flush_each_unit {
+                writer.flush()?;
+            }
+        }
+
+        writer.flush()
+    }
+
+    fn slow_scan_erase(
+        &mut self,
+        char_count: usize,
+        config: SlowScanConfig
+    ) -> Result<(), io::Error> {
+        self.slow_scan_erase_with_clock(char_count, config, &mut SystemClock)
+    }
+
+    fn slow_scan_erase_with_clock<C>(
+        &mut self,
+        char_count: usize,
+        config: SlowScanConfig,
+        clock: &mut C
+    ) -> Result<(), io::Error>
+    where
+        C: Clock + ?Sized
+    {
+        let writer = &mut Retry::new(self, config.io_retries);
+        let mut pacer = Pacer::with_config(clock, &config);
+
+        for i in 1..=char_count {
+            writer.write_all(b"\x08 \x08")?;
+            writer.flush()?;
+
+            let more = i < char_count;
+            if let Some(it) =
+                config.pause_after(config.control_char_delay, more)
+            {
+                pacer.sleep(it);
+            }
+        }
+
+        Ok(())
+    }
+
+    fn slow_scan_write_trail<I>(
+        &mut self,
+        iter: I,
+        trail: usize,
+        config: SlowScanConfig
+    ) -> Result<(), io::Error>
+    where
+        I: Iterator<Item = char>
+    {
+        self.slow_scan_write_trail_with_clock(
+            iter,
+            trail,
+            config,
+            &mut SystemClock
+        )
+    }
+
+    fn slow_scan_write_trail_with_clock<I, C>(
+        &mut self,
+        iter: I,
+        trail: usize,
+        config: SlowScanConfig,
+        clock: &mut C
+    ) -> Result<(), io::Error>
+    where
+        I: Iterator<Item = char>,
+        C: Clock + ?Sized
+    {
+        if trail == 0 {
+            return self
+                .slow_scan_write_by_chars_with_clock(iter, config, clock);
+        }
+
+        let writer = &mut Retry::new(self, config.io_retries);
+        write_trail(writer, iter, trail, config, clock)
+    }
+
+    fn slow_scan_write_by_chars_cancellable<I>(
+        &mut self,
+        iter: I,
+        config: SlowScanConfig,
+        cancel: &Arc<AtomicBool>
+    ) -> Result<Option<Interrupted>, io::Error>
+    where
+        I: Iterator<Item = char>
+    {
+        let mut clock = InterruptibleClock::new(SystemClock, cancel.clone());
+        self.slow_scan_write_by_chars_cancellable_with_clock(
+            iter, config, cancel, &mut clock
+        )
+    }
+
+    fn slow_scan_write_by_chars_cancellable_with_clock<I, C>(
+        &mut self,
+        mut iter: I,
+        config: SlowScanConfig,
+        cancel: &Arc<AtomicBool>,
+        clock: &mut C
+    ) -> Result<Option<Interrupted>, io::Error>
+    where
+        I: Iterator<Item = char>,
+        C: Clock + ?Sized
+    {
+        let mut cancelled = false;
+        let mut units_written = 0;
+        // 每读取一个字符之前检查标志位，取消后不再从输入中读取
+        let source = std::iter::from_fn(|| {
+            cancelled = cancel.load(atomic::Ordering::Acquire);
+            if cancelled { None } else { iter.next() }
+        });
+
+        write_by_chars(
+            self,
+            source,
+            config,
+            clock,
+            |_, _| {
+                units_written += 1;
+                Ok(())
+            },
+            |_| {}
+        )?;
+
+        if !cancelled {
+            return Ok(None);
+        }
+
+        let units_remaining_estimate = match iter.size_hint() {
+            (lower, Some(upper)) if lower == upper => Some(lower),
+            _ => None
+        };
+
+        Ok(Some(Interrupted {
+            units_written,
+            units_remaining_estimate
+        }))
+    }
+
+    #[cfg(feature = "terminal")]
+    fn slow_scan_rich<I>(
+        &mut self,
+        iter: I,
+        config: SlowScanConfig
+    ) -> Result<(), io::Error>
+    where
+        I: Iterator<Item = RichUnit>
+    {
+        self.slow_scan_rich_with_clock(iter, config, &mut SystemClock)
+    }
+
+    #[cfg(feature = "terminal")]
+    fn slow_scan_rich_with_clock<I, C>(
+        &mut self,
+        iter: I,
+        config: SlowScanConfig,
+        clock: &mut C
+    ) -> Result<(), io::Error>
+    where
+        I: Iterator<Item = RichUnit>,
+        C: Clock + ?Sized
+    {
+        let writer = &mut Retry::new(self, config.io_retries);
+        write_rich(writer, iter, config, clock)
+    }
+
+    fn slow_scan_write_by_words<I>(
+        &mut self,
+        iter: I,
+        config: SlowScanConfig
+    ) -> Result<(), io::Error>
+    where
+        I: Iterator<Item = char>
+    {
+        self.slow_scan_write_by_words_with_clock(iter, config, &mut SystemClock)
+    }
+
+    fn slow_scan_write_by_words_with_clock<I, C>(
+        &mut self,
+        iter: I,
+        config: SlowScanConfig,
+        clock: &mut C
+    ) -> Result<(), io::Error>
+    where
+        I: Iterator<Item = char>,
+        C: Clock + ?Sized
+    {
+        let writer = &mut Retry::new(self, config.io_retries);
+        write_by_words(writer, iter, config, clock)
+    }
+
+    fn slow_scan_write_by_chars_with_hook_and_clock<I, F, C>(
+        &mut self,
+        iter: I,
+        config: SlowScanConfig,
+        on_unit: F,
+        clock: &mut C
+    ) -> Result<(), io::Error>
+    where
+        I: Iterator<Item = char>,
+        F: FnMut(&mut dyn Write, char) -> io::Result<()>,
+        C: Clock + ?Sized
+    {
+        write_by_chars(self, iter, config, clock, on_unit, |_| {})
+    }
+}
+
+/// 逐字符写入的实现，每个字符写入后调用 `on_unit`，每次延迟开始前调用
+/// `on_delay_start`
+///
+/// ---
+///
+/// Implementation of writing character by character, calling `on_unit` after
+/// each character is written and `on_delay_start` before each delay starts
+fn write_by_chars<W, I, C, F, D>(
+    writer: &mut W,
+    iter: I,
+    config: SlowScanConfig,
+    clock: &mut C,
+    mut on_unit: F,
+    mut on_delay_start: D
+) -> Result<(), io::Error>
+where
+    W: Write,
+    I: Iterator<Item = char>,
+    C: Clock + ?Sized,
+    F: FnMut(&mut dyn Write, char) -> io::Result<()>,
+    D: FnMut(char)
+{
+    let writer = &mut Retry::new(writer, config.io_retries);
+    let mut config = config;
+    // 只计入字符，指令和提示符标记不算作单元；达到上限后不再读取输入
+    let mut units =
+        Directives::new(iter, config.inline_directives, config.prompt_marks);
+    let mut remaining = config.head_units.unwrap_or(usize::MAX);
+    let mut iter = std::iter::from_fn(move || {
+        if remaining == 0 {
+            return None;
+        }
+        let it = units.next()?;
+        if let Unit::Char(_) = it {
+            remaining -= 1;
+        }
+        Some(it)
+    })
+    .peekable();
+    let mut buf = [0; 4];
+    let mut pacer = Pacer::with_config(clock, &config);
+    let mut run = None;
+    let mut unflushed = 0;
+    let mut shown = 0;
+    let mut column = 0;
+
+    while let Some(it) = iter.next() {
+        let it = match it {
+            Unit::Char(it) => it,
+            Unit::Directive(Directive::Speed(it)) => {
+                pacer.speed = it;
+                continue;
+            }
+            Unit::Directive(Directive::Pause(it)) => {
+                pacer.sleep(it);
+                continue;
+            }
+            Unit::Directive(Directive::Delay(it)) => {
+                config.base_delay = it;
+                continue;
+            }
+            Unit::PromptMark(it) => {
+                writer.write_all(it.as_bytes())?;
+                continue;
+            }
+        };
+
+        // 紧跟 VS16 的表情符号与 VS16 一并写入，并按 `emoji_width` 计算延迟
+        let emoji = config.emoji_width.is_some()
+            && iter.next_if_eq(&Unit::Char(VS16)).is_some();
+        let delay = if emoji {
+            config.width_delay(config.emoji_width)
+        } else {
+            config.char_delay(it)
+        };
+
+        // 连续相同字符的数量，达到 `coalesce_runs` 或延续上一组时整组显示
+        let mut len = 1;
+        if emoji {
+            run = None;
+        } else if let Some(n) = config.coalesce_runs.filter(|it| *it > 1) {
+            while len < n && iter.next_if_eq(&Unit::Char(it)).is_some() {
+                len += 1;
+            }
+            run = (len == n || run == Some(it)).then_some(it);
+        }
+        let (group, groups) = match run {
+            Some(_) => (len, 1),
+            None => (1, len)
+        };
+
+        // 电传打字机模式下字符所占的列数，脱字符记法占两列
+        let columns = if emoji {
+            config.emoji_width.unwrap_or(2)
+        } else if config.caret_notation && caret(it).is_some() {
+            2
+        } else {
+            config.char_width(it).unwrap_or(0)
+        };
 
-            if iter.peek().is_some() || config.tail_delay {
-                match unicode_width::UnicodeWidthChar::width_cjk(it) {
-                    // 全宽字符（如中文字符）
-                    Some(2) => {
-                        #[cfg(not(feature = "unstable"))]
-                        sleep(config.full_width_delay);
-                        #[cfg(feature = "unstable")]
-                        {
-                            now += config.full_width_delay;
-                            sleep_until(now);
+        for i in 1..=groups {
+            for _ in 0..group {
+                if let Some(width) = config.teletype_width.filter(|it| *it > 0)
+                {
+                    if column > 0 && column + columns > width {
+                        writer.write_all(b"\r\n")?;
+                        if config.flush_each_unit {
+                            writer.flush()?;
                         }
+                        pacer.sleep(config.carriage_return_delay);
+                        column = 0;
                     }
-                    // 控制字符（如 \n、\t 等）延迟
+                    column = match it {
+                        '\n' | '\r' => 0,
+                        '\t' => (column / 8 + 1) * 8,
+                        _ => column + columns
+                    };
+                }
+                match config.caret_notation.then(|| caret(it)).flatten() {
+                    Some(caret) => writer.write_all(&caret)?,
                     None => {
-                        #[cfg(not(feature = "unstable"))]
-                        sleep(config.control_char_delay);
-                        #[cfg(feature = "unstable")]
-                        {
-                            now += config.control_char_delay;
-                            sleep_until(now);
-                        }
-                    }
-                    // 半宽字符（如英文字母、数字）
-                    _ => {
-                        #[cfg(not(feature = "unstable"))]
-                        sleep(config.base_delay);
-                        #[cfg(feature = "unstable")]
-                        {
-                            now += config.base_delay;
-                            sleep_until(now);
-                        }
+                        writer.write_all(it.encode_utf8(&mut buf).as_ref())?
                     }
                 }
+                on_unit(writer, it)?;
+                if emoji {
+                    writer.write_all(VS16.encode_utf8(&mut buf).as_ref())?;
+                    on_unit(writer, VS16)?;
+                }
+            }
+            // 紧随其后的提示符标记不是可见内容，在延迟之前一并写入
+            if i == groups {
+                while let Some(Unit::PromptMark(it)) =
+                    iter.next_if(|it| matches!(it, Unit::PromptMark(_)))
+                {
+                    writer.write_all(it.as_bytes())?;
+                }
+            }
+            unflushed += 1;
+            if config.flush_each_unit && unflushed >= config.chars_per_flush {
+                writer.flush()?;
+                unflushed = 0;
+            }
+
+            // 每显示 `K` 个单元之后以停顿代替原本的延迟
+            shown += 1;
+            let delay = match config.burst {
+                Some((k, pause)) if k > 0 && shown % k == 0 => pause,
+                _ => delay
+            };
+
+            let more = i < groups || iter.peek().is_some();
+            if let Some(delay) = config.pause_after(delay, more) {
+                on_delay_start(it);
+
+                pacer.sleep(delay);
             }
         }
+    }
 
-        Ok(())
+    writer.flush()
+}
+
+/// 返回不可打印控制字符的脱字符记法，`\n`、`\t` 和其他字符返回 `None`
+///
+/// ---
+///
+/// Return the caret notation of a non-printable control character, or `None`
+/// for `\n`, `\t` and any other character
+fn caret(c: char) -> Option<[u8; 2]> {
+    match c {
+        '\n' | '\t' => None,
+        '\0'..='\x1f' | '\x7f' => Some([b'^', c as u8 ^ 0x40]),
+        _ => None
+    }
+}
+
+/// 供 [`slow_scan_print!`] 使用，不属于公开 API
+///
+/// ---
+///
+/// Used by [`slow_scan_print!`], not part of the public API
+#[doc(hidden)]
+pub fn __slow_scan_write_fmt<W: Write>(
+    writer: &mut W,
+    args: fmt::Arguments,
+    config: SlowScanConfig
+) -> Result<(), io::Error> {
+    match args.as_str() {
+        Some(it) => writer.slow_scan_write_by_chars(it.chars(), config),
+        None => {
+            writer.slow_scan_write_by_chars(args.to_string().chars(), config)
+        }
+    }
+}
+
+/// 判断字符是否为 Unicode 行分隔符或段落分隔符
+///
+/// ---
+///
+/// Check whether a character is the Unicode line or paragraph separator
+fn is_unicode_separator(c: char) -> bool {
+    matches!(c, '\u{2028}' | '\u{2029}')
+}
+
+/// 判断字符是否位于私用区
+///
+/// ---
+///
+/// Check whether a character is in a private use area
+fn is_private_use(c: char) -> bool {
+    matches!(
+        c,
+        '\u{e000}'..='\u{f8ff}'
+            | '\u{f0000}'..='\u{ffffd}'
+            | '\u{100000}'..='\u{10fffd}'
+    )
+}
+
+/// 将光标从第 `from` 列相对移动到第 `to` 列
+///
+/// ---
+///
+/// Move the cursor relatively from column `from` to column `to`
+fn move_cursor<W: Write + ?Sized>(
+    writer: &mut W,
+    from: usize,
+    to: usize
+) -> io::Result<()> {
+    match to.cmp(&from) {
+        Ordering::Greater => write!(writer, "\x1b[{}C", to - from),
+        Ordering::Less => write!(writer, "\x1b[{}D", from - to),
+        Ordering::Equal => Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::clock::VirtualClock;
+
+    #[test]
+    fn tiny_speed_factor_caps_delay() {
+        let mut config = SlowScanConfig::default();
+        config
+            .set_base_delay(Duration::from_millis(1))
+            .set_speed_factor(1e-300);
+
+        let start = Instant::now();
+        let mut clock = VirtualClock::new(start);
+        let mut writer = Vec::new();
+        writer
+            .slow_scan_write_by_chars_with_clock(
+                "ab".chars(),
+                config,
+                &mut clock
+            )
+            .unwrap();
+
+        assert_eq!(writer, b"ab");
+        assert_eq!(clock.deadlines, [start + MAX_DELAY]);
     }
 }
@@ -0,0 +1,104 @@
+use std::error::Error;
+use std::time::Duration;
+
+/// 逐行模式下记录每行及其节奏信息的目标
+///
+/// ---
+///
+/// Target recording every line and its pacing in line mode
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum LogTarget {
+    /// 标准错误
+    ///
+    /// ---
+    ///
+    /// Standard error
+    Stderr,
+    /// 系统日志
+    ///
+    /// ---
+    ///
+    /// System log
+    #[cfg(feature = "syslog")]
+    Syslog
+}
+
+/// 日志记录的写入目标
+///
+/// ---
+///
+/// Destination the log records are written to
+enum Sink {
+    Stderr,
+    #[cfg(feature = "syslog")]
+    Syslog(syslog::Logger<syslog::LoggerBackend, syslog::Formatter3164>)
+}
+
+/// 记录每行显示时的计划偏移和之后的延迟
+///
+/// 每条记录形如 `offset=40ms delay=20ms line="hello"`，偏移是按配置的延迟
+/// 累加计算出的计划时间，而不是实际测量的时间
+///
+/// ---
+///
+/// Logger recording the scheduled offset of every line and the delay after it
+///
+/// Every record looks like `offset=40ms delay=20ms line="hello"`, the offset
+/// being the scheduled time summed up from the configured delays rather than
+/// a measured one
+pub(crate) struct LineLogger {
+    sink: Sink,
+    offset: Duration
+}
+
+impl LineLogger {
+    /// 打开日志目标
+    ///
+    /// ---
+    ///
+    /// Open the log target
+    pub(crate) fn open(target: LogTarget) -> Result<Self, Box<dyn Error>> {
+        let sink = match target {
+            LogTarget::Stderr => Sink::Stderr,
+            #[cfg(feature = "syslog")]
+            LogTarget::Syslog => {
+                Sink::Syslog(syslog::unix(syslog::Formatter3164 {
+                    facility: syslog::Facility::LOG_USER,
+                    hostname: None,
+                    process: env!("CARGO_PKG_NAME").to_string(),
+                    pid: std::process::id()
+                })?)
+            }
+        };
+
+        Ok(Self {
+            sink,
+            offset: Duration::ZERO
+        })
+    }
+
+    /// 记录一行及其之后的延迟，并将计划偏移推进该延迟
+    ///
+    /// ---
+    ///
+    /// Record a line and the delay after it, advancing the scheduled offset by
+    /// that delay
+    pub(crate) fn log(&mut self, line: &str, delay: Duration) {
+        let record = format!(
+            "offset={:?} delay={:?} line={:?}",
+            self.offset,
+            delay,
+            line.trim_end_matches(['\r', '\n'])
+        );
+
+        match &mut self.sink {
+            Sink::Stderr => eprintln!("{}", record),
+            #[cfg(feature = "syslog")]
+            Sink::Syslog(it) => {
+                let _ = it.info(record);
+            }
+        }
+
+        self.offset += delay;
+    }
+}
@@ -0,0 +1,214 @@
+use std::io::{self, Write};
+
+use crate::{SlowScanConfig, SlowScanWrite};
+
+/// 按行缓冲并慢速扫描输出的写入器
+///
+/// 写入的数据会先被缓冲，直到遇到换行符时才按 `slow_scan_write_by_chars` 的
+/// 规则逐字符显示完整的一行，未完成的行会一直保留到换行符到来。刷新或被丢弃时
+/// 会显示剩余的不完整行。适合作为日志后端等逐步写入数据的场景的输出目标
+///
+/// # 示例
+/// ```
+/// use std::io::Write;
+/// use std::time::Duration;
+///
+/// use slow_scan_print::{SlowScanConfig, SlowScanLineWriter};
+///
+/// let mut config = SlowScanConfig::default();
+/// config.set_base_delay(Duration::ZERO);
+///
+/// let mut writer = SlowScanLineWriter::new(Vec::new(), config);
+///
+/// writer.write_all(b"hel").unwrap();
+/// assert!(writer.get_ref().is_empty());
+///
+/// writer.write_all(b"lo\nwor").unwrap();
+/// assert_eq!(writer.get_ref(), b"hello\n");
+///
+/// writer.flush().unwrap();
+/// assert_eq!(writer.get_ref(), b"hello\nwor");
+/// ```
+///
+/// ---
+///
+/// Writer buffering by line and writing with a slow scan
+///
+/// Written data is buffered until a line break arrives, then the completed
+/// line is revealed character by character following the rules of
+/// `slow_scan_write_by_chars`; partial lines are held until their terminator
+/// arrives. On flush or drop the remaining partial line is revealed. Suitable
+/// as the sink of incremental writers such as logging backends
+#[derive(Debug)]
+pub struct SlowScanLineWriter<W: Write> {
+    inner: W,
+    config: SlowScanConfig,
+    buf: Vec<u8>
+}
+
+impl<W: Write> SlowScanLineWriter<W> {
+    pub fn new(inner: W, config: SlowScanConfig) -> Self {
+        Self {
+            inner,
+            config,
+            buf: Vec::new()
+        }
+    }
+
+    /// 返回内部写入器的引用
+    ///
+    /// ---
+    ///
+    /// Return a reference to the inner writer
+    pub fn get_ref(&self) -> &W { &self.inner }
+
+    /// 返回内部写入器的可变引用
+    ///
+    /// ---
+    ///
+    /// Return a mutable reference to the inner writer
+    pub fn get_mut(&mut self) -> &mut W { &mut self.inner }
+
+    /// 慢速扫描输出缓冲区中前 `len` 个字节，成功后将其移出缓冲区
+    ///
+    /// 写入失败时这些字节保留在缓冲区中，之后的刷新会再次尝试
+    ///
+    /// ---
+    ///
+    /// Write the first `len` bytes of the buffer with a slow scan, removing
+    /// them from the buffer once succeeded
+    ///
+    /// On failure the bytes stay in the buffer so that a later flush retries
+    fn reveal(&mut self, len: usize) -> io::Result<()> {
+        self.inner.slow_scan_write_by_chars(
+            String::from_utf8_lossy(&self.buf[..len]).chars(),
+            self.config
+        )?;
+        self.buf.drain(..len);
+
+        Ok(())
+    }
+}
+
+impl<W: Write> Write for SlowScanLineWriter<W> {
+    /// 逐行接收数据，只有成功显示的行和最后不完整的行才算作已写入
+    ///
+    /// 某一行显示失败时退回该行在本次调用中加入的字节：此前已有行被接收时
+    /// 返回已接收的字节数，否则返回错误
+    ///
+    /// ---
+    ///
+    /// Accept data line by line, counting only the lines revealed successfully
+    /// and the trailing partial line as written
+    ///
+    /// When revealing a line fails, the bytes this call added for it are taken
+    /// back: the count of bytes accepted so far is returned if any line was
+    /// accepted before, otherwise the error
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        let mut written = 0;
+
+        while let Some(it) = buf[written..].iter().position(|it| *it == b'\n') {
+            let end = written + it + 1;
+            self.buf.extend_from_slice(&buf[written..end]);
+
+            if let Err(it) = self.reveal(self.buf.len()) {
+                self.buf.truncate(self.buf.len() - (end - written));
+                return if written > 0 { Ok(written) } else { Err(it) };
+            }
+
+            written = end;
+        }
+
+        self.buf.extend_from_slice(&buf[written..]);
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        if !self.buf.is_empty() {
+            self.reveal(self.buf.len())?;
+        }
+
+        self.inner.flush()
+    }
+}
+
+impl<W: Write> Drop for SlowScanLineWriter<W> {
+    fn drop(&mut self) { let _ = self.flush(); }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// 拒绝写入包含指定字节的数据的写入器
+    ///
+    /// ---
+    ///
+    /// Writer rejecting data containing the given byte
+    #[derive(Default)]
+    struct Picky {
+        out: Vec<u8>,
+        reject: Option<u8>
+    }
+
+    impl Write for Picky {
+        fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+            if self.reject.is_some_and(|it| buf.contains(&it)) {
+                return Err(io::Error::other("rejected"));
+            }
+
+            self.out.write(buf)
+        }
+
+        fn flush(&mut self) -> io::Result<()> { Ok(()) }
+    }
+
+    fn writer() -> SlowScanLineWriter<Picky> {
+        let mut config = SlowScanConfig::default();
+        config.set_base_delay(std::time::Duration::ZERO);
+
+        SlowScanLineWriter::new(Picky::default(), config)
+    }
+
+    #[test]
+    fn failed_write_takes_nothing() {
+        let mut writer = writer();
+        writer.get_mut().reject = Some(b'a');
+
+        assert!(writer.write(b"ab\n").is_err());
+        assert!(writer.buf.is_empty());
+
+        writer.get_mut().reject = None;
+        writer.write_all(b"ab\n").unwrap();
+        assert_eq!(writer.get_ref().out, b"ab\n");
+    }
+
+    #[test]
+    fn write_counts_lines_revealed_before_failure() {
+        let mut writer = writer();
+        writer.get_mut().reject = Some(b'c');
+
+        assert_eq!(writer.write(b"ab\ncd\nef").unwrap(), 3);
+        assert!(writer.buf.is_empty());
+        assert_eq!(writer.get_ref().out, b"ab\n");
+
+        writer.get_mut().reject = None;
+        writer.write_all(b"cd\nef").unwrap();
+        writer.flush().unwrap();
+        assert_eq!(writer.get_ref().out, b"ab\ncd\nef");
+    }
+
+    #[test]
+    fn failed_reveal_keeps_line() {
+        let mut writer = writer();
+        writer.write_all(b"ab").unwrap();
+        writer.get_mut().reject = Some(b'a');
+
+        assert!(writer.flush().is_err());
+        assert_eq!(writer.buf, b"ab");
+
+        writer.get_mut().reject = None;
+        writer.flush().unwrap();
+        assert_eq!(writer.get_ref().out, b"ab");
+    }
+}
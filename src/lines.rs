@@ -0,0 +1,218 @@
+use std::io::{self, BufRead};
+
+/// 按行读取但限制每段最大字节数的迭代器
+///
+/// 与 [`BufRead::lines`] 不同，每次产生的字符串保留行尾的换行符；超过
+/// `max_bytes` 字节的行会被切分为多段，只有最后一段带有换行符，因此没有换行
+/// 符的超长输入也只会占用有限的内存。切分只发生在 UTF-8 字符边界上，
+/// `max_bytes` 小于一个字符的长度时该字符仍然完整地放在一段中。无效的 UTF-8
+/// 会被替换为 `U+FFFD`
+///
+/// # 示例
+/// ```
+/// use slow_scan_print::BoundedLines;
+///
+/// let input = format!("{}\nab\n", "x".repeat(10));
+/// let lines = BoundedLines::new(input.as_bytes(), 4)
+///     .collect::<Result<Vec<_>, _>>()
+///     .unwrap();
+///
+/// assert_eq!(lines, ["xxxx", "xxxx", "xx\n", "ab\n"]);
+///
+/// let lines = BoundedLines::new("世界".as_bytes(), 4)
+///     .collect::<Result<Vec<_>, _>>()
+///     .unwrap();
+///
+/// assert_eq!(lines, ["世", "界"]);
+/// ```
+///
+/// 调用 [`set_unicode_lines`](Self::set_unicode_lines) 后，行分隔符
+/// `U+2028` 和段落分隔符 `U+2029` 也会结束一行，并像换行符一样保留在所在段的
+/// 末尾
+///
+/// ```
+/// use slow_scan_print::BoundedLines;
+///
+/// let mut lines =
+///     BoundedLines::new("a\u{2028}b\u{2029}\u{2028}c\n".as_bytes(), 64);
+/// lines.set_unicode_lines(true);
+///
+/// let lines = lines.collect::<Result<Vec<_>, _>>().unwrap();
+/// assert_eq!(lines, ["a\u{2028}", "b\u{2029}", "\u{2028}", "c\n"]);
+/// ```
+///
+/// ---
+///
+/// Iterator reading by line but bounding the size of each segment in bytes
+///
+/// Unlike [`BufRead::lines`], every produced string keeps its line break;
+/// lines longer than `max_bytes` bytes are split into several segments and
+/// only the last one carries the line break, so that overlong input without
+/// line breaks only takes a bounded amount of memory. Splits only happen at
+/// UTF-8 character boundaries, and a character longer than `max_bytes` is
+/// still kept whole in one segment. Invalid UTF-8 is replaced with `U+FFFD`
+///
+/// After [`set_unicode_lines`](Self::set_unicode_lines), the line separator
+/// `U+2028` and the paragraph separator `U+2029` also end a line and are kept
+/// at the end of their segment just like a line break
+#[derive(Debug)]
+pub struct BoundedLines<R> {
+    reader: R,
+    max_bytes: usize,
+    /// 上一段因切分在字符边界而留下的字节
+    ///
+    /// ---
+    ///
+    /// Bytes left over by the previous segment when splitting at a character
+    /// boundary
+    carry: Vec<u8>,
+    unicode_lines: bool
+}
+
+/// Unicode 行分隔符和段落分隔符的 UTF-8 编码
+///
+/// ---
+///
+/// UTF-8 encodings of the Unicode line and paragraph separators
+const UNICODE_SEPARATORS: [[u8; 3]; 2] =
+    [[0xE2, 0x80, 0xA8], [0xE2, 0x80, 0xA9]];
+
+impl<R: BufRead> BoundedLines<R> {
+    pub fn new(reader: R, max_bytes: usize) -> Self {
+        Self {
+            reader,
+            max_bytes,
+            carry: Vec::new(),
+            unicode_lines: false
+        }
+    }
+
+    /// 设置是否把 `U+2028` 和 `U+2029` 也视为行边界
+    ///
+    /// ---
+    ///
+    /// Set whether `U+2028` and `U+2029` are treated as line boundaries too
+    pub fn set_unicode_lines(&mut self, enabled: bool) -> &mut Self {
+        self.unicode_lines = enabled;
+        self
+    }
+
+    /// 在 `from` 之后查找第一个 Unicode 行分隔符，找到时把其后的字节移入
+    /// `carry` 并返回 `true`
+    ///
+    /// ---
+    ///
+    /// Look for the first Unicode line separator after `from`, moving the
+    /// bytes following it into `carry` and returning `true` when found
+    fn split_at_separator(
+        &mut self,
+        segment: &mut Vec<u8>,
+        from: usize
+    ) -> bool {
+        if !self.unicode_lines {
+            return false;
+        }
+
+        let end = segment[from..]
+            .windows(3)
+            .position(|it| UNICODE_SEPARATORS.iter().any(|sep| it == sep))
+            .map(|it| from + it + 3);
+
+        match end {
+            Some(end) => {
+                self.carry = segment.split_off(end);
+                true
+            }
+            None => false
+        }
+    }
+
+    /// 读取一段，遇到换行符、达到 `max_bytes` 或输入结束时停止
+    ///
+    /// ---
+    ///
+    /// Read a segment, stopping at a line break, at `max_bytes` or at the end
+    /// of the input
+    fn read_segment(&mut self) -> io::Result<Vec<u8>> {
+        let mut segment = std::mem::take(&mut self.carry);
+        if self.split_at_separator(&mut segment, 0) {
+            return Ok(segment);
+        }
+
+        loop {
+            // 达到上限且末尾至少有一个完整的字符时停止
+            let full =
+                segment.len() >= self.max_bytes && utf8_boundary(&segment) > 0;
+            if full || segment.last() == Some(&b'\n') {
+                break;
+            }
+
+            let buf = self.reader.fill_buf()?;
+            if buf.is_empty() {
+                return Ok(segment);
+            }
+
+            let len = buf
+                .len()
+                .min(self.max_bytes.saturating_sub(segment.len()).max(1));
+            let len = buf[..len]
+                .iter()
+                .position(|it| *it == b'\n')
+                .map_or(len, |it| it + 1);
+
+            // 分隔符可能跨越两次读取，因此从上一段末尾的两个字节开始查找
+            let from = segment.len().saturating_sub(2);
+            segment.extend_from_slice(&buf[..len]);
+            self.reader.consume(len);
+
+            if self.split_at_separator(&mut segment, from) {
+                return Ok(segment);
+            }
+        }
+
+        if segment.last() != Some(&b'\n') {
+            let boundary = utf8_boundary(&segment);
+            self.carry = segment.split_off(boundary);
+        }
+
+        Ok(segment)
+    }
+}
+
+impl<R: BufRead> Iterator for BoundedLines<R> {
+    type Item = io::Result<String>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        match self.read_segment() {
+            Ok(it) if it.is_empty() => None,
+            Ok(it) => Some(Ok(String::from_utf8_lossy(&it).into_owned())),
+            Err(it) => Some(Err(it))
+        }
+    }
+}
+
+/// 返回不切断末尾不完整 UTF-8 字符的最长前缀长度
+///
+/// ---
+///
+/// Return the length of the longest prefix that does not cut an incomplete
+/// trailing UTF-8 character
+fn utf8_boundary(bytes: &[u8]) -> usize {
+    // 从末尾向前找到最后一个字符的起始字节，最多回退 3 个字节
+    for back in 1..=bytes.len().min(4) {
+        let i = bytes.len() - back;
+        let len = match bytes[i] {
+            0x00..=0x7F => 1,
+            0xC0..=0xDF => 2,
+            0xE0..=0xEF => 3,
+            0xF0..=0xF7 => 4,
+            // 后续字节，继续向前查找
+            0x80..=0xBF => continue,
+            _ => return bytes.len()
+        };
+
+        return if back < len { i } else { bytes.len() };
+    }
+
+    bytes.len()
+}
@@ -0,0 +1,52 @@
+use std::env;
+
+/// 判断区域设置是否为 CJK（中文、日文、韩文）区域
+///
+/// 只比较区域设置的语言部分，即 `_`、`-`、`.`、`@` 之前的内容，不区分大小写
+///
+/// # 示例
+/// ```
+/// use slow_scan_print::is_cjk_locale;
+///
+/// assert!(is_cjk_locale("zh_CN.UTF-8"));
+/// assert!(is_cjk_locale("ja_JP.eucJP"));
+/// assert!(is_cjk_locale("ko-KR"));
+/// assert!(!is_cjk_locale("en_US.UTF-8"));
+/// assert!(!is_cjk_locale("C"));
+/// ```
+///
+/// ---
+///
+/// Tell whether a locale is a CJK (Chinese, Japanese or Korean) one
+///
+/// Only the language part of the locale is compared, that is what comes before
+/// `_`, `-`, `.` or `@`, case-insensitively
+pub fn is_cjk_locale(locale: &str) -> bool {
+    let language = locale
+        .split(['_', '-', '.', '@'])
+        .next()
+        .unwrap_or_default();
+
+    ["zh", "ja", "ko"]
+        .iter()
+        .any(|it| language.eq_ignore_ascii_case(it))
+}
+
+/// 根据 `LC_ALL`、`LC_CTYPE` 和 `LANG` 环境变量判断当前是否为 CJK 区域
+///
+/// 按顺序使用第一个非空的变量，都为空时视为非 CJK 区域
+///
+/// ---
+///
+/// Tell whether the current locale is a CJK one from the `LC_ALL`, `LC_CTYPE`
+/// and `LANG` environment variables
+///
+/// The first non-empty variable in that order is used, and the locale is
+/// considered non-CJK when all of them are empty
+pub fn detect_cjk_locale() -> bool {
+    ["LC_ALL", "LC_CTYPE", "LANG"]
+        .iter()
+        .filter_map(|it| env::var(it).ok())
+        .find(|it| !it.is_empty())
+        .is_some_and(|it| is_cjk_locale(&it))
+}
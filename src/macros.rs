@@ -0,0 +1,82 @@
+/// 格式化并慢速扫描输出到写入器
+///
+/// 用法与 [`write!`] 相同，只是在写入器之后需要传入 [`SlowScanConfig`]。格式化
+/// 结果按 [`SlowScanWrite::slow_scan_write_by_chars`] 的规则输出，没有格式化
+/// 参数时直接使用格式字符串而不分配内存
+///
+/// [`SlowScanConfig`]: crate::SlowScanConfig
+/// [`SlowScanWrite::slow_scan_write_by_chars`]: crate::SlowScanWrite::slow_scan_write_by_chars
+///
+/// # 示例
+/// ```
+/// use std::time::Duration;
+///
+/// use slow_scan_print::{SlowScanConfig, slow_scan_print};
+///
+/// let mut config = SlowScanConfig::default();
+/// config.set_base_delay(Duration::ZERO);
+///
+/// let mut writer = Vec::new();
+/// slow_scan_print!(writer, config, "x = {}", 42).unwrap();
+///
+/// assert_eq!(writer, b"x = 42");
+/// ```
+///
+/// ---
+///
+/// Format and slow scan output to a writer
+///
+/// Used the same way as [`write!`], except that a [`SlowScanConfig`] has to be
+/// passed after the writer. The formatted result is output following the rules
+/// of [`SlowScanWrite::slow_scan_write_by_chars`], and the format string is
+/// used directly without allocating when there are no format arguments
+#[macro_export]
+macro_rules! slow_scan_print {
+    ($writer:expr, $config:expr, $($arg:tt)*) => {
+        $crate::__slow_scan_write_fmt(
+            &mut $writer,
+            ::std::format_args!($($arg)*),
+            $config
+        )
+    };
+}
+
+/// 格式化并慢速扫描输出到写入器，末尾追加换行符
+///
+/// 与 [`slow_scan_print!`] 相同，但像 [`writeln!`] 一样在末尾追加 `\n`
+///
+/// # 示例
+/// ```
+/// use std::time::Duration;
+///
+/// use slow_scan_print::{SlowScanConfig, slow_scan_println};
+///
+/// let mut config = SlowScanConfig::default();
+/// config.set_base_delay(Duration::ZERO);
+///
+/// let mut writer = Vec::new();
+/// slow_scan_println!(writer, config, "x = {}", 42).unwrap();
+/// slow_scan_println!(writer, config).unwrap();
+///
+/// assert_eq!(writer, b"x = 42\n\n");
+/// ```
+///
+/// ---
+///
+/// Format and slow scan output to a writer, appending a line break
+///
+/// Same as [`slow_scan_print!`], but appends `\n` like [`writeln!`]
+#[macro_export]
+macro_rules! slow_scan_println {
+    ($writer:expr, $config:expr $(,)?) => {
+        $crate::slow_scan_print!($writer, $config, "\n")
+    };
+    ($writer:expr, $config:expr, $($arg:tt)*) => {
+        $crate::slow_scan_print!(
+            $writer,
+            $config,
+            "{}\n",
+            ::std::format_args!($($arg)*)
+        )
+    };
+}
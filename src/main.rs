@@ -1,41 +1,121 @@
 use std::collections::VecDeque;
-use std::io::{BufRead, BufReader};
+use std::fs::File;
+use std::io::{self, BufRead, BufReader, BufWriter, IsTerminal, Read, Write};
 use std::process::exit;
+use std::sync::{Mutex, MutexGuard};
+use std::time::{Duration, Instant};
+use std::{env, iter};
 
 use chain_reader::*;
 use console::Term;
 use line_ending::LineEnding;
 use once_cell::sync::Lazy;
+#[cfg(feature = "regex")]
+use regex::Regex;
 use rust_i18n::{set_locale, t};
-use slow_scan_print::SlowScanWrite;
+#[cfg(feature = "watch")]
+use slow_scan_print::FileWatch;
+#[cfg(all(unix, feature = "pty"))]
+use slow_scan_print::Pty;
+#[cfg(feature = "timer-resolution")]
+use slow_scan_print::TimerResolution;
+use slow_scan_print::{
+    Align,
+    BoundedLines,
+    CharClass,
+    Clock,
+    ColorDowngrade,
+    CursorGuard,
+    DiffClassifier,
+    DiffLineKind,
+    HexDump,
+    Pager,
+    Profile,
+    Region,
+    RunStats,
+    SlowScanConfig,
+    SlowScanWrite,
+    SpinnerReader,
+    SystemClock,
+    TraceClock,
+    Unbuffered,
+    align_padding,
+    collapse_cr,
+    reflow,
+    sanitize_for_terminal,
+    word_wrap_with_config,
+    write_dim_rule
+};
+use unicode_segmentation::UnicodeSegmentation;
 use utf8_chars::BufReadCharsExt;
 
 use crate::args::Args;
 use crate::input::InputSource;
+use crate::interval_writer::IntervalWriter;
+use crate::line_log::LineLogger;
+use crate::tracking_writer::TrackingWriter;
 
 rust_i18n::i18n!();
 
 mod args;
+mod config;
 mod input;
+mod interval_writer;
+mod line_log;
+mod tracking_writer;
 
 static ARGS: Lazy<Args> = Lazy::new(Args::new);
 static STDOUT: Lazy<Term> = Lazy::new(Term::stdout);
+#[cfg(all(unix, feature = "pty"))]
+static PTY: Lazy<Option<Pty>> = Lazy::new(|| ARGS.pty.then(open_pty));
+static PROFILE: Mutex<Option<Profile>> = Mutex::new(None);
+static TRACE: Lazy<Mutex<TraceClock>> =
+    Lazy::new(|| Mutex::new(TraceClock::new(SystemClock)));
+static SLOW_SCAN_CONFIG: Lazy<Mutex<SlowScanConfig>> =
+    Lazy::new(|| Mutex::new(ARGS.slow_scan_config));
 static LINE_ENDING: Lazy<&str> =
     Lazy::new(|| LineEnding::from_current_platform().as_str());
 
+const ENTER_ALT_SCREEN: &str = "\x1b[?1049h";
+const LEAVE_ALT_SCREEN: &str = "\x1b[?1049l";
+/// 与 [`BufReader::new`] 相同的默认缓冲区容量
+///
+/// ---
+///
+/// Default buffer capacity, the same as that of [`BufReader::new`]
+const DEFAULT_BUF_SIZE: usize = 8 * 1024;
+/// 等待输入时旋转指示器的帧间隔
+///
+/// ---
+///
+/// Frame interval of the spinner while waiting for input
+const SPINNER_INTERVAL: Duration = Duration::from_millis(100);
+
 fn main() {
     init_locale();
     setup_ctrlc_handle();
+    setup_terminal();
+    setup_panic_hook();
+
+    let cursor_guard = hides_cursor(ARGS.hide_cursor, STDOUT.is_term())
+        .then(|| CursorGuard::new(STDOUT.clone()).ok())
+        .flatten();
+
+    // 按对齐延迟的粒度提高系统计时器的精度
+    #[cfg(feature = "timer-resolution")]
+    let timer_resolution =
+        slow_scan_config().round_to().map(TimerResolution::new);
 
-    if ARGS.hide_cursor {
-        let _ = STDOUT.hide_cursor();
+    if should_wait_start(ARGS.wait_start, io::stdin().is_terminal()) {
+        wait_for_key();
     }
 
     slow_scan_print();
 
-    if ARGS.hide_cursor {
-        let _ = STDOUT.show_cursor();
-    }
+    #[cfg(feature = "timer-resolution")]
+    drop(timer_resolution);
+    drop(cursor_guard);
+    restore_terminal();
 }
 
 #[inline]
@@ -47,49 +127,1353 @@ fn init_locale() {
 
 #[inline]
 fn setup_ctrlc_handle() {
-    ctrlc::set_handler(move || {
-        if ARGS.hide_cursor {
-            let _ = STDOUT.show_cursor();
+    ctrlc::set_handler(move || exit_restoring_terminal()).unwrap_or_else(
+        |it| {
+            eprintln!("{}", t!("error.set_ctrlc_handle_error", error = it));
         }
-        exit(1)
-    })
-    .unwrap_or_else(|it| {
-        eprintln!("{}", t!("error.set_ctrlc_handle_error", error = it));
-    });
+    );
+}
+
+#[inline]
+fn setup_panic_hook() {
+    let default_hook = std::panic::take_hook();
+
+    std::panic::set_hook(Box::new(move |it| {
+        restore_terminal();
+        default_hook(it);
+    }));
+}
+
+/// 按参数切换到备用屏幕
+///
+/// 光标由 `main` 中的 [`CursorGuard`] 负责隐藏和恢复
+///
+/// ---
+///
+/// Switch to the alternate screen as requested
+///
+/// The cursor is hidden and restored by the [`CursorGuard`] in `main`
+#[inline]
+fn setup_terminal() {
+    if let Some((enter, _)) = alt_screen(ARGS.alt_screen, STDOUT.is_term()) {
+        let _ = STDOUT.write_str(enter);
+    }
+}
+
+/// 恢复 [`setup_terminal`] 修改的终端状态
+///
+/// ---
+///
+/// Restore the terminal state changed by [`setup_terminal`]
+#[inline]
+fn restore_terminal() {
+    if let Some((_, leave)) = alt_screen(ARGS.alt_screen, STDOUT.is_term()) {
+        let _ = STDOUT.write_str(leave);
+    }
+}
+
+/// 判断是否隐藏光标，未请求或输出不是终端时不隐藏
+///
+/// 输出被重定向时不写入光标控制序列，避免污染输出文件
+///
+/// ---
+///
+/// Determine whether to hide the cursor, which is not done when it is not
+/// requested or the output is not a terminal
+///
+/// No cursor control sequences are written when the output is redirected, so
+/// they do not pollute the output file
+fn hides_cursor(requested: bool, is_term: bool) -> bool { requested && is_term }
+
+/// 返回进入和离开备用屏幕的序列，未请求或输出不是终端时返回 `None`
+///
+/// 输出被重定向时不写入这些序列，避免污染输出文件
+///
+/// ---
+///
+/// Return the sequences entering and leaving the alternate screen, or `None`
+/// when it is not requested or the output is not a terminal
+///
+/// The sequences are not written when the output is redirected, so they do
+/// not pollute the output file
+fn alt_screen(
+    requested: bool,
+    is_term: bool
+) -> Option<(&'static str, &'static str)> {
+    (requested && is_term).then_some((ENTER_ALT_SCREEN, LEAVE_ALT_SCREEN))
+}
+
+/// 恢复终端状态并立即退出
+///
+/// `exit` 不会运行析构函数，因此需要在这里显式地显示光标
+///
+/// ---
+///
+/// Restore the terminal state and exit immediately
+///
+/// `exit` does not run destructors, so the cursor has to be shown explicitly
+/// here
+fn exit_restoring_terminal() -> ! {
+    if hides_cursor(ARGS.hide_cursor, STDOUT.is_term()) {
+        let _ = STDOUT.show_cursor();
+    }
+
+    restore_terminal();
+    exit(1)
 }
 
 #[inline]
 fn slow_scan_print() {
+    #[cfg(feature = "watch")]
+    if ARGS.watch {
+        slow_scan_watch(&ARGS.files[0]);
+        return;
+    }
+
+    // 输出不是终端时无法定位光标，按顺序显示
+    if ARGS.split && STDOUT.is_term() {
+        slow_scan_print_split();
+        return;
+    }
+
     let mut readers = VecDeque::with_capacity(ARGS.files.len());
 
-    for it in ARGS.files.iter() {
+    #[cfg(feature = "command")]
+    let command = ARGS.cmd.as_deref();
+    #[cfg(not(feature = "command"))]
+    let command = None::<&str>;
+
+    #[cfg(feature = "command")]
+    if let Some(it) = command {
+        match InputSource::command(it) {
+            Ok(it) => readers.push_back(it),
+            Err(it) => eprintln!("{}", it)
+        }
+    }
+
+    // 脚本和命令的输出本身就是要显示的内容，不再读取输入文件
+    let read_files = ARGS.script.is_none() && command.is_none();
+    for it in ARGS.files.iter().filter(|_| read_files) {
         match InputSource::open(it) {
             Ok(it) => readers.push_back(it),
             Err(it) => eprintln!("{}", it)
         }
     }
 
-    let mut reader =
-        BufReader::new(ChainReader::new(readers, |_| ErrorAction::Skip));
+    #[cfg(all(unix, feature = "pty"))]
+    let to_stdout = !ARGS.benchmark && !ARGS.pty;
+    #[cfg(not(all(unix, feature = "pty")))]
+    let to_stdout = !ARGS.benchmark;
+
+    // 分隔线只用于与终端中的提示符区分，输出被重定向时不写入
+    let rule_width = (ARGS.dim_prompt && to_stdout && STDOUT.is_term())
+        .then(|| usize::from(STDOUT.size().1));
+    if let Some(it) = rule_width {
+        let _ = write_dim_rule(&mut STDOUT.clone(), it, &LINE_ENDING);
+    }
+
+    let chain = ChainReader::new(readers, |it| {
+        eprintln!("{}", it);
+        ErrorAction::Skip
+    });
+    let chain: Box<dyn Read> = if ARGS.spinner && to_stdout && STDOUT.is_term()
+    {
+        Box::new(SpinnerReader::new(chain, STDOUT.clone(), SPINNER_INTERVAL))
+    } else {
+        Box::new(chain)
+    };
+
+    // 容量为 1 时单字节的读取会绕过内部缓冲区直接读取底层输入
+    let capacity = if ARGS.unbuffered { 1 } else { DEFAULT_BUF_SIZE };
+    let mut reader = BufReader::with_capacity(capacity, chain);
+
+    let start = Instant::now();
+
+    // 需要按全部内容计算延迟时先读入内存
+    let (units, last_byte) = if ARGS.repeat <= 1
+        && ARGS.reflow.is_none()
+        && !ARGS.safe
+        && ARGS.min_total_duration.is_none()
+    {
+        slow_scan_print_from(reader)
+    } else {
+        let mut content = Vec::new();
+
+        if let Err(it) = reader.read_to_end(&mut content) {
+            eprintln!(
+                "{}",
+                t!("error.io_error_on_slow_scan_print", error = it)
+            );
+            return;
+        }
+
+        if let Some(width) = ARGS.reflow {
+            content =
+                reflow(&String::from_utf8_lossy(&content), width).into_bytes();
+        }
+        if ARGS.safe {
+            content = sanitize_for_terminal(&String::from_utf8_lossy(&content))
+                .into_bytes();
+        }
+        if let Some(it) = ARGS.min_total_duration {
+            lock(&SLOW_SCAN_CONFIG).raise_base_delay_over(
+                it,
+                String::from_utf8_lossy(&content).chars()
+            );
+        }
+
+        repeat(ARGS.repeat, ARGS.repeat_delay, &mut SystemClock, || {
+            slow_scan_print_from(content.as_slice())
+        })
+    };
+
+    // 结尾的分隔线需要从新的一行开始
+    if needs_final_newline(
+        ARGS.auto_final_newline || rule_width.is_some(),
+        to_stdout && STDOUT.is_term(),
+        last_byte
+    ) {
+        let _ = STDOUT.write_str(&LINE_ENDING);
+    }
+
+    if let Some(it) = rule_width {
+        let _ = write_dim_rule(&mut STDOUT.clone(), it, &LINE_ENDING);
+    }
+
+    if ARGS.benchmark {
+        println!(
+            "{}",
+            t!(
+                "report.benchmark",
+                elapsed = format!("{:?}", start.elapsed()),
+                units = units
+            )
+        );
+    }
+
+    if ARGS.profile {
+        print_profile();
+    }
+
+    if ARGS.stats_json {
+        let stats = match lock(&PROFILE).clone() {
+            Some(it) => RunStats::new(start.elapsed(), units, it),
+            None => RunStats::without_profile(start.elapsed(), units)
+        };
+        eprintln!("{}", stats.to_json());
+    }
+
+    if let Some(it) = &ARGS.trace {
+        write_trace(it);
+    }
+}
+
+/// 显示文件的内容，之后监视文件并只显示新追加的内容，直到出错或被中断
+///
+/// ---
+///
+/// Reveal the content of a file, then watch it and reveal only newly appended
+/// content until an error occurs or the program is interrupted
+#[cfg(feature = "watch")]
+fn slow_scan_watch(path: &str) {
+    let mut watch = match FileWatch::new(path) {
+        Ok(it) => it,
+        Err(it) => {
+            eprintln!(
+                "{}",
+                t!("error.cannot_watch_file", uri = path, src = it)
+            );
+            return;
+        }
+    };
+
+    let mut appended = watch.read_appended();
+    loop {
+        let mut content = match appended {
+            Ok(it) => it,
+            Err(it) => {
+                eprintln!(
+                    "{}",
+                    t!("error.io_error_on_slow_scan_print", error = it)
+                );
+                return;
+            }
+        };
+
+        if ARGS.safe {
+            content = sanitize_for_terminal(&String::from_utf8_lossy(&content))
+                .into_bytes();
+        }
+        slow_scan_print_from(content.as_slice());
+
+        appended = watch.wait_appended();
+    }
+}
+
+/// 将每个输入文件同时显示在终端中各自的横向区域中，各自按延迟独立推进
+///
+/// ---
+///
+/// Reveal every input file at once in its own horizontal band of the
+/// terminal, each one paced independently
+fn slow_scan_print_split() {
+    let mut readers = Vec::with_capacity(ARGS.files.len());
+    for it in &ARGS.files {
+        match InputSource::open(it) {
+            Ok(it) => readers.push(BufReader::new(it)),
+            Err(it) => eprintln!("{}", it)
+        }
+    }
+
+    let rows = usize::from(STDOUT.size().0);
+    let bands = Region::bands(readers.len(), rows);
+    let streams = readers
+        .iter_mut()
+        .map(|it| it.chars().map_while(Result::ok))
+        .zip(bands);
+
+    let mut writer = ColorDowngrade::new(STDOUT.clone(), ARGS.color_depth);
+    let result = writer
+        .write_all(b"\x1b[2J")
+        .and_then(|_| {
+            writer.slow_scan_write_interleaved(streams, slow_scan_config())
+        })
+        // 结束后将光标移到最后一行之后，避免提示符覆盖区域中的内容
+        .and_then(|_| write!(writer, "\x1b[{};1H{}", rows, *LINE_ENDING))
+        .and_then(|_| writer.flush());
+
+    if let Err(it) = result {
+        eprintln!("{}", t!("error.io_error_on_slow_scan_print", error = it));
+    }
+}
+
+/// 按次数重复显示内容，两次之间通过时钟等待 `delay`
+///
+/// 次数为 0 时按 1 次处理。返回所有次数显示的单元总数和最后写入的字节
+///
+/// ---
+///
+/// Reveal the content the given number of times, waiting `delay` through the
+/// clock between two runs
+///
+/// A count of 0 is treated as 1. Return the total number of units revealed
+/// over all runs and the last byte written
+fn repeat<C, F>(
+    times: u32,
+    delay: Duration,
+    clock: &mut C,
+    mut print: F
+) -> (usize, Option<u8>)
+where
+    C: Clock + ?Sized,
+    F: FnMut() -> (usize, Option<u8>)
+{
+    let mut units = 0;
+    let mut last_byte = None;
+
+    for i in 0..times.max(1) {
+        if i > 0 {
+            let deadline = clock.now() + delay;
+            clock.sleep_until(deadline);
+        }
+
+        let (n, it) = print();
+        units += n;
+        last_byte = it.or(last_byte);
+    }
+
+    (units, last_byte)
+}
+
+/// 将记录的每个单元的计划和实际时间以 CSV 格式写入文件，失败时打印错误
+///
+/// ---
+///
+/// Write the recorded scheduled and actual time of every unit to a file as
+/// CSV, printing an error on failure
+fn write_trace(path: &str) {
+    File::create(path)
+        .and_then(|it| lock(&TRACE).write_csv(BufWriter::new(it)))
+        .unwrap_or_else(|it| {
+            eprintln!("{}", t!("error.cannot_write_trace", error = it));
+        });
+}
+
+/// 向标准错误输出按字符类型统计的数量和延迟
+///
+/// ---
+///
+/// Print the count and delay per character class to stderr
+fn print_profile() {
+    let profile = lock(&PROFILE).clone().unwrap_or_default();
+
+    for it in CharClass::ALL {
+        let class = match it {
+            CharClass::HalfWidth => t!("report.half_width"),
+            CharClass::FullWidth => t!("report.full_width"),
+            CharClass::Control => t!("report.control_char")
+        };
+
+        eprintln!(
+            "{}",
+            t!(
+                "report.profile",
+                class = class,
+                count = profile.count(it),
+                delay = format!("{:?}", mean_delay(&profile, it)),
+                total = format!("{:?}", profile.total_delay(it))
+            )
+        );
+    }
+}
+
+/// 返回某类字符平均每个的延迟，没有该类字符时返回配置的延迟
+///
+/// ---
+///
+/// Return the mean delay per character of a class, or the configured delay
+/// when there are no characters of the class
+fn mean_delay(profile: &Profile, class: CharClass) -> Duration {
+    u32::try_from(profile.count(class))
+        .ok()
+        .filter(|it| *it > 0)
+        .map_or(slow_scan_config().class_delay(class), |it| {
+            profile.total_delay(class) / it
+        })
+}
+
+/// 需要统计时开始按字符类型统计，之后的 `record_*` 才会生效
+///
+/// ---
+///
+/// Start counting per character class when needed, the `record_*` functions
+/// only taking effect afterwards
+fn start_profile() {
+    if ARGS.profile || ARGS.stats_json {
+        lock(&PROFILE).get_or_insert_with(Profile::new);
+    }
+}
+
+/// 记录一个逐字符输出的字符及其延迟
+///
+/// ---
+///
+/// Record a character written character by character along with its delay
+fn record_char(it: char) {
+    if let Some(profile) = lock(&PROFILE).as_mut() {
+        let config = slow_scan_config();
+        let class = config.char_class(it);
+        profile.record(class, config.class_delay(class));
+    }
+}
+
+/// 记录一个块中的字符，块之后的延迟记在最后一个字符上
+///
+/// ---
+///
+/// Record the characters of a chunk, the delay following the chunk being
+/// counted on its last character
+fn record_chunk(chunk: &[u8]) {
+    if let Some(profile) = lock(&PROFILE).as_mut() {
+        let config = slow_scan_config();
+        let text = String::from_utf8_lossy(chunk);
+        let mut iter = text.chars().peekable();
+
+        while let Some(it) = iter.next() {
+            let delay = match iter.peek() {
+                Some(_) => Duration::ZERO,
+                None => config.chunk_delay(chunk)
+            };
+            profile.record(config.char_class(it), delay);
+        }
+    }
+}
+
+/// 获取锁，即使锁已中毒也继续使用其中的数据
+///
+/// ---
+///
+/// Acquire the lock, keeping on using the data even if it is poisoned
+fn lock<T>(mutex: &Mutex<T>) -> MutexGuard<'_, T> {
+    mutex.lock().unwrap_or_else(|it| it.into_inner())
+}
+
+/// 返回当前的慢速扫描配置
+///
+/// 通常与命令行参数中的配置相同，`--min-total-duration` 会在读入内容后调整
+/// 其中的基础延迟
+///
+/// ---
+///
+/// Return the current slow scan configuration
+///
+/// Usually the same as the one of the command line arguments, the base delay
+/// of which is adjusted by `--min-total-duration` once the content is read
+fn slow_scan_config() -> SlowScanConfig { *lock(&SLOW_SCAN_CONFIG) }
+
+/// 判断是否需要补上结尾的换行，使 shell 提示符从新的一行开始
+///
+/// 只在请求了换行、输出是终端且已输出的内容不以换行结尾时返回 `true`
+///
+/// ---
+///
+/// Determine whether a final line break is needed so that the shell prompt
+/// starts on a new line
+///
+/// Only returns `true` when it is requested, the output is a terminal and the
+/// content written so far does not end with a line break
+fn needs_final_newline(
+    requested: bool,
+    is_term: bool,
+    last_byte: Option<u8>
+) -> bool {
+    requested && is_term && last_byte.is_some_and(|it| it != b'\n')
+}
+
+/// 从读取器打印内容，返回打印的单元数量和最后写入的字节
+///
+/// ---
+///
+/// Print the content of a reader, returning the number of printed units and
+/// the last byte written
+#[inline]
+fn slow_scan_print_from<R: BufRead>(reader: R) -> (usize, Option<u8>) {
+    #[cfg(all(unix, feature = "pty"))]
+    if let Some(it) = PTY.as_ref() {
+        return slow_scan_print_tracked(reader, it.master());
+    }
+
+    if ARGS.benchmark {
+        slow_scan_print_tracked(reader, io::sink())
+    } else if let Some(it) = ARGS.flush_interval {
+        slow_scan_print_tracked(reader, IntervalWriter::new(STDOUT.clone(), it))
+    } else {
+        slow_scan_print_tracked(reader, STDOUT.clone())
+    }
+}
+
+/// 分配伪终端并向标准错误输出其从设备的路径，失败时打印错误并退出程序
+///
+/// ---
+///
+/// Allocate a pseudo-terminal and print the path of its slave to stderr,
+/// printing an error and exiting on failure
+#[cfg(all(unix, feature = "pty"))]
+fn open_pty() -> Pty {
+    let pty = Pty::open()
+        .and_then(|pty| pty.slave_path().map(|path| (pty, path)))
+        .unwrap_or_else(|it| {
+            eprintln!("{}", t!("error.cannot_open_pty", error = it));
+            exit(1)
+        });
+
+    eprintln!("{}", t!("report.pty", path = pty.1.display()));
+
+    pty.0
+}
+
+/// 打印到写入器并记录最后写入的字节
+///
+/// ---
+///
+/// Print to a writer, keeping track of the last byte written
+#[inline]
+fn slow_scan_print_tracked<R: BufRead, W: Write>(
+    reader: R,
+    writer: W
+) -> (usize, Option<u8>) {
+    let mut writer =
+        TrackingWriter::new(ColorDowngrade::new(writer, ARGS.color_depth));
+    let units = slow_scan_print_to(reader, &mut writer);
+
+    (units, writer.last_byte())
+}
+
+#[inline]
+fn slow_scan_print_to<R: BufRead, W: Write>(
+    mut reader: R,
+    writer: &mut W
+) -> usize {
+    let mut units = 0;
+
+    if ARGS.unbuffered {
+        start_profile();
+        let iter = Unbuffered::new(reader).map_while(|it| {
+            it.map_err(|it| {
+                eprintln!(
+                    "{}",
+                    t!("error.io_error_on_slow_scan_print", error = it)
+                );
+            })
+            .ok()
+        });
+
+        writer
+            .slow_scan_write_by_chunks(
+                iter.inspect(|it| {
+                    units += 1;
+                    record_chunk(it);
+                }),
+                slow_scan_config()
+            )
+            .unwrap_or_else(|it| {
+                eprintln!(
+                    "{}",
+                    t!("error.io_error_on_slow_scan_print", error = it)
+                );
+            });
+
+        return units;
+    }
+
+    #[cfg(feature = "regex")]
+    if let Some(it) = &ARGS.redact {
+        slow_scan_print_redacted(reader, writer, it, &mut units)
+            .unwrap_or_else(|it| {
+                eprintln!(
+                    "{}",
+                    t!("error.io_error_on_slow_scan_print", error = it)
+                );
+            });
+
+        return units;
+    }
+
+    if ARGS.diff_color {
+        slow_scan_print_diff(reader, writer, &mut units).unwrap_or_else(|it| {
+            eprintln!(
+                "{}",
+                t!("error.io_error_on_slow_scan_print", error = it)
+            );
+        });
+
+        return units;
+    }
+
+    if let Some(it) = &ARGS.timing {
+        let mut content = Vec::new();
+
+        if let Err(it) = reader.read_to_end(&mut content) {
+            eprintln!(
+                "{}",
+                t!("error.io_error_on_slow_scan_print", error = it)
+            );
+            return units;
+        }
+
+        // 时间文件与输入长度不一致时库会在写入前返回 `InvalidInput`
+        writer
+            .slow_scan_write_timed(&content, it)
+            .unwrap_or_else(|it| {
+                eprintln!(
+                    "{}",
+                    t!("error.io_error_on_slow_scan_print", error = it)
+                );
+
+                if it.kind() == io::ErrorKind::InvalidInput {
+                    exit(1)
+                }
+            });
+
+        return it.len();
+    }
+
+    if let Some(frames) = ARGS.frames {
+        let mut content = String::new();
+
+        if let Err(it) = reader.read_to_string(&mut content) {
+            eprintln!(
+                "{}",
+                t!("error.io_error_on_slow_scan_print", error = it)
+            );
+            return units;
+        }
+
+        writer
+            .slow_scan_write_frames(&content, frames, ARGS.frame_time)
+            .unwrap_or_else(|it| {
+                eprintln!(
+                    "{}",
+                    t!("error.io_error_on_slow_scan_print", error = it)
+                );
+            });
+
+        return content.chars().count();
+    }
+
+    if ARGS.build_graphemes {
+        let mut content = String::new();
+
+        if let Err(it) = reader.read_to_string(&mut content) {
+            eprintln!(
+                "{}",
+                t!("error.io_error_on_slow_scan_print", error = it)
+            );
+            return units;
+        }
+
+        writer
+            .slow_scan_write_building_graphemes(&content, slow_scan_config())
+            .unwrap_or_else(|it| {
+                eprintln!(
+                    "{}",
+                    t!("error.io_error_on_slow_scan_print", error = it)
+                );
+            });
+
+        return content.graphemes(true).count();
+    }
+
+    if let Some(script) = &ARGS.script {
+        writer
+            .slow_scan_write_script(script, slow_scan_config())
+            .unwrap_or_else(|it| {
+                eprintln!(
+                    "{}",
+                    t!("error.io_error_on_slow_scan_print", error = it)
+                );
+            });
+
+        return script.iter().map(|it| it.text.chars().count()).sum();
+    }
+
+    if let Some(line_time) = ARGS.equal_line_time.filter(|_| ARGS.line_mode) {
+        let mut content = String::new();
+
+        if let Err(it) = reader.read_to_string(&mut content) {
+            eprintln!(
+                "{}",
+                t!("error.io_error_on_slow_scan_print", error = it)
+            );
+            return units;
+        }
+
+        // 逐行模式下的延迟即每行之后的停顿
+        let gap = slow_scan_config()
+            .inter_chunk_delay()
+            .unwrap_or(*slow_scan_config().base_delay());
+
+        writer
+            .slow_scan_write_equal_lines(
+                &content,
+                line_time,
+                gap,
+                slow_scan_config()
+            )
+            .unwrap_or_else(|it| {
+                eprintln!(
+                    "{}",
+                    t!("error.io_error_on_slow_scan_print", error = it)
+                );
+            });
+
+        return content.split_inclusive('\n').count();
+    }
+
+    if let Some(schedule) = &ARGS.schedule {
+        let mut content = String::new();
+
+        if let Err(it) = reader.read_to_string(&mut content) {
+            eprintln!(
+                "{}",
+                t!("error.io_error_on_slow_scan_print", error = it)
+            );
+            return units;
+        }
+
+        writer
+            .slow_scan_write_scheduled(&content, schedule, slow_scan_config())
+            .unwrap_or_else(|it| {
+                eprintln!(
+                    "{}",
+                    t!("error.io_error_on_slow_scan_print", error = it)
+                );
+            });
+
+        return content.chars().count();
+    }
 
-    if ARGS.line_mode {
-        let iter = reader.lines().map(|it| {
+    #[cfg(feature = "markdown")]
+    if ARGS.markdown {
+        let mut content = String::new();
+
+        if let Err(it) = reader.read_to_string(&mut content) {
+            eprintln!(
+                "{}",
+                t!("error.io_error_on_slow_scan_print", error = it)
+            );
+            return units;
+        }
+
+        writer
+            .slow_scan_write_markdown(&content, slow_scan_config())
+            .unwrap_or_else(|it| {
+                eprintln!(
+                    "{}",
+                    t!("error.io_error_on_slow_scan_print", error = it)
+                );
+            });
+
+        return content.chars().count();
+    }
+
+    // 以下的输出方式按字符统计；逐词模式的延迟不对应字符，不做统计
+    if !ARGS.word_mode {
+        start_profile();
+    }
+
+    if let Some(width) = align_width().filter(|_| !ARGS.hex && !ARGS.line_mode)
+    {
+        slow_scan_print_aligned(reader, writer, width, &mut units)
+            .unwrap_or_else(|it| {
+                eprintln!(
+                    "{}",
+                    t!("error.io_error_on_slow_scan_print", error = it)
+                );
+            });
+
+        return units;
+    }
+
+    if ARGS.hex {
+        let iter = HexDump::new(reader).map_while(|it| {
+            it.map_err(|it| {
+                eprintln!(
+                    "{}",
+                    t!("error.io_error_on_slow_scan_print", error = it)
+                );
+            })
+            .ok()
+        });
+
+        writer.slow_scan_write_by_chunks(
+            paged(iter).inspect(|it| {
+                units += 1;
+                record_chunk(it.as_bytes());
+            }),
+            slow_scan_config()
+        )
+    } else if ARGS.line_mode {
+        let max_line_bytes = ARGS.max_line_bytes.unwrap_or(usize::MAX);
+        let mut logger = ARGS.log_target.map(|it| {
+            LineLogger::open(it).unwrap_or_else(|it| {
+                eprintln!("{}", t!("error.cannot_open_log", error = it));
+                exit(1);
+            })
+        });
+        let unicode_lines = *slow_scan_config().unicode_lines();
+        let mut lines = BoundedLines::new(reader, max_line_bytes);
+        lines.set_unicode_lines(unicode_lines);
+        let iter = lines.map(|it| {
             let mut it = it.unwrap_or_else(|_| String::new());
-            it.push_str(&LINE_ENDING);
+            let separator =
+                unicode_lines && it.ends_with(['\u{2028}', '\u{2029}']);
+            // 超长行切分出的中间段既不去除空白也不追加换行符
+            let complete =
+                it.ends_with('\n') || separator || it.len() < max_line_bytes;
+            if it.ends_with('\n') {
+                it.pop();
+                if it.ends_with('\r') {
+                    it.pop();
+                }
+            } else if separator {
+                it.pop();
+            }
+            if ARGS.strip_trailing_whitespace && complete {
+                it.truncate(it.trim_end_matches([' ', '\t']).len());
+            }
+            if let Some(width) = ARGS.wrap {
+                it = word_wrap_with_config(
+                    it.chars(),
+                    width,
+                    &slow_scan_config()
+                )
+                .collect();
+            }
+            if let Some(width) = align_width() {
+                it = it
+                    .split('\n')
+                    .map(|it| {
+                        let padding = align_padding(it, width, ARGS.align);
+                        format!("{}{}", " ".repeat(padding), it)
+                    })
+                    .collect::<Vec<_>>()
+                    .join("\n");
+            }
+            if complete {
+                it.push_str(&LINE_ENDING);
+            }
             it
         });
+        let iter = iter.inspect(|it| {
+            if let Some(logger) = &mut logger {
+                let delay = slow_scan_config().chunk_delay(it.as_bytes());
+                logger.log(it, delay);
+            }
+        });
 
-        STDOUT
-            .clone()
-            .slow_scan_write_by_chunks(iter, ARGS.slow_scan_config)
+        writer.slow_scan_write_by_chunks(
+            paged(iter).inspect(|it| {
+                units += 1;
+                record_chunk(it.as_bytes());
+            }),
+            slow_scan_config()
+        )
     } else {
         let iter = reader.chars().map(|it| it.unwrap());
+        let iter: Box<dyn Iterator<Item = char>> = if ARGS.collapse_cr {
+            Box::new(collapse_cr(iter))
+        } else {
+            Box::new(iter)
+        };
+        let iter: Box<dyn Iterator<Item = char>> = match ARGS.wrap {
+            Some(width) => Box::new(word_wrap_with_config(
+                iter,
+                width,
+                &slow_scan_config()
+            )),
+            None => Box::new(iter)
+        };
 
-        STDOUT
-            .clone()
-            .slow_scan_write_by_chars(iter, ARGS.slow_scan_config)
+        // 只在需要时记录，避免长时间运行时记录无限增长
+        let mut system = SystemClock;
+        let mut trace = ARGS.trace.is_some().then(|| lock(&TRACE));
+        let clock: &mut dyn Clock = match trace.as_deref_mut() {
+            Some(it) => it,
+            None => &mut system
+        };
+
+        if ARGS.word_mode {
+            writer.slow_scan_write_by_words_with_clock(
+                iter.inspect(|_| units += 1),
+                slow_scan_config(),
+                clock
+            )
+        } else if let Some(trail) = trail_length() {
+            writer.slow_scan_write_trail_with_clock(
+                iter.inspect(|it| {
+                    units += 1;
+                    record_char(*it);
+                }),
+                trail,
+                slow_scan_config(),
+                clock
+            )
+        } else {
+            let mut pager = new_pager();
+
+            writer.slow_scan_write_by_chars_with_hook_and_clock(
+                iter.inspect(|_| units += 1),
+                slow_scan_config(),
+                |writer, it| {
+                    record_char(it);
+
+                    if pager.as_mut().is_some_and(|pager| pager.advance(it)) {
+                        writer.flush()?;
+                        wait_for_key();
+                    }
+                    Ok(())
+                },
+                clock
+            )
+        }
     }
     .unwrap_or_else(|it| {
         eprintln!("{}", t!("error.io_error_on_slow_scan_print", error = it));
     });
+
+    units
+}
+
+/// 输出为终端且没有设置 `NO_COLOR` 时返回拖尾的长度
+///
+/// ---
+///
+/// Return the length of the trail when the output is a terminal and
+/// `NO_COLOR` is not set
+#[inline]
+fn trail_length() -> Option<usize> {
+    let no_color = env::var_os("NO_COLOR").is_some_and(|it| !it.is_empty());
+
+    ARGS.trail.filter(|_| STDOUT.is_term() && !no_color)
+}
+
+/// 输出为终端且需要对齐时返回终端宽度
+///
+/// ---
+///
+/// Return the terminal width when the output is a terminal and alignment is
+/// requested
+#[inline]
+fn align_width() -> Option<usize> {
+    (ARGS.align != Align::Left && STDOUT.is_term())
+        .then(|| STDOUT.size().1.into())
+}
+
+/// 逐行对齐并逐字符打印，每行的前导空格立即写入
+///
+/// ---
+///
+/// Print character by character with every line aligned, writing the leading
+/// spaces of each line immediately
+fn slow_scan_print_aligned<R: BufRead, W: Write>(
+    reader: R,
+    writer: &mut W,
+    width: usize,
+    units: &mut usize
+) -> io::Result<()> {
+    let mut lines = reader.lines().peekable();
+    // 每行单独输出，因此单元数限制需要跨行计算
+    let mut remaining = slow_scan_config().head_units().unwrap_or(usize::MAX);
+
+    while let Some(it) = lines.next() {
+        let mut line = it.unwrap_or_else(|_| String::new());
+        if let Some(width) = ARGS.wrap {
+            line =
+                word_wrap_with_config(line.chars(), width, &slow_scan_config())
+                    .collect();
+        }
+
+        let mut rows = line.split('\n').peekable();
+
+        while let Some(it) = rows.next() {
+            if remaining == 0 {
+                return Ok(());
+            }
+
+            let padding = align_padding(it, width, ARGS.align);
+            writer.write_all(" ".repeat(padding).as_bytes())?;
+
+            let row = it.chars().chain(['\n']).take(remaining).count();
+            remaining -= row;
+
+            let mut config = slow_scan_config();
+            config.set_head_units(Some(row));
+            if remaining > 0
+                && (rows.peek().is_some() || lines.peek().is_some())
+            {
+                config.set_tail_delay(true);
+            }
+
+            writer.slow_scan_write_by_chars(
+                it.chars().chain(['\n']).take(row).inspect(|it| {
+                    *units += 1;
+                    record_char(*it);
+                }),
+                config
+            )?;
+        }
+    }
+
+    Ok(())
+}
+
+/// 逐行为统一差异格式的输入着色并打印
+///
+/// 新增和删除的行逐字符显示，文件头和块头整行立即显示，不像差异的内容按普通
+/// 方式逐字符显示
+///
+/// ---
+///
+/// Print input in the unified diff format line by line with colors
+///
+/// Added and removed lines are revealed character by character, file and hunk
+/// headers appear at once, and content that does not look like a diff is
+/// revealed character by character as usual
+fn slow_scan_print_diff<R: BufRead, W: Write>(
+    reader: R,
+    writer: &mut W,
+    units: &mut usize
+) -> io::Result<()> {
+    let mut classifier = DiffClassifier::new();
+    let mut lines = reader.lines().peekable();
+
+    while let Some(it) = lines.next() {
+        let line = it.unwrap_or_else(|_| String::new());
+        let kind = classifier.classify(&line);
+
+        let mut config = slow_scan_config();
+        config.set_tail_delay(true);
+        if matches!(kind, DiffLineKind::Header | DiffLineKind::Hunk) {
+            config
+                .set_base_delay(Duration::ZERO)
+                .set_full_width_delay(Duration::ZERO)
+                .set_control_char_delay(Duration::ZERO);
+        }
+
+        if let Some(it) = kind.color() {
+            writer.write_all(it.as_bytes())?;
+        }
+        writer.slow_scan_write_by_chars(
+            line.chars().inspect(|_| *units += 1),
+            config
+        )?;
+        if kind.color().is_some() {
+            writer.write_all(b"\x1b[0m")?;
+        }
+
+        // 换行作为单独的块写入，之后停顿控制字符的延迟，与其他延迟一样受速度
+        // 倍率、抖动和对齐粒度的影响
+        let mut config = slow_scan_config();
+        config
+            .set_inter_chunk_delay(Some(*config.control_char_delay()))
+            .set_chunk_reference_width(None)
+            .set_flush_each_unit(true);
+        if lines.peek().is_some() {
+            config.set_tail_delay(true);
+        }
+        writer.slow_scan_write_by_chunks(
+            iter::once(LINE_ENDING.as_bytes()),
+            config
+        )?;
+        *units += 1;
+    }
+
+    Ok(())
+}
+
+/// 按参数创建与终端尺寸相符的分页器
+///
+/// ---
+///
+/// Create a pager matching the terminal size as requested
+#[inline]
+fn new_pager() -> Option<Pager> {
+    (ARGS.page && !ARGS.benchmark && STDOUT.is_term()).then(|| {
+        let (rows, columns) = STDOUT.size();
+        Pager::new(columns.into(), rows.into())
+    })
+}
+
+/// 为逐块输出分页，在写入会使一屏溢出的块之前等待按键
+///
+/// ---
+///
+/// Page chunked output, waiting for a key before writing the chunk that would
+/// overflow the screen
+fn paged<I>(iter: I) -> impl Iterator<Item = String>
+where
+    I: Iterator<Item = String>
+{
+    let mut pager = new_pager();
+    let mut pending = false;
+
+    iter.inspect(move |it| {
+        if pending {
+            wait_for_key();
+        }
+
+        pending = pager.as_mut().is_some_and(|pager| {
+            it.chars().filter(|it| pager.advance(*it)).count() > 0
+        });
+    })
+}
+
+/// 判断开始显示之前是否等待按键
+///
+/// 标准输入不是终端时（例如通过管道提供输入）无法读取按键，不等待
+///
+/// ---
+///
+/// Determine whether to wait for a key press before revealing
+///
+/// Key presses cannot be read when stdin is not a terminal (e.g. the input is
+/// piped in), so no wait happens
+fn should_wait_start(requested: bool, stdin_is_term: bool) -> bool {
+    requested && stdin_is_term
+}
+
+/// 等待按下任意键，按下 Ctrl+C 时恢复终端并退出
+///
+/// ---
+///
+/// Wait for any key press, restoring the terminal and exiting on Ctrl+C
+fn wait_for_key() {
+    if STDOUT
+        .read_key()
+        .is_err_and(|it| it.kind() == io::ErrorKind::Interrupted)
+    {
+        exit_restoring_terminal()
+    }
+}
+
+/// 逐行打印并在每行显示完成后遮盖匹配 `redact` 的内容
+///
+/// 逐行模式下整行立即显示，否则逐字符显示
+///
+/// ---
+///
+/// Print line by line, masking the content matching `redact` after each line
+/// is revealed
+///
+/// In line mode the whole line appears at once, otherwise it is revealed
+/// character by character
+#[cfg(feature = "regex")]
+fn slow_scan_print_redacted<R: BufRead, W: Write>(
+    reader: R,
+    writer: &mut W,
+    redact: &Regex,
+    units: &mut usize
+) -> io::Result<()> {
+    let mut config = slow_scan_config();
+    let line_delay = if ARGS.line_mode {
+        let line_delay = *config.base_delay();
+        config
+            .set_base_delay(Duration::ZERO)
+            .set_full_width_delay(Duration::ZERO)
+            .set_control_char_delay(Duration::ZERO);
+        line_delay
+    } else {
+        *config.control_char_delay()
+    };
+
+    let mut clock = SystemClock;
+    let mut lines = reader.lines().peekable();
+
+    while let Some(it) = lines.next() {
+        let line = it.unwrap_or_else(|_| String::new());
+        let spans = redact
+            .find_iter(&line)
+            .map(|it| it.range())
+            .collect::<Vec<_>>();
+
+        writer.slow_scan_write_redacted_with_clock(
+            &line,
+            &spans,
+            ARGS.redact_hold,
+            config,
+            &mut clock
+        )?;
+        writer.write_all(LINE_ENDING.as_bytes())?;
+        writer.flush()?;
+
+        *units += if ARGS.line_mode {
+            1
+        } else {
+            line.chars().count() + 1
+        };
+
+        let delay = match lines.peek() {
+            Some(_) => Some(line_delay),
+            None => config.tail_behavior().delay(line_delay)
+        };
+        if let Some(it) = delay {
+            let deadline = clock.now() + it;
+            clock.sleep_until(deadline);
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::{Duration, Instant};
+
+    use super::*;
+
+    /// 不实际休眠、只记录每次等待截止时间的时钟
+    ///
+    /// ---
+    ///
+    /// Clock recording the deadline of every wait without actually sleeping
+    struct VirtualClock {
+        now: Instant,
+        deadlines: Vec<Instant>
+    }
+
+    impl VirtualClock {
+        fn new(now: Instant) -> Self {
+            Self {
+                now,
+                deadlines: Vec::new()
+            }
+        }
+    }
+
+    impl Clock for VirtualClock {
+        fn now(&mut self) -> Instant { self.now }
+
+        fn sleep_until(&mut self, deadline: Instant) {
+            self.now = self.now.max(deadline);
+            self.deadlines.push(deadline);
+        }
+    }
+
+    #[test]
+    fn alt_screen_only_on_request_and_terminal() {
+        assert_eq!(
+            alt_screen(true, true),
+            Some(("\x1b[?1049h", "\x1b[?1049l"))
+        );
+        assert_eq!(alt_screen(true, false), None);
+        assert_eq!(alt_screen(false, true), None);
+        assert_eq!(alt_screen(false, false), None);
+    }
+
+    #[test]
+    fn final_newline_only_when_missing() {
+        assert!(needs_final_newline(true, true, Some(b'c')));
+        assert!(!needs_final_newline(true, true, Some(b'\n')));
+        assert!(!needs_final_newline(true, true, None));
+    }
+
+    #[test]
+    fn final_newline_only_on_request_and_terminal() {
+        assert!(!needs_final_newline(false, true, Some(b'c')));
+        assert!(!needs_final_newline(true, false, Some(b'c')));
+    }
+
+    #[test]
+    fn wait_start_only_with_terminal_stdin() {
+        assert!(should_wait_start(true, true));
+        assert!(!should_wait_start(true, false));
+        assert!(!should_wait_start(false, true));
+    }
+
+    #[test]
+    fn cursor_hidden_only_on_request_and_terminal() {
+        assert!(hides_cursor(true, true));
+        assert!(!hides_cursor(true, false));
+        assert!(!hides_cursor(false, true));
+    }
+
+    #[test]
+    fn repeat_waits_between_runs() {
+        let start = Instant::now();
+        let mut clock = VirtualClock::new(start);
+        let mut runs = 0;
+
+        let (units, last_byte) =
+            repeat(3, Duration::from_secs(1), &mut clock, || {
+                runs += 1;
+                (2, Some(b'x'))
+            });
+
+        assert_eq!(runs, 3);
+        assert_eq!(units, 6);
+        assert_eq!(last_byte, Some(b'x'));
+        // 只在两次之间等待，第一次之前和最后一次之后都不等待
+        assert_eq!(
+            clock.deadlines,
+            [
+                start + Duration::from_secs(1),
+                start + Duration::from_secs(2)
+            ]
+        );
+    }
+
+    #[test]
+    fn repeat_zero_times_runs_once() {
+        let mut clock = VirtualClock::new(Instant::now());
+        let mut runs = 0;
+
+        repeat(0, Duration::from_secs(1), &mut clock, || {
+            runs += 1;
+            (1, None)
+        });
+
+        assert_eq!(runs, 1);
+        assert!(clock.deadlines.is_empty());
+    }
 }
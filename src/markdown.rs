@@ -0,0 +1,205 @@
+use std::io::{self, Write};
+
+use pulldown_cmark::{Event, Parser, Tag, TagEnd};
+
+use crate::{Clock, Pacer, SlowScanConfig, TailBehavior, write_by_chars};
+
+/// 标题相对于正文的加速倍率
+///
+/// ---
+///
+/// Speed-up factor of headings relative to body text
+const HEADING_SPEEDUP: u32 = 4;
+
+/// 块之间的停顿相当于多少个 `base_delay`
+///
+/// ---
+///
+/// Number of `base_delay`s the pause between blocks amounts to
+const BLOCK_PAUSE_UNITS: u32 = 10;
+
+/// 按 Markdown 结构调整节奏并显示文本
+///
+/// 标题以粗体按正文四分之一的延迟快速显示，段落按正常节奏显示，代码块和行内
+/// 代码以暗色立即写入，强调和加粗使用对应的 SGR 样式；顶层的块之间空一行并
+/// 停顿 10 个 `base_delay`。最后一段按节奏显示的文本之后按 `tail_delay` 处理
+///
+/// ---
+///
+/// Reveal text with its pacing adjusted to the Markdown structure
+///
+/// Headings are revealed quickly in bold at a quarter of the body delays,
+/// paragraphs at the normal pace, code blocks and inline code are written
+/// instantly in a dim style, and emphasis and strong emphasis use the matching
+/// SGR styles; top-level blocks are separated by a blank line and a pause of
+/// 10 `base_delay`s. The last paced text is followed by `tail_delay` as usual
+pub(crate) fn write_markdown<W, C>(
+    writer: &mut W,
+    s: &str,
+    config: SlowScanConfig,
+    clock: &mut C
+) -> io::Result<()>
+where
+    W: Write,
+    C: Clock + ?Sized
+{
+    let events = Parser::new(s).collect::<Vec<_>>();
+    let last_text = events.iter().rposition(|it| {
+        matches!(
+            it,
+            Event::Text(_)
+                | Event::Html(_)
+                | Event::InlineHtml(_)
+                | Event::SoftBreak
+                | Event::HardBreak
+        )
+    });
+
+    let mut body = config;
+    body.tail_delay = TailBehavior::LastUnit;
+    let heading = quick(body);
+
+    let mut blocks = 0;
+    let mut items = 0;
+    let mut in_heading = false;
+    let mut in_code = false;
+    let mut line_start = true;
+
+    for (i, event) in events.into_iter().enumerate() {
+        let block = matches!(
+            event,
+            Event::Start(
+                Tag::Paragraph
+                    | Tag::Heading { .. }
+                    | Tag::CodeBlock(_)
+                    | Tag::List(_)
+            ) | Event::Rule
+        );
+        // 列表项中的段落不算作顶层的块
+        if block && items == 0 {
+            if blocks > 0 {
+                writer.write_all(b"\n")?;
+                writer.flush()?;
+                Pacer::with_config(clock, &config)
+                    .sleep(config.base_delay * BLOCK_PAUSE_UNITS);
+            }
+            blocks += 1;
+        }
+
+        let text = match event {
+            Event::Start(Tag::Heading { .. }) => {
+                in_heading = true;
+                writer.write_all(b"\x1b[1m")?;
+                continue;
+            }
+            Event::End(TagEnd::Heading(_)) => {
+                in_heading = false;
+                writer.write_all(b"\x1b[22m\n")?;
+                line_start = true;
+                continue;
+            }
+            Event::Start(Tag::CodeBlock(_)) => {
+                in_code = true;
+                writer.write_all(b"\x1b[2m")?;
+                continue;
+            }
+            Event::End(TagEnd::CodeBlock) => {
+                in_code = false;
+                writer.write_all(b"\x1b[22m")?;
+                continue;
+            }
+            Event::Start(Tag::Emphasis) => {
+                writer.write_all(b"\x1b[3m")?;
+                continue;
+            }
+            Event::End(TagEnd::Emphasis) => {
+                writer.write_all(b"\x1b[23m")?;
+                continue;
+            }
+            Event::Start(Tag::Strong) => {
+                writer.write_all(b"\x1b[1m")?;
+                continue;
+            }
+            // 标题中的加粗结束后保持粗体
+            Event::End(TagEnd::Strong) if !in_heading => {
+                writer.write_all(b"\x1b[22m")?;
+                continue;
+            }
+            Event::Start(Tag::Item) => {
+                items += 1;
+                writer.write_all(b"- ")?;
+                line_start = false;
+                continue;
+            }
+            Event::End(TagEnd::Item) => {
+                items -= 1;
+                if !line_start {
+                    writer.write_all(b"\n")?;
+                    line_start = true;
+                }
+                continue;
+            }
+            Event::End(TagEnd::Paragraph) => {
+                writer.write_all(b"\n")?;
+                line_start = true;
+                continue;
+            }
+            Event::Code(it) => {
+                write!(writer, "\x1b[2m{}\x1b[22m", &*it)?;
+                line_start = false;
+                continue;
+            }
+            Event::Rule => {
+                writer.write_all(b"---\n")?;
+                line_start = true;
+                continue;
+            }
+            Event::Text(it) | Event::Html(it) | Event::InlineHtml(it) => {
+                it.to_string()
+            }
+            Event::SoftBreak | Event::HardBreak => String::from("\n"),
+            _ => continue
+        };
+
+        line_start = text.ends_with('\n');
+
+        if in_code {
+            writer.write_all(text.as_bytes())?;
+            writer.flush()?;
+            continue;
+        }
+
+        let mut pacing = if in_heading { heading } else { body };
+        if Some(i) == last_text {
+            pacing.tail_delay = config.tail_delay;
+        }
+        write_by_chars(
+            writer,
+            text.chars(),
+            pacing,
+            clock,
+            |_, _| Ok(()),
+            |_| {}
+        )?;
+    }
+
+    writer.flush()
+}
+
+/// 返回所有字符延迟都按 [`HEADING_SPEEDUP`] 缩短的配置
+///
+/// ---
+///
+/// Return the configuration with every character delay shortened by
+/// [`HEADING_SPEEDUP`]
+fn quick(config: SlowScanConfig) -> SlowScanConfig {
+    let mut config = config;
+    config.base_delay /= HEADING_SPEEDUP;
+    config.full_width_delay /= HEADING_SPEEDUP;
+    config.control_char_delay /= HEADING_SPEEDUP;
+    config.uppercase_delay =
+        config.uppercase_delay.map(|it| it / HEADING_SPEEDUP);
+    config.space_delay = config.space_delay.map(|it| it / HEADING_SPEEDUP);
+    config.pua_delay = config.pua_delay.map(|it| it / HEADING_SPEEDUP);
+    config
+}
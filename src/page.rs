@@ -0,0 +1,96 @@
+use unicode_width::UnicodeWidthChar;
+
+use crate::ansi::AnsiState;
+
+/// 记录已显示的行数并判断何时一屏已满的分页器
+///
+/// 按终端的宽度和高度统计输出占用的行数，长行按宽度折行计算，ANSI 转义序列
+/// 不计入显示宽度。当已写满的行数达到高度减一（为光标所在行留出位置）时报告
+/// 需要暂停，之后重新开始计数
+///
+/// # 示例
+/// ```
+/// use slow_scan_print::Pager;
+///
+/// let mut pager = Pager::new(80, 3);
+///
+/// let pauses = "1\n2\n3\n4\n5\n"
+///     .chars()
+///     .enumerate()
+///     .filter(|(_, it)| pager.advance(*it))
+///     .map(|(i, _)| i)
+///     .collect::<Vec<_>>();
+///
+/// assert_eq!(pauses, [3, 7]);
+/// ```
+///
+/// ---
+///
+/// Pager counting the displayed rows and telling when a screen is full
+///
+/// Rows taken by the output are counted against the width and height of the
+/// terminal, long lines wrap at the width, and ANSI escape sequences do not
+/// count toward the display width. A pause is reported once the completed
+/// rows reach the height minus one (leaving room for the cursor row), after
+/// which counting starts over
+#[derive(Debug, Clone)]
+pub struct Pager {
+    width: usize,
+    height: usize,
+    column: usize,
+    rows: usize,
+    ansi: AnsiState
+}
+
+impl Pager {
+    /// 创建指定屏幕宽度和高度的分页器
+    ///
+    /// ---
+    ///
+    /// Create a pager for a screen of the given width and height
+    pub fn new(width: usize, height: usize) -> Self {
+        Self {
+            width: width.max(1),
+            height: height.max(2),
+            column: 0,
+            rows: 0,
+            ansi: AnsiState::default()
+        }
+    }
+
+    /// 记录一个已写入的字符，返回是否应在继续之前暂停
+    ///
+    /// ---
+    ///
+    /// Record a written character, returning whether to pause before going on
+    pub fn advance(&mut self, c: char) -> bool {
+        if self.ansi.advance(c) {
+            return false;
+        }
+
+        match c {
+            '\n' => {
+                self.rows += 1;
+                self.column = 0;
+            }
+            '\r' => self.column = 0,
+            _ => {
+                let width = c.width_cjk().unwrap_or(0);
+
+                if self.column + width > self.width {
+                    self.rows += 1;
+                    self.column = 0;
+                }
+
+                self.column += width;
+            }
+        }
+
+        if self.rows + 1 >= self.height {
+            self.rows = 0;
+            return true;
+        }
+
+        false
+    }
+}
@@ -0,0 +1,274 @@
+use std::time::Duration;
+
+/// 决定延迟的字符类型
+///
+/// ---
+///
+/// Character class deciding the delay
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CharClass {
+    /// 半角字符，使用 `base_delay`
+    ///
+    /// ---
+    ///
+    /// Half-width character, using `base_delay`
+    HalfWidth,
+    /// 全角字符，使用 `full_width_delay`
+    ///
+    /// ---
+    ///
+    /// Full-width character, using `full_width_delay`
+    FullWidth,
+    /// 控制字符，使用 `control_char_delay`
+    ///
+    /// ---
+    ///
+    /// Control character, using `control_char_delay`
+    Control
+}
+
+impl CharClass {
+    /// 全部字符类型
+    ///
+    /// ---
+    ///
+    /// Every character class
+    pub const ALL: [Self; 3] =
+        [Self::HalfWidth, Self::FullWidth, Self::Control];
+
+    /// 按显示宽度判断字符类型，`None` 表示控制字符
+    ///
+    /// ---
+    ///
+    /// Classify by display width, `None` meaning a control character
+    pub(crate) fn from_width(width: Option<usize>) -> Self {
+        match width {
+            Some(2) => Self::FullWidth,
+            None => Self::Control,
+            _ => Self::HalfWidth
+        }
+    }
+
+    /// 返回该类型在 JSON 统计信息中的键名
+    ///
+    /// ---
+    ///
+    /// Return the key of this class in the JSON statistics
+    fn key(self) -> &'static str {
+        match self {
+            Self::HalfWidth => "half_width",
+            Self::FullWidth => "full_width",
+            Self::Control => "control"
+        }
+    }
+
+    fn index(self) -> usize {
+        match self {
+            Self::HalfWidth => 0,
+            Self::FullWidth => 1,
+            Self::Control => 2
+        }
+    }
+}
+
+/// 按字符类型统计数量和累计延迟
+///
+/// # 示例
+/// ```
+/// use std::time::Duration;
+///
+/// use slow_scan_print::{CharClass, Profile, SlowScanConfig};
+///
+/// let config = SlowScanConfig::default();
+/// let mut profile = Profile::new();
+///
+/// for it in "ab世界\n".chars() {
+///     let class = config.char_class(it);
+///     profile.record(class, config.class_delay(class));
+/// }
+///
+/// assert_eq!(profile.count(CharClass::HalfWidth), 2);
+/// assert_eq!(profile.count(CharClass::FullWidth), 2);
+/// assert_eq!(profile.count(CharClass::Control), 1);
+/// assert_eq!(
+///     profile.total_delay(CharClass::FullWidth),
+///     *config.full_width_delay() * 2
+/// );
+/// ```
+///
+/// ---
+///
+/// Count and accumulated delay per character class
+#[derive(Debug, Clone, Default)]
+pub struct Profile {
+    counts: [usize; 3],
+    delays: [Duration; 3]
+}
+
+impl Profile {
+    pub const fn new() -> Self {
+        Self {
+            counts: [0; 3],
+            delays: [Duration::ZERO; 3]
+        }
+    }
+
+    /// 记录一个字符及其延迟
+    ///
+    /// ---
+    ///
+    /// Record a character and its delay
+    pub fn record(&mut self, class: CharClass, delay: Duration) {
+        self.counts[class.index()] += 1;
+        self.delays[class.index()] += delay;
+    }
+
+    /// 返回某类字符的数量
+    ///
+    /// ---
+    ///
+    /// Return the number of characters of a class
+    pub fn count(&self, class: CharClass) -> usize {
+        self.counts[class.index()]
+    }
+
+    /// 返回某类字符的累计延迟
+    ///
+    /// ---
+    ///
+    /// Return the accumulated delay of a class
+    pub fn total_delay(&self, class: CharClass) -> Duration {
+        self.delays[class.index()]
+    }
+}
+
+/// 一次运行的统计信息，可输出为单行 JSON 供脚本解析
+///
+/// JSON 包含 `elapsed`（总耗时）、`units`（单元数）、`total_sleep`（累计延迟）
+/// 和按字符类型分类的 `classes`，每类包含 `count` 和 `delay`；时间均以秒为
+/// 单位。没有按字符类型统计时省略 `total_sleep` 和 `classes`
+///
+/// # 示例
+/// ```
+/// use std::time::Duration;
+///
+/// use slow_scan_print::{Profile, RunStats, SlowScanConfig};
+///
+/// let config = SlowScanConfig::default();
+/// let mut profile = Profile::new();
+///
+/// for it in "ab世\n".chars() {
+///     let class = config.char_class(it);
+///     profile.record(class, config.class_delay(class));
+/// }
+///
+/// let stats = RunStats::new(Duration::from_millis(1500), 4, profile);
+///
+/// assert_eq!(stats.total_sleep(), Some(Duration::from_millis(80)));
+/// assert_eq!(
+///     stats.to_json(),
+///     concat!(
+///         r#"{"elapsed":1.5,"units":4,"total_sleep":0.08,"classes":{"#,
+///         r#""half_width":{"count":2,"delay":0.04},"#,
+///         r#""full_width":{"count":1,"delay":0.04},"#,
+///         r#""control":{"count":1,"delay":0}}}"#
+///     )
+/// );
+///
+/// let stats = RunStats::without_profile(Duration::from_secs(2), 3);
+///
+/// assert_eq!(stats.total_sleep(), None);
+/// assert_eq!(stats.to_json(), r#"{"elapsed":2,"units":3}"#);
+/// ```
+///
+/// ---
+///
+/// Statistics of a run, which can be output as a single line of JSON for
+/// scripts to parse
+///
+/// The JSON contains `elapsed` (the total duration), `units` (the number of
+/// units), `total_sleep` (the accumulated delay) and `classes` broken down by
+/// character class, each with a `count` and a `delay`; times are all in
+/// seconds. `total_sleep` and `classes` are omitted when nothing was counted
+/// per character class
+#[derive(Debug, Clone)]
+pub struct RunStats {
+    elapsed: Duration,
+    units: usize,
+    profile: Option<Profile>
+}
+
+impl RunStats {
+    pub fn new(elapsed: Duration, units: usize, profile: Profile) -> Self {
+        Self {
+            elapsed,
+            units,
+            profile: Some(profile)
+        }
+    }
+
+    /// 创建没有按字符类型统计的统计信息
+    ///
+    /// ---
+    ///
+    /// Create statistics without counts per character class
+    pub fn without_profile(elapsed: Duration, units: usize) -> Self {
+        Self {
+            elapsed,
+            units,
+            profile: None
+        }
+    }
+
+    /// 返回所有字符类型的累计延迟之和，没有按字符类型统计时返回 `None`
+    ///
+    /// ---
+    ///
+    /// Return the sum of the accumulated delays of every character class,
+    /// `None` when nothing was counted per character class
+    pub fn total_sleep(&self) -> Option<Duration> {
+        self.profile.as_ref().map(|profile| {
+            CharClass::ALL
+                .iter()
+                .map(|it| profile.total_delay(*it))
+                .sum()
+        })
+    }
+
+    /// 将统计信息输出为单行 JSON
+    ///
+    /// ---
+    ///
+    /// Output the statistics as a single line of JSON
+    pub fn to_json(&self) -> String {
+        let head = format!(
+            r#"{{"elapsed":{},"units":{}"#,
+            self.elapsed.as_secs_f64(),
+            self.units
+        );
+
+        let Some(profile) = &self.profile else {
+            return head + "}";
+        };
+
+        let classes = CharClass::ALL
+            .iter()
+            .map(|it| {
+                format!(
+                    r#""{}":{{"count":{},"delay":{}}}"#,
+                    it.key(),
+                    profile.count(*it),
+                    profile.total_delay(*it).as_secs_f64()
+                )
+            })
+            .collect::<Vec<_>>()
+            .join(",");
+
+        format!(
+            r#"{},"total_sleep":{},"classes":{{{}}}}}"#,
+            head,
+            self.total_sleep().unwrap_or_default().as_secs_f64(),
+            classes
+        )
+    }
+}
@@ -0,0 +1,92 @@
+use std::fs::File;
+use std::io;
+use std::path::PathBuf;
+
+use nix::pty::openpty;
+use nix::sys::termios::{SetArg, cfmakeraw, tcgetattr, tcsetattr};
+use nix::unistd::ttyname;
+
+/// 伪终端（PTY）
+///
+/// 写入主设备的内容可以从从设备读取，使从设备一端的程序认为自己连接着终端。
+/// 从设备处于原始模式，写入的字节会原样到达，不会被回显或按行缓冲
+///
+/// # 示例
+/// ```
+/// use std::io::Read;
+/// use std::time::{Duration, Instant};
+///
+/// use slow_scan_print::{Pty, SlowScanConfig, SlowScanWrite};
+///
+/// let pty = Pty::open().unwrap();
+///
+/// let mut config = SlowScanConfig::default();
+/// config.set_base_delay(Duration::from_millis(20));
+///
+/// let start = Instant::now();
+/// pty.master()
+///     .slow_scan_write_by_chars("hi\n".chars(), config)
+///     .unwrap();
+/// assert!(start.elapsed() >= Duration::from_millis(40));
+///
+/// let mut buf = [0; 3];
+/// pty.slave().read_exact(&mut buf).unwrap();
+/// assert_eq!(&buf, b"hi\n");
+/// ```
+///
+/// ---
+///
+/// Pseudo-terminal (PTY)
+///
+/// What is written to the master can be read from the slave, so that the
+/// program on the slave side believes it is attached to a terminal. The slave
+/// is in raw mode, so written bytes arrive unchanged, without being echoed or
+/// buffered by line
+#[derive(Debug)]
+pub struct Pty {
+    master: File,
+    slave: File
+}
+
+impl Pty {
+    /// 分配一个新的伪终端并将从设备设为原始模式
+    ///
+    /// ---
+    ///
+    /// Allocate a new pseudo-terminal and put the slave into raw mode
+    pub fn open() -> io::Result<Self> {
+        let pty = openpty(None, None)?;
+
+        let mut termios = tcgetattr(&pty.slave)?;
+        cfmakeraw(&mut termios);
+        tcsetattr(&pty.slave, SetArg::TCSANOW, &termios)?;
+
+        Ok(Self {
+            master: File::from(pty.master),
+            slave: File::from(pty.slave)
+        })
+    }
+
+    /// 返回主设备，写入其中的内容会出现在从设备
+    ///
+    /// ---
+    ///
+    /// Return the master, whatever is written to it shows up on the slave
+    pub fn master(&self) -> &File { &self.master }
+
+    /// 返回从设备
+    ///
+    /// ---
+    ///
+    /// Return the slave
+    pub fn slave(&self) -> &File { &self.slave }
+
+    /// 返回从设备的路径，供下游程序打开
+    ///
+    /// ---
+    ///
+    /// Return the path of the slave for downstream programs to open
+    pub fn slave_path(&self) -> io::Result<PathBuf> {
+        Ok(ttyname(&self.slave)?)
+    }
+}
@@ -0,0 +1,213 @@
+use std::io::{self, Write};
+use std::time::Instant;
+
+use crate::{Clock, SlowScanConfig};
+
+/// 屏幕上的一个区域，以左上角的行和列表示，均从 0 开始
+///
+/// 写入区域的字符从左上角开始向右排列，遇到 `\n` 时回到区域左边界的下一行。
+/// 设置了高度的区域写满之后回到第一行继续，每行开始写入之前先清除该行从区域
+/// 左边界到行尾的内容
+///
+/// ---
+///
+/// Region of the screen, given by the row and column of its top left corner,
+/// both starting from 0
+///
+/// Characters written to the region flow right from the top left corner, and
+/// `\n` moves back to the left edge of the region on the next row. A region
+/// with a height starts over from its first row once full, clearing each row
+/// from the left edge of the region to the end of the line before writing to
+/// it
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct Region {
+    row: usize,
+    column: usize,
+    height: Option<usize>
+}
+
+impl Region {
+    /// 创建左上角位于 `row` 行 `column` 列的区域，高度不限
+    ///
+    /// ---
+    ///
+    /// Create a region whose top left corner is at `row` and `column`, with
+    /// an unlimited height
+    pub const fn new(row: usize, column: usize) -> Self {
+        Self {
+            row,
+            column,
+            height: None
+        }
+    }
+
+    /// 将区域的高度限制为 `height` 行，为 0 时不限制
+    ///
+    /// ---
+    ///
+    /// Limit the height of the region to `height` rows, no limit when 0
+    pub const fn with_height(mut self, height: usize) -> Self {
+        self.height = if height == 0 { None } else { Some(height) };
+        self
+    }
+
+    /// 将 `rows` 行高的屏幕从上到下平均分为 `count` 个横向的区域
+    ///
+    /// 除不尽时靠后的区域多分到一行；行数少于区域数量时部分区域的高度为 0，
+    /// 即不限制高度
+    ///
+    /// # 示例
+    /// ```
+    /// use std::time::Duration;
+    ///
+    /// use slow_scan_print::{Region, SlowScanConfig, SlowScanWrite};
+    ///
+    /// assert_eq!(
+    ///     Region::bands(3, 10),
+    ///     [
+    ///         Region::new(0, 0).with_height(3),
+    ///         Region::new(3, 0).with_height(3),
+    ///         Region::new(6, 0).with_height(4)
+    ///     ]
+    /// );
+    ///
+    /// // 4 行高的屏幕分给两个文件，第一个文件写满两行后回到第一行
+    /// // A screen 4 rows high is shared by two files, and the first one starts
+    /// // over from its first row once its two rows are full
+    /// let bands = Region::bands(2, 4);
+    /// let mut config = SlowScanConfig::default();
+    /// config.set_base_delay(Duration::from_millis(1));
+    ///
+    /// let mut writer = Vec::new();
+    /// writer
+    ///     .slow_scan_write_interleaved(
+    ///         [("a\nb\nc".chars(), bands[0]), ("x".chars(), bands[1])],
+    ///         config
+    ///     )
+    ///     .unwrap();
+    ///
+    /// assert_eq!(
+    ///     writer,
+    ///     b"\x1b[1;1H\x1b[Ka\x1b[3;1H\x1b[Kx\x1b[2;1H\x1b[Kb\x1b[1;1H\x1b[Kc"
+    /// );
+    /// ```
+    ///
+    /// ---
+    ///
+    /// Split a screen `rows` rows high evenly into `count` horizontal regions
+    /// from top to bottom
+    ///
+    /// Later regions get one more row when it does not divide evenly; when
+    /// there are fewer rows than regions some regions have a height of 0, that
+    /// is an unlimited height
+    pub fn bands(count: usize, rows: usize) -> Vec<Self> {
+        (0..count)
+            .map(|i| {
+                let top = i * rows / count;
+                let bottom = (i + 1) * rows / count;
+                Self::new(top, 0).with_height(bottom - top)
+            })
+            .collect()
+    }
+}
+
+/// 交错写入中的一个流及其写入位置和下一个字符的到期时间
+///
+/// ---
+///
+/// A stream of an interleaved write along with its writing position and the
+/// due time of its next character
+struct Stream<I> {
+    iter: I,
+    region: Region,
+    row: usize,
+    column: usize,
+    due: Instant
+}
+
+/// 交错写入多个流的实现，每次写入到期时间最早的流的下一个字符
+///
+/// ---
+///
+/// Implementation of writing several streams interleaved, each time writing
+/// the next character of the stream due earliest
+pub(crate) fn write_interleaved<W, S, I, C>(
+    writer: &mut W,
+    streams: S,
+    config: SlowScanConfig,
+    clock: &mut C
+) -> io::Result<()>
+where
+    W: Write + ?Sized,
+    S: IntoIterator<Item = (I, Region)>,
+    I: Iterator<Item = char>,
+    C: Clock + ?Sized
+{
+    let start = clock.now();
+    let mut streams = streams
+        .into_iter()
+        .map(|(iter, region)| Stream {
+            iter,
+            region,
+            row: region.row,
+            column: region.column,
+            due: start
+        })
+        .collect::<Vec<_>>();
+    let mut last = start;
+    let mut last_written = start;
+    let mut buf = [0; 4];
+
+    // 到期时间相同时 `min_by_key` 返回靠前的流
+    while let Some(index) = (0..streams.len()).min_by_key(|it| streams[*it].due)
+    {
+        let stream = &mut streams[index];
+        let Some(it) = stream.iter.next() else {
+            streams.remove(index);
+            continue;
+        };
+
+        if stream.due > clock.now() {
+            clock.sleep_until(stream.due);
+        }
+
+        let region = stream.region;
+        if it == '\n' {
+            stream.row += 1;
+            stream.column = region.column;
+            if region
+                .height
+                .is_some_and(|it| stream.row >= region.row + it)
+            {
+                stream.row = region.row;
+            }
+        } else {
+            write!(writer, "\x1b[{};{}H", stream.row + 1, stream.column + 1)?;
+            // 有高度的区域会循环写入，新的一行开始时先清除旧的内容
+            if region.height.is_some() && stream.column == region.column {
+                writer.write_all(b"\x1b[K")?;
+            }
+            writer.write_all(it.encode_utf8(&mut buf).as_ref())?;
+            stream.column += config.char_width(it).unwrap_or(0);
+
+            if config.flush_each_unit {
+                writer.flush()?;
+            }
+        }
+
+        last_written = stream.due;
+        stream.due += config.char_delay(it);
+        last = last.max(stream.due);
+    }
+
+    // 最后写入的字符之后的延迟，`LastUnit` 时等待所有流的延迟结束
+    let tail = config.tail_delay.delay(last - last_written);
+    if let Some(it) = tail
+        .map(|it| last_written + it)
+        .filter(|it| *it > clock.now())
+    {
+        clock.sleep_until(it);
+    }
+
+    writer.flush()
+}
@@ -0,0 +1,63 @@
+use std::io::{self, ErrorKind, Write};
+use std::thread;
+use std::time::Duration;
+
+/// `WouldBlock` 之后第一次重试前的等待时间，之后每次翻倍
+///
+/// ---
+///
+/// Wait before the first retry after `WouldBlock`, doubled on every retry
+const INITIAL_BACKOFF: Duration = Duration::from_millis(1);
+
+/// 重试暂时性 I/O 错误的写入器
+///
+/// `Interrupted` 立即重试；`WouldBlock` 在短暂退避后重试，最多 `retries` 次，
+/// 用尽后才返回错误
+///
+/// ---
+///
+/// Writer retrying transient I/O errors
+///
+/// `Interrupted` is retried immediately; `WouldBlock` is retried after a
+/// brief backoff, at most `retries` times before the error is returned
+pub(crate) struct Retry<'a, W: Write + ?Sized> {
+    inner: &'a mut W,
+    retries: usize
+}
+
+impl<'a, W: Write + ?Sized> Retry<'a, W> {
+    pub(crate) fn new(inner: &'a mut W, retries: usize) -> Self {
+        Self { inner, retries }
+    }
+
+    fn retry<T>(
+        &mut self,
+        mut op: impl FnMut(&mut W) -> io::Result<T>
+    ) -> io::Result<T> {
+        let mut backoff = INITIAL_BACKOFF;
+        let mut retries = 0;
+
+        loop {
+            match op(self.inner) {
+                Err(it) if it.kind() == ErrorKind::Interrupted => {}
+                Err(it)
+                    if it.kind() == ErrorKind::WouldBlock
+                        && retries < self.retries =>
+                {
+                    thread::sleep(backoff);
+                    backoff *= 2;
+                    retries += 1;
+                }
+                result => return result
+            }
+        }
+    }
+}
+
+impl<W: Write + ?Sized> Write for Retry<'_, W> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.retry(|it| it.write(buf))
+    }
+
+    fn flush(&mut self) -> io::Result<()> { self.retry(|it| it.flush()) }
+}
@@ -0,0 +1,149 @@
+use std::io::{self, Write};
+use std::time::Duration;
+
+use console::Style;
+
+use crate::{Clock, Pacer, SlowScanConfig};
+
+/// 恢复默认样式的 SGR 序列
+///
+/// ---
+///
+/// SGR sequence restoring the default style
+const RESET: &str = "\x1b[0m";
+
+/// 带有独立样式和延迟的一段文本
+///
+/// ---
+///
+/// Piece of text with its own style and delay
+#[derive(Debug, Clone, Default)]
+pub struct RichUnit {
+    /// 要显示的文本
+    ///
+    /// ---
+    ///
+    /// Text to reveal
+    pub text: String,
+    /// 文本的样式，为 `None` 时使用默认样式
+    ///
+    /// ---
+    ///
+    /// Style of the text, the default style being used when `None`
+    pub style: Option<Style>,
+    /// 文本中每个字符之后的延迟，为 `None` 时按配置和字符类型决定
+    ///
+    /// ---
+    ///
+    /// Delay after every character of the text, decided by the configuration
+    /// and the character class when `None`
+    pub delay: Option<Duration>
+}
+
+impl RichUnit {
+    /// 创建使用默认样式和延迟的文本
+    ///
+    /// ---
+    ///
+    /// Create text using the default style and delays
+    pub fn new<S: Into<String>>(text: S) -> Self {
+        Self {
+            text: text.into(),
+            ..Self::default()
+        }
+    }
+
+    /// 设置文本的样式
+    ///
+    /// ---
+    ///
+    /// Set the style of the text
+    pub fn with_style(mut self, style: Style) -> Self {
+        self.style = Some(style);
+        self
+    }
+
+    /// 设置文本中每个字符之后的延迟
+    ///
+    /// ---
+    ///
+    /// Set the delay after every character of the text
+    pub fn with_delay(mut self, delay: Duration) -> Self {
+        self.delay = Some(delay);
+        self
+    }
+}
+
+/// 逐个字符显示带样式的文本段的实现
+///
+/// 样式的前缀只在与上一段不同时写入，相同样式的相邻文本段之间不会插入重置
+/// 序列；最后仍有样式时写入重置序列
+///
+/// ---
+///
+/// Implementation of revealing styled pieces of text character by character
+///
+/// The prefix of a style is only written when it differs from the previous
+/// piece, so no reset is inserted between adjacent pieces of the same style; a
+/// reset is written at the end when a style is still active
+pub(crate) fn write_rich<W, I, C>(
+    writer: &mut W,
+    iter: I,
+    config: SlowScanConfig,
+    clock: &mut C
+) -> io::Result<()>
+where
+    W: Write + ?Sized,
+    I: Iterator<Item = RichUnit>,
+    C: Clock + ?Sized
+{
+    let mut units = iter.peekable();
+    let mut pacer = Pacer::with_config(clock, &config);
+    let mut active = String::new();
+    let mut buf = [0; 4];
+
+    while let Some(unit) = units.next() {
+        let prefix = unit.style.as_ref().map(style_prefix).unwrap_or_default();
+        if prefix != active {
+            if !active.is_empty() {
+                writer.write_all(RESET.as_bytes())?;
+            }
+            writer.write_all(prefix.as_bytes())?;
+            active = prefix;
+        }
+
+        let mut chars = unit.text.chars().peekable();
+        while let Some(it) = chars.next() {
+            writer.write_all(it.encode_utf8(&mut buf).as_bytes())?;
+            if config.flush_each_unit {
+                writer.flush()?;
+            }
+
+            let delay = unit.delay.unwrap_or_else(|| config.char_delay(it));
+            let more = chars.peek().is_some() || units.peek().is_some();
+            if let Some(it) = config.pause_after(delay, more) {
+                pacer.sleep(it);
+            }
+        }
+    }
+
+    if !active.is_empty() {
+        writer.write_all(RESET.as_bytes())?;
+    }
+    writer.flush()
+}
+
+/// 返回样式在文本之前写入的 SGR 序列，不使用任何样式时为空
+///
+/// ---
+///
+/// Return the SGR sequences a style writes before the text, empty when no
+/// styling is applied
+fn style_prefix(style: &Style) -> String {
+    let styled = style.apply_to("").to_string();
+
+    match styled.strip_suffix(RESET) {
+        Some(it) => it.to_string(),
+        None => styled
+    }
+}
@@ -0,0 +1,94 @@
+use crate::ansi::AnsiState;
+
+/// 去除可能改变终端状态的转义序列和控制字符，使不受信任的文本可以安全地显示
+///
+/// 只保留设置颜色和样式的 SGR 序列（`ESC [ 参数 m`，参数只含数字、`;` 和
+/// `:`），其余 CSI 序列、OSC 序列（如修改窗口标题或剪贴板）和双字符转义序列
+/// （如重置终端的 `ESC c`）连同未结束的序列一并删除；除 `\t`、`\n` 和 `\r`
+/// 外的 C0 控制字符、`DEL` 以及 C1 控制字符（如单字符的 CSI `U+009B`）也会
+/// 被删除
+///
+/// # 参数
+/// - `s`: 不受信任的文本
+///
+/// # 返回值
+/// 返回清理后的文本
+///
+/// # 示例
+/// ```
+/// use slow_scan_print::sanitize_for_terminal;
+///
+/// // 重置终端的 ESC c 被删除，颜色保留
+/// // The terminal reset ESC c is removed while colors are kept
+/// assert_eq!(
+///     sanitize_for_terminal("\x1bcok \x1b[1;31merror\x1b[0m\n"),
+///     "ok \x1b[1;31merror\x1b[0m\n"
+/// );
+///
+/// // 修改窗口标题、移动光标和响铃都被删除
+/// // Setting the window title, moving the cursor and ringing the bell are
+/// // all removed
+/// assert_eq!(
+///     sanitize_for_terminal("\x1b]0;pwned\x07a\x1b[2Jb\x07\u{9b}6nc"),
+///     "ab6nc"
+/// );
+/// ```
+///
+/// ---
+///
+/// Remove escape sequences and control characters that could change the
+/// state of the terminal, so that untrusted text can be shown safely
+///
+/// Only SGR sequences setting colors and styles (`ESC [ params m` whose
+/// parameters contain nothing but digits, `;` and `:`) are kept; other CSI
+/// sequences, OSC sequences (such as changing the window title or the
+/// clipboard) and two-character escapes (such as `ESC c` resetting the
+/// terminal) are removed along with unterminated sequences. C0 control
+/// characters other than `\t`, `\n` and `\r`, `DEL` and C1 control characters
+/// (such as the single-character CSI `U+009B`) are removed as well
+///
+/// # Arguments
+/// - `s`: Untrusted text
+///
+/// # Returns
+/// Returns the sanitized text
+pub fn sanitize_for_terminal(s: &str) -> String {
+    let mut output = String::with_capacity(s.len());
+    let mut state = AnsiState::default();
+    let mut sequence = String::new();
+
+    for it in s.chars() {
+        if state.advance(it) {
+            sequence.push(it);
+            if state == AnsiState::Ground {
+                if is_sgr(&sequence) {
+                    output.push_str(&sequence);
+                }
+                sequence.clear();
+            }
+            continue;
+        }
+
+        if !it.is_control() || matches!(it, '\t' | '\n' | '\r') {
+            output.push(it);
+        }
+    }
+
+    output
+}
+
+/// 判断完整的转义序列是否为只设置样式的 SGR 序列
+///
+/// ---
+///
+/// Check whether a complete escape sequence is an SGR sequence only setting
+/// styles
+fn is_sgr(sequence: &str) -> bool {
+    sequence
+        .strip_prefix("\x1b[")
+        .and_then(|it| it.strip_suffix('m'))
+        .is_some_and(|it| {
+            it.chars()
+                .all(|it| it.is_ascii_digit() || it == ';' || it == ':')
+        })
+}
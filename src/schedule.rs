@@ -0,0 +1,84 @@
+use std::collections::HashMap;
+use std::io;
+use std::time::Duration;
+
+/// 解析 CSV 格式的逐行节奏表
+///
+/// 每行包含以逗号分隔的两个字段：从 0 开始的行号和该行每个字符的延迟秒数
+/// （可带小数）。字段两侧的空白和空行会被忽略
+///
+/// # 参数
+/// - `text`: 节奏表的内容
+///
+/// # 返回值
+/// 返回行号到延迟的映射，格式错误或行号重复时返回 `InvalidData` 错误并指出
+/// 所在行号
+///
+/// # 示例
+/// ```
+/// use std::time::Duration;
+///
+/// use slow_scan_print::parse_schedule;
+///
+/// let schedule = parse_schedule("0,0.5\n3, 1.25\n").unwrap();
+///
+/// assert_eq!(schedule.len(), 2);
+/// assert_eq!(schedule[&0], Duration::from_millis(500));
+/// assert_eq!(schedule[&3], Duration::from_millis(1250));
+///
+/// assert!(parse_schedule("0\n").is_err());
+/// assert!(parse_schedule("0,1\n0,2\n").is_err());
+/// ```
+///
+/// ---
+///
+/// Parse a per-line pacing schedule in the CSV format
+///
+/// Each line contains two comma-separated fields: the zero-based line index
+/// and the delay in seconds (possibly fractional) for every character of that
+/// line. Whitespace around fields and empty lines are ignored
+///
+/// # Arguments
+/// - `text`: Content of the schedule
+///
+/// # Returns
+/// Returns the mapping from line index to delay, or an `InvalidData` error
+/// naming the line number when the format is wrong or a line index is repeated
+pub fn parse_schedule(text: &str) -> io::Result<HashMap<usize, Duration>> {
+    let mut schedule = HashMap::new();
+
+    for (i, it) in text
+        .lines()
+        .enumerate()
+        .filter(|(_, it)| !it.trim().is_empty())
+    {
+        let mut fields = it.split(',').map(str::trim);
+
+        let index = fields.next().and_then(|it| it.parse::<usize>().ok());
+        let delay = fields
+            .next()
+            .and_then(|it| it.parse::<f64>().ok())
+            .and_then(|it| Duration::try_from_secs_f64(it).ok());
+
+        let (Some(index), Some(delay), None) = (index, delay, fields.next())
+        else {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!("invalid schedule entry on line {}: {:?}", i + 1, it)
+            ));
+        };
+
+        if schedule.insert(index, delay).is_some() {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!(
+                    "duplicate schedule entry for line {} on line {}",
+                    index,
+                    i + 1
+                )
+            ));
+        }
+    }
+
+    Ok(schedule)
+}
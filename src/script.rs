@@ -0,0 +1,200 @@
+use std::io;
+use std::time::Duration;
+
+use crate::directive::parse_duration;
+
+/// 脚本中的一段文本及其样式和节奏
+///
+/// ---
+///
+/// A segment of text of a script along with its style and pacing
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ScriptSegment {
+    /// 要显示的文本
+    ///
+    /// ---
+    ///
+    /// Text to reveal
+    pub text: String,
+    /// 每个半角字符之后的延迟，全角字符加倍；为 `None` 时使用配置中的延迟
+    ///
+    /// ---
+    ///
+    /// Delay after every half-width character, doubled for full-width ones;
+    /// the delays of the configuration are used when `None`
+    pub delay: Option<Duration>,
+    /// SGR 参数，如 `1;31`；为空时不设置样式
+    ///
+    /// ---
+    ///
+    /// SGR parameters such as `1;31`; no style is set when empty
+    pub sgr: String
+}
+
+/// 解析按行编写的动画脚本
+///
+/// 以 `@` 开头的行是指令，其余行是按原样显示的文本（包括换行符）：
+///
+/// - `@delay <时间>` 设置之后文本的字符延迟，如 `50ms`、`0.1s`；
+///   `@delay default` 恢复配置中的延迟
+/// - `@style <样式...>` 设置之后文本的样式，可组合 `bold`、`dim`、`italic`、
+///   `underline`、`reverse`、颜色名（`black`、`red`、`green`、`yellow`、
+///   `blue`、`magenta`、`cyan`、`white`）以及 `on-<颜色>` 背景色；不带参数或
+///   使用 `reset` 时清除样式
+/// - `@@` 开头的行表示以一个 `@` 开头的文本
+///
+/// # 参数
+/// - `text`: 脚本的内容
+///
+/// # 返回值
+/// 返回依次显示的各段文本，未知或格式错误的指令返回 `InvalidData` 错误并指出
+/// 所在行号
+///
+/// # 示例
+/// ```
+/// use std::time::Duration;
+///
+/// use slow_scan_print::{ScriptSegment, parse_script};
+///
+/// let script = "Hello\n@style bold red\n@delay 50ms\nWorld\n@@home\n";
+/// let segments = parse_script(script).unwrap();
+///
+/// assert_eq!(
+///     segments,
+///     [
+///         ScriptSegment {
+///             text: String::from("Hello\n"),
+///             delay: None,
+///             sgr: String::new()
+///         },
+///         ScriptSegment {
+///             text: String::from("World\n@home\n"),
+///             delay: Some(Duration::from_millis(50)),
+///             sgr: String::from("1;31")
+///         }
+///     ]
+/// );
+///
+/// assert!(parse_script("@delay soon\n").is_err());
+/// assert!(parse_script("@style sparkly\n").is_err());
+/// assert!(parse_script("@pause 1s\n").is_err());
+/// ```
+///
+/// ---
+///
+/// Parse a line-based animation script
+///
+/// Lines starting with `@` are directives, any other line is text shown as is
+/// (line break included):
+///
+/// - `@delay <TIME>` sets the character delay of the following text, such as
+///   `50ms` or `0.1s`; `@delay default` restores the delays of the
+///   configuration
+/// - `@style <STYLE...>` sets the style of the following text, combining
+///   `bold`, `dim`, `italic`, `underline`, `reverse`, color names (`black`,
+///   `red`, `green`, `yellow`, `blue`, `magenta`, `cyan`, `white`) and
+///   `on-<COLOR>` backgrounds; no argument or `reset` clears the style
+/// - a line starting with `@@` is text starting with a single `@`
+///
+/// # Arguments
+/// - `text`: Content of the script
+///
+/// # Returns
+/// Returns the segments of text to reveal in turn, or an `InvalidData` error
+/// naming the line number for unknown or malformed directives
+pub fn parse_script(text: &str) -> io::Result<Vec<ScriptSegment>> {
+    let mut segments = Vec::new();
+    let mut current = ScriptSegment {
+        text: String::new(),
+        delay: None,
+        sgr: String::new()
+    };
+
+    for (i, line) in text.split_inclusive('\n').enumerate() {
+        let Some(directive) = line.strip_prefix('@') else {
+            current.text.push_str(line);
+            continue;
+        };
+        if directive.starts_with('@') {
+            current.text.push_str(directive);
+            continue;
+        }
+
+        let invalid = || {
+            io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!(
+                    "invalid script directive on line {}: {:?}",
+                    i + 1,
+                    line.trim_end()
+                )
+            )
+        };
+
+        let mut words = directive.split_whitespace();
+        let (delay, sgr) = match words.next() {
+            Some("delay") => {
+                let delay = match (words.next(), words.next()) {
+                    (Some("default"), None) => None,
+                    (Some(it), None) => {
+                        Some(parse_duration(it).ok_or_else(invalid)?)
+                    }
+                    _ => return Err(invalid())
+                };
+                (delay, current.sgr.clone())
+            }
+            Some("style") => {
+                let sgr = words
+                    .filter(|it| *it != "reset")
+                    .map(|it| style_code(it).ok_or_else(invalid))
+                    .collect::<io::Result<Vec<_>>>()?
+                    .join(";");
+                (current.delay, sgr)
+            }
+            _ => return Err(invalid())
+        };
+
+        let next = ScriptSegment {
+            text: String::new(),
+            delay,
+            sgr
+        };
+        let done = std::mem::replace(&mut current, next);
+        if !done.text.is_empty() {
+            segments.push(done);
+        }
+    }
+
+    if !current.text.is_empty() {
+        segments.push(current);
+    }
+
+    Ok(segments)
+}
+
+/// 返回样式名对应的 SGR 参数
+///
+/// ---
+///
+/// Return the SGR parameter of a style name
+fn style_code(name: &str) -> Option<String> {
+    const COLORS: [&str; 8] = [
+        "black", "red", "green", "yellow", "blue", "magenta", "cyan", "white"
+    ];
+
+    let color = |name: &str| COLORS.iter().position(|it| *it == name);
+
+    let code = match name {
+        "bold" => 1,
+        "dim" => 2,
+        "italic" => 3,
+        "underline" => 4,
+        "reverse" => 7,
+        _ => match name.strip_prefix("on-") {
+            Some(it) => 40 + color(it)?,
+            None => 30 + color(name)?
+        }
+    };
+
+    Some(code.to_string())
+}
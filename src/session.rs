@@ -0,0 +1,155 @@
+use std::time::{Duration, Instant};
+
+use crate::{Clock, SystemClock};
+
+/// 在多次写入之间共享同一条时间线的会话
+///
+/// 每次写入方法都以调用时的当前时间作为节奏的起点，连续调用时写入本身花费的
+/// 时间会在调用的边界处累积为漂移。会话本身实现了 [`Clock`]，`now` 返回的是
+/// 时间线上计划到达的时间点而不是实际时间，每次等待结束后时间线前进到该次的
+/// 截止时间；把同一个会话传给多次 `*_with_clock` 调用，前后的动画就会首尾
+/// 相接，没有间隙也没有漂移
+///
+/// 时间线落后于实际时间时（例如两次调用之间做了其他耗时的工作），之后的等待会
+/// 立即返回直到追上。需要从当前时间重新开始时调用 [`reset`](Self::reset)，
+/// 需要在两段之间留出停顿时调用 [`advance`](Self::advance)
+///
+/// # 示例
+/// ```
+/// use std::time::{Duration, Instant};
+///
+/// use slow_scan_print::{Clock, SlowScanConfig, SlowScanSession, SlowScanWrite};
+///
+/// /// 每次读取时间都会流逝 1ms 的时钟，模拟写入本身花费的时间
+/// struct LaggingClock {
+///     now: Instant,
+///     deadlines: Vec<Instant>
+/// }
+///
+/// impl Clock for LaggingClock {
+///     fn now(&mut self) -> Instant {
+///         self.now += Duration::from_millis(1);
+///         self.now
+///     }
+///
+///     fn sleep_until(&mut self, deadline: Instant) {
+///         self.now = self.now.max(deadline);
+///         self.deadlines.push(deadline);
+///     }
+/// }
+///
+/// let ms = Duration::from_millis;
+/// let mut config = SlowScanConfig::default();
+/// config.set_base_delay(ms(10)).set_tail_delay(true);
+///
+/// let mut session = SlowScanSession::new(LaggingClock {
+///     now: Instant::now(),
+///     deadlines: Vec::new()
+/// });
+/// let start = session.next();
+///
+/// let mut writer = Vec::new();
+/// writer
+///     .slow_scan_write_by_chars_with_clock("ab".chars(), config, &mut session)
+///     .unwrap();
+/// writer
+///     .slow_scan_write_by_chars_with_clock("cd".chars(), config, &mut session)
+///     .unwrap();
+///
+/// // 两次写入之间没有间隙，时间线上的截止时间严格按 10ms 递增
+/// // No gap between the two writes, the deadlines advance by exactly 10ms
+/// let deadlines = session
+///     .inner()
+///     .deadlines
+///     .iter()
+///     .map(|it| *it - start)
+///     .collect::<Vec<_>>();
+/// assert_eq!(deadlines, [ms(10), ms(20), ms(30), ms(40)]);
+///
+/// session.advance(ms(100));
+/// assert_eq!(session.next() - start, ms(140));
+/// ```
+///
+/// ---
+///
+/// Session sharing one timeline across multiple writes
+///
+/// Every write method starts its pacing from the current time when called, so
+/// on consecutive calls the time spent writing itself adds up to drift at the
+/// call boundaries. The session implements [`Clock`] itself, `now` returning
+/// the instant planned on the timeline rather than the real time, and the
+/// timeline moves to the deadline of every wait once it ends; passing the same
+/// session to several `*_with_clock` calls makes the animations follow each
+/// other end to end, with neither gaps nor drift
+///
+/// When the timeline falls behind the real time (e.g. other time-consuming
+/// work was done between two calls), following waits return immediately until
+/// it catches up. Call [`reset`](Self::reset) to start again from the current
+/// time, or [`advance`](Self::advance) to leave a pause between two pieces
+#[derive(Debug, Clone)]
+pub struct SlowScanSession<C = SystemClock> {
+    inner: C,
+    next: Instant
+}
+
+impl<C: Clock> SlowScanSession<C> {
+    /// 创建以当前时间为起点的会话
+    ///
+    /// ---
+    ///
+    /// Create a session starting from the current time
+    pub fn new(mut inner: C) -> Self {
+        Self {
+            next: inner.now(),
+            inner
+        }
+    }
+
+    /// 返回时间线当前所在的时间点
+    ///
+    /// ---
+    ///
+    /// Return the instant the timeline is currently at
+    pub fn next(&self) -> Instant { self.next }
+
+    /// 返回内部的时钟
+    ///
+    /// ---
+    ///
+    /// Return the inner clock
+    pub fn inner(&self) -> &C { &self.inner }
+
+    /// 将时间线重置为当前时间
+    ///
+    /// ---
+    ///
+    /// Reset the timeline to the current time
+    pub fn reset(&mut self) -> &mut Self {
+        self.next = self.inner.now();
+        self
+    }
+
+    /// 将时间线向后推进指定时长，下一次写入的第一个等待会相应推迟
+    ///
+    /// ---
+    ///
+    /// Move the timeline forward by the given time, postponing the first wait
+    /// of the next write accordingly
+    pub fn advance(&mut self, delay: Duration) -> &mut Self {
+        self.next += delay;
+        self
+    }
+}
+
+impl Default for SlowScanSession {
+    fn default() -> Self { Self::new(SystemClock) }
+}
+
+impl<C: Clock> Clock for SlowScanSession<C> {
+    fn now(&mut self) -> Instant { self.next }
+
+    fn sleep_until(&mut self, deadline: Instant) {
+        self.inner.sleep_until(deadline);
+        self.next = self.next.max(deadline);
+    }
+}
@@ -0,0 +1,160 @@
+use std::io::{self, Read, Write};
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::thread::{self, JoinHandle};
+use std::time::Duration;
+
+/// 旋转指示器依次显示的帧
+///
+/// ---
+///
+/// Frames the spinner shows in turn
+const FRAMES: [char; 4] = ['|', '/', '-', '\\'];
+
+/// 在第一次读取完成之前显示旋转指示器的读取器包装
+///
+/// 创建时即在后台线程中以 `interval` 为间隔向 `writer` 循环写入 `\r` 和一帧
+/// 指示器；第一次读取返回（无论是否读到数据）后停止后台线程，用 `\r \r` 清除
+/// 指示器，之后再把读取的结果交给调用者，因此指示器不会与随后显示的内容混在
+/// 一起
+///
+/// # 示例
+/// ```
+/// use std::io::{self, Read, Write};
+/// use std::sync::{Arc, Mutex};
+/// use std::thread;
+/// use std::time::Duration;
+///
+/// use slow_scan_print::SpinnerReader;
+///
+/// #[derive(Clone)]
+/// struct Shared(Arc<Mutex<Vec<u8>>>);
+///
+/// impl Write for Shared {
+///     fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+///         self.0.lock().unwrap().write(buf)
+///     }
+///
+///     fn flush(&mut self) -> io::Result<()> { Ok(()) }
+/// }
+///
+/// /// 第一次读取前先等待一段时间的慢速输入
+/// struct Slow(&'static [u8]);
+///
+/// impl Read for Slow {
+///     fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+///         thread::sleep(Duration::from_millis(50));
+///         self.0.read(buf)
+///     }
+/// }
+///
+/// let output = Shared(Arc::new(Mutex::new(Vec::new())));
+/// let mut reader = SpinnerReader::new(
+///     Slow(b"hi"),
+///     output.clone(),
+///     Duration::from_millis(5)
+/// );
+///
+/// let mut content = String::new();
+/// reader.read_to_string(&mut content).unwrap();
+/// output.clone().write_all(content.as_bytes()).unwrap();
+///
+/// let output = String::from_utf8(output.0.lock().unwrap().clone()).unwrap();
+/// // 指示器先转动，在内容显示之前被清除
+/// assert!(output.starts_with("\r|"));
+/// assert!(output.ends_with("\r \rhi"));
+/// ```
+///
+/// ---
+///
+/// Reader wrapper showing a spinner until the first read completes
+///
+/// A background thread starts writing `\r` and a spinner frame to `writer`
+/// every `interval` as soon as it is created; once the first read returns,
+/// with or without data, the thread is stopped and the spinner is cleared
+/// with `\r \r` before the result is handed to the caller, so the spinner
+/// never mixes with the content shown afterwards
+#[derive(Debug)]
+pub struct SpinnerReader<R, W: Write + Send + 'static> {
+    inner: R,
+    spinner: Option<Spinner<W>>
+}
+
+impl<R: Read, W: Write + Send + 'static> SpinnerReader<R, W> {
+    pub fn new(inner: R, writer: W, interval: Duration) -> Self {
+        Self {
+            inner,
+            spinner: Some(Spinner::start(writer, interval))
+        }
+    }
+}
+
+impl<R: Read, W: Write + Send + 'static> Read for SpinnerReader<R, W> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let result = self.inner.read(buf);
+
+        if let Some(it) = self.spinner.take() {
+            it.stop()?;
+        }
+
+        result
+    }
+}
+
+/// 在后台线程中转动的旋转指示器
+///
+/// ---
+///
+/// Spinner turning in a background thread
+#[derive(Debug)]
+struct Spinner<W> {
+    stop: Arc<AtomicBool>,
+    handle: JoinHandle<io::Result<W>>
+}
+
+impl<W: Write + Send + 'static> Spinner<W> {
+    fn start(mut writer: W, interval: Duration) -> Self {
+        let stop = Arc::new(AtomicBool::new(false));
+
+        let handle = {
+            let stop = stop.clone();
+            thread::spawn(move || {
+                let mut shown = false;
+
+                for it in FRAMES.iter().cycle() {
+                    if stop.load(Ordering::Acquire) {
+                        break;
+                    }
+
+                    write!(writer, "\r{}", it)?;
+                    writer.flush()?;
+                    shown = true;
+                    thread::park_timeout(interval);
+                }
+
+                if shown {
+                    writer.write_all(b"\r \r")?;
+                    writer.flush()?;
+                }
+
+                Ok(writer)
+            })
+        };
+
+        Self { stop, handle }
+    }
+
+    /// 停止后台线程并等待指示器被清除
+    ///
+    /// ---
+    ///
+    /// Stop the background thread and wait for the spinner to be cleared
+    fn stop(self) -> io::Result<W> {
+        self.stop.store(true, Ordering::Release);
+        self.handle.thread().unpark();
+
+        self.handle.join().unwrap_or_else(|_| {
+            Err(io::Error::other("spinner thread panicked"))
+        })
+    }
+}
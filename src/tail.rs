@@ -0,0 +1,124 @@
+use std::time::Duration;
+
+/// 最后一个单元写入之后的行为
+///
+/// 可由 `bool` 转换得到：`true` 对应 [`LastUnit`](Self::LastUnit)，`false`
+/// 对应 [`None`](Self::None)，因此 `set_tail_delay(true)` 等原有写法仍然可用
+///
+/// # 示例
+/// ```
+/// use std::time::{Duration, Instant};
+///
+/// use slow_scan_print::{SlowScanConfig, SlowScanWrite, TailBehavior};
+/// # struct VirtualClock {
+/// #     now: std::time::Instant,
+/// #     deadlines: Vec<std::time::Instant>
+/// # }
+/// #
+/// # impl VirtualClock {
+/// #     fn new(now: std::time::Instant) -> Self {
+/// #         Self {
+/// #             now,
+/// #             deadlines: Vec::new()
+/// #         }
+/// #     }
+/// # }
+/// #
+/// # impl slow_scan_print::Clock for VirtualClock {
+/// #     fn now(&mut self) -> std::time::Instant { self.now }
+/// #
+/// #     fn sleep_until(&mut self, deadline: std::time::Instant) {
+/// #         self.now = self.now.max(deadline);
+/// #         self.deadlines.push(deadline);
+/// #     }
+/// # }
+///
+/// let deadlines = |tail: TailBehavior| {
+///     let start = Instant::now();
+///     let mut clock = VirtualClock::new(start);
+///
+///     let mut config = SlowScanConfig::default();
+///     config
+///         .set_base_delay(Duration::from_secs(1))
+///         .set_tail_delay(tail);
+///
+///     let mut writer = Vec::new();
+///     writer
+///         .slow_scan_write_by_chars_with_clock(
+///             "ab".chars(),
+///             config,
+///             &mut clock
+///         )
+///         .unwrap();
+///
+///     clock
+///         .deadlines
+///         .iter()
+///         .map(|it| *it - start)
+///         .collect::<Vec<_>>()
+/// };
+///
+/// assert_eq!(deadlines(TailBehavior::None), [Duration::from_secs(1)]);
+/// assert_eq!(
+///     deadlines(TailBehavior::LastUnit),
+///     [Duration::from_secs(1), Duration::from_secs(2)]
+/// );
+/// assert_eq!(
+///     deadlines(TailBehavior::Custom(Duration::from_secs(5))),
+///     [Duration::from_secs(1), Duration::from_secs(6)]
+/// );
+/// assert_eq!(TailBehavior::from(true), TailBehavior::LastUnit);
+/// assert_eq!(TailBehavior::from(false), TailBehavior::None);
+/// ```
+///
+/// ---
+///
+/// Behavior after the last unit is written
+///
+/// Can be converted from a `bool`: `true` maps to
+/// [`LastUnit`](Self::LastUnit) and `false` to [`None`](Self::None), so that
+/// existing code such as `set_tail_delay(true)` keeps working
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum TailBehavior {
+    /// 最后一个单元之后不延迟
+    ///
+    /// ---
+    ///
+    /// No delay after the last unit
+    #[default]
+    None,
+    /// 最后一个单元之后按其自身的延迟等待
+    ///
+    /// ---
+    ///
+    /// Wait for the own delay of the last unit after it
+    LastUnit,
+    /// 最后一个单元之后等待指定的时间
+    ///
+    /// ---
+    ///
+    /// Wait for the given time after the last unit
+    Custom(Duration)
+}
+
+impl TailBehavior {
+    /// 返回最后一个单元之后的延迟，`unit_delay` 为该单元自身的延迟
+    ///
+    /// ---
+    ///
+    /// Return the delay after the last unit, `unit_delay` being the own delay
+    /// of that unit
+    pub fn delay(self, unit_delay: Duration) -> Option<Duration> {
+        match self {
+            Self::None => None,
+            Self::LastUnit => Some(unit_delay),
+            Self::Custom(it) => Some(it)
+        }
+    }
+}
+
+impl From<bool> for TailBehavior {
+    fn from(value: bool) -> Self {
+        if value { Self::LastUnit } else { Self::None }
+    }
+}
@@ -0,0 +1,84 @@
+use std::time::Duration;
+
+#[cfg(windows)]
+#[link(name = "winmm")]
+unsafe extern "system" {
+    fn timeBeginPeriod(period: u32) -> u32;
+    fn timeEndPeriod(period: u32) -> u32;
+}
+
+/// 在存活期间提高系统计时器精度的守卫
+///
+/// Windows 上调用 `timeBeginPeriod` 将计时器精度提高到指定的毫秒数（至少
+/// 1ms），离开作用域时调用 `timeEndPeriod` 恢复；其他平台上不做任何事。适合与
+/// [`SlowScanConfig::round_to`](crate::SlowScanConfig::round_to) 一同使用
+///
+/// # 示例
+/// ```
+/// use std::time::Duration;
+///
+/// use slow_scan_print::TimerResolution;
+///
+/// let resolution = TimerResolution::new(Duration::from_millis(1));
+/// assert_eq!(resolution.period(), Duration::from_millis(1));
+///
+/// // 不足 1ms 时按 1ms 处理
+/// // Periods below 1ms are treated as 1ms
+/// let resolution = TimerResolution::new(Duration::from_micros(100));
+/// assert_eq!(resolution.period(), Duration::from_millis(1));
+/// ```
+///
+/// ---
+///
+/// Guard raising the precision of the system timer while alive
+///
+/// On Windows it calls `timeBeginPeriod` to raise the timer precision to the
+/// given number of milliseconds (at least 1ms) and `timeEndPeriod` to restore
+/// it when dropped; it does nothing on other platforms. Suits being used along
+/// with [`SlowScanConfig::round_to`](crate::SlowScanConfig::round_to)
+#[derive(Debug)]
+pub struct TimerResolution {
+    period: u32,
+    #[cfg(windows)]
+    active: bool
+}
+
+impl TimerResolution {
+    /// 请求以 `period` 为精度的系统计时器，精度按毫秒取整且至少为 1ms
+    ///
+    /// ---
+    ///
+    /// Request a system timer with a precision of `period`, rounded to
+    /// milliseconds and at least 1ms
+    pub fn new(period: Duration) -> Self {
+        let period =
+            u32::try_from(period.as_millis()).unwrap_or(u32::MAX).max(1);
+
+        Self {
+            period,
+            // 返回 0（TIMERR_NOERROR）时才需要在析构时恢复
+            #[cfg(windows)]
+            active: unsafe { timeBeginPeriod(period) } == 0
+        }
+    }
+
+    /// 返回请求的计时器精度
+    ///
+    /// ---
+    ///
+    /// Return the requested timer precision
+    pub fn period(&self) -> Duration {
+        Duration::from_millis(u64::from(self.period))
+    }
+}
+
+impl Drop for TimerResolution {
+    fn drop(&mut self) {
+        #[cfg(windows)]
+        if self.active {
+            unsafe {
+                timeEndPeriod(self.period);
+            }
+        }
+    }
+}
@@ -0,0 +1,70 @@
+use std::io;
+use std::time::Duration;
+
+/// 解析 `script` 格式的时间文件
+///
+/// 每行包含以空白分隔的两个字段：写入该段之前的延迟秒数（可带小数）和该段的
+/// 字节数。空行会被忽略
+///
+/// # 参数
+/// - `text`: 时间文件的内容
+///
+/// # 返回值
+/// 按顺序返回每一段的延迟和字节数，格式错误时返回 `InvalidData` 错误并指出
+/// 所在行号
+///
+/// # 示例
+/// ```
+/// use std::time::Duration;
+///
+/// use slow_scan_print::parse_timing;
+///
+/// let timing = parse_timing("0.5 3\n1.25 2\n").unwrap();
+///
+/// assert_eq!(
+///     timing,
+///     [
+///         (Duration::from_millis(500), 3),
+///         (Duration::from_millis(1250), 2)
+///     ]
+/// );
+/// assert!(parse_timing("0.5\n").is_err());
+/// ```
+///
+/// ---
+///
+/// Parse a timing file in the `script` format
+///
+/// Each line contains two whitespace-separated fields: the delay in seconds
+/// (possibly fractional) before writing the segment and the byte count of the
+/// segment. Empty lines are ignored
+///
+/// # Arguments
+/// - `text`: Content of the timing file
+///
+/// # Returns
+/// Returns the delay and byte count of every segment in order, or an
+/// `InvalidData` error naming the line number when the format is wrong
+pub fn parse_timing(text: &str) -> io::Result<Vec<(Duration, usize)>> {
+    text.lines()
+        .enumerate()
+        .filter(|(_, it)| !it.trim().is_empty())
+        .map(|(i, it)| {
+            let mut fields = it.split_whitespace();
+
+            let delay = fields
+                .next()
+                .and_then(|it| it.parse::<f64>().ok())
+                .and_then(|it| Duration::try_from_secs_f64(it).ok());
+            let len = fields.next().and_then(|it| it.parse::<usize>().ok());
+
+            match (delay, len, fields.next()) {
+                (Some(delay), Some(len), None) => Ok((delay, len)),
+                _ => Err(io::Error::new(
+                    io::ErrorKind::InvalidData,
+                    format!("invalid timing entry on line {}: {:?}", i + 1, it)
+                ))
+            }
+        })
+        .collect()
+}
@@ -0,0 +1,41 @@
+use std::io::{self, Write};
+
+/// 记录最后写入字节的写入器包装
+///
+/// ---
+///
+/// Writer wrapper remembering the last byte written
+pub(crate) struct TrackingWriter<W: Write> {
+    inner: W,
+    last_byte: Option<u8>
+}
+
+impl<W: Write> TrackingWriter<W> {
+    pub(crate) fn new(inner: W) -> Self {
+        Self {
+            inner,
+            last_byte: None
+        }
+    }
+
+    /// 返回最后写入的字节，尚未写入任何数据时返回 `None`
+    ///
+    /// ---
+    ///
+    /// Return the last byte written, or `None` if nothing has been written yet
+    pub(crate) fn last_byte(&self) -> Option<u8> { self.last_byte }
+}
+
+impl<W: Write> Write for TrackingWriter<W> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        let len = self.inner.write(buf)?;
+
+        if let Some(it) = buf[..len].last() {
+            self.last_byte = Some(*it);
+        }
+
+        Ok(len)
+    }
+
+    fn flush(&mut self) -> io::Result<()> { self.inner.flush() }
+}
@@ -0,0 +1,133 @@
+use std::collections::VecDeque;
+use std::io::{self, Write};
+
+use crate::{Clock, Pacer, SlowScanConfig};
+
+/// 拖尾中最新字符的灰度
+///
+/// ---
+///
+/// Gray level of the newest character of the trail
+const TRAIL_BRIGHTEST: usize = 255;
+
+/// 拖尾末端接近正常文字的灰度
+///
+/// ---
+///
+/// Gray level near normal text at the end of the trail
+const TRAIL_DIMMEST: usize = 160;
+
+/// 带拖尾写入的实现，每写入一个字符后重新绘制最近的字符
+///
+/// ---
+///
+/// Implementation of writing with a trail, redrawing the latest characters
+/// after every character written
+pub(crate) fn write_trail<W, I, C>(
+    writer: &mut W,
+    iter: I,
+    trail: usize,
+    config: SlowScanConfig,
+    clock: &mut C
+) -> io::Result<()>
+where
+    W: Write + ?Sized,
+    I: Iterator<Item = char>,
+    C: Clock + ?Sized
+{
+    let mut iter = iter.peekable();
+    let mut pacer = Pacer::with_config(clock, &config);
+    let mut glowing = VecDeque::with_capacity(trail + 1);
+    let mut buf = [0; 4];
+
+    while let Some(it) = iter.next() {
+        match config.char_width(it).filter(|it| *it > 0) {
+            Some(width) => {
+                let shown = glowing.iter().map(|(_, it)| it).sum();
+                glowing.push_back((it, width));
+                redraw(writer, &mut glowing, shown, trail)?;
+            }
+            // 控制字符可能移动光标，先让拖尾恢复正常再原样写入
+            None => {
+                settle(writer, &mut glowing)?;
+                writer.write_all(it.encode_utf8(&mut buf).as_bytes())?;
+            }
+        }
+
+        if config.flush_each_unit {
+            writer.flush()?;
+        }
+
+        let delay = config.char_delay(it);
+        if let Some(it) = config.pause_after(delay, iter.peek().is_some()) {
+            pacer.sleep(it);
+        }
+    }
+
+    settle(writer, &mut glowing)?;
+    writer.flush()
+}
+
+/// 退回拖尾的起点重新写入其中的字符，越新的字符越亮，超出拖尾长度的字符恢复
+/// 正常样式并移出拖尾
+///
+/// `shown` 为已经显示在屏幕上的字符所占的列数，最后一个字符尚未显示
+///
+/// ---
+///
+/// Move back to the start of the trail and rewrite its characters, newer ones
+/// brighter, restoring the normal style of characters beyond the trail length
+/// and dropping them from the trail
+///
+/// `shown` is the number of columns taken by the characters already on screen,
+/// the last character not being shown yet
+fn redraw<W: Write + ?Sized>(
+    writer: &mut W,
+    glowing: &mut VecDeque<(char, usize)>,
+    shown: usize,
+    trail: usize
+) -> io::Result<()> {
+    if shown > 0 {
+        write!(writer, "\x1b[{}D", shown)?;
+    }
+
+    let fading = glowing.len().saturating_sub(trail);
+    if fading > 0 {
+        writer.write_all(b"\x1b[0m")?;
+    }
+
+    let mut buf = [0; 4];
+    for (i, (it, _)) in glowing.iter().enumerate() {
+        let age = glowing.len() - 1 - i;
+        if age < trail {
+            let level = TRAIL_BRIGHTEST
+                - (TRAIL_BRIGHTEST - TRAIL_DIMMEST) * age / trail;
+            write!(writer, "\x1b[38;2;{0};{0};{0}m", level)?;
+        }
+        writer.write_all(it.encode_utf8(&mut buf).as_bytes())?;
+    }
+
+    if fading < glowing.len() {
+        writer.write_all(b"\x1b[0m")?;
+    }
+    glowing.drain(..fading);
+
+    Ok(())
+}
+
+/// 将拖尾中的所有字符恢复为正常样式并清空拖尾
+///
+/// ---
+///
+/// Restore the normal style of every character of the trail and empty it
+fn settle<W: Write + ?Sized>(
+    writer: &mut W,
+    glowing: &mut VecDeque<(char, usize)>
+) -> io::Result<()> {
+    if glowing.is_empty() {
+        return Ok(());
+    }
+
+    let shown = glowing.iter().map(|(_, it)| it).sum();
+    redraw(writer, glowing, shown, 0)
+}
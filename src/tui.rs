@@ -0,0 +1,173 @@
+use std::time::{Duration, Instant};
+
+use ratatui::buffer::Buffer;
+use ratatui::layout::Rect;
+
+use crate::{Clock, SlowScanConfig, SystemClock};
+
+/// 随时间把文本逐步显示到 ratatui [`Buffer`] 中的辅助结构
+///
+/// 不占用标准输出，而是在现有的渲染循环中每帧调用一次
+/// [`tick`](Self::tick)，把到目前为止应当显示的字符写入缓冲区的指定区域。
+/// 每个字符的出现时间按 `slow_scan_write_by_chars` 的延迟规则计算，从第一次
+/// `tick` 开始计时。文本在区域宽度处自动折行，`\n` 另起一行，其余控制字符
+/// 被忽略，超出区域的部分不会写入
+///
+/// # 示例
+/// ```
+/// use std::cell::Cell;
+/// use std::rc::Rc;
+/// use std::time::{Duration, Instant};
+///
+/// use ratatui::buffer::Buffer;
+/// use ratatui::layout::Rect;
+/// use slow_scan_print::{BufferReveal, Clock, SlowScanConfig};
+///
+/// struct SharedClock(Rc<Cell<Instant>>);
+///
+/// impl Clock for SharedClock {
+///     fn now(&mut self) -> Instant { self.0.get() }
+///
+///     fn sleep_until(&mut self, deadline: Instant) {
+///         self.0.set(self.0.get().max(deadline));
+///     }
+/// }
+///
+/// let start = Instant::now();
+/// let now = Rc::new(Cell::new(start));
+///
+/// let mut config = SlowScanConfig::default();
+/// config
+///     .set_base_delay(Duration::from_secs(1))
+///     .set_full_width_delay(Duration::from_secs(2));
+///
+/// let area = Rect::new(0, 0, 4, 2);
+/// let mut reveal =
+///     BufferReveal::with_clock("ab世\ncd", config, SharedClock(now.clone()));
+///
+/// let row = |buf: &Buffer, y: u16| {
+///     (0..4).map(|x| buf[(x, y)].symbol()).collect::<String>()
+/// };
+///
+/// let mut buf = Buffer::empty(area);
+/// reveal.tick(&mut buf, area);
+/// assert_eq!(row(&buf, 0), "a   ");
+///
+/// now.set(start + Duration::from_secs(1));
+/// reveal.tick(&mut buf, area);
+/// assert_eq!(row(&buf, 0), "ab  ");
+///
+/// now.set(start + Duration::from_secs(4));
+/// reveal.tick(&mut buf, area);
+/// assert_eq!(row(&buf, 0), "ab世 ");
+/// assert_eq!(row(&buf, 1), "c   ");
+/// assert!(!reveal.is_finished());
+///
+/// now.set(start + Duration::from_secs(5));
+/// reveal.tick(&mut buf, area);
+/// assert_eq!(row(&buf, 1), "cd  ");
+/// assert!(reveal.is_finished());
+/// ```
+///
+/// ---
+///
+/// Helper revealing text into a ratatui [`Buffer`] over time
+///
+/// Instead of owning stdout, [`tick`](Self::tick) is called once per frame
+/// from an existing render loop and writes every character due so far into
+/// the given area of the buffer. The instant each character appears at
+/// follows the delay rules of `slow_scan_write_by_chars`, counted from the
+/// first `tick`. Text wraps at the width of the area, `\n` starts a new row,
+/// other control characters are ignored, and whatever falls outside the area
+/// is not written
+#[derive(Debug, Clone)]
+pub struct BufferReveal<C = SystemClock> {
+    chars: Vec<char>,
+    config: SlowScanConfig,
+    clock: C,
+    start: Option<Instant>,
+    /// 已经到期的字符数量
+    ///
+    /// ---
+    ///
+    /// Number of characters already due
+    shown: usize,
+    /// 下一个字符相对于开始时间的出现时间
+    ///
+    /// ---
+    ///
+    /// Offset from the start at which the next character appears
+    next_due: Duration
+}
+
+impl BufferReveal {
+    pub fn new(text: &str, config: SlowScanConfig) -> Self {
+        Self::with_clock(text, config, SystemClock)
+    }
+}
+
+impl<C: Clock> BufferReveal<C> {
+    /// 使用指定的时钟创建
+    ///
+    /// ---
+    ///
+    /// Create using the given clock
+    pub fn with_clock(text: &str, config: SlowScanConfig, clock: C) -> Self {
+        Self {
+            chars: text.chars().collect(),
+            config,
+            clock,
+            start: None,
+            shown: 0,
+            next_due: Duration::ZERO
+        }
+    }
+
+    /// 所有字符是否都已显示
+    ///
+    /// ---
+    ///
+    /// Whether every character has been revealed
+    pub fn is_finished(&self) -> bool { self.shown == self.chars.len() }
+
+    /// 把到目前为止应当显示的字符写入 `buf` 的 `area` 区域
+    ///
+    /// ---
+    ///
+    /// Write every character due so far into the `area` of `buf`
+    pub fn tick(&mut self, buf: &mut Buffer, area: Rect) {
+        let now = self.clock.now();
+        let elapsed = now - *self.start.get_or_insert(now);
+
+        while self.shown < self.chars.len() && self.next_due <= elapsed {
+            self.next_due += self.config.char_delay(self.chars[self.shown]);
+            self.shown += 1;
+        }
+
+        let (mut x, mut y) = (area.x, area.y);
+        for it in &self.chars[..self.shown] {
+            if *it == '\n' {
+                (x, y) = (area.x, y + 1);
+                continue;
+            }
+
+            let Some(width) = self.config.char_width(*it).filter(|it| *it > 0)
+            else {
+                continue;
+            };
+            let width = width as u16;
+
+            if x + width > area.x + area.width && x > area.x {
+                (x, y) = (area.x, y + 1);
+            }
+            if y >= area.y + area.height {
+                break;
+            }
+
+            if let Some(cell) = buf.cell_mut((x, y)) {
+                cell.set_char(*it);
+            }
+            x += width;
+        }
+    }
+}
@@ -0,0 +1,133 @@
+use std::io::{self, ErrorKind, Read};
+
+/// 不经缓冲逐字节读取的迭代器，每个 UTF-8 字符一读完整就作为一块产生
+///
+/// 每次只向底层读取器请求一个字节，因此输入一到达就能立即显示，适合配合
+/// [`SlowScanWrite::slow_scan_write_by_chunks`] 实时转发管道中的输出。字节按
+/// 原样产生，不完整或无效的 UTF-8 序列不会被替换
+///
+/// [`SlowScanWrite::slow_scan_write_by_chunks`]: crate::SlowScanWrite::slow_scan_write_by_chunks
+///
+/// # 示例
+/// ```
+/// use std::cell::RefCell;
+/// use std::io::{self, Read, Write};
+/// use std::rc::Rc;
+/// use std::time::Duration;
+///
+/// use slow_scan_print::{SlowScanConfig, SlowScanWrite, Unbuffered};
+///
+/// /// 每次读取时记录已经输出的字节数
+/// struct Trickle {
+///     data: &'static [u8],
+///     output: Rc<RefCell<Vec<u8>>>,
+///     seen: Vec<usize>
+/// }
+///
+/// impl Read for Trickle {
+///     fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+///         self.seen.push(self.output.borrow().len());
+///         let len = buf.len().min(self.data.len());
+///         buf[..len].copy_from_slice(&self.data[..len]);
+///         self.data = &self.data[len..];
+///         Ok(len)
+///     }
+/// }
+///
+/// struct Shared(Rc<RefCell<Vec<u8>>>);
+///
+/// impl Write for Shared {
+///     fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+///         self.0.borrow_mut().write(buf)
+///     }
+///
+///     fn flush(&mut self) -> io::Result<()> { Ok(()) }
+/// }
+///
+/// let output = Rc::new(RefCell::new(Vec::new()));
+/// let mut reader = Trickle {
+///     data: "a世b".as_bytes(),
+///     output: output.clone(),
+///     seen: Vec::new()
+/// };
+///
+/// let mut config = SlowScanConfig::default();
+/// config.set_base_delay(Duration::ZERO);
+///
+/// Shared(output.clone())
+///     .slow_scan_write_by_chunks(
+///         Unbuffered::new(&mut reader).map_while(Result::ok),
+///         config
+///     )
+///     .unwrap();
+///
+/// assert_eq!(*output.borrow(), "a世b".as_bytes());
+/// // 每个字符在读取下一个字符之前就已经输出
+/// assert_eq!(reader.seen, [0, 1, 1, 1, 4, 5]);
+/// ```
+///
+/// ---
+///
+/// Iterator reading byte by byte without buffering, producing every UTF-8
+/// character as a chunk as soon as it is complete
+///
+/// Only a single byte is requested from the underlying reader at a time, so
+/// input can be shown the moment it arrives, which suits relaying the output
+/// of a pipe live together with
+/// [`SlowScanWrite::slow_scan_write_by_chunks`]. Bytes are produced as is and
+/// incomplete or invalid UTF-8 sequences are not replaced
+#[derive(Debug)]
+pub struct Unbuffered<R> {
+    reader: R
+}
+
+impl<R: Read> Unbuffered<R> {
+    pub fn new(reader: R) -> Self { Self { reader } }
+
+    /// 读取一个字节，输入结束时返回 `None`
+    ///
+    /// ---
+    ///
+    /// Read a single byte, returning `None` at the end of the input
+    fn read_byte(&mut self) -> io::Result<Option<u8>> {
+        let mut byte = 0;
+
+        loop {
+            match self.reader.read(std::slice::from_mut(&mut byte)) {
+                Ok(0) => return Ok(None),
+                Ok(_) => return Ok(Some(byte)),
+                Err(it) if it.kind() == ErrorKind::Interrupted => {}
+                Err(it) => return Err(it)
+            }
+        }
+    }
+}
+
+impl<R: Read> Iterator for Unbuffered<R> {
+    type Item = io::Result<Vec<u8>>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let lead = match self.read_byte() {
+            Ok(it) => it?,
+            Err(it) => return Some(Err(it))
+        };
+
+        let len = match lead {
+            0xC0..=0xDF => 2,
+            0xE0..=0xEF => 3,
+            0xF0..=0xF7 => 4,
+            _ => 1
+        };
+
+        let mut chunk = vec![lead];
+        while chunk.len() < len {
+            match self.read_byte() {
+                Ok(Some(it)) => chunk.push(it),
+                Ok(None) => break,
+                Err(it) => return Some(Err(it))
+            }
+        }
+
+        Some(Ok(chunk))
+    }
+}
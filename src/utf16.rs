@@ -0,0 +1,271 @@
+use std::io::{self, Read};
+
+/// UTF-16 的字节序
+///
+/// ---
+///
+/// Byte order of UTF-16
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Utf16Endian {
+    /// 小端序，BOM 为 `FF FE`
+    ///
+    /// ---
+    ///
+    /// Little endian, with the BOM `FF FE`
+    Little,
+    /// 大端序，BOM 为 `FE FF`
+    ///
+    /// ---
+    ///
+    /// Big endian, with the BOM `FE FF`
+    Big
+}
+
+impl Utf16Endian {
+    /// 按内容开头的 BOM 判断字节序，没有 UTF-16 的 BOM 时返回 `None`
+    ///
+    /// 以 `FF FE 00 00` 开头的内容是 UTF-32LE 的 BOM，不视为 UTF-16
+    ///
+    /// # 示例
+    /// ```
+    /// use slow_scan_print::Utf16Endian;
+    ///
+    /// assert_eq!(
+    ///     Utf16Endian::from_bom(b"\xff\xfea\0"),
+    ///     Some(Utf16Endian::Little)
+    /// );
+    /// assert_eq!(
+    ///     Utf16Endian::from_bom(b"\xfe\xff\0a"),
+    ///     Some(Utf16Endian::Big)
+    /// );
+    /// assert_eq!(Utf16Endian::from_bom(b"\xff\xfe\0\0"), None);
+    /// assert_eq!(Utf16Endian::from_bom(b"plain"), None);
+    /// ```
+    ///
+    /// ---
+    ///
+    /// Tell the byte order from the BOM at the start of the content, returning
+    /// `None` when there is no UTF-16 BOM
+    ///
+    /// Content starting with `FF FE 00 00` carries the UTF-32LE BOM and is not
+    /// treated as UTF-16
+    pub fn from_bom(bytes: &[u8]) -> Option<Self> {
+        match bytes {
+            [0xFF, 0xFE, 0, 0, ..] => None,
+            [0xFF, 0xFE, ..] => Some(Self::Little),
+            [0xFE, 0xFF, ..] => Some(Self::Big),
+            _ => None
+        }
+    }
+
+    /// 将两个字节按字节序组合为一个码元
+    ///
+    /// ---
+    ///
+    /// Combine two bytes into a code unit in this byte order
+    fn unit(self, bytes: [u8; 2]) -> u16 {
+        match self {
+            Self::Little => u16::from_le_bytes(bytes),
+            Self::Big => u16::from_be_bytes(bytes)
+        }
+    }
+}
+
+/// 将 UTF-16 输入解码为 UTF-8 的读取器
+///
+/// 代理对被组合为一个字符，开头的 BOM 会被丢弃。默认以有损模式解码，不成对的
+/// 代理项和末尾多出的单个字节被替换为 U+FFFD；关闭有损模式时遇到这些情况返回
+/// [`io::ErrorKind::InvalidData`] 错误
+///
+/// # 示例
+/// ```
+/// use std::io::Read;
+/// use std::time::Duration;
+///
+/// use slow_scan_print::{SlowScanConfig, Utf16Endian, Utf16Reader};
+///
+/// let mut encoded = vec![0xFF, 0xFE];
+/// encoded.extend("a中😀".encode_utf16().flat_map(u16::to_le_bytes));
+///
+/// let endian = Utf16Endian::from_bom(&encoded).unwrap();
+/// let mut text = String::new();
+/// Utf16Reader::new(encoded.as_slice(), endian)
+///     .read_to_string(&mut text)
+///     .unwrap();
+/// assert_eq!(text, "a中😀");
+///
+/// let mut config = SlowScanConfig::default();
+/// config
+///     .set_base_delay(Duration::from_millis(10))
+///     .set_full_width_delay(Duration::from_millis(20));
+/// // 解码后按字符类型正常计算节奏
+/// // Pacing works on the decoded characters as usual
+/// assert_eq!(
+///     config.estimate_duration(text.chars()),
+///     Duration::from_millis(30)
+/// );
+///
+/// // 不成对的代理项
+/// // Unpaired surrogate
+/// let broken = [0x3D, 0xD8, 0x61, 0x00];
+/// let mut text = String::new();
+/// Utf16Reader::new(broken.as_slice(), Utf16Endian::Little)
+///     .read_to_string(&mut text)
+///     .unwrap();
+/// assert_eq!(text, "\u{FFFD}a");
+///
+/// let mut strict = Utf16Reader::new(broken.as_slice(), Utf16Endian::Little)
+///     .with_lossy(false);
+/// assert!(strict.read_to_string(&mut String::new()).is_err());
+/// ```
+///
+/// ---
+///
+/// Reader decoding UTF-16 input into UTF-8
+///
+/// Surrogate pairs are combined into a single character and a leading BOM is
+/// dropped. Decoding is lossy by default, unpaired surrogates and a trailing
+/// odd byte being replaced with U+FFFD; with the lossy mode turned off these
+/// cases result in an [`io::ErrorKind::InvalidData`] error
+#[derive(Debug)]
+pub struct Utf16Reader<R> {
+    inner: R,
+    endian: Utf16Endian,
+    lossy: bool,
+    started: bool,
+    high: Option<u16>,
+    bytes: Vec<u8>,
+    decoded: Vec<u8>,
+    pos: usize
+}
+
+impl<R: Read> Utf16Reader<R> {
+    /// 创建按指定字节序解码的读取器
+    ///
+    /// ---
+    ///
+    /// Create a reader decoding in the given byte order
+    pub fn new(inner: R, endian: Utf16Endian) -> Self {
+        Self {
+            inner,
+            endian,
+            lossy: true,
+            started: false,
+            high: None,
+            bytes: Vec::new(),
+            decoded: Vec::new(),
+            pos: 0
+        }
+    }
+
+    /// 设置是否以有损模式解码
+    ///
+    /// ---
+    ///
+    /// Set whether to decode in lossy mode
+    pub fn with_lossy(mut self, lossy: bool) -> Self {
+        self.lossy = lossy;
+        self
+    }
+
+    /// 读取下一批输入并解码，输入结束时返回 `false`
+    ///
+    /// ---
+    ///
+    /// Read and decode the next batch of input, returning `false` at the end
+    /// of the input
+    fn fill(&mut self) -> io::Result<bool> {
+        let mut buf = [0; 4096];
+        let len = self.inner.read(&mut buf)?;
+        self.decoded.clear();
+        self.pos = 0;
+
+        if len == 0 {
+            // 输入结束时剩下的高位代理项和单个字节都无法组成字符
+            let high = self.high.take();
+            if high.is_some() || !self.bytes.is_empty() {
+                self.bytes.clear();
+                self.push(None)?;
+            }
+            return Ok(!self.decoded.is_empty());
+        }
+
+        self.bytes.extend_from_slice(&buf[..len]);
+        let even = self.bytes.len() / 2 * 2;
+        let units = self.bytes[..even]
+            .chunks_exact(2)
+            .map(|it| self.endian.unit([it[0], it[1]]))
+            .collect::<Vec<_>>();
+        self.bytes.drain(..even);
+
+        for unit in units {
+            match (self.high.take(), unit) {
+                (Some(high), 0xDC00..=0xDFFF) => {
+                    let c = 0x10000
+                        + ((u32::from(high) - 0xD800) << 10)
+                        + (u32::from(unit) - 0xDC00);
+                    self.push(char::from_u32(c))?;
+                }
+                (high, 0xD800..=0xDBFF) => {
+                    if high.is_some() {
+                        self.push(None)?;
+                    }
+                    self.high = Some(unit);
+                }
+                (high, _) => {
+                    if high.is_some() {
+                        self.push(None)?;
+                    }
+                    self.push(char::from_u32(u32::from(unit)))?;
+                }
+            }
+        }
+
+        Ok(true)
+    }
+
+    /// 写入解码出的字符，`None` 表示无效的输入
+    ///
+    /// ---
+    ///
+    /// Write a decoded character, `None` meaning invalid input
+    fn push(&mut self, c: Option<char>) -> io::Result<()> {
+        let c = match c {
+            Some(it) => it,
+            None if self.lossy => char::REPLACEMENT_CHARACTER,
+            None => {
+                return Err(io::Error::new(
+                    io::ErrorKind::InvalidData,
+                    "invalid UTF-16 input"
+                ));
+            }
+        };
+
+        // 只丢弃内容开头的 BOM
+        let bom = !self.started && c == '\u{FEFF}';
+        self.started = true;
+        if !bom {
+            let mut buf = [0; 4];
+            self.decoded
+                .extend_from_slice(c.encode_utf8(&mut buf).as_bytes());
+        }
+
+        Ok(())
+    }
+}
+
+impl<R: Read> Read for Utf16Reader<R> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        while self.pos >= self.decoded.len() {
+            if !self.fill()? {
+                return Ok(0);
+            }
+        }
+
+        let len = buf.len().min(self.decoded.len() - self.pos);
+        buf[..len].copy_from_slice(&self.decoded[self.pos..self.pos + len]);
+        self.pos += len;
+
+        Ok(len)
+    }
+}
@@ -0,0 +1,167 @@
+use std::fs::File;
+use std::io::{self, Read, Seek, SeekFrom};
+use std::path::Path;
+use std::sync::mpsc::{Receiver, channel};
+
+use notify::{
+    Event,
+    EventKind,
+    RecommendedWatcher,
+    RecursiveMode,
+    Watcher,
+    recommended_watcher
+};
+
+/// 监视文件的变化，只读取新追加的内容
+///
+/// 记录已经读取到的位置，每次只读取该位置之后的字节，因此可以在文件被其他
+/// 程序追加内容时只显示新的部分。文件被截短时从头重新读取
+///
+/// # 示例
+/// ```
+/// use std::fs::{self, OpenOptions};
+/// use std::io::Write;
+/// use std::time::Duration;
+///
+/// use slow_scan_print::{FileWatch, SlowScanConfig, SlowScanWrite};
+///
+/// let path = std::env::temp_dir()
+///     .join(format!("slow-scan-print-watch-{}.log", std::process::id()));
+/// fs::write(&path, "old\n").unwrap();
+///
+/// let mut watch = FileWatch::new(&path).unwrap();
+/// assert_eq!(watch.read_appended().unwrap(), b"old\n");
+///
+/// OpenOptions::new()
+///     .append(true)
+///     .open(&path)
+///     .unwrap()
+///     .write_all(b"new\n")
+///     .unwrap();
+///
+/// // 只有追加的部分会被显示
+/// // Only the appended part is revealed
+/// let appended = watch.read_appended().unwrap();
+/// let mut config = SlowScanConfig::default();
+/// config.set_base_delay(Duration::from_millis(1));
+///
+/// let mut writer = Vec::new();
+/// writer
+///     .slow_scan_write_by_chars(
+///         String::from_utf8_lossy(&appended).chars(),
+///         config
+///     )
+///     .unwrap();
+/// assert_eq!(writer, b"new\n");
+/// assert_eq!(watch.offset(), 8);
+///
+/// // 截短后从头读取
+/// // Reading starts over after truncation
+/// fs::write(&path, "x\n").unwrap();
+/// assert_eq!(watch.read_appended().unwrap(), b"x\n");
+///
+/// fs::remove_file(&path).unwrap();
+/// ```
+///
+/// ---
+///
+/// Watch a file for changes, reading only newly appended content
+///
+/// The position read up to is remembered and only the bytes after it are read
+/// each time, so that only the new part is shown while another program
+/// appends to the file. Reading starts over from the beginning when the file
+/// is truncated
+#[derive(Debug)]
+pub struct FileWatch {
+    file: File,
+    offset: u64,
+    events: Receiver<notify::Result<Event>>,
+    _watcher: RecommendedWatcher
+}
+
+impl FileWatch {
+    /// 打开文件并开始监视其变化，读取位置从文件开头开始
+    ///
+    /// # 错误
+    /// 无法打开文件或无法监视文件时返回错误
+    ///
+    /// ---
+    ///
+    /// Open a file and start watching it for changes, reading from the
+    /// beginning of the file
+    ///
+    /// # Errors
+    /// Returns an error when the file cannot be opened or watched
+    pub fn new<P: AsRef<Path>>(path: P) -> io::Result<Self> {
+        let path = path.as_ref();
+        let file = File::open(path)?;
+
+        let (sender, events) = channel();
+        let mut watcher =
+            recommended_watcher(sender).map_err(io::Error::other)?;
+        watcher
+            .watch(path, RecursiveMode::NonRecursive)
+            .map_err(io::Error::other)?;
+
+        Ok(Self {
+            file,
+            offset: 0,
+            events,
+            _watcher: watcher
+        })
+    }
+
+    /// 返回已经读取到的位置
+    ///
+    /// ---
+    ///
+    /// Return the position read up to
+    pub fn offset(&self) -> u64 { self.offset }
+
+    /// 读取上次读取的位置之后追加的内容，没有新内容时返回空的 `Vec`
+    ///
+    /// ---
+    ///
+    /// Read what was appended after the position of the last read, returning
+    /// an empty `Vec` when there is nothing new
+    pub fn read_appended(&mut self) -> io::Result<Vec<u8>> {
+        if self.file.metadata()?.len() < self.offset {
+            self.offset = 0;
+        }
+
+        let mut appended = Vec::new();
+        self.file.seek(SeekFrom::Start(self.offset))?;
+        self.offset += self.file.read_to_end(&mut appended)? as u64;
+
+        Ok(appended)
+    }
+
+    /// 阻塞直到文件有新追加的内容并返回这些内容
+    ///
+    /// # 错误
+    /// 监视出错或读取失败时返回错误
+    ///
+    /// ---
+    ///
+    /// Block until something is appended to the file and return it
+    ///
+    /// # Errors
+    /// Returns an error when watching or reading fails
+    pub fn wait_appended(&mut self) -> io::Result<Vec<u8>> {
+        loop {
+            let event = self
+                .events
+                .recv()
+                .map_err(io::Error::other)?
+                .map_err(io::Error::other)?;
+
+            if matches!(event.kind, EventKind::Modify(_) | EventKind::Create(_))
+            {
+                let appended = self.read_appended()?;
+                if !appended.is_empty() {
+                    return Ok(appended);
+                }
+            }
+        }
+    }
+}
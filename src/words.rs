@@ -0,0 +1,87 @@
+use std::io::{self, Write};
+
+use crate::{Clock, Pacer, SlowScanConfig};
+
+/// 按单词写入的实现，每个单词连同其后的空白一起写入并延迟一次 `base_delay`
+///
+/// 开头的空白与第一个单词一起写入，只由空白组成的输入作为一个单元写入。设置
+/// `head_units` 时按单词计数，达到上限后不再读取输入
+///
+/// ---
+///
+/// Implementation of writing word by word, every word being written together
+/// with the whitespace following it and delayed by `base_delay` once
+///
+/// Leading whitespace is written with the first word, and input made of
+/// whitespace only is written as a single unit. `head_units` counts words when
+/// set, no more input being read once the limit is reached
+pub(crate) fn write_by_words<W, I, C>(
+    writer: &mut W,
+    iter: I,
+    config: SlowScanConfig,
+    clock: &mut C
+) -> io::Result<()>
+where
+    W: Write + ?Sized,
+    I: Iterator<Item = char>,
+    C: Clock + ?Sized
+{
+    let mut pacer = Pacer::with_config(clock, &config);
+    let mut word = String::new();
+    let mut has_word = false;
+    let mut remaining = config.head_units.unwrap_or(usize::MAX);
+
+    if remaining == 0 {
+        return writer.flush();
+    }
+
+    for it in iter {
+        // 空白之后出现新的单词时，之前的单词连同空白已经完整
+        let complete = has_word
+            && !it.is_whitespace()
+            && word.ends_with(char::is_whitespace);
+        if complete {
+            write_word(writer, &word, config)?;
+            word.clear();
+
+            remaining -= 1;
+            if remaining == 0 {
+                break;
+            }
+            pacer.sleep(config.base_delay);
+        }
+
+        has_word |= !it.is_whitespace();
+        word.push(it);
+    }
+
+    // 达到上限而提前结束时，最后一个单词已经写入，只剩结尾的延迟
+    if !word.is_empty() {
+        write_word(writer, &word, config)?;
+    }
+    if (!word.is_empty() || remaining == 0)
+        && let Some(it) = config.pause_after(config.base_delay, false)
+    {
+        pacer.sleep(it);
+    }
+
+    writer.flush()
+}
+
+/// 写入一个单词，按配置在之后刷新
+///
+/// ---
+///
+/// Write a word, flushing afterwards as configured
+fn write_word<W: Write + ?Sized>(
+    writer: &mut W,
+    word: &str,
+    config: SlowScanConfig
+) -> io::Result<()> {
+    writer.write_all(word.as_bytes())?;
+    if config.flush_each_unit {
+        writer.flush()?;
+    }
+
+    Ok(())
+}
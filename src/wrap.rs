@@ -0,0 +1,305 @@
+use std::collections::VecDeque;
+
+use crate::SlowScanConfig;
+use crate::ansi::AnsiState;
+
+/// 制表符的间隔宽度
+///
+/// ---
+///
+/// Interval width of tab stops
+const TAB_WIDTH: usize = 8;
+
+/// 按单词自动换行的字符迭代器适配器
+///
+/// 以空白字符分隔单词，当下一个单词放不下时在其之前换行。ANSI 转义序列不计入
+/// 显示宽度，因此带样式的文本也会在正确的可见列处换行；超过宽度的单词在宽度处
+/// 强制断开。字符宽度按配置计算。由 [`word_wrap`] 或 [`word_wrap_with_config`]
+/// 创建
+///
+/// ---
+///
+/// Character iterator adapter wrapping lines at word boundaries
+///
+/// Words are separated by whitespace and a line break is inserted before a word
+/// that does not fit. ANSI escape sequences do not count toward the display
+/// width, so styled text wraps at the correct visible column; words longer
+/// than the width are broken at the width. Character widths follow the
+/// configuration. Created by [`word_wrap`] or [`word_wrap_with_config`]
+#[derive(Debug, Clone)]
+pub struct WordWrap<I> {
+    iter: I,
+    width: usize,
+    config: SlowScanConfig,
+    column: usize,
+    ansi: AnsiState,
+    spaces: String,
+    word: String,
+    word_width: usize,
+    out: VecDeque<char>
+}
+
+/// 将字符流按单词自动换行到指定宽度，字符宽度按默认配置计算
+///
+/// # 参数
+/// - `iter`: 字符迭代器
+/// - `width`: 每行的最大显示宽度
+///
+/// # 示例
+/// ```
+/// use slow_scan_print::word_wrap;
+///
+/// let wrapped =
+///     word_wrap("\x1b[31mred red red\x1b[0m".chars(), 7).collect::<String>();
+///
+/// assert_eq!(wrapped, "\x1b[31mred red\nred\x1b[0m");
+/// ```
+///
+/// ---
+///
+/// Wrap a character stream at word boundaries to the given width, computing
+/// character widths with the default configuration
+///
+/// # Arguments
+/// - `iter`: Iterator of characters
+/// - `width`: Maximum display width of each line
+pub fn word_wrap<I>(iter: I, width: usize) -> WordWrap<I::IntoIter>
+where
+    I: IntoIterator<Item = char>
+{
+    word_wrap_with_config(iter, width, &SlowScanConfig::default())
+}
+
+/// 将字符流按单词自动换行到指定宽度，字符宽度按给定的配置计算
+///
+/// 与 [`word_wrap`] 相同，但 `width_fn`、`half_width_only` 和
+/// `cjk_ambiguous_wide` 等影响显示宽度的设置会生效
+///
+/// # 参数
+/// - `iter`: 字符迭代器
+/// - `width`: 每行的最大显示宽度
+/// - `config`: 决定字符宽度的配置
+///
+/// # 示例
+/// ```
+/// use slow_scan_print::{SlowScanConfig, word_wrap_with_config};
+///
+/// let mut config = SlowScanConfig::default();
+/// let wrap = |config: &SlowScanConfig| {
+///     word_wrap_with_config("世界 你好".chars(), 6, config)
+///         .collect::<String>()
+/// };
+///
+/// assert_eq!(wrap(&config), "世界\n你好");
+///
+/// config.set_half_width_only(true);
+/// assert_eq!(wrap(&config), "世界 你好");
+///
+/// // 超过宽度的单词在宽度处断开
+/// // Words longer than the width are broken at the width
+/// let wrapped = word_wrap_with_config("ab abcdefg".chars(), 3, &config)
+///     .collect::<String>();
+///
+/// assert_eq!(wrapped, "ab\nabc\ndef\ng");
+/// ```
+///
+/// ---
+///
+/// Wrap a character stream at word boundaries to the given width, computing
+/// character widths with the given configuration
+///
+/// Same as [`word_wrap`], but the settings affecting display widths such as
+/// `width_fn`, `half_width_only` and `cjk_ambiguous_wide` take effect
+///
+/// # Arguments
+/// - `iter`: Iterator of characters
+/// - `width`: Maximum display width of each line
+/// - `config`: Configuration deciding character widths
+pub fn word_wrap_with_config<I>(
+    iter: I,
+    width: usize,
+    config: &SlowScanConfig
+) -> WordWrap<I::IntoIter>
+where
+    I: IntoIterator<Item = char>
+{
+    WordWrap {
+        iter: iter.into_iter(),
+        width,
+        config: *config,
+        column: 0,
+        ansi: AnsiState::default(),
+        spaces: String::new(),
+        word: String::new(),
+        word_width: 0,
+        out: VecDeque::new()
+    }
+}
+
+/// 将硬换行的段落重新排版到指定宽度
+///
+/// 以空行分隔段落，段落内的各行去除首尾空白后以单个空格连接，再按
+/// [`word_wrap`] 换行到 `width`。段落之间的空行原样保留，输入以换行符结尾时
+/// 输出也以换行符结尾
+///
+/// # 参数
+/// - `s`: 要重新排版的文本
+/// - `width`: 每行的最大显示宽度
+///
+/// # 示例
+/// ```
+/// use slow_scan_print::reflow;
+///
+/// let text = "The quick brown\nfox jumps\nover the lazy dog.\n\nThe end.\n";
+///
+/// assert_eq!(
+///     reflow(text, 20),
+///     "The quick brown fox\njumps over the lazy\ndog.\n\nThe end.\n"
+/// );
+/// ```
+///
+/// ---
+///
+/// Re-flow hard-wrapped paragraphs to the given width
+///
+/// Paragraphs are separated by blank lines. The lines within a paragraph are
+/// trimmed and joined with single spaces, then wrapped to `width` with
+/// [`word_wrap`]. Blank lines between paragraphs are kept as is, and the
+/// output ends with a line break when the input does
+///
+/// # Arguments
+/// - `s`: Text to re-flow
+/// - `width`: Maximum display width of each line
+pub fn reflow(s: &str, width: usize) -> String {
+    let mut lines = Vec::new();
+    let mut paragraph = Vec::new();
+
+    for it in s.lines().map(str::trim) {
+        if !it.is_empty() {
+            paragraph.push(it);
+            continue;
+        }
+
+        if !paragraph.is_empty() {
+            lines.push(word_wrap(paragraph.join(" ").chars(), width).collect());
+            paragraph.clear();
+        }
+        lines.push(String::new());
+    }
+
+    if !paragraph.is_empty() {
+        lines.push(word_wrap(paragraph.join(" ").chars(), width).collect());
+    }
+
+    let mut reflowed = lines.join("\n");
+    if s.ends_with('\n') {
+        reflowed.push('\n');
+    }
+
+    reflowed
+}
+
+impl<I> WordWrap<I> {
+    /// 计算从当前列开始写入待定空白后的列
+    ///
+    /// ---
+    ///
+    /// Compute the column after writing the pending whitespace from the current
+    /// column
+    fn column_after_spaces(&self) -> usize {
+        self.spaces
+            .chars()
+            .fold(self.column, |column, it| match it {
+                '\t' => (column / TAB_WIDTH + 1) * TAB_WIDTH,
+                _ => column + 1
+            })
+    }
+
+    /// 输出待定的单词，必要时在其之前换行
+    ///
+    /// ---
+    ///
+    /// Emit the pending word, breaking the line before it if needed
+    fn commit_word(&mut self) {
+        if self.word.is_empty() {
+            return;
+        }
+
+        let column = self.column_after_spaces();
+
+        if self.word_width > 0
+            && self.column > 0
+            && column + self.word_width > self.width
+        {
+            self.out.push_back('\n');
+            self.spaces.clear();
+            self.column = 0;
+        } else {
+            self.out.extend(self.spaces.drain(..));
+            self.column = column;
+        }
+
+        self.out.extend(self.word.drain(..));
+        self.column += self.word_width;
+        self.word_width = 0;
+    }
+
+    /// 输出待定的空白
+    ///
+    /// ---
+    ///
+    /// Emit the pending whitespace
+    fn commit_spaces(&mut self) {
+        self.column = self.column_after_spaces();
+        self.out.extend(self.spaces.drain(..));
+    }
+}
+
+impl<I: Iterator<Item = char>> Iterator for WordWrap<I> {
+    type Item = char;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while self.out.is_empty() {
+            let Some(it) = self.iter.next() else {
+                self.commit_word();
+                self.commit_spaces();
+                break;
+            };
+
+            if self.ansi.advance(it) {
+                self.word.push(it);
+                continue;
+            }
+
+            match it {
+                '\n' => {
+                    self.commit_word();
+                    self.commit_spaces();
+                    self.out.push_back('\n');
+                    self.column = 0;
+                }
+                ' ' | '\t' => {
+                    self.commit_word();
+                    self.spaces.push(it);
+                }
+                _ => {
+                    let width = self.config.char_width(it).unwrap_or(0);
+
+                    // 单独一行也放不下的单词在宽度处断开
+                    if !self.word.is_empty()
+                        && self.word_width + width > self.width
+                    {
+                        self.commit_word();
+                        self.out.push_back('\n');
+                        self.column = 0;
+                    }
+
+                    self.word.push(it);
+                    self.word_width += width;
+                }
+            }
+        }
+
+        self.out.pop_front()
+    }
+}
@@ -0,0 +1,431 @@
+//! 运行编译出的命令行程序，检查其输出和退出状态
+//!
+//! ---
+//!
+//! Run the compiled command line program and check its output and exit status
+
+use std::io::Write;
+use std::path::PathBuf;
+use std::process::{Command, Output, Stdio};
+use std::time::Duration;
+
+/// 以英文区域运行程序，`stdin` 为 `None` 时不提供标准输入
+///
+/// ---
+///
+/// Run the program under an English locale, providing no stdin when `stdin`
+/// is `None`
+fn run(args: &[&str], stdin: Option<&[u8]>) -> Output {
+    let mut child = Command::new(env!("CARGO_BIN_EXE_slow-scan-print"))
+        .args(args)
+        .env("LC_ALL", "en_US.UTF-8")
+        .env_remove("NO_COLOR")
+        .stdin(if stdin.is_some() {
+            Stdio::piped()
+        } else {
+            Stdio::null()
+        })
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .unwrap();
+
+    if let Some(it) = stdin {
+        child.stdin.take().unwrap().write_all(it).unwrap();
+    }
+
+    child.wait_with_output().unwrap()
+}
+
+/// 在临时目录中写入测试文件并返回其路径
+///
+/// ---
+///
+/// Write a fixture into the temporary directory and return its path
+fn fixture(name: &str, content: &str) -> PathBuf {
+    let path = std::env::temp_dir().join(format!(
+        "slow-scan-print-cli-{}-{}",
+        std::process::id(),
+        name
+    ));
+    std::fs::write(&path, content).unwrap();
+    path
+}
+
+/// 从 `Benchmark finished: N units in T` 中取出单元数和时长
+///
+/// ---
+///
+/// Extract the unit count and duration from
+/// `Benchmark finished: N units in T`
+fn parse_benchmark(stdout: &[u8]) -> (usize, Duration) {
+    let stdout = String::from_utf8_lossy(stdout);
+    let rest = stdout
+        .trim()
+        .strip_prefix("Benchmark finished: ")
+        .unwrap_or_else(|| panic!("unexpected report: {:?}", stdout));
+    let (units, elapsed) = rest.split_once(" units in ").unwrap();
+
+    let split = elapsed
+        .find(|it: char| !(it.is_ascii_digit() || it == '.'))
+        .unwrap();
+    let (value, unit) = elapsed.split_at(split);
+    let value = value.parse::<f64>().unwrap();
+    let secs = match unit {
+        "s" => value,
+        "ms" => value / 1e3,
+        "µs" => value / 1e6,
+        "ns" => value / 1e9,
+        _ => panic!("unexpected unit: {:?}", unit)
+    };
+
+    (units.parse().unwrap(), Duration::from_secs_f64(secs))
+}
+
+#[test]
+fn benchmark_reports_units_and_elapsed() {
+    let input = fixture("benchmark.txt", "héllo世\n");
+    let output = run(
+        &[
+            "--benchmark",
+            "-d",
+            "10ms",
+            "-f",
+            "20ms",
+            input.to_str().unwrap()
+        ],
+        None
+    );
+
+    assert!(output.status.success());
+    let (units, elapsed) = parse_benchmark(&output.stdout);
+
+    // 六个可见字符和一个换行符；五个半角字符和一个全角字符之后各有一次延迟
+    assert_eq!(units, 7);
+    assert!(elapsed >= Duration::from_millis(70), "{:?}", elapsed);
+    assert!(elapsed < Duration::from_secs(5), "{:?}", elapsed);
+
+    std::fs::remove_file(input).unwrap();
+}
+
+#[test]
+fn config_from_stdin_sets_delay() {
+    let input = fixture("config-stdin.txt", "abc");
+    let output = run(
+        &["--benchmark", "--config", "-", input.to_str().unwrap()],
+        Some(b"delay = \"100ms\"\n")
+    );
+
+    assert!(output.status.success());
+    let (units, elapsed) = parse_benchmark(&output.stdout);
+
+    // 三个字符中前两个之后各有一次配置文件给出的延迟
+    assert_eq!(units, 3);
+    assert!(elapsed >= Duration::from_millis(200), "{:?}", elapsed);
+
+    std::fs::remove_file(input).unwrap();
+}
+
+#[test]
+fn cli_delay_overrides_config() {
+    let input = fixture("config-override.txt", "abc");
+    let output = run(
+        &[
+            "--benchmark",
+            "--config",
+            "-",
+            "-d",
+            "0s",
+            input.to_str().unwrap()
+        ],
+        Some(b"delay = \"1s\"\n")
+    );
+
+    assert!(output.status.success());
+    let (units, elapsed) = parse_benchmark(&output.stdout);
+
+    assert_eq!(units, 3);
+    assert!(elapsed < Duration::from_secs(1), "{:?}", elapsed);
+
+    std::fs::remove_file(input).unwrap();
+}
+
+#[test]
+fn config_and_input_cannot_both_use_stdin() {
+    let output = run(&["--config", "-", "-"], Some(b"delay = \"1s\"\n"));
+
+    assert_eq!(output.status.code(), Some(2));
+    assert!(output.stdout.is_empty());
+}
+
+#[test]
+fn alt_screen_is_skipped_when_redirected() {
+    let output = run(&["--alt-screen", "-d", "0s", "-"], Some(b"abc"));
+
+    assert!(output.status.success());
+    assert_eq!(output.stdout, b"abc");
+}
+
+#[test]
+fn timing_mismatch_is_reported_once() {
+    let timing = fixture("timing-mismatch.tm", "0.1 2\n");
+    let output =
+        run(&["--timing", timing.to_str().unwrap(), "-"], Some(b"abc"));
+
+    assert_eq!(output.status.code(), Some(1));
+    assert!(output.stdout.is_empty());
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert_eq!(stderr.matches("2 bytes").count(), 1, "{}", stderr);
+
+    std::fs::remove_file(timing).unwrap();
+}
+
+#[test]
+fn auto_final_newline_keeps_redirected_output() {
+    for input in [&b"abc"[..], b"abc\n"] {
+        let output =
+            run(&["--auto-final-newline", "-d", "0s", "-"], Some(input));
+
+        assert!(output.status.success());
+        assert_eq!(output.stdout, input);
+    }
+}
+
+#[test]
+fn wait_start_is_skipped_without_terminal() {
+    let output = run(&["--wait-start", "-d", "0s", "-"], Some(b"abc"));
+
+    assert!(output.status.success());
+    assert_eq!(output.stdout, b"abc");
+}
+
+// 期望的输出使用 `\n` 作为行尾
+#[cfg(unix)]
+#[test]
+fn line_mode_strips_trailing_whitespace() {
+    let input = b"a  \nb\t\t\nc \t\r\n  d \t";
+    let output = run(
+        &["-l", "--strip-trailing-whitespace", "-d", "0s", "-"],
+        Some(input)
+    );
+
+    assert!(output.status.success());
+    // 行尾的空格、制表符和 CRLF 被去除，行首的空白保留
+    assert_eq!(output.stdout, b"a\nb\nc\n  d\n");
+
+    let output = run(&["-l", "-d", "0s", "-"], Some(input));
+    assert_eq!(output.stdout, b"a  \nb\t\t\nc \t\n  d \t\n");
+}
+
+#[test]
+fn hide_cursor_is_skipped_when_redirected() {
+    let output = run(&["--hide-cursor", "-d", "0s", "-"], Some(b"abc"));
+
+    assert!(output.status.success());
+    assert_eq!(output.stdout, b"abc");
+}
+
+#[cfg(feature = "command")]
+#[test]
+fn cmd_output_is_revealed() {
+    let output = run(&["--cmd", "echo hello", "-d", "0s"], None);
+
+    assert!(output.status.success());
+    assert_eq!(String::from_utf8_lossy(&output.stdout).trim_end(), "hello");
+    assert!(output.stderr.is_empty());
+}
+
+#[cfg(feature = "command")]
+#[test]
+fn cmd_failure_is_reported() {
+    let output = run(&["--cmd", "echo partial && exit 3", "-d", "0s"], None);
+
+    assert_eq!(
+        String::from_utf8_lossy(&output.stdout).trim_end(),
+        "partial"
+    );
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.contains("did not exit successfully"), "{}", stderr);
+}
+
+#[test]
+fn extreme_inline_speed_is_ignored() {
+    let output = run(
+        &["--inline-directives", "-d", "1ms", "-"],
+        Some(b"a\x1b[>speed=1e-300\x07bc")
+    );
+
+    assert!(output.status.success(), "{:?}", output);
+    // 超出范围的指令无法识别，按普通字符原样输出
+    assert_eq!(output.stdout, b"a\x1b[>speed=1e-300\x07bc");
+}
+
+#[test]
+fn out_of_range_speed_is_rejected() {
+    for it in ["1e-300", "0", "-1", "inf", "1e300"] {
+        let output = run(
+            &[&format!("--speed={}", it), "-d", "1ms", "-"],
+            Some(b"ab\n")
+        );
+
+        assert_eq!(output.status.code(), Some(1), "{}", it);
+        assert!(output.stdout.is_empty());
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        assert!(stderr.contains("--speed"), "{}", stderr);
+    }
+}
+
+#[test]
+fn out_of_range_rate_is_rejected() {
+    for it in ["1e300", "1e-300", "inf", "-1"] {
+        let output = run(
+            &[&format!("--rate={}", it), "-d", "1ms", "-"],
+            Some(b"ab\n")
+        );
+
+        assert_eq!(output.status.code(), Some(1), "{}", it);
+        assert!(output.stdout.is_empty());
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        assert!(stderr.contains("--rate"), "{}", stderr);
+    }
+
+    let output = run(&["--rate=0", "-d", "1ms", "-"], Some(b"ab\n"));
+    assert!(output.status.success());
+    assert_eq!(output.stdout, b"ab\n");
+}
+
+#[test]
+fn word_mode_conflicts_with_other_writers() {
+    for it in [
+        &["--diff-color"][..],
+        &["--align", "right"],
+        &["--unbuffered"]
+    ] {
+        let output = run(&[&["-w", "-d", "0s"][..], it, &["-"]].concat(), None);
+
+        assert_eq!(output.status.code(), Some(2), "{:?}", it);
+        assert!(output.stdout.is_empty());
+    }
+}
+
+#[test]
+fn word_mode_head_units_counts_words() {
+    let output = run(
+        &["-w", "--head-units", "2", "-d", "0s", "-"],
+        Some(b"a b c\n")
+    );
+
+    assert!(output.status.success(), "{:?}", output);
+    assert_eq!(output.stdout, b"a b ");
+}
+
+#[test]
+fn line_mode_profile_counts_characters() {
+    let output = run(
+        &["-l", "--profile", "-d", "1ms", "-"],
+        Some("ab\n世\n".as_bytes())
+    );
+
+    assert!(output.status.success(), "{:?}", output);
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    // 每行之后的延迟计入行尾的换行符
+    assert!(
+        stderr.contains("half-width: 2 chars × 0ns = 0ns"),
+        "{}",
+        stderr
+    );
+    assert!(
+        stderr.contains("full-width: 1 chars × 0ns = 0ns"),
+        "{}",
+        stderr
+    );
+    assert!(
+        stderr.contains("control: 2 chars × 1ms = 2ms"),
+        "{}",
+        stderr
+    );
+}
+
+#[test]
+fn profile_conflicts_with_word_mode() {
+    let output = run(&["-w", "--profile", "-"], Some(b"a b\n"));
+
+    assert_eq!(output.status.code(), Some(2));
+    assert!(output.stdout.is_empty());
+}
+
+#[test]
+fn stats_json_counts_line_mode() {
+    let output = run(
+        &["-l", "--stats-json", "-d", "1ms", "-"],
+        Some("ab\n世\n".as_bytes())
+    );
+
+    assert!(output.status.success(), "{:?}", output);
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.contains(r#""units":2,"#), "{}", stderr);
+    assert!(stderr.contains(r#""total_sleep":0.002,"#), "{}", stderr);
+    assert!(
+        stderr.contains(r#""full_width":{"count":1,"delay":0}"#),
+        "{}",
+        stderr
+    );
+}
+
+#[test]
+fn stats_json_omits_classes_not_collected() {
+    let output = run(&["-w", "--stats-json", "-d", "0s", "-"], Some(b"a b\n"));
+
+    assert!(output.status.success(), "{:?}", output);
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.contains(r#""units":4}"#), "{}", stderr);
+    assert!(!stderr.contains("classes"), "{}", stderr);
+}
+
+#[test]
+fn wrap_breaks_long_words() {
+    let output = run(&["--wrap", "3", "-d", "0s", "-"], Some(b"ab abcdefg\n"));
+
+    assert!(output.status.success(), "{:?}", output);
+    assert_eq!(output.stdout, b"ab\nabc\ndef\ng\n");
+}
+
+#[test]
+fn wrap_follows_configured_widths() {
+    let input = "世界 你好\n".as_bytes();
+    let wide = run(&["--wrap", "6", "-d", "0s", "-"], Some(input));
+    let narrow = run(
+        &["--wrap", "6", "--no-full-width", "-d", "0s", "-"],
+        Some(input)
+    );
+
+    assert_eq!(wide.stdout, "世界\n你好\n".as_bytes());
+    assert_eq!(narrow.stdout, input);
+}
+
+#[test]
+fn diff_line_pauses_follow_speed() {
+    let start = std::time::Instant::now();
+    let output = run(
+        &[
+            "--diff-color",
+            "-d",
+            "0s",
+            "-c",
+            "2s",
+            "--speed",
+            "100",
+            "-"
+        ],
+        Some(b"a\nb\nc\n")
+    );
+
+    assert!(output.status.success(), "{:?}", output);
+    assert_eq!(output.stdout, b"a\nb\nc\n");
+    // 两次 2 秒的停顿按 100 倍速缩短为 20ms
+    assert!(
+        start.elapsed() < Duration::from_secs(2),
+        "{:?}",
+        start.elapsed()
+    );
+}